@@ -0,0 +1,428 @@
+// src/csv_row_offset_index_module.rs
+
+/// Sidecar line-offset index for row-level random access
+///
+/// Finding data row N of a CSV file normally means reading every record before
+/// it. This module builds, in one streaming pass, a small sidecar file that
+/// records the byte offset of every Kth data record. Looking up a row then
+/// seeks to the nearest indexed record at or before it and reads at most K-1
+/// records forward.
+///
+/// # Sidecar File
+/// The index for `data.csv` is `data.csv.row_index`, a plain text file:
+/// ```text
+/// # rows_and_columns row offset index
+/// source_file_size_bytes = 52311
+/// source_modified_nanoseconds = 1760000123456789012
+/// row_interval = 1000
+/// data_rows = 2400
+/// 34
+/// 21877
+/// 43950
+/// ```
+/// Each line after the settings is the byte offset of data row 0, K, 2K, ...
+/// The index is stale as soon as the CSV file's size or modification time
+/// (to the nanosecond, so a same-size rewrite within one second is caught)
+/// differs from the recorded values, and is then rebuilt before use. Older
+/// sidecar files recorded `source_modified_seconds`; they are still read, and
+/// are rebuilt unless the file's modification time has no fraction of a second.
+///
+/// # Design Philosophy
+/// - Optional: without a sidecar file rows are found by reading from the start
+/// - Human-inspectable: plain text, one offset per line
+/// - Vanilla Rust: std-only and returns `io::Error`, like the record reader
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::csv_record_reader_module::CsvRecordReader;
+
+/// Suffix appended to the CSV file name to name its sidecar index
+const ROW_OFFSET_INDEX_FILE_SUFFIX: &str = ".row_index";
+
+/// First line of every sidecar index file
+const ROW_OFFSET_INDEX_FILE_HEADER: &str = "# rows_and_columns row offset index";
+
+/// Default number of data rows between two indexed offsets
+pub const DEFAULT_ROW_OFFSET_INTERVAL: usize = 1000;
+
+/// Byte offsets of every Kth data record of one CSV file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRowOffsetIndex {
+    /// Size of the CSV file when the index was built
+    pub source_file_size_bytes: u64,
+
+    /// Modification time of the CSV file when the index was built (nanoseconds since the Unix epoch)
+    pub source_modified_nanoseconds: u64,
+
+    /// Number of data rows between two indexed offsets (K)
+    pub row_interval: usize,
+
+    /// Number of data rows in the file (header not counted)
+    pub data_row_count: usize,
+
+    /// Byte offset of data row 0, K, 2K, ...
+    pub indexed_row_offsets: Vec<u64>,
+}
+
+impl CsvRowOffsetIndex {
+    /// Finds where to start reading to reach a data row
+    ///
+    /// # Arguments
+    /// * `row_index` - 0-based data row index
+    ///
+    /// # Returns
+    /// * `Option<(u64, usize)>` - Byte offset to seek to and the number of records
+    ///   to skip after it, or `None` if the row is out of range
+    pub fn seek_position_for_row(&self, row_index: usize) -> Option<(u64, usize)> {
+        if row_index >= self.data_row_count {
+            return None;
+        }
+
+        let indexed_offset = self.indexed_row_offsets.get(row_index / self.row_interval)?;
+        Some((*indexed_offset, row_index % self.row_interval))
+    }
+
+    /// Checks whether the index still describes the CSV file
+    ///
+    /// # Arguments
+    /// * `source_file_size_bytes` - Current size of the CSV file
+    /// * `source_modified_nanoseconds` - Current modification time of the CSV file
+    ///
+    /// # Returns
+    /// * `bool` - True if neither the size nor the modification time changed
+    pub fn is_current_for(&self, source_file_size_bytes: u64, source_modified_nanoseconds: u64) -> bool {
+        self.source_file_size_bytes == source_file_size_bytes
+            && self.source_modified_nanoseconds == source_modified_nanoseconds
+    }
+
+    /// Formats the index as the text of a sidecar file
+    ///
+    /// # Returns
+    /// * `String` - The settings lines followed by one offset per line
+    pub fn to_sidecar_file_content(&self) -> String {
+        let mut file_content = String::new();
+        file_content.push_str(ROW_OFFSET_INDEX_FILE_HEADER);
+        file_content.push('\n');
+        file_content.push_str(&format!("source_file_size_bytes = {}\n", self.source_file_size_bytes));
+        file_content.push_str(&format!("source_modified_nanoseconds = {}\n", self.source_modified_nanoseconds));
+        file_content.push_str(&format!("row_interval = {}\n", self.row_interval));
+        file_content.push_str(&format!("data_rows = {}\n", self.data_row_count));
+        for indexed_row_offset in &self.indexed_row_offsets {
+            file_content.push_str(&format!("{}\n", indexed_row_offset));
+        }
+        file_content
+    }
+
+    /// Parses the text of a sidecar file
+    ///
+    /// # Arguments
+    /// * `file_content` - The sidecar file's text
+    ///
+    /// # Returns
+    /// * `Result<CsvRowOffsetIndex, String>` - The index, or a message naming the bad line
+    pub fn from_sidecar_file_content(file_content: &str) -> Result<CsvRowOffsetIndex, String> {
+        let mut content_lines = file_content.lines().enumerate();
+
+        match content_lines.next() {
+            Some((_, first_line)) if first_line == ROW_OFFSET_INDEX_FILE_HEADER => {}
+            _ => return Err("missing the row offset index header line".to_string()),
+        }
+
+        // Reads the next `key = number` line, returning which of the accepted keys it had
+        let mut read_setting = |accepted_key_names: &[&str]| -> Result<(usize, u64), String> {
+            let (line_index, setting_line) = content_lines
+                .next()
+                .ok_or_else(|| format!("missing '{}'", accepted_key_names[0]))?;
+            accepted_key_names
+                .iter()
+                .enumerate()
+                .find_map(|(key_position, key_name)| {
+                    let value_text = setting_line
+                        .strip_prefix(key_name)?
+                        .trim_start()
+                        .strip_prefix('=')?;
+                    Some((key_position, value_text.trim().parse::<u64>().ok()?))
+                })
+                .ok_or_else(|| format!("line {}: expected '{} = <number>'", line_index + 1, accepted_key_names[0]))
+        };
+
+        let (_, source_file_size_bytes) = read_setting(&["source_file_size_bytes"])?;
+        let source_modified_nanoseconds = match read_setting(&["source_modified_nanoseconds", "source_modified_seconds"])? {
+            (0, modified_nanoseconds) => modified_nanoseconds,
+            (_, modified_seconds) => modified_seconds.saturating_mul(1_000_000_000),
+        };
+        let row_interval = read_setting(&["row_interval"])?.1 as usize;
+        let data_row_count = read_setting(&["data_rows"])?.1 as usize;
+        if row_interval == 0 {
+            return Err("row_interval must be at least 1".to_string());
+        }
+
+        let indexed_row_offsets = content_lines
+            .map(|(line_index, offset_line)| {
+                offset_line
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| format!("line {}: expected a byte offset", line_index + 1))
+            })
+            .collect::<Result<Vec<u64>, String>>()?;
+        if indexed_row_offsets.len() != data_row_count.div_ceil(row_interval) {
+            return Err(format!(
+                "expected {} offsets for {} data rows, found {}",
+                data_row_count.div_ceil(row_interval),
+                data_row_count,
+                indexed_row_offsets.len()
+            ));
+        }
+
+        Ok(CsvRowOffsetIndex {
+            source_file_size_bytes,
+            source_modified_nanoseconds,
+            row_interval,
+            data_row_count,
+            indexed_row_offsets,
+        })
+    }
+}
+
+/// Returns the path of a CSV file's sidecar index
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+///
+/// # Returns
+/// * `PathBuf` - The CSV path with `.row_index` appended
+pub fn row_offset_index_path(csv_file_path: &Path) -> PathBuf {
+    let mut index_file_name = csv_file_path.as_os_str().to_os_string();
+    index_file_name.push(ROW_OFFSET_INDEX_FILE_SUFFIX);
+    PathBuf::from(index_file_name)
+}
+
+/// Reads the size and modification time that identify a CSV file's content
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+///
+/// # Returns
+/// * `Result<(u64, u64), io::Error>` - Size in bytes and modification time in nanoseconds since the Unix epoch
+fn read_source_file_identity(csv_file_path: &Path) -> Result<(u64, u64), io::Error> {
+    let file_metadata = fs::metadata(csv_file_path)?;
+    let modified_nanoseconds = file_metadata
+        .modified()
+        .ok()
+        .and_then(|modified_time| modified_time.duration_since(UNIX_EPOCH).ok())
+        .map(|since_epoch| since_epoch.as_nanos() as u64)
+        .unwrap_or(0);
+
+    Ok((file_metadata.len(), modified_nanoseconds))
+}
+
+/// Builds the row offset index of a CSV file in one streaming pass
+///
+/// The first record is the header row and is not indexed.
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `row_interval` - Number of data rows between two indexed offsets (at least 1)
+///
+/// # Returns
+/// * `Result<CsvRowOffsetIndex, io::Error>` - The index, or an I/O or parse error
+pub fn build_row_offset_index(csv_file_path: &Path, row_interval: usize) -> Result<CsvRowOffsetIndex, io::Error> {
+    if row_interval == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Row interval must be at least 1"));
+    }
+
+    let (source_file_size_bytes, source_modified_nanoseconds) = read_source_file_identity(csv_file_path)?;
+    let mut csv_record_reader = CsvRecordReader::new(BufReader::new(File::open(csv_file_path)?));
+
+    if csv_record_reader.read_next_record()?.is_none() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Empty CSV file"));
+    }
+
+    let mut indexed_row_offsets = Vec::new();
+    let mut data_row_count = 0;
+    while let Some(csv_record) = csv_record_reader.read_next_record()? {
+        if data_row_count % row_interval == 0 {
+            indexed_row_offsets.push(csv_record.starting_byte_offset);
+        }
+        data_row_count += 1;
+    }
+
+    Ok(CsvRowOffsetIndex {
+        source_file_size_bytes,
+        source_modified_nanoseconds,
+        row_interval,
+        data_row_count,
+        indexed_row_offsets,
+    })
+}
+
+/// Writes a row offset index to the CSV file's sidecar file
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file the index describes
+/// * `row_offset_index` - The index to write
+///
+/// # Returns
+/// * `Result<PathBuf, io::Error>` - Path of the written sidecar file
+pub fn write_row_offset_index_file(
+    csv_file_path: &Path,
+    row_offset_index: &CsvRowOffsetIndex,
+) -> Result<PathBuf, io::Error> {
+    let index_file_path = row_offset_index_path(csv_file_path);
+    let mut index_file_writer = BufWriter::new(File::create(&index_file_path)?);
+    index_file_writer.write_all(row_offset_index.to_sidecar_file_content().as_bytes())?;
+    index_file_writer.flush()?;
+    Ok(index_file_path)
+}
+
+/// Loads a CSV file's sidecar index, rebuilding it first if it is stale
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+///
+/// # Returns
+/// * `Result<Option<CsvRowOffsetIndex>, io::Error>` - The current index, or `None`
+///   if the file has no sidecar index
+///
+/// # Errors
+/// * `io::ErrorKind::InvalidData` - If the sidecar file is malformed
+pub fn load_current_row_offset_index(csv_file_path: &Path) -> Result<Option<CsvRowOffsetIndex>, io::Error> {
+    let index_file_path = row_offset_index_path(csv_file_path);
+    if !index_file_path.exists() {
+        return Ok(None);
+    }
+
+    let file_content = fs::read_to_string(&index_file_path)?;
+    let row_offset_index = CsvRowOffsetIndex::from_sidecar_file_content(&file_content).map_err(|parse_message| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Malformed row index '{}': {} (delete it or rebuild it with --index)",
+                index_file_path.display(),
+                parse_message
+            ),
+        )
+    })?;
+
+    let (source_file_size_bytes, source_modified_nanoseconds) = read_source_file_identity(csv_file_path)?;
+    if row_offset_index.is_current_for(source_file_size_bytes, source_modified_nanoseconds) {
+        return Ok(Some(row_offset_index));
+    }
+
+    // The CSV file changed since the index was built: rebuild it with the same interval
+    eprintln!("Note: '{}' is out of date; rebuilding it", index_file_path.display());
+    let rebuilt_row_offset_index = build_row_offset_index(csv_file_path, row_offset_index.row_interval)?;
+    write_row_offset_index_file(csv_file_path, &rebuilt_row_offset_index)?;
+    Ok(Some(rebuilt_row_offset_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Writes a CSV file into the temporary directory and returns its path
+    fn write_test_csv_file(file_name: &str, csv_text: &str) -> PathBuf {
+        let csv_file_path = std::env::temp_dir().join(format!("{}_{}", std::process::id(), file_name));
+        fs::write(&csv_file_path, csv_text).expect("test CSV file should be writable");
+        csv_file_path
+    }
+
+    #[test]
+    fn test_build_index_and_seek_positions() {
+        let csv_text = "id,note\n0,a\n1,\"two\nlines\"\n\n2,c\n3,d\n4,e\n";
+        let csv_file_path = write_test_csv_file("row_offset_index_build.csv", csv_text);
+
+        let row_offset_index = build_row_offset_index(&csv_file_path, 2).expect("index should build");
+        assert_eq!(row_offset_index.data_row_count, 5);
+        assert_eq!(row_offset_index.indexed_row_offsets, vec![8, 27, 35]);
+        assert_eq!(&csv_text[27..30], "2,c");
+
+        assert_eq!(row_offset_index.seek_position_for_row(0), Some((8, 0)));
+        assert_eq!(row_offset_index.seek_position_for_row(3), Some((27, 1)));
+        assert_eq!(row_offset_index.seek_position_for_row(4), Some((35, 0)));
+        assert_eq!(row_offset_index.seek_position_for_row(5), None);
+
+        fs::remove_file(&csv_file_path).expect("test CSV file should be removable");
+    }
+
+    #[test]
+    fn test_sidecar_content_round_trip_and_errors() {
+        let row_offset_index = CsvRowOffsetIndex {
+            source_file_size_bytes: 120,
+            source_modified_nanoseconds: 1_760_000_123_456_789_012,
+            row_interval: 10,
+            data_row_count: 25,
+            indexed_row_offsets: vec![14, 60, 101],
+        };
+        let file_content = row_offset_index.to_sidecar_file_content();
+        assert_eq!(
+            CsvRowOffsetIndex::from_sidecar_file_content(&file_content),
+            Ok(row_offset_index.clone())
+        );
+        assert!(row_offset_index.is_current_for(120, 1_760_000_123_456_789_012));
+        assert!(!row_offset_index.is_current_for(121, 1_760_000_123_456_789_012));
+        assert!(!row_offset_index.is_current_for(120, 1_760_000_123_456_789_013));
+
+        // Sidecar files from before nanosecond times are read in whole seconds
+        let seconds_content = file_content.replace(
+            "source_modified_nanoseconds = 1760000123456789012",
+            "source_modified_seconds = 1760000123",
+        );
+        let seconds_index = CsvRowOffsetIndex::from_sidecar_file_content(&seconds_content).expect("old sidecar should parse");
+        assert_eq!(seconds_index.source_modified_nanoseconds, 1_760_000_123_000_000_000);
+
+        let missing_offset_content = file_content.replace("101\n", "");
+        assert!(CsvRowOffsetIndex::from_sidecar_file_content(&missing_offset_content).is_err());
+        assert!(CsvRowOffsetIndex::from_sidecar_file_content("row_interval = 10\n").is_err());
+    }
+
+    #[test]
+    fn test_stale_index_is_rebuilt() {
+        let csv_file_path = write_test_csv_file("row_offset_index_stale.csv", "id\n1\n2\n");
+        assert_eq!(load_current_row_offset_index(&csv_file_path).expect("lookup should work"), None);
+
+        let row_offset_index = build_row_offset_index(&csv_file_path, 1).expect("index should build");
+        write_row_offset_index_file(&csv_file_path, &row_offset_index).expect("index should be writable");
+
+        fs::write(&csv_file_path, "id\n1\n2\n3\n").expect("test CSV file should be writable");
+        let rebuilt_row_offset_index = load_current_row_offset_index(&csv_file_path)
+            .expect("stale index should be rebuilt")
+            .expect("index file should exist");
+        assert_eq!(rebuilt_row_offset_index.data_row_count, 3);
+        assert_eq!(rebuilt_row_offset_index.indexed_row_offsets, vec![3, 5, 7]);
+
+        fs::remove_file(row_offset_index_path(&csv_file_path)).expect("index file should be removable");
+        fs::remove_file(&csv_file_path).expect("test CSV file should be removable");
+    }
+
+    #[test]
+    fn test_same_size_rewrite_within_one_second_is_stale() {
+        let csv_file_path = write_test_csv_file("row_offset_index_same_second.csv", "id\n1\n22\n");
+        let whole_second = UNIX_EPOCH + Duration::from_secs(1_760_000_000);
+        let set_modified_time = |after_whole_second: Duration| {
+            File::options()
+                .write(true)
+                .open(&csv_file_path)
+                .and_then(|csv_file| csv_file.set_modified(whole_second + after_whole_second))
+                .expect("modification time should be settable");
+        };
+        set_modified_time(Duration::from_millis(100));
+        let row_offset_index = build_row_offset_index(&csv_file_path, 1).expect("index should build");
+        write_row_offset_index_file(&csv_file_path, &row_offset_index).expect("index should be writable");
+        assert_eq!(row_offset_index.indexed_row_offsets, vec![3, 5]);
+
+        // Same size, same whole second, different row boundaries
+        fs::write(&csv_file_path, "id\n11\n2\n").expect("test CSV file should be writable");
+        set_modified_time(Duration::from_millis(600));
+        let rebuilt_row_offset_index = load_current_row_offset_index(&csv_file_path)
+            .expect("stale index should be rebuilt")
+            .expect("index file should exist");
+        assert_eq!(rebuilt_row_offset_index.indexed_row_offsets, vec![3, 6]);
+
+        fs::remove_file(row_offset_index_path(&csv_file_path)).expect("index file should be removable");
+        fs::remove_file(&csv_file_path).expect("test CSV file should be removable");
+    }
+}
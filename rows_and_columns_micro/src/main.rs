@@ -12,8 +12,16 @@
 //! 
 //! # To display a specific row:
 //! cargo run -- --row 5 path/to/large_file.csv
+//! 
+//! # To display rows 100 through 109:
+//! cargo run -- --rows 100..109 path/to/large_file.csv
+//! 
+//! # To build a sidecar row index so --row and --rows seek instead of scanning:
+//! cargo run -- --index path/to/large_file.csv --every 1000
 //! ```
 
+mod csv_record_reader_module;
+mod csv_row_offset_index_module;
 mod rows_and_columns_module;
 
 /// Main entry point for the CSV inspection tool.
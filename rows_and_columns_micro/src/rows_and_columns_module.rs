@@ -3,13 +3,23 @@
 //! This module provides functions for inspecting large CSV files without
 //! loading them entirely into memory. It supports header inspection and
 //! random access to specific rows using efficient streaming techniques.
+//! When a sidecar row offset index exists (see `--index`), rows are reached
+//! by seeking close to them instead of reading the file from the start.
 
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
 use std::path::Path;
 use std::env;
 use std::process;
 
+use crate::csv_record_reader_module::CsvRecordReader;
+use crate::csv_row_offset_index_module::{
+    DEFAULT_ROW_OFFSET_INTERVAL,
+    build_row_offset_index,
+    load_current_row_offset_index,
+    write_row_offset_index_file,
+};
+
 /// Parses command line arguments and executes the appropriate CSV inspection function.
 ///
 /// This function handles the CLI commands, validates arguments, and calls the
@@ -19,6 +29,8 @@ use std::process;
 ///
 /// * `--describe <file_path>` - Show column count, header names, and row count
 /// * `--row <row_index> <file_path>` - Display a specific row with headers
+/// * `--rows <first>..<last> <file_path>` - Display a range of rows with headers
+/// * `--index <file_path> [--every <K>]` - Build the sidecar row offset index
 ///
 /// # Returns
 ///
@@ -105,6 +117,81 @@ pub fn csv_inspection_main() {
                 }
             }
         },
+        "--rows" => {
+            // Need at least 4 arguments for --rows command
+            if args.len() < 4 {
+                eprintln!("Error: Missing row range or file path");
+                print_usage(&args[0]);
+                process::exit(1);
+            }
+            
+            // Parse row range
+            let (first_row_index, last_row_index) = match parse_row_index_range(&args[2]) {
+                Ok(row_range) => row_range,
+                Err(message) => {
+                    eprintln!("Error: {}", message);
+                    process::exit(1);
+                }
+            };
+            
+            let file_path = &args[3];
+            
+            // Validate file path exists
+            if !Path::new(file_path).exists() {
+                eprintln!("Error: File '{}' does not exist", file_path);
+                process::exit(1);
+            }
+            
+            // Display the specified rows
+            match display_csv_row_range(file_path, first_row_index, last_row_index) {
+                Ok(_) => (),
+                Err(e) => {
+                    eprintln!("Error displaying rows: {}", e);
+                    process::exit(1);
+                }
+            }
+        },
+        "--index" => {
+            if args.len() < 3 {
+                eprintln!("Error: Missing file path");
+                print_usage(&args[0]);
+                process::exit(1);
+            }
+            
+            let file_path = &args[2];
+            
+            // Parse the optional row interval
+            let row_interval = match args.get(3).map(String::as_str) {
+                None => DEFAULT_ROW_OFFSET_INTERVAL,
+                Some("--every") => match args.get(4).and_then(|interval_text| interval_text.parse::<usize>().ok()) {
+                    Some(row_interval) if row_interval > 0 => row_interval,
+                    _ => {
+                        eprintln!("Error: --every needs a positive number of rows");
+                        process::exit(1);
+                    }
+                },
+                Some(unknown_option) => {
+                    eprintln!("Error: Unknown option '{}'", unknown_option);
+                    print_usage(&args[0]);
+                    process::exit(1);
+                }
+            };
+            
+            // Validate file path exists
+            if !Path::new(file_path).exists() {
+                eprintln!("Error: File '{}' does not exist", file_path);
+                process::exit(1);
+            }
+            
+            // Build and save the sidecar index
+            match create_row_offset_index(file_path, row_interval) {
+                Ok(_) => (),
+                Err(e) => {
+                    eprintln!("Error building row index: {}", e);
+                    process::exit(1);
+                }
+            }
+        },
         _ => {
             eprintln!("Error: Unknown command '{}'", args[1]);
             print_usage(&args[0]);
@@ -206,47 +293,157 @@ fn print_usage(program_name: &str) {
     eprintln!("Usage:");
     eprintln!("  {} --describe <csv_file_path>", program_name);
     eprintln!("  {} --row <row_index> <csv_file_path>", program_name);
+    eprintln!("  {} --rows <first>..<last> <csv_file_path>", program_name);
+    eprintln!("  {} --index <csv_file_path> [--every <rows>]", program_name);
     eprintln!("\nExample:");
     eprintln!("  {} --describe data.csv", program_name);
     eprintln!("  {} --row 5 data.csv", program_name);
+    eprintln!("  {} --rows 100..109 data.csv", program_name);
+    eprintln!("  {} --index data.csv --every 1000", program_name);
+    eprintln!("\nRow indexes start at 0 and ranges include both ends.");
+    eprintln!("--index writes data.csv.row_index; --row and --rows then seek");
+    eprintln!("straight to the requested rows (the index is rebuilt when the");
+    eprintln!("CSV file changes).");
 }
 
-/// Parses a line of CSV text into separate fields.
+/// Parses a row range of the form `first..last` (0-based, both ends included).
 ///
 /// # Arguments
 ///
-/// * `line` - A line of CSV text to parse
+/// * `range_text` - The range as typed on the command line
 ///
 /// # Returns
 ///
-/// * `Vec<String>` - Vector of parsed fields
-fn parse_csv_line(line: &str) -> Vec<String> {
-    let mut fields = Vec::new();
-    let mut current_field = String::new();
-    let mut in_quotes = false;
-    
-    for ch in line.chars() {
-        match ch {
-            '"' => {
-                // Toggle quoted state
-                in_quotes = !in_quotes;
-            },
-            ',' if !in_quotes => {
-                // End of field if comma outside quotes
-                fields.push(current_field.trim().to_string());
-                current_field = String::new();
-            },
-            _ => {
-                // Add any other character to current field
-                current_field.push(ch);
-            }
-        }
+/// * `Result<(usize, usize), String>` - First and last row index, or a message explaining the problem
+fn parse_row_index_range(range_text: &str) -> Result<(usize, usize), String> {
+    let (first_text, last_text) = range_text
+        .split_once("..")
+        .ok_or_else(|| format!("Row range must look like first..last: '{}'", range_text))?;
+    
+    let first_row_index = first_text.trim().parse::<usize>()
+        .map_err(|_| format!("Row indexes must be non-negative integers: '{}'", range_text))?;
+    let last_row_index = last_text.trim().parse::<usize>()
+        .map_err(|_| format!("Row indexes must be non-negative integers: '{}'", range_text))?;
+    
+    if last_row_index < first_row_index {
+        return Err(format!("Row range ends before it starts: '{}'", range_text));
     }
     
-    // Add the last field
-    fields.push(current_field.trim().to_string());
+    Ok((first_row_index, last_row_index))
+}
+
+/// Builds the sidecar row offset index of a CSV file and reports its size.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to the CSV file
+/// * `row_interval` - Number of data rows between two indexed offsets
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Success or an I/O error
+pub fn create_row_offset_index(file_path: impl AsRef<Path>, row_interval: usize) -> Result<(), io::Error> {
+    let row_offset_index = build_row_offset_index(file_path.as_ref(), row_interval)?;
+    let index_file_path = write_row_offset_index_file(file_path.as_ref(), &row_offset_index)?;
+    
+    println!("Row index written: {}", index_file_path.display());
+    println!("  Data rows: {}", row_offset_index.data_row_count);
+    println!("  Offsets stored: {} (every {} rows)", row_offset_index.indexed_row_offsets.len(), row_interval);
+    
+    Ok(())
+}
+
+/// Opens a CSV file and positions a record reader at a data row.
+///
+/// Uses the sidecar row offset index when one exists, seeking to the nearest
+/// indexed record and skipping at most `row_interval - 1` records. Without an
+/// index, records are skipped from the start of the file.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to the CSV file
+/// * `row_index` - Index of the row the reader should return next (0-based, not counting header)
+///
+/// # Returns
+///
+/// * `Result<(Vec<String>, CsvRecordReader<BufReader<File>>), io::Error>` - Trimmed headers and the positioned reader
+fn open_csv_records_at_row(
+    file_path: &Path,
+    row_index: usize,
+) -> Result<(Vec<String>, CsvRecordReader<BufReader<File>>), io::Error> {
+    // Open the file
+    let file = File::open(file_path)?;
+    let mut csv_record_reader = CsvRecordReader::new(BufReader::new(file));
+    
+    // Read only the first record for headers
+    let headers = match read_next_trimmed_csv_record(&mut csv_record_reader)? {
+        Some(header_fields) => header_fields,
+        None => return Err(io::Error::new(io::ErrorKind::InvalidData, "Empty CSV file")),
+    };
+    
+    // Seek close to the target row when a current index exists
+    let mut records_to_skip = row_index;
+    if let Some(row_offset_index) = load_current_row_offset_index(file_path)? {
+        let (seek_byte_offset, remaining_records_to_skip) = row_offset_index
+            .seek_position_for_row(row_index)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Row index {} out of range (file has only {} data rows)",
+                        row_index, row_offset_index.data_row_count
+                    ),
+                )
+            })?;
+        
+        let mut file = File::open(file_path)?;
+        file.seek(SeekFrom::Start(seek_byte_offset))?;
+        csv_record_reader = CsvRecordReader::new(BufReader::new(file));
+        records_to_skip = remaining_records_to_skip;
+    }
+    
+    // Skip records before the target row, one record at a time
+    // (a record can span several lines when a quoted field contains line breaks)
+    for skipped_record_count in 0..records_to_skip {
+        if csv_record_reader.read_next_record()?.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Row index {} out of range (file has only {} data rows)",
+                    row_index,
+                    row_index - records_to_skip + skipped_record_count
+                ),
+            ));
+        }
+    }
     
-    fields
+    Ok((headers, csv_record_reader))
+}
+
+/// Reads the next CSV record and trims each field for display.
+///
+/// Quoted fields may contain commas, escaped quotes, and line breaks, so
+/// records are read with the shared RFC 4180 record reader rather than by
+/// splitting lines.
+///
+/// # Arguments
+///
+/// * `csv_record_reader` - The record reader to take the next record from
+///
+/// # Returns
+///
+/// * `Result<Option<Vec<String>>, io::Error>` - Trimmed fields, `None` at end of file, or an I/O error
+fn read_next_trimmed_csv_record<R: BufRead>(
+    csv_record_reader: &mut CsvRecordReader<R>,
+) -> Result<Option<Vec<String>>, io::Error> {
+    let next_record = csv_record_reader.read_next_record()?;
+    
+    Ok(next_record.map(|csv_record| {
+        csv_record.field_values
+            .iter()
+            .map(|field_value| field_value.trim().to_string())
+            .collect()
+    }))
 }
 
 /// Describes a CSV file's structure and outputs a summary.
@@ -262,29 +459,19 @@ fn parse_csv_line(line: &str) -> Vec<String> {
 pub fn describe_csv(file_path: impl AsRef<Path>) -> Result<(), io::Error> {
     // Open the file
     let file = File::open(file_path)?;
-    let mut reader = BufReader::new(file);
+    let mut csv_record_reader = CsvRecordReader::new(BufReader::new(file));
     
-    // Read only the first line for headers
-    let mut header_line = String::new();
-    let bytes_read = reader.read_line(&mut header_line)?;
+    // Read only the first record for headers
+    let headers = match read_next_trimmed_csv_record(&mut csv_record_reader)? {
+        Some(header_fields) => header_fields,
+        None => return Err(io::Error::new(io::ErrorKind::InvalidData, "Empty CSV file")),
+    };
     
-    if bytes_read == 0 {
-        return Err(io::Error::new(io::ErrorKind::InvalidData, "Empty CSV file"));
-    }
-    
-    // Parse headers
-    let headers = parse_csv_line(&header_line);
-    
-    // Count remaining lines without loading their content
+    // Count remaining records without keeping their content
+    // (blank lines are skipped by the record reader)
     let mut row_count = 0;
-    let mut line_buffer = String::new();
-    
-    while reader.read_line(&mut line_buffer)? > 0 {
-        if !line_buffer.trim().is_empty() {
-            row_count += 1;
-        }
-        // Clear buffer for next line to minimize memory usage
-        line_buffer.clear();
+    while csv_record_reader.read_next_record()?.is_some() {
+        row_count += 1;
     }
     
     // Calculate column widths for display
@@ -331,7 +518,8 @@ fn print_headers_table(headers: &[String], column_widths: &[usize]) {
 }
 
 /// Displays a specific row from a CSV file with headers.
-/// Only reads the header row and the requested row, not the entire file.
+/// Reads the header row and the requested row; the records in between are
+/// skipped, or mostly jumped over when a sidecar row offset index exists.
 ///
 /// # Arguments
 ///
@@ -342,66 +530,19 @@ fn print_headers_table(headers: &[String], column_widths: &[usize]) {
 ///
 /// * `Result<(), io::Error>` - Success or an I/O error
 pub fn display_csv_row(file_path: impl AsRef<Path>, row_index: usize) -> Result<(), io::Error> {
-    // Open the file
-    let file = File::open(&file_path)?;
-    let mut reader = BufReader::new(file);
-    
-    // Read only the first line for headers
-    let mut header_line = String::new();
-    let bytes_read = reader.read_line(&mut header_line)?;
-    
-    if bytes_read == 0 {
-        return Err(io::Error::new(io::ErrorKind::InvalidData, "Empty CSV file"));
-    }
-    
-    // Parse headers
-    let headers = parse_csv_line(&header_line);
+    // Open the file at the target row
+    let (headers, mut csv_record_reader) = open_csv_records_at_row(file_path.as_ref(), row_index)?;
     
-    // Skip to the target row (for very large files, this is more efficient than reading each line)
-    let mut current_row = 0;
-    let mut row_data = Vec::new();
-    let mut line_buffer = String::new();
-    
-    while current_row < row_index {
-        // Read a line and check if we reached EOF
-        let bytes_read = reader.read_line(&mut line_buffer)?;
-        if bytes_read == 0 {
+    // Read the target row
+    let row_data = match read_next_trimmed_csv_record(&mut csv_record_reader)? {
+        Some(row_fields) => row_fields,
+        None => {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
-                format!("Row index {} out of range (file has only {} data rows)", row_index, current_row)
+                format!("Row index {} out of range (file has only {} data rows)", row_index, row_index)
             ));
         }
-        
-        // If line is not empty, count it as a data row
-        if !line_buffer.trim().is_empty() {
-            current_row += 1;
-        }
-        
-        // Clear the buffer for next line
-        line_buffer.clear();
-    }
-    
-    // Read the target row
-    reader.read_line(&mut line_buffer)?;
-    if !line_buffer.trim().is_empty() {
-        row_data = parse_csv_line(&line_buffer);
-    } else {
-        // Try to read the next non-empty line if current is empty
-        while reader.read_line(&mut line_buffer)? > 0 {
-            if !line_buffer.trim().is_empty() {
-                row_data = parse_csv_line(&line_buffer);
-                break;
-            }
-            line_buffer.clear();
-        }
-    }
-    
-    if row_data.is_empty() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("Row {} is empty or couldn't be read", row_index)
-        ));
-    }
+    };
     
     // Calculate column widths (max of header and value width)
     let mut column_widths = Vec::new();
@@ -444,6 +585,93 @@ pub fn display_csv_row(file_path: impl AsRef<Path>, row_index: usize) -> Result<
     Ok(())
 }
 
+/// Displays a range of rows from a CSV file with headers.
+/// Reads the header row and the requested rows; the records before the range
+/// are skipped, or mostly jumped over when a sidecar row offset index exists.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to the CSV file
+/// * `first_row_index` - Index of the first row to display (0-based, not counting header)
+/// * `last_row_index` - Index of the last row to display (included)
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - Success or an I/O error
+pub fn display_csv_row_range(
+    file_path: impl AsRef<Path>,
+    first_row_index: usize,
+    last_row_index: usize,
+) -> Result<(), io::Error> {
+    // Open the file at the first row of the range
+    let (headers, mut csv_record_reader) = open_csv_records_at_row(file_path.as_ref(), first_row_index)?;
+    
+    // Read the rows of the range (a range past the end stops at the last row)
+    let mut range_rows = Vec::new();
+    for _ in first_row_index..=last_row_index {
+        match read_next_trimmed_csv_record(&mut csv_record_reader)? {
+            Some(row_fields) => range_rows.push(row_fields),
+            None => break,
+        }
+    }
+    if range_rows.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Row index {} out of range (file has only {} data rows)", first_row_index, first_row_index)
+        ));
+    }
+    let shown_last_row_index = first_row_index + range_rows.len() - 1;
+    
+    // Calculate column widths (row number column first, then max of header and value widths)
+    let mut column_widths = vec!["row".len().max(shown_last_row_index.to_string().len())];
+    for (i, header) in headers.iter().enumerate() {
+        let value_width = range_rows
+            .iter()
+            .map(|row_data| row_data.get(i).map_or(0, |value| value.len()))
+            .max()
+            .unwrap_or(0);
+        column_widths.push(header.len().max(value_width));
+    }
+    
+    // Print table
+    println!("Rows {}..{} Data:", first_row_index, shown_last_row_index);
+    println!("================");
+    
+    // Print top border
+    print_horizontal_border(&column_widths);
+    
+    // Print header row
+    print!("| {:<width$} | ", "row", width = column_widths[0]);
+    for (i, header) in headers.iter().enumerate() {
+        let width = column_widths[i + 1];
+        print!("{:<width$} | ", header, width = width);
+    }
+    println!();
+    
+    // Print separator
+    print_horizontal_border(&column_widths);
+    
+    // Print value rows
+    for (row_offset, row_data) in range_rows.iter().enumerate() {
+        print!("| {:>width$} | ", first_row_index + row_offset, width = column_widths[0]);
+        for (i, _) in headers.iter().enumerate() {
+            let width = column_widths[i + 1];
+            let value = if i < row_data.len() { &row_data[i] } else { "" };
+            print!("{:<width$} | ", value, width = width);
+        }
+        println!();
+    }
+    
+    // Print bottom border
+    print_horizontal_border(&column_widths);
+    
+    if shown_last_row_index < last_row_index {
+        println!("(file ends after row {})", shown_last_row_index);
+    }
+    
+    Ok(())
+}
+
 /// Helper function to print horizontal borders for tables.
 ///
 /// # Arguments
@@ -457,4 +685,17 @@ fn print_horizontal_border(column_widths: &[usize]) {
     println!();
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn test_parse_row_index_range() {
+        assert_eq!(parse_row_index_range("5..9"), Ok((5, 9)));
+        assert_eq!(parse_row_index_range("0..0"), Ok((0, 0)));
+        assert!(parse_row_index_range("9..5").is_err());
+        assert!(parse_row_index_range("5").is_err());
+        assert!(parse_row_index_range("..9").is_err());
+        assert!(parse_row_index_range("-1..3").is_err());
+    }
+}
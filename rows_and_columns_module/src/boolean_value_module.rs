@@ -0,0 +1,179 @@
+// src/boolean_value_module.rs
+
+/// Boolean vocabulary for rows_and_columns
+///
+/// A value is boolean only if it is one of the configured true or false
+/// tokens, compared without regard to case. The defaults are `true`/`yes` and
+/// `false`/`no`; `1`/`0` and single letters like `y`/`n` or `t`/`f` are not
+/// boolean unless listed, because count columns and letter code columns use
+/// them too. Other languages are added the same way, e.g. `oui`/`non` or
+/// `ja`/`nein`.
+///
+/// The tokens are set with `true_values` and `false_values` in the
+/// `[type_inference]` section of the metadata file. A column is only inferred
+/// as boolean once it contains at least one true and one false token.
+use super::null_value_module::{MissingValueCounts, NullValueSentinels};
+
+/// True tokens used when the metadata file does not set `true_values`
+pub const DEFAULT_TRUE_TOKENS: [&str; 2] = ["true", "yes"];
+
+/// False tokens used when the metadata file does not set `false_values`
+pub const DEFAULT_FALSE_TOKENS: [&str; 2] = ["false", "no"];
+
+/// The words accepted as true and false
+#[derive(Debug, Clone, PartialEq)]
+pub struct BooleanVocabulary {
+    /// Tokens read as true (any case)
+    pub true_tokens: Vec<String>,
+
+    /// Tokens read as false (any case)
+    pub false_tokens: Vec<String>,
+}
+
+impl Default for BooleanVocabulary {
+    fn default() -> Self {
+        BooleanVocabulary {
+            true_tokens: DEFAULT_TRUE_TOKENS.iter().map(|true_token| true_token.to_string()).collect(),
+            false_tokens: DEFAULT_FALSE_TOKENS.iter().map(|false_token| false_token.to_string()).collect(),
+        }
+    }
+}
+
+impl BooleanVocabulary {
+    /// Reads a value as a boolean
+    ///
+    /// # Arguments
+    /// * `trimmed_value` - The trimmed value
+    ///
+    /// # Returns
+    /// * `Option<bool>` - The boolean, or None if the value is not in the vocabulary
+    pub fn parse_boolean_value(&self, trimmed_value: &str) -> Option<bool> {
+        let lowercase_value = trimmed_value.to_lowercase();
+        let token_matches = |vocabulary_token: &String| vocabulary_token.to_lowercase() == lowercase_value;
+
+        if self.true_tokens.iter().any(token_matches) {
+            Some(true)
+        } else if self.false_tokens.iter().any(token_matches) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Finds a token listed as both true and false
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The first true token that is also a false token (any case)
+    pub fn find_conflicting_token(&self) -> Option<&str> {
+        self.true_tokens
+            .iter()
+            .find(|true_token| {
+                self.false_tokens
+                    .iter()
+                    .any(|false_token| false_token.to_lowercase() == true_token.to_lowercase())
+            })
+            .map(|true_token| true_token.as_str())
+    }
+}
+
+/// True, false and missing counts of one boolean column
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BooleanValueCounts {
+    /// Values read as true
+    pub true_value_count: usize,
+
+    /// Values read as false
+    pub false_value_count: usize,
+
+    /// Blanks, null sentinels and values outside the vocabulary
+    pub missing_value_counts: MissingValueCounts,
+}
+
+impl BooleanValueCounts {
+    /// Counts one value as true, false or missing
+    ///
+    /// # Arguments
+    /// * `value_string` - The field value
+    /// * `boolean_vocabulary` - Tokens read as true and false
+    /// * `null_value_sentinels` - Values counted as null
+    pub fn record_value(
+        &mut self,
+        value_string: &str,
+        boolean_vocabulary: &BooleanVocabulary,
+        null_value_sentinels: &NullValueSentinels,
+    ) {
+        let trimmed_value = value_string.trim();
+        if let Some(missing_value_kind) = null_value_sentinels.classify_missing_value(trimmed_value) {
+            self.missing_value_counts.record_missing_value(missing_value_kind);
+            return;
+        }
+
+        match boolean_vocabulary.parse_boolean_value(trimmed_value) {
+            Some(true) => self.true_value_count += 1,
+            Some(false) => self.false_value_count += 1,
+            None => self.missing_value_counts.type_invalid_count += 1,
+        }
+    }
+
+    /// Number of values counted (true, false and missing together)
+    ///
+    /// # Returns
+    /// * `usize` - Total number of values
+    pub fn total_value_count(&self) -> usize {
+        self.true_value_count + self.false_value_count + self.missing_value_counts.total_missing_count()
+    }
+
+    /// Number of blank and null sentinel values (values outside the vocabulary excluded)
+    ///
+    /// # Returns
+    /// * `usize` - Number of null values
+    pub fn null_value_count(&self) -> usize {
+        self.missing_value_counts.blank_value_count + self.missing_value_counts.null_sentinel_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_vocabulary_excludes_digits_and_letters() {
+        let boolean_vocabulary = BooleanVocabulary::default();
+
+        assert_eq!(boolean_vocabulary.parse_boolean_value("TRUE"), Some(true));
+        assert_eq!(boolean_vocabulary.parse_boolean_value("No"), Some(false));
+        assert_eq!(boolean_vocabulary.parse_boolean_value("1"), None);
+        assert_eq!(boolean_vocabulary.parse_boolean_value("y"), None);
+    }
+
+    #[test]
+    fn test_record_values() {
+        let boolean_vocabulary = BooleanVocabulary::default();
+        let null_value_sentinels = NullValueSentinels::default();
+        let mut boolean_value_counts = BooleanValueCounts::default();
+        for column_value in ["yes", "No", "", "NA", "maybe", "YES"] {
+            boolean_value_counts.record_value(column_value, &boolean_vocabulary, &null_value_sentinels);
+        }
+
+        assert_eq!(boolean_value_counts.true_value_count, 2);
+        assert_eq!(boolean_value_counts.false_value_count, 1);
+        assert_eq!(boolean_value_counts.null_value_count(), 2);
+        assert_eq!(boolean_value_counts.missing_value_counts.type_invalid_count, 1);
+        assert_eq!(boolean_value_counts.total_value_count(), 6);
+    }
+
+    #[test]
+    fn test_configured_vocabulary_and_conflicts() {
+        let mut boolean_vocabulary = BooleanVocabulary {
+            true_tokens: vec!["oui".to_string(), "ja".to_string()],
+            false_tokens: vec!["non".to_string(), "nein".to_string()],
+        };
+
+        assert_eq!(boolean_vocabulary.parse_boolean_value("Oui"), Some(true));
+        assert_eq!(boolean_vocabulary.parse_boolean_value("NEIN"), Some(false));
+        assert_eq!(boolean_vocabulary.find_conflicting_token(), None);
+
+        boolean_vocabulary.false_tokens.push("JA".to_string());
+        assert_eq!(boolean_vocabulary.find_conflicting_token(), Some("ja"));
+    }
+}
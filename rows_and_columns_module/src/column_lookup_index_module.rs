@@ -0,0 +1,767 @@
+// src/column_lookup_index_module.rs
+
+/// Value-to-row lookup indexes for the columns of an imported dataset
+///
+/// Any column of a dataset made by the directory import can get a lookup
+/// index, not only an id column. The index answers "which rows have
+/// breed = Siamese" by binary search in a sorted key file instead of reading
+/// every value of the column.
+///
+/// # Index File
+/// The index of `column_004_breed` is
+/// `dataset_NNN_name/lookup_indexes/column_004_breed.lookup`, a plain text file:
+/// ```text
+/// # rows_and_columns lookup index
+/// # column_directory = column_004_breed
+/// # imported_rows = 5
+/// # source_file_size_bytes = 262
+/// # source_modified_seconds = 1760000000
+/// # column_storage_files = 5
+/// # column_storage_bytes = 38
+/// # column_storage_modified_nanoseconds = 1760000123456789012
+/// # distinct_values = 5
+/// Black Cat<TAB>3
+/// Maine Coon<TAB>5
+/// Persian<TAB>4
+/// Siamese<TAB>1
+/// Tabby<TAB>2
+/// ```
+/// After the header, each line holds one distinct value (escaped like a
+/// column segment line: `\\`, `\n`, `\r`), a tab, and the 1-based numbers of
+/// the rows holding it. Lines are sorted by the escaped value, byte by byte.
+///
+/// # Staleness
+/// The header records the import it was built from (row count and source file
+/// size and modification time, copied from `import_progress.toml`) and the
+/// files storing the column (their number, total size and newest modification
+/// time). When either no longer matches, for example because a stored cell was
+/// edited by hand, the index is rebuilt before it answers a lookup.
+///
+/// # Command Line
+/// ```text
+/// rows_and_columns --index-column <dataset> <column>
+/// rows_and_columns --lookup <dataset> <column> <value>
+/// ```
+/// `<column>` is a column name, column directory name or 1-based column number.
+/// `<value>` must match the stored value exactly, including its case:
+/// `Siamese` finds the rows that `siamese` does not.
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// Import the dataset layout written by the directory import
+use super::csv_directory_import_module::{
+    DATASET_METADATA_FILE_NAME,
+    ImportProgressRecord,
+    open_imported_dataset_reader,
+};
+
+// Import column naming and selection shared with the CSV export
+use super::csv_directory_export_module::{DatasetColumn, list_dataset_columns, select_dataset_columns};
+
+// Import the storage reader and the one-value-per-line escaping
+use super::dataset_storage_module::{DatasetStorageReader, escape_segment_value};
+
+// Import metadata reading for the column names
+use super::metadata_manager_module::read_metadata_toml_file;
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_configuration_error,
+    create_file_system_error,
+    create_metadata_error,
+};
+
+/// Directory inside a dataset directory that holds the lookup index files
+const LOOKUP_INDEX_DIRECTORY_NAME: &str = "lookup_indexes";
+
+/// Extension of a lookup index file
+const LOOKUP_INDEX_FILE_EXTENSION: &str = "lookup";
+
+/// First line of every lookup index file
+const LOOKUP_INDEX_FILE_HEADER: &str = "# rows_and_columns lookup index";
+
+/// Most matching row numbers printed for one lookup
+const LOOKUP_RESULT_DISPLAY_LIMIT: usize = 100;
+
+/// What a lookup index was built from (the header lines of the index file)
+#[derive(Debug, Clone, PartialEq)]
+struct LookupIndexHeader {
+    /// Column directory the index describes
+    column_directory_name: String,
+
+    /// Rows in the dataset when the index was built
+    imported_row_count: usize,
+
+    /// Size of the dataset's source CSV file when the index was built
+    source_file_size_bytes: u64,
+
+    /// Modification time of the dataset's source CSV file when the index was built
+    source_modified_seconds: u64,
+
+    /// The files storing the column when the index was built
+    column_storage_identity: ColumnStorageIdentity,
+
+    /// Number of index lines (distinct values)
+    distinct_value_count: usize,
+}
+
+impl LookupIndexHeader {
+    /// Checks whether the index still describes the dataset's column
+    ///
+    /// # Arguments
+    /// * `import_progress_record` - The dataset's current progress record
+    /// * `column_directory_name` - The column the index should describe
+    /// * `column_storage_identity` - The files storing the column now
+    ///
+    /// # Returns
+    /// * `bool` - True if the dataset has not changed since the index was built
+    fn is_current_for(
+        &self,
+        import_progress_record: &ImportProgressRecord,
+        column_directory_name: &str,
+        column_storage_identity: ColumnStorageIdentity,
+    ) -> bool {
+        self.column_directory_name == column_directory_name
+            && self.imported_row_count == import_progress_record.imported_row_count
+            && self.source_file_size_bytes == import_progress_record.source_file_size_bytes
+            && self.source_modified_seconds == import_progress_record.source_modified_seconds
+            && self.column_storage_identity == column_storage_identity
+    }
+
+    /// Formats the header lines of an index file
+    ///
+    /// # Returns
+    /// * `String` - The header lines, each ending with a line feed
+    fn to_header_lines(&self) -> String {
+        let mut header_text = String::new();
+        header_text.push_str(LOOKUP_INDEX_FILE_HEADER);
+        header_text.push('\n');
+        header_text.push_str(&format!("# column_directory = {}\n", self.column_directory_name));
+        header_text.push_str(&format!("# imported_rows = {}\n", self.imported_row_count));
+        header_text.push_str(&format!("# source_file_size_bytes = {}\n", self.source_file_size_bytes));
+        header_text.push_str(&format!("# source_modified_seconds = {}\n", self.source_modified_seconds));
+        header_text.push_str(&format!("# column_storage_files = {}\n", self.column_storage_identity.storage_file_count));
+        header_text.push_str(&format!("# column_storage_bytes = {}\n", self.column_storage_identity.storage_byte_count));
+        header_text.push_str(&format!(
+            "# column_storage_modified_nanoseconds = {}\n",
+            self.column_storage_identity.newest_modified_nanoseconds
+        ));
+        header_text.push_str(&format!("# distinct_values = {}\n", self.distinct_value_count));
+        header_text
+    }
+
+    /// Reads the header lines at the start of an index file
+    ///
+    /// # Arguments
+    /// * `index_reader` - Reader positioned at the start of the index file
+    ///
+    /// # Returns
+    /// * `io::Result<(LookupIndexHeader, u64)>` - The header and the byte offset of the first value line
+    ///
+    /// # Errors
+    /// * `io::ErrorKind::InvalidData` - If a header line is missing or malformed
+    fn read_from<R: BufRead>(index_reader: &mut R) -> io::Result<(LookupIndexHeader, u64)> {
+        let mut header_byte_count = 0;
+        let mut read_header_line = |expected_key_name: Option<&str>| -> io::Result<String> {
+            let mut header_line = String::new();
+            header_byte_count += index_reader.read_line(&mut header_line)? as u64;
+            let header_line = header_line.trim_end_matches(['\n', '\r']);
+            let header_value = match expected_key_name {
+                None => (header_line == LOOKUP_INDEX_FILE_HEADER).then(String::new),
+                Some(key_name) => header_line
+                    .strip_prefix("# ")
+                    .and_then(|setting_text| setting_text.strip_prefix(key_name))
+                    .and_then(|remaining_text| remaining_text.trim_start().strip_prefix('='))
+                    .map(|value_text| value_text.trim().to_string()),
+            };
+            header_value.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected '{}' in the header", expected_key_name.unwrap_or(LOOKUP_INDEX_FILE_HEADER)),
+                )
+            })
+        };
+        let parse_header_number = |number_text: String| -> io::Result<u64> {
+            number_text.parse::<u64>().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("'{}' is not a number", number_text))
+            })
+        };
+
+        read_header_line(None)?;
+        let column_directory_name = read_header_line(Some("column_directory"))?;
+        let imported_row_count = parse_header_number(read_header_line(Some("imported_rows"))?)? as usize;
+        let source_file_size_bytes = parse_header_number(read_header_line(Some("source_file_size_bytes"))?)?;
+        let source_modified_seconds = parse_header_number(read_header_line(Some("source_modified_seconds"))?)?;
+        let column_storage_identity = ColumnStorageIdentity {
+            storage_file_count: parse_header_number(read_header_line(Some("column_storage_files"))?)?,
+            storage_byte_count: parse_header_number(read_header_line(Some("column_storage_bytes"))?)?,
+            newest_modified_nanoseconds: parse_header_number(read_header_line(Some("column_storage_modified_nanoseconds"))?)?,
+        };
+        let distinct_value_count = parse_header_number(read_header_line(Some("distinct_values"))?)? as usize;
+
+        let lookup_index_header = LookupIndexHeader {
+            column_directory_name,
+            imported_row_count,
+            source_file_size_bytes,
+            source_modified_seconds,
+            column_storage_identity,
+            distinct_value_count,
+        };
+        Ok((lookup_index_header, header_byte_count))
+    }
+}
+
+/// Number, total size and newest modification time of the files storing one column
+///
+/// Any change to a stored value changes at least the modification time of the
+/// file holding it, so a matching identity means the column is unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ColumnStorageIdentity {
+    /// Files inside the column directory (cell files or segment files and their indexes)
+    storage_file_count: u64,
+
+    /// Total size of those files
+    storage_byte_count: u64,
+
+    /// Newest modification time among them, in nanoseconds since the Unix epoch
+    newest_modified_nanoseconds: u64,
+}
+
+impl ColumnStorageIdentity {
+    /// Reads the identity of the files inside a column directory
+    ///
+    /// # Arguments
+    /// * `column_directory` - The column directory
+    ///
+    /// # Returns
+    /// * `io::Result<ColumnStorageIdentity>` - The identity of every file below the directory
+    fn read_from_directory(column_directory: &Path) -> io::Result<ColumnStorageIdentity> {
+        let mut column_storage_identity = ColumnStorageIdentity::default();
+        let mut pending_directories = vec![column_directory.to_path_buf()];
+
+        while let Some(directory_path) = pending_directories.pop() {
+            for directory_entry in fs::read_dir(&directory_path)? {
+                let directory_entry = directory_entry?;
+                let entry_metadata = directory_entry.metadata()?;
+                if entry_metadata.is_dir() {
+                    pending_directories.push(directory_entry.path());
+                    continue;
+                }
+                let modified_nanoseconds = entry_metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified_time| modified_time.duration_since(UNIX_EPOCH).ok())
+                    .map(|since_epoch| since_epoch.as_nanos() as u64)
+                    .unwrap_or(0);
+                column_storage_identity.storage_file_count += 1;
+                column_storage_identity.storage_byte_count += entry_metadata.len();
+                column_storage_identity.newest_modified_nanoseconds =
+                    column_storage_identity.newest_modified_nanoseconds.max(modified_nanoseconds);
+            }
+        }
+
+        Ok(column_storage_identity)
+    }
+}
+
+/// What building a lookup index produced
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnLookupIndexSummary {
+    /// The written index file
+    pub index_file_path: PathBuf,
+
+    /// Name of the indexed column
+    pub column_name: String,
+
+    /// Number of rows indexed
+    pub indexed_row_count: usize,
+
+    /// Number of distinct values (index lines)
+    pub distinct_value_count: usize,
+}
+
+/// The rows found for one value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnLookupResult {
+    /// Name of the column searched
+    pub column_name: String,
+
+    /// The value looked up
+    pub looked_up_value: String,
+
+    /// 1-based numbers of the rows holding the value, in row order
+    pub matching_row_numbers: Vec<usize>,
+
+    /// Whether the index was stale and had to be rebuilt first
+    pub index_was_rebuilt: bool,
+}
+
+/// Returns the path of a column's lookup index file
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `column_directory_name` - The column directory
+///
+/// # Returns
+/// * `PathBuf` - `lookup_indexes/<column directory>.lookup` inside the dataset
+pub fn lookup_index_file_path(dataset_directory: &Path, column_directory_name: &str) -> PathBuf {
+    dataset_directory
+        .join(LOOKUP_INDEX_DIRECTORY_NAME)
+        .join(format!("{}.{}", column_directory_name, LOOKUP_INDEX_FILE_EXTENSION))
+}
+
+/// Builds (or rebuilds) the lookup index of one dataset column
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `column_selector` - Column name, column directory name or 1-based column number
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ColumnLookupIndexSummary>` - What was written
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the import is unfinished or the column is unknown
+/// * `RowsAndColumnsError::FileSystemError` - If the column cannot be read or the index cannot be written
+pub fn build_column_lookup_index(
+    dataset_directory: &Path,
+    column_selector: &str,
+) -> RowsAndColumnsResult<ColumnLookupIndexSummary> {
+    let (import_progress_record, mut dataset_storage_reader, dataset_column) =
+        open_dataset_column(dataset_directory, column_selector)?;
+
+    write_column_lookup_index(
+        dataset_directory,
+        &import_progress_record,
+        dataset_storage_reader.as_mut(),
+        dataset_column,
+    )
+}
+
+/// Finds the rows of a dataset column that hold a value
+///
+/// The value must match the stored value exactly, case included. A stale
+/// index is rebuilt first; a missing index is an error, since answering
+/// without one would mean reading the whole column.
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `column_selector` - Column name, column directory name or 1-based column number
+/// * `looked_up_value` - The value to find
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ColumnLookupResult>` - The matching rows (possibly none)
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the column is unknown or has no lookup index
+/// * `RowsAndColumnsError::MetadataError` - If the index file is malformed
+/// * `RowsAndColumnsError::FileSystemError` - If the index cannot be read or rebuilt
+pub fn look_up_rows_by_value(
+    dataset_directory: &Path,
+    column_selector: &str,
+    looked_up_value: &str,
+) -> RowsAndColumnsResult<ColumnLookupResult> {
+    let (import_progress_record, mut dataset_storage_reader, dataset_column) =
+        open_dataset_column(dataset_directory, column_selector)?;
+
+    let index_file_path = lookup_index_file_path(dataset_directory, &dataset_column.column_directory_name);
+    if !index_file_path.exists() {
+        return Err(create_configuration_error(&format!(
+            "Column '{}' has no lookup index yet; build it with: rows_and_columns --index-column {} {}",
+            dataset_column.column_name,
+            dataset_directory.display(),
+            dataset_column.column_directory_name
+        )));
+    }
+    let index_file_path_text = index_file_path.display().to_string();
+    let map_index_read_error = |io_error: io::Error| {
+        if io_error.kind() == io::ErrorKind::InvalidData {
+            create_metadata_error(&format!("Malformed lookup index: {}", io_error), &index_file_path_text)
+        } else {
+            create_file_system_error(&format!("Failed to read lookup index: {}", index_file_path_text), io_error)
+        }
+    };
+
+    let mut index_file = File::open(&index_file_path).map_err(map_index_read_error)?;
+    let (lookup_index_header, _) =
+        LookupIndexHeader::read_from(&mut BufReader::new(&mut index_file)).map_err(map_index_read_error)?;
+
+    // The dataset or the stored column changed since the index was built: rebuild it before answering
+    let column_storage_identity = read_column_storage_identity(dataset_directory, &dataset_column.column_directory_name)?;
+    let index_was_rebuilt = !lookup_index_header.is_current_for(
+        &import_progress_record,
+        &dataset_column.column_directory_name,
+        column_storage_identity,
+    );
+    let column_name = dataset_column.column_name.clone();
+    if index_was_rebuilt {
+        println!("Note: the lookup index of column '{}' is out of date; rebuilding it", column_name);
+        write_column_lookup_index(
+            dataset_directory,
+            &import_progress_record,
+            dataset_storage_reader.as_mut(),
+            dataset_column,
+        )?;
+    }
+
+    let mut index_file = File::open(&index_file_path).map_err(map_index_read_error)?;
+    let matching_row_numbers = find_rows_in_lookup_index(&mut index_file, looked_up_value)
+        .map_err(map_index_read_error)?
+        .unwrap_or_default();
+
+    Ok(ColumnLookupResult {
+        column_name,
+        looked_up_value: looked_up_value.to_string(),
+        matching_row_numbers,
+        index_was_rebuilt,
+    })
+}
+
+/// Displays a summary of a built lookup index
+///
+/// # Arguments
+/// * `index_summary` - The summary returned by the build
+pub fn display_column_lookup_index_summary(index_summary: &ColumnLookupIndexSummary) {
+    println!("✓ Lookup index written: {}", index_summary.index_file_path.display());
+    println!("  Column: {}", index_summary.column_name);
+    println!("  Rows indexed: {}", index_summary.indexed_row_count);
+    println!("  Distinct values: {}", index_summary.distinct_value_count);
+}
+
+/// Displays the rows found by a lookup
+///
+/// # Arguments
+/// * `lookup_result` - The result returned by the lookup
+pub fn display_column_lookup_result(lookup_result: &ColumnLookupResult) {
+    println!(
+        "{} = \"{}\": {} matching row(s)",
+        lookup_result.column_name,
+        lookup_result.looked_up_value,
+        lookup_result.matching_row_numbers.len()
+    );
+    if lookup_result.matching_row_numbers.is_empty() {
+        return;
+    }
+
+    let shown_row_numbers: Vec<String> = lookup_result.matching_row_numbers
+        .iter()
+        .take(LOOKUP_RESULT_DISPLAY_LIMIT)
+        .map(|row_number| row_number.to_string())
+        .collect();
+    println!("  Rows: {}", shown_row_numbers.join(", "));
+    if lookup_result.matching_row_numbers.len() > LOOKUP_RESULT_DISPLAY_LIMIT {
+        println!("  ... and {} more", lookup_result.matching_row_numbers.len() - LOOKUP_RESULT_DISPLAY_LIMIT);
+    }
+}
+
+/// Opens a completely imported dataset and finds one of its columns
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `column_selector` - Column name, column directory name or 1-based column number
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(ImportProgressRecord, Box<dyn DatasetStorageReader>, DatasetColumn)>` -
+///   The progress record, a reader over the dataset, and the column
+pub fn open_dataset_column(
+    dataset_directory: &Path,
+    column_selector: &str,
+) -> RowsAndColumnsResult<(ImportProgressRecord, Box<dyn DatasetStorageReader>, DatasetColumn)> {
+    let (import_progress_record, dataset_storage_reader) = open_imported_dataset_reader(dataset_directory)?;
+    let metadata_document = read_metadata_toml_file(&dataset_directory.join(DATASET_METADATA_FILE_NAME))?;
+    let dataset_columns = list_dataset_columns(&import_progress_record, metadata_document.as_ref());
+
+    let dataset_column = select_dataset_columns(&dataset_columns, &[column_selector.to_string()])
+        .map_err(|selection_problem| create_configuration_error(&selection_problem))?
+        .remove(0);
+    Ok((import_progress_record, dataset_storage_reader, dataset_column))
+}
+
+/// Reads the identity of the files storing one dataset column
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `column_directory_name` - The column directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ColumnStorageIdentity>` - Number, size and newest modification time of the column's files
+fn read_column_storage_identity(
+    dataset_directory: &Path,
+    column_directory_name: &str,
+) -> RowsAndColumnsResult<ColumnStorageIdentity> {
+    let column_directory = dataset_directory.join(column_directory_name);
+    ColumnStorageIdentity::read_from_directory(&column_directory).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to read column files: {}", column_directory.display()), io_error)
+    })
+}
+
+/// Reads a column and writes its sorted lookup index file
+///
+/// The index is written to a temporary file and renamed into place, so an
+/// interrupted build never leaves a half-written index behind.
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `import_progress_record` - The dataset's progress record (recorded in the header)
+/// * `dataset_storage_reader` - Reader over the dataset
+/// * `dataset_column` - The column to index
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ColumnLookupIndexSummary>` - What was written
+fn write_column_lookup_index(
+    dataset_directory: &Path,
+    import_progress_record: &ImportProgressRecord,
+    dataset_storage_reader: &mut dyn DatasetStorageReader,
+    dataset_column: DatasetColumn,
+) -> RowsAndColumnsResult<ColumnLookupIndexSummary> {
+    let column_storage_identity = read_column_storage_identity(dataset_directory, &dataset_column.column_directory_name)?;
+    let column_values = dataset_storage_reader.read_column_values(dataset_column.column_number.saturating_sub(1))?;
+
+    // Group row numbers by escaped value; the map keeps the values in byte order
+    let mut rows_by_escaped_value: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (value_position, column_value) in column_values.iter().enumerate() {
+        rows_by_escaped_value
+            .entry(escape_segment_value(column_value))
+            .or_default()
+            .push(value_position + 1);
+    }
+
+    let lookup_index_header = LookupIndexHeader {
+        column_directory_name: dataset_column.column_directory_name.clone(),
+        imported_row_count: import_progress_record.imported_row_count,
+        source_file_size_bytes: import_progress_record.source_file_size_bytes,
+        source_modified_seconds: import_progress_record.source_modified_seconds,
+        column_storage_identity,
+        distinct_value_count: rows_by_escaped_value.len(),
+    };
+
+    let index_file_path = lookup_index_file_path(dataset_directory, &dataset_column.column_directory_name);
+    let temporary_file_path = index_file_path.with_extension(format!("{}.tmp", LOOKUP_INDEX_FILE_EXTENSION));
+    let map_index_write_error = |io_error: io::Error| {
+        create_file_system_error(&format!("Failed to write lookup index: {}", index_file_path.display()), io_error)
+    };
+
+    if let Some(index_directory) = index_file_path.parent() {
+        fs::create_dir_all(index_directory).map_err(map_index_write_error)?;
+    }
+    let mut index_writer = BufWriter::new(File::create(&temporary_file_path).map_err(map_index_write_error)?);
+    index_writer
+        .write_all(lookup_index_header.to_header_lines().as_bytes())
+        .map_err(map_index_write_error)?;
+    for (escaped_value, row_numbers) in &rows_by_escaped_value {
+        let row_number_texts: Vec<String> = row_numbers.iter().map(|row_number| row_number.to_string()).collect();
+        writeln!(index_writer, "{}\t{}", escaped_value, row_number_texts.join(",")).map_err(map_index_write_error)?;
+    }
+    index_writer.flush().map_err(map_index_write_error)?;
+    drop(index_writer);
+    fs::rename(&temporary_file_path, &index_file_path).map_err(map_index_write_error)?;
+
+    Ok(ColumnLookupIndexSummary {
+        index_file_path,
+        column_name: dataset_column.column_name,
+        indexed_row_count: column_values.len(),
+        distinct_value_count: lookup_index_header.distinct_value_count,
+    })
+}
+
+/// Binary searches a lookup index file for a value
+///
+/// Probes land at arbitrary byte offsets and move forward to the next line
+/// start, so only a handful of lines are read however large the index is.
+///
+/// # Arguments
+/// * `index_file` - The open index file
+/// * `looked_up_value` - The value to find (unescaped)
+///
+/// # Returns
+/// * `io::Result<Option<Vec<usize>>>` - The row numbers holding the value, or None if it does not occur
+fn find_rows_in_lookup_index(index_file: &mut File, looked_up_value: &str) -> io::Result<Option<Vec<usize>>> {
+    index_file.seek(SeekFrom::Start(0))?;
+    let (_, first_value_line_offset) = LookupIndexHeader::read_from(&mut BufReader::new(&mut *index_file))?;
+    let escaped_target_value = escape_segment_value(looked_up_value);
+
+    // Every line before `lower_offset` holds a smaller value, every line from `upper_offset` on a larger one
+    let mut lower_offset = first_value_line_offset;
+    let mut upper_offset = index_file.metadata()?.len();
+    while lower_offset < upper_offset {
+        let middle_offset = lower_offset + (upper_offset - lower_offset) / 2;
+        let mut probe_line_offset = if middle_offset <= first_value_line_offset {
+            first_value_line_offset
+        } else {
+            // The line holding the byte before the middle ends where the next line starts
+            read_index_line_at(index_file, middle_offset - 1)?.1
+        };
+        if probe_line_offset >= upper_offset {
+            probe_line_offset = lower_offset;
+        }
+
+        let (index_line, next_line_offset) = read_index_line_at(index_file, probe_line_offset)?;
+        let (escaped_value, row_list_text) = index_line.rsplit_once('\t').ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("no tab in the line at byte {}", probe_line_offset))
+        })?;
+
+        match escaped_value.cmp(escaped_target_value.as_str()) {
+            std::cmp::Ordering::Less => lower_offset = next_line_offset,
+            std::cmp::Ordering::Greater => upper_offset = probe_line_offset,
+            std::cmp::Ordering::Equal => {
+                let row_numbers = row_list_text
+                    .split(',')
+                    .map(|row_number_text| {
+                        row_number_text.parse::<usize>().map_err(|_| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!("bad row number '{}' at byte {}", row_number_text, probe_line_offset),
+                            )
+                        })
+                    })
+                    .collect::<io::Result<Vec<usize>>>()?;
+                return Ok(Some(row_numbers));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads from a byte offset to the end of that line
+///
+/// # Arguments
+/// * `index_file` - The open index file
+/// * `line_offset` - Where to start reading
+///
+/// # Returns
+/// * `io::Result<(String, u64)>` - The text up to the line feed, and the offset just after it
+fn read_index_line_at(index_file: &mut File, line_offset: u64) -> io::Result<(String, u64)> {
+    index_file.seek(SeekFrom::Start(line_offset))?;
+    let mut line_bytes = Vec::new();
+    BufReader::new(&mut *index_file).read_until(b'\n', &mut line_bytes)?;
+    let next_line_offset = line_offset + line_bytes.len() as u64;
+
+    if line_bytes.last() == Some(&b'\n') {
+        line_bytes.pop();
+    }
+    Ok((String::from_utf8_lossy(&line_bytes).to_string(), next_line_offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_round_trip() {
+        let lookup_index_header = LookupIndexHeader {
+            column_directory_name: "column_004_breed".to_string(),
+            imported_row_count: 5,
+            source_file_size_bytes: 262,
+            source_modified_seconds: 1_760_000_000,
+            column_storage_identity: ColumnStorageIdentity {
+                storage_file_count: 5,
+                storage_byte_count: 42,
+                newest_modified_nanoseconds: 1_760_000_123_456_789_012,
+            },
+            distinct_value_count: 4,
+        };
+        let header_text = lookup_index_header.to_header_lines();
+        let (read_header, first_value_line_offset) = LookupIndexHeader::read_from(&mut header_text.as_bytes())
+            .expect("header should parse");
+        assert_eq!(read_header, lookup_index_header);
+        assert_eq!(first_value_line_offset, header_text.len() as u64);
+
+        let broken_header_text = header_text.replace("# imported_rows = 5", "# imported_rows = five");
+        assert!(LookupIndexHeader::read_from(&mut broken_header_text.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_binary_search_finds_every_value() {
+        let lookup_index_header = LookupIndexHeader {
+            column_directory_name: "column_001_word".to_string(),
+            imported_row_count: 0,
+            source_file_size_bytes: 0,
+            source_modified_seconds: 0,
+            column_storage_identity: ColumnStorageIdentity::default(),
+            distinct_value_count: 0,
+        };
+        let stored_values = ["", "a\tb", "apple", "banana", "line\nbreak", "pear", "zebra"];
+        let mut index_text = lookup_index_header.to_header_lines();
+        let mut escaped_values: Vec<String> = stored_values.iter().map(|value| escape_segment_value(value)).collect();
+        escaped_values.sort();
+        for (value_position, escaped_value) in escaped_values.iter().enumerate() {
+            index_text.push_str(&format!("{}\t{},{}\n", escaped_value, value_position + 1, value_position + 10));
+        }
+
+        let index_file_path = std::env::temp_dir().join(format!("{}_lookup_search_test.lookup", std::process::id()));
+        fs::write(&index_file_path, &index_text).expect("test index should be writable");
+        let mut index_file = File::open(&index_file_path).expect("test index should open");
+
+        for stored_value in stored_values {
+            let value_position = escaped_values
+                .iter()
+                .position(|escaped_value| *escaped_value == escape_segment_value(stored_value))
+                .expect("value should be in the test index");
+            assert_eq!(
+                find_rows_in_lookup_index(&mut index_file, stored_value).expect("search should work"),
+                Some(vec![value_position + 1, value_position + 10]),
+                "looking up {:?}",
+                stored_value
+            );
+        }
+        for missing_value in ["0", "applf", "b", "zz", "line"] {
+            assert_eq!(
+                find_rows_in_lookup_index(&mut index_file, missing_value).expect("search should work"),
+                None
+            );
+        }
+
+        fs::remove_file(&index_file_path).expect("test index should be removable");
+    }
+
+    #[test]
+    fn test_edited_column_file_makes_the_index_stale() {
+        let dataset_directory = std::env::temp_dir().join(format!("{}_lookup_staleness_test", std::process::id()));
+        let cell_directory = dataset_directory.join("column_001_breed").join("row_0001");
+        fs::create_dir_all(&cell_directory).expect("test column should be creatable");
+        let cell_file_path = cell_directory.join("value.txt");
+        fs::write(&cell_file_path, "Siamese").expect("test cell should be writable");
+
+        let import_progress_record = ImportProgressRecord {
+            source_csv_file: "cats.csv".to_string(),
+            source_file_size_bytes: 262,
+            source_modified_seconds: 1_760_000_000,
+            column_directory_names: vec!["column_001_breed".to_string()],
+            row_number_digits: 4,
+            storage_settings: Default::default(),
+            imported_row_count: 1,
+            import_complete: true,
+        };
+        let lookup_index_header = LookupIndexHeader {
+            column_directory_name: "column_001_breed".to_string(),
+            imported_row_count: 1,
+            source_file_size_bytes: 262,
+            source_modified_seconds: 1_760_000_000,
+            column_storage_identity: read_column_storage_identity(&dataset_directory, "column_001_breed")
+                .expect("column files should be readable"),
+            distinct_value_count: 1,
+        };
+        assert_eq!(lookup_index_header.column_storage_identity.storage_file_count, 1);
+        assert_eq!(lookup_index_header.column_storage_identity.storage_byte_count, 7);
+        let is_current = || {
+            let column_storage_identity = read_column_storage_identity(&dataset_directory, "column_001_breed")
+                .expect("column files should be readable");
+            lookup_index_header.is_current_for(&import_progress_record, "column_001_breed", column_storage_identity)
+        };
+        assert!(is_current());
+
+        // Same size, new content: the modification time gives the edit away
+        fs::write(&cell_file_path, "Persian").expect("test cell should be writable");
+        let edited_file = File::options().write(true).open(&cell_file_path).expect("test cell should open");
+        edited_file
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            .expect("modification time should be settable");
+        assert!(!is_current());
+
+        fs::remove_dir_all(&dataset_directory).expect("test dataset should be removable");
+    }
+}
@@ -0,0 +1,398 @@
+// src/column_type_inference_module.rs
+
+/// Column data type inference for rows_and_columns
+///
+/// Each column keeps a `ColumnTypeTally`: for every candidate type it counts
+/// how many non-empty values parse as that type and remembers the first value
+/// (and its line) that does not. The column type is the first candidate, in
+/// order boolean, integer, float, decimal, date, time, datetime, that enough
+/// values match; otherwise string. Plain numbers are floats; a currency symbol,
+/// thousands separator or parenthesized negative makes a column decimal.
+///
+/// # Inference Modes
+/// Chosen with `mode` in the `[type_inference]` section of the metadata file:
+/// - `sample` - only the first `CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION` data rows are
+///   tallied and 70% of their values must match (the default)
+/// - `full` - every data row is tallied and every non-empty value must match,
+///   so a single "N/A" in an integer column makes it a string column
+///
+/// The same section lists extra `date_formats`, `time_formats` and
+/// `datetime_formats` to accept besides ISO-8601 (see `temporal_value_module`),
+/// and the `true_values` / `false_values` vocabulary (see `boolean_value_module`).
+/// Boolean is only chosen when the column holds both a true and a false token.
+///
+/// Either way the counts and first mismatches are written to each column's
+/// section of the metadata file, so the reason for a type can be looked up.
+use super::csv_processor_module::CsvColumnDataType;
+
+// Import the configurable true/false vocabulary
+use super::boolean_value_module::BooleanVocabulary;
+
+// Import fixed-point decimal parsing
+use super::decimal_value_module::parse_decimal_value;
+
+// Import date and time parsing
+use super::temporal_value_module::{
+    TemporalFormatSet,
+    parse_date_value,
+    parse_datetime_value,
+    parse_time_value,
+};
+
+/// Share of sampled values that must match a type in sample mode
+const SAMPLE_MODE_REQUIRED_MATCH_RATIO: f64 = 0.7;
+
+/// Candidate types in the order they are tried (narrowest first)
+const CANDIDATE_COLUMN_DATA_TYPES: [CsvColumnDataType; 7] = [
+    CsvColumnDataType::Boolean,
+    CsvColumnDataType::Integer,
+    CsvColumnDataType::Float,
+    CsvColumnDataType::Decimal,
+    CsvColumnDataType::Date,
+    CsvColumnDataType::Time,
+    CsvColumnDataType::DateTime,
+];
+
+/// How many rows type inference looks at
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TypeInferenceMode {
+    /// The first few data rows; a 70% majority decides
+    #[default]
+    Sample,
+
+    /// Every data row; all non-empty values must match
+    FullScan,
+}
+
+impl TypeInferenceMode {
+    /// Converts the mode to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&str` - String representation of the mode
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            TypeInferenceMode::Sample => "sample",
+            TypeInferenceMode::FullScan => "full",
+        }
+    }
+
+    /// Creates a mode from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from the TOML file
+    ///
+    /// # Returns
+    /// * `Option<TypeInferenceMode>` - The mode or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<TypeInferenceMode> {
+        match toml_string.to_lowercase().as_str() {
+            "sample" => Some(TypeInferenceMode::Sample),
+            "full" | "full_scan" => Some(TypeInferenceMode::FullScan),
+            _ => None,
+        }
+    }
+}
+
+/// Settings from the `[type_inference]` section of the metadata file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypeInferenceSettings {
+    /// How many rows are tallied and how many values must match
+    pub inference_mode: TypeInferenceMode,
+
+    /// Date and time formats accepted besides ISO-8601
+    pub temporal_formats: TemporalFormatSet,
+
+    /// Tokens read as true and false
+    pub boolean_vocabulary: BooleanVocabulary,
+}
+
+/// How the column types of one file were inferred
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeInferenceSummary {
+    /// Settings the types were inferred with
+    pub inference_settings: TypeInferenceSettings,
+
+    /// Number of data rows that were tallied
+    pub scanned_row_count: usize,
+}
+
+/// First value in a column that does not parse as a candidate type
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatchExample {
+    /// The offending value (trimmed)
+    pub offending_value: String,
+
+    /// Line number (1-based) where the value's record starts
+    pub line_number: usize,
+}
+
+/// Match count of one candidate type within one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnTypeMatchCount {
+    /// The candidate type
+    pub data_type: CsvColumnDataType,
+
+    /// Number of non-empty values that parse as the candidate type
+    pub matched_value_count: usize,
+
+    /// First non-empty value that does not parse as the candidate type
+    pub first_mismatch: Option<TypeMismatchExample>,
+}
+
+/// Per-type evidence gathered for one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnTypeTally {
+    /// Number of non-empty values tallied
+    pub checked_value_count: usize,
+
+    /// One entry per candidate type, in candidate order
+    pub type_match_counts: Vec<ColumnTypeMatchCount>,
+
+    /// Most digits after the decimal point among values that parse as decimals
+    /// (raised to the `decimal_scale` stored in the metadata file, if larger)
+    pub decimal_scale: u32,
+
+    /// First currency symbol seen in a decimal value
+    pub currency_symbol: Option<char>,
+
+    /// Number of values read as true
+    pub true_token_count: usize,
+
+    /// Number of values read as false
+    pub false_token_count: usize,
+}
+
+impl Default for ColumnTypeTally {
+    fn default() -> Self {
+        ColumnTypeTally {
+            checked_value_count: 0,
+            type_match_counts: CANDIDATE_COLUMN_DATA_TYPES
+                .iter()
+                .map(|candidate_type| ColumnTypeMatchCount {
+                    data_type: candidate_type.clone(),
+                    matched_value_count: 0,
+                    first_mismatch: None,
+                })
+                .collect(),
+            decimal_scale: 0,
+            currency_symbol: None,
+            true_token_count: 0,
+            false_token_count: 0,
+        }
+    }
+}
+
+impl ColumnTypeTally {
+    /// Adds one value to the tally (empty values are ignored)
+    ///
+    /// Callers leave out null sentinels such as `NA` before tallying, so that
+    /// a missing value does not count against any type.
+    ///
+    /// # Arguments
+    /// * `field_value` - The raw field value
+    /// * `line_number` - Line number where the value's record starts
+    /// * `inference_settings` - Formats used to recognise typed values
+    pub fn record_value(&mut self, field_value: &str, line_number: usize, inference_settings: &TypeInferenceSettings) {
+        let trimmed_value = field_value.trim();
+        if trimmed_value.is_empty() {
+            return;
+        }
+
+        self.checked_value_count += 1;
+        if let Some(parsed_decimal_value) = parse_decimal_value(trimmed_value) {
+            self.decimal_scale = self.decimal_scale.max(parsed_decimal_value.decimal_value.scale);
+            self.currency_symbol = self.currency_symbol.or(parsed_decimal_value.currency_symbol);
+        }
+        match inference_settings.boolean_vocabulary.parse_boolean_value(trimmed_value) {
+            Some(true) => self.true_token_count += 1,
+            Some(false) => self.false_token_count += 1,
+            None => {}
+        }
+
+        for type_match_count in &mut self.type_match_counts {
+            if value_matches_data_type(trimmed_value, &type_match_count.data_type, inference_settings) {
+                type_match_count.matched_value_count += 1;
+            } else if type_match_count.first_mismatch.is_none() {
+                type_match_count.first_mismatch = Some(TypeMismatchExample {
+                    offending_value: trimmed_value.to_string(),
+                    line_number,
+                });
+            }
+        }
+    }
+
+    /// Picks the column type from the tally
+    ///
+    /// # Arguments
+    /// * `inference_mode` - Decides how many values must match
+    ///
+    /// # Returns
+    /// * `CsvColumnDataType` - The first candidate with enough matches, or String
+    ///   (boolean also needs at least one true and one false token)
+    pub fn decide_data_type(&self, inference_mode: TypeInferenceMode) -> CsvColumnDataType {
+        if self.checked_value_count == 0 {
+            return CsvColumnDataType::String;
+        }
+
+        let required_match_count = match inference_mode {
+            TypeInferenceMode::Sample => {
+                (self.checked_value_count as f64 * SAMPLE_MODE_REQUIRED_MATCH_RATIO).ceil() as usize
+            }
+            TypeInferenceMode::FullScan => self.checked_value_count,
+        };
+
+        let has_both_boolean_tokens = self.true_token_count > 0 && self.false_token_count > 0;
+        self.type_match_counts
+            .iter()
+            .filter(|type_match_count| {
+                type_match_count.data_type != CsvColumnDataType::Boolean || has_both_boolean_tokens
+            })
+            .find(|type_match_count| type_match_count.matched_value_count >= required_match_count)
+            .map(|type_match_count| type_match_count.data_type.clone())
+            .unwrap_or(CsvColumnDataType::String)
+    }
+
+    /// Counts tallied values that do not parse as a type
+    ///
+    /// # Arguments
+    /// * `data_type` - The type the column was given
+    ///
+    /// # Returns
+    /// * `usize` - Number of tallied values that are invalid for the type (0 for String)
+    pub fn mismatched_value_count(&self, data_type: &CsvColumnDataType) -> usize {
+        self.type_match_counts
+            .iter()
+            .find(|type_match_count| type_match_count.data_type == *data_type)
+            .map(|type_match_count| self.checked_value_count - type_match_count.matched_value_count)
+            .unwrap_or(0)
+    }
+}
+
+/// Checks whether a trimmed value parses as a data type
+///
+/// # Arguments
+/// * `trimmed_value` - The non-empty, trimmed value
+/// * `data_type` - The candidate type
+/// * `inference_settings` - Formats used to recognise typed values
+///
+/// # Returns
+/// * `bool` - True if the value is valid for the type
+pub fn value_matches_data_type(
+    trimmed_value: &str,
+    data_type: &CsvColumnDataType,
+    inference_settings: &TypeInferenceSettings,
+) -> bool {
+    let temporal_formats = &inference_settings.temporal_formats;
+    match data_type {
+        CsvColumnDataType::Boolean => inference_settings.boolean_vocabulary.parse_boolean_value(trimmed_value).is_some(),
+        CsvColumnDataType::Integer => trimmed_value.parse::<i64>().is_ok(),
+        CsvColumnDataType::Float => trimmed_value.parse::<f64>().is_ok(),
+        CsvColumnDataType::Decimal => parse_decimal_value(trimmed_value).is_some(),
+        CsvColumnDataType::Date => parse_date_value(trimmed_value, temporal_formats).is_some(),
+        CsvColumnDataType::Time => parse_time_value(trimmed_value, temporal_formats).is_some(),
+        CsvColumnDataType::DateTime => parse_datetime_value(trimmed_value, temporal_formats).is_some(),
+        CsvColumnDataType::String => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper to tally values as if they were on consecutive lines starting at line 2
+    fn tally_values(column_values: &[&str]) -> ColumnTypeTally {
+        let mut column_type_tally = ColumnTypeTally::default();
+        for (value_index, column_value) in column_values.iter().enumerate() {
+            column_type_tally.record_value(column_value, value_index + 2, &TypeInferenceSettings::default());
+        }
+        column_type_tally
+    }
+
+    #[test]
+    fn test_full_scan_records_first_offending_value() {
+        let mut column_values = vec!["3"; 12];
+        column_values.push("N/A");
+        column_values.push("7");
+        let column_type_tally = tally_values(&column_values);
+
+        assert_eq!(column_type_tally.decide_data_type(TypeInferenceMode::Sample), CsvColumnDataType::Integer);
+        assert_eq!(column_type_tally.decide_data_type(TypeInferenceMode::FullScan), CsvColumnDataType::String);
+
+        let integer_match_count = &column_type_tally.type_match_counts[1];
+        assert_eq!(integer_match_count.matched_value_count, 13);
+        assert_eq!(
+            integer_match_count.first_mismatch,
+            Some(TypeMismatchExample { offending_value: "N/A".to_string(), line_number: 14 })
+        );
+    }
+
+    #[test]
+    fn test_integers_widen_to_float() {
+        let column_type_tally = tally_values(&["3", "", "5", "3.5", "8"]);
+
+        assert_eq!(column_type_tally.checked_value_count, 4);
+        assert_eq!(column_type_tally.decide_data_type(TypeInferenceMode::FullScan), CsvColumnDataType::Float);
+    }
+
+    #[test]
+    fn test_currency_column_is_decimal_with_scale() {
+        let column_type_tally = tally_values(&["$1,234.50", "-12.30", "(7.5)", "$3"]);
+
+        assert_eq!(column_type_tally.decide_data_type(TypeInferenceMode::FullScan), CsvColumnDataType::Decimal);
+        assert_eq!(column_type_tally.decimal_scale, 2);
+        assert_eq!(column_type_tally.currency_symbol, Some('$'));
+        assert_eq!(
+            tally_values(&["1.5", "-2.25"]).decide_data_type(TypeInferenceMode::FullScan),
+            CsvColumnDataType::Float
+        );
+    }
+
+    #[test]
+    fn test_temporal_columns() {
+        assert_eq!(
+            tally_values(&["2024-03-01", "2024-03-02"]).decide_data_type(TypeInferenceMode::FullScan),
+            CsvColumnDataType::Date
+        );
+        assert_eq!(
+            tally_values(&["2024-03-01", "2024-03-02T10:00:00Z"]).decide_data_type(TypeInferenceMode::FullScan),
+            CsvColumnDataType::DateTime
+        );
+        assert_eq!(
+            tally_values(&["09:30", "17:45:10"]).decide_data_type(TypeInferenceMode::FullScan),
+            CsvColumnDataType::Time
+        );
+    }
+
+    #[test]
+    fn test_zero_one_column_is_integer_unless_configured() {
+        assert_eq!(
+            tally_values(&["1", "0", "0", "1"]).decide_data_type(TypeInferenceMode::FullScan),
+            CsvColumnDataType::Integer
+        );
+        assert_eq!(tally_values(&[]).decide_data_type(TypeInferenceMode::Sample), CsvColumnDataType::String);
+
+        let zero_one_settings = TypeInferenceSettings {
+            boolean_vocabulary: BooleanVocabulary {
+                true_tokens: vec!["1".to_string()],
+                false_tokens: vec!["0".to_string()],
+            },
+            ..TypeInferenceSettings::default()
+        };
+        let mut column_type_tally = ColumnTypeTally::default();
+        for (value_index, column_value) in ["1", "0", "0", "1"].iter().enumerate() {
+            column_type_tally.record_value(column_value, value_index + 2, &zero_one_settings);
+        }
+        assert_eq!(column_type_tally.decide_data_type(TypeInferenceMode::FullScan), CsvColumnDataType::Boolean);
+    }
+
+    #[test]
+    fn test_boolean_needs_both_tokens() {
+        assert_eq!(
+            tally_values(&["yes", "YES", "yes"]).decide_data_type(TypeInferenceMode::FullScan),
+            CsvColumnDataType::String
+        );
+        assert_eq!(
+            tally_values(&["yes", "No", "yes"]).decide_data_type(TypeInferenceMode::FullScan),
+            CsvColumnDataType::Boolean
+        );
+    }
+}
@@ -0,0 +1,356 @@
+// src/column_type_review_module.rs
+
+/// Interactive column type review for rows_and_columns
+///
+/// Option 1 of the post-analysis menu. The screen lists every column with its
+/// type, a few sample values and how many sampled values matched each
+/// candidate type. Columns can be given another type, renamed, or ignored
+/// (left out of the statistics and the data import).
+///
+/// A type change is first checked against every value in the file. If any
+/// value (other than a blank or null sentinel) would fail to parse as the new
+/// type, the change is refused and the failing values are shown, so a saved
+/// type never makes the next analysis fail. Every accepted change is written
+/// to the metadata TOML file straight away, where the next analysis picks it
+/// up as the authoritative schema.
+///
+/// # Commands
+/// - `<n>` - show the type evidence for column n
+/// - `type <n> <type>` - change the type of column n
+/// - `rename <n> <name>` - rename column n
+/// - `ignore <n>` / `include <n>` - leave column n out, or take it back in
+/// - `list`, `help`, and an empty line to return to the menu
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvColumnDataType,
+    CsvColumnInformation,
+    create_or_update_metadata_file,
+    preview_column_type_change,
+};
+
+// Import the shared prompt reader
+use super::rows_and_columns_module::get_user_menu_selection;
+
+// Import our error handling system
+use super::error_types_module::RowsAndColumnsResult;
+
+/// Number of sample values shown per column in the review table
+const REVIEW_SAMPLE_VALUE_LIMIT: usize = 3;
+
+/// Type names accepted by the `type` command, in candidate order
+const REVIEW_TYPE_NAMES: &str = "boolean, integer, float, decimal, date, time, datetime, string";
+
+/// One command typed at the review prompt (column indexes are 0-based)
+#[derive(Debug, Clone, PartialEq)]
+enum ColumnReviewCommand {
+    /// Show the type evidence for a column
+    ShowColumnDetails(usize),
+
+    /// Give a column another type
+    ChangeDataType(usize, CsvColumnDataType),
+
+    /// Give a column another name
+    RenameColumn(usize, String),
+
+    /// Leave a column out of statistics and import
+    IgnoreColumn(usize),
+
+    /// Take an ignored column back in
+    IncludeColumn(usize),
+
+    /// Show the review table again
+    ListColumns,
+
+    /// Show the command help
+    ShowHelp,
+
+    /// Return to the post-analysis menu
+    ReturnToMenu,
+}
+
+/// Runs the column type review screen until the user returns to the menu
+///
+/// # Arguments
+/// * `analysis_results` - The completed analysis; accepted edits are applied to its columns
+///
+/// # Returns
+/// * `RowsAndColumnsResult<usize>` - Number of changes written to the metadata file
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If reading input, the CSV file or writing the metadata file fails
+/// * `RowsAndColumnsError::CsvProcessingError` - If a record cannot be parsed during a preview
+pub fn run_column_type_review_screen(analysis_results: &mut CsvAnalysisResults) -> RowsAndColumnsResult<usize> {
+    let mut saved_change_count = 0;
+    display_column_review_table(&analysis_results.column_information_list);
+    display_column_review_help();
+
+    loop {
+        let command_text = get_user_menu_selection("Review (empty line returns to menu)")?;
+        let review_command = match parse_column_review_command(
+            &command_text,
+            analysis_results.column_information_list.len(),
+        ) {
+            Ok(review_command) => review_command,
+            Err(command_problem) => {
+                println!("{}", command_problem);
+                println!();
+                continue;
+            }
+        };
+
+        let column_changed = match review_command {
+            ColumnReviewCommand::ShowColumnDetails(column_index) => {
+                display_column_type_evidence(&analysis_results.column_information_list[column_index]);
+                false
+            }
+            ColumnReviewCommand::ChangeDataType(column_index, candidate_data_type) => {
+                change_column_data_type(analysis_results, column_index, candidate_data_type)?
+            }
+            ColumnReviewCommand::RenameColumn(column_index, new_column_name) => {
+                let column_info = &mut analysis_results.column_information_list[column_index];
+                println!("✓ Column {} renamed from '{}' to '{}'", column_index + 1, column_info.column_name, new_column_name);
+                column_info.column_name = new_column_name;
+                true
+            }
+            ColumnReviewCommand::IgnoreColumn(column_index) | ColumnReviewCommand::IncludeColumn(column_index) => {
+                let column_ignored = matches!(review_command, ColumnReviewCommand::IgnoreColumn(_));
+                let column_info = &mut analysis_results.column_information_list[column_index];
+                let column_changed = column_info.column_ignored != column_ignored;
+                column_info.column_ignored = column_ignored;
+                println!(
+                    "✓ Column {} ('{}') is {}",
+                    column_index + 1,
+                    column_info.column_name,
+                    if column_ignored { "ignored" } else { "included" }
+                );
+                column_changed
+            }
+            ColumnReviewCommand::ListColumns => {
+                display_column_review_table(&analysis_results.column_information_list);
+                false
+            }
+            ColumnReviewCommand::ShowHelp => {
+                display_column_review_help();
+                false
+            }
+            ColumnReviewCommand::ReturnToMenu => return Ok(saved_change_count),
+        };
+
+        if column_changed {
+            create_or_update_metadata_file(analysis_results)?;
+            saved_change_count += 1;
+            println!("  Saved to {}", analysis_results.metadata_file_path.display());
+        }
+        println!();
+    }
+}
+
+/// Previews a type change against every value and applies it if nothing fails
+///
+/// # Arguments
+/// * `analysis_results` - The completed analysis
+/// * `column_index` - 0-based column index
+/// * `candidate_data_type` - The requested type
+///
+/// # Returns
+/// * `RowsAndColumnsResult<bool>` - True if the column type changed
+fn change_column_data_type(
+    analysis_results: &mut CsvAnalysisResults,
+    column_index: usize,
+    candidate_data_type: CsvColumnDataType,
+) -> RowsAndColumnsResult<bool> {
+    let current_data_type = &analysis_results.column_information_list[column_index].detected_data_type;
+    if *current_data_type == candidate_data_type {
+        println!("Column {} is already {}", column_index + 1, candidate_data_type.to_toml_string());
+        return Ok(false);
+    }
+
+    let type_change_preview = preview_column_type_change(analysis_results, column_index, &candidate_data_type)?;
+    println!(
+        "Preview: {} of {} values would fail validation as {}",
+        type_change_preview.failing_value_count,
+        type_change_preview.checked_value_count,
+        candidate_data_type.to_toml_string()
+    );
+
+    if type_change_preview.failing_value_count > 0 {
+        for failing_value_example in &type_change_preview.failing_value_examples {
+            println!("  line {}: \"{}\"", failing_value_example.line_number, failing_value_example.offending_value);
+        }
+        println!("Type not changed. Add such values to null_values in the metadata file, or choose another type.");
+        return Ok(false);
+    }
+
+    let column_info = &mut analysis_results.column_information_list[column_index];
+    column_info.data_type_declared_in_metadata = candidate_data_type != column_info.inferred_data_type;
+    column_info.missing_value_counts.type_invalid_count = 0;
+    println!(
+        "✓ Column {} ('{}') changed from {} to {}",
+        column_index + 1,
+        column_info.column_name,
+        column_info.detected_data_type.to_toml_string(),
+        candidate_data_type.to_toml_string()
+    );
+    column_info.detected_data_type = candidate_data_type;
+
+    Ok(true)
+}
+
+/// Parses one line typed at the review prompt
+///
+/// # Arguments
+/// * `command_text` - The trimmed input line
+/// * `column_count` - Number of columns (column numbers are 1-based)
+///
+/// # Returns
+/// * `Result<ColumnReviewCommand, String>` - The command, or a message explaining the problem
+fn parse_column_review_command(command_text: &str, column_count: usize) -> Result<ColumnReviewCommand, String> {
+    let mut command_words = command_text.split_whitespace();
+    let command_word = match command_words.next() {
+        Some(command_word) => command_word.to_lowercase(),
+        None => return Ok(ColumnReviewCommand::ReturnToMenu),
+    };
+
+    let parse_column_number = |column_number_text: Option<&str>| -> Result<usize, String> {
+        let column_number_text = column_number_text.ok_or_else(|| format!("'{}' needs a column number", command_word))?;
+        match column_number_text.parse::<usize>() {
+            Ok(column_number) if (1..=column_count).contains(&column_number) => Ok(column_number - 1),
+            _ => Err(format!("Column number must be between 1 and {}: '{}'", column_count, column_number_text)),
+        }
+    };
+
+    match command_word.as_str() {
+        "list" | "l" => Ok(ColumnReviewCommand::ListColumns),
+        "help" | "h" | "?" => Ok(ColumnReviewCommand::ShowHelp),
+        "back" | "done" | "q" => Ok(ColumnReviewCommand::ReturnToMenu),
+        "type" | "t" => {
+            let column_index = parse_column_number(command_words.next())?;
+            let type_name = command_words.next().ok_or_else(|| format!("'type' needs a type: {}", REVIEW_TYPE_NAMES))?;
+            let candidate_data_type = CsvColumnDataType::from_toml_string(type_name)
+                .ok_or_else(|| format!("Unknown type '{}'. Choose one of: {}", type_name, REVIEW_TYPE_NAMES))?;
+            Ok(ColumnReviewCommand::ChangeDataType(column_index, candidate_data_type))
+        }
+        "rename" | "r" => {
+            let column_index = parse_column_number(command_words.next())?;
+            let new_column_name = command_words.collect::<Vec<&str>>().join(" ");
+            if new_column_name.is_empty() {
+                return Err("'rename' needs a new name".to_string());
+            }
+            Ok(ColumnReviewCommand::RenameColumn(column_index, new_column_name))
+        }
+        "ignore" | "i" => Ok(ColumnReviewCommand::IgnoreColumn(parse_column_number(command_words.next())?)),
+        "include" => Ok(ColumnReviewCommand::IncludeColumn(parse_column_number(command_words.next())?)),
+        _ if command_word.chars().all(|character| character.is_ascii_digit()) => {
+            Ok(ColumnReviewCommand::ShowColumnDetails(parse_column_number(Some(command_word.as_str()))?))
+        }
+        _ => Err(format!("Unknown command '{}'. Type 'help' for the list of commands.", command_text)),
+    }
+}
+
+/// Displays every column with its type and sample values
+///
+/// # Arguments
+/// * `column_information_list` - The analyzed columns
+fn display_column_review_table(column_information_list: &[CsvColumnInformation]) {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Review/Edit Column Data Types");
+    println!("═══════════════════════════════════════════════════════════════");
+
+    for column_info in column_information_list {
+        let type_text = if column_info.column_ignored {
+            "(ignored)".to_string()
+        } else if column_info.data_type_declared_in_metadata {
+            format!("{}*", column_info.detected_data_type.to_toml_string())
+        } else {
+            column_info.detected_data_type.to_toml_string().to_string()
+        };
+        let sample_text = column_info.sample_values
+            .iter()
+            .take(REVIEW_SAMPLE_VALUE_LIMIT)
+            .map(|sample_value| sample_value.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+
+        println!("  {:>3}. {:<24} {:<10} {}", column_info.column_index + 1, column_info.column_name, type_text, sample_text);
+    }
+
+    println!("  (* type set by hand, detected type differs)");
+    println!();
+}
+
+/// Displays the per-type match counts behind one column's type
+///
+/// # Arguments
+/// * `column_info` - The column to describe
+fn display_column_type_evidence(column_info: &CsvColumnInformation) {
+    let column_type_tally = &column_info.type_tally;
+    println!("Column {}: {}", column_info.column_index + 1, column_info.column_name);
+    println!(
+        "  type: {}    detected: {}{}",
+        column_info.detected_data_type.to_toml_string(),
+        column_info.inferred_data_type.to_toml_string(),
+        if column_info.column_ignored { "    (ignored)" } else { "" }
+    );
+    println!("  samples: {}", column_info.sample_values.join(", "));
+    println!("  matches among {} checked values:", column_type_tally.checked_value_count);
+
+    for type_match_count in &column_type_tally.type_match_counts {
+        let mismatch_text = match &type_match_count.first_mismatch {
+            Some(first_mismatch) => format!(
+                "    first non-{}: \"{}\" (line {})",
+                type_match_count.data_type.to_toml_string(),
+                first_mismatch.offending_value,
+                first_mismatch.line_number
+            ),
+            None => String::new(),
+        };
+        println!(
+            "    {:<9} {:>6}{}",
+            type_match_count.data_type.to_toml_string(),
+            type_match_count.matched_value_count,
+            mismatch_text
+        );
+    }
+}
+
+/// Displays the review commands
+fn display_column_review_help() {
+    println!("Commands:");
+    println!("  <n>                show the type evidence for column n");
+    println!("  type <n> <type>    change the type ({})", REVIEW_TYPE_NAMES);
+    println!("  rename <n> <name>  rename the column");
+    println!("  ignore <n>         leave the column out of statistics and import");
+    println!("  include <n>        take an ignored column back in");
+    println!("  list               show the columns again");
+    println!("  (empty line)       return to the menu");
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_review_commands() {
+        assert_eq!(parse_column_review_command("2", 3), Ok(ColumnReviewCommand::ShowColumnDetails(1)));
+        assert_eq!(
+            parse_column_review_command("type 3 Money", 3),
+            Ok(ColumnReviewCommand::ChangeDataType(2, CsvColumnDataType::Decimal))
+        );
+        assert_eq!(
+            parse_column_review_command("rename 1 postal  code", 3),
+            Ok(ColumnReviewCommand::RenameColumn(0, "postal code".to_string()))
+        );
+        assert_eq!(parse_column_review_command("ignore 1", 3), Ok(ColumnReviewCommand::IgnoreColumn(0)));
+        assert_eq!(parse_column_review_command("", 3), Ok(ColumnReviewCommand::ReturnToMenu));
+    }
+
+    #[test]
+    fn test_reject_invalid_review_commands() {
+        assert!(parse_column_review_command("4", 3).is_err());
+        assert!(parse_column_review_command("type 1 colour", 3).is_err());
+        assert!(parse_column_review_command("type 1", 3).is_err());
+        assert!(parse_column_review_command("rename 2", 3).is_err());
+        assert!(parse_column_review_command("explode", 3).is_err());
+    }
+}
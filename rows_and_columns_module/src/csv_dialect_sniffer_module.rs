@@ -0,0 +1,357 @@
+// src/csv_dialect_sniffer_module.rs
+
+/// Automatic CSV dialect detection for rows_and_columns
+///
+/// This module guesses the delimiter and quote character of a delimited text
+/// file by reading a small sample from the start of the file. Every candidate
+/// dialect is run through the real `CsvRecordReader`, and the candidate whose
+/// records most consistently have the same number of fields wins.
+///
+/// # Scoring
+/// - Candidates: delimiters `,` `;` `\t` `|` combined with quotes `"` and `'`
+/// - Consistency: share of sampled records whose field count equals the most
+///   common field count; a candidate whose most common count is 1 scores 0
+///   (that delimiter does not split anything)
+/// - A candidate that cannot parse a fully sampled file (e.g. an unterminated
+///   quote) scores 0
+/// - Ties keep candidate order, with the extension default delimiter first
+///
+/// # Confidence
+/// `winner_consistency * (1 - runner_up_consistency / 2)`, further scaled down
+/// when fewer than `CSV_DIALECT_SNIFF_MIN_CONFIDENT_RECORDS` records could be
+/// sampled. The runner-up is the best candidate with a different delimiter, so
+/// a user can see which alternative nearly won and override it in the metadata.
+///
+/// # Design Philosophy
+/// - No-Load: only the first `CSV_DIALECT_SNIFF_SAMPLE_BYTES` bytes are read
+/// - One parser: the sniffer uses the same reader as every analysis pass
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+// Import the transcoding reader so non-UTF-8 samples can be sniffed
+use super::text_encoding_module::{CsvTextEncoding, TextDecodingReader};
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+};
+
+// Import the record reader and dialect description
+use super::csv_record_reader_module::{CsvDialect, CsvRecordReader};
+
+/// Configuration constants for dialect sniffing
+const CSV_DIALECT_SNIFF_SAMPLE_BYTES: u64 = 64 * 1024;
+const CSV_DIALECT_SNIFF_SAMPLE_RECORDS: usize = 50;
+const CSV_DIALECT_SNIFF_MIN_CONFIDENT_RECORDS: usize = 5;
+const CSV_DIALECT_SNIFF_CANDIDATE_DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+const CSV_DIALECT_SNIFF_CANDIDATE_QUOTES: [char; 2] = ['"', '\''];
+
+/// How well one candidate delimiter/quote pair fits the sample
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvDialectCandidateScore {
+    /// Candidate field delimiter
+    pub field_delimiter: char,
+
+    /// Candidate quote character
+    pub quote_character: Option<char>,
+
+    /// Share of sampled records (0.0 - 1.0) with the most common field count
+    pub consistency_score: f64,
+
+    /// The most common field count under this candidate
+    pub modal_field_count: usize,
+}
+
+/// Result of sniffing a file's dialect
+#[derive(Debug, Clone)]
+pub struct CsvDialectSniffingReport {
+    /// The base dialect with the winning delimiter and quote character applied
+    pub sniffed_dialect: CsvDialect,
+
+    /// The winning candidate (None if no candidate split the sample at all)
+    pub winning_candidate: Option<CsvDialectCandidateScore>,
+
+    /// Best candidate with a different delimiter than the winner
+    pub runner_up_candidate: Option<CsvDialectCandidateScore>,
+
+    /// Confidence in the winner (0.0 - 1.0)
+    pub confidence: f64,
+
+    /// Number of records the scores are based on
+    pub sampled_record_count: usize,
+
+    /// Whether the sniffed dialect was used, or a dialect from the metadata file overrode it
+    pub applied_to_analysis: bool,
+}
+
+/// Sniffs the dialect of a CSV file from a sample at the start of the file
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `text_encoding` - Encoding used to decode the sample
+/// * `base_dialect` - Dialect supplying everything except delimiter and quote
+///   (and the preferred delimiter when candidates tie)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvDialectSniffingReport>` - The sniffing report
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be opened or read
+pub fn sniff_csv_dialect_from_file(
+    csv_file_path: &Path,
+    text_encoding: CsvTextEncoding,
+    base_dialect: &CsvDialect,
+) -> RowsAndColumnsResult<CsvDialectSniffingReport> {
+    let csv_file = File::open(csv_file_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to open CSV file for dialect sniffing: {}", csv_file_path.display()),
+            io_error,
+        )
+    })?;
+
+    // Read one byte more than the sample size to learn whether the sample is the whole file
+    let mut sample_bytes = Vec::new();
+    csv_file
+        .take(CSV_DIALECT_SNIFF_SAMPLE_BYTES + 1)
+        .read_to_end(&mut sample_bytes)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to read CSV sample for dialect sniffing: {}", csv_file_path.display()),
+                io_error,
+            )
+        })?;
+
+    let mut sample_was_truncated = sample_bytes.len() as u64 > CSV_DIALECT_SNIFF_SAMPLE_BYTES;
+    sample_bytes.truncate(CSV_DIALECT_SNIFF_SAMPLE_BYTES as usize);
+
+    // Decode the sample to UTF-8; text up to an undecodable byte is still a usable
+    // sample, and the analysis passes report the undecodable bytes themselves
+    let mut decoded_sample_bytes = Vec::new();
+    if TextDecodingReader::new(Cursor::new(sample_bytes), text_encoding)
+        .read_to_end(&mut decoded_sample_bytes)
+        .is_err()
+    {
+        sample_was_truncated = true;
+    }
+
+    Ok(sniff_csv_dialect_from_sample(&decoded_sample_bytes, sample_was_truncated, base_dialect))
+}
+
+/// Sniffs the dialect from an in-memory sample
+///
+/// # Arguments
+/// * `sample_bytes` - Bytes from the start of the file
+/// * `sample_was_truncated` - Whether the file continues past the sample
+/// * `base_dialect` - Dialect supplying everything except delimiter and quote
+///
+/// # Returns
+/// * `CsvDialectSniffingReport` - The sniffing report
+pub fn sniff_csv_dialect_from_sample(
+    sample_bytes: &[u8],
+    sample_was_truncated: bool,
+    base_dialect: &CsvDialect,
+) -> CsvDialectSniffingReport {
+    // The base delimiter goes first so it wins ties (e.g. tab for .tsv files)
+    let mut candidate_delimiters = vec![base_dialect.field_delimiter];
+    for candidate_delimiter in CSV_DIALECT_SNIFF_CANDIDATE_DELIMITERS {
+        if !candidate_delimiters.contains(&candidate_delimiter) {
+            candidate_delimiters.push(candidate_delimiter);
+        }
+    }
+
+    let mut candidate_scores = Vec::new();
+    let mut sampled_record_count = 0;
+
+    for candidate_delimiter in &candidate_delimiters {
+        for candidate_quote in CSV_DIALECT_SNIFF_CANDIDATE_QUOTES {
+            let candidate_dialect = CsvDialect {
+                field_delimiter: *candidate_delimiter,
+                quote_character: Some(candidate_quote),
+                ..base_dialect.clone()
+            };
+
+            let (candidate_score, candidate_record_count) =
+                score_candidate_dialect(sample_bytes, sample_was_truncated, candidate_dialect);
+            sampled_record_count = sampled_record_count.max(candidate_record_count);
+            candidate_scores.push(candidate_score);
+        }
+    }
+
+    // Pick the highest score; earlier candidates win ties
+    let mut winning_candidate: Option<CsvDialectCandidateScore> = None;
+    for candidate_score in &candidate_scores {
+        let is_better = match &winning_candidate {
+            Some(current_winner) => candidate_score.consistency_score > current_winner.consistency_score,
+            None => candidate_score.consistency_score > 0.0,
+        };
+        if is_better {
+            winning_candidate = Some(candidate_score.clone());
+        }
+    }
+
+    let runner_up_candidate = winning_candidate.as_ref().and_then(|winner| {
+        let mut best_alternative: Option<&CsvDialectCandidateScore> = None;
+        for candidate_score in &candidate_scores {
+            if candidate_score.field_delimiter == winner.field_delimiter {
+                continue;
+            }
+            let is_better = match best_alternative {
+                Some(current_best) => candidate_score.consistency_score > current_best.consistency_score,
+                None => true,
+            };
+            if is_better {
+                best_alternative = Some(candidate_score);
+            }
+        }
+        best_alternative.cloned()
+    });
+
+    let mut sniffed_dialect = base_dialect.clone();
+    let confidence = match &winning_candidate {
+        Some(winner) => {
+            sniffed_dialect.field_delimiter = winner.field_delimiter;
+            sniffed_dialect.quote_character = winner.quote_character;
+
+            let runner_up_consistency = runner_up_candidate
+                .as_ref()
+                .map(|runner_up| runner_up.consistency_score)
+                .unwrap_or(0.0);
+            let sample_size_factor = (sampled_record_count as f64
+                / CSV_DIALECT_SNIFF_MIN_CONFIDENT_RECORDS as f64)
+                .min(1.0);
+
+            winner.consistency_score * (1.0 - runner_up_consistency / 2.0) * sample_size_factor
+        }
+        None => 0.0,
+    };
+
+    CsvDialectSniffingReport {
+        sniffed_dialect,
+        winning_candidate,
+        runner_up_candidate,
+        confidence,
+        sampled_record_count,
+        applied_to_analysis: true,
+    }
+}
+
+/// Parses the sample with one candidate dialect and scores field count consistency
+///
+/// # Arguments
+/// * `sample_bytes` - Bytes from the start of the file
+/// * `sample_was_truncated` - Whether the file continues past the sample
+/// * `candidate_dialect` - The dialect to try
+///
+/// # Returns
+/// * `(CsvDialectCandidateScore, usize)` - The score and the number of records it is based on
+fn score_candidate_dialect(
+    sample_bytes: &[u8],
+    sample_was_truncated: bool,
+    candidate_dialect: CsvDialect,
+) -> (CsvDialectCandidateScore, usize) {
+    let field_delimiter = candidate_dialect.field_delimiter;
+    let quote_character = candidate_dialect.quote_character;
+    let mut csv_record_reader = CsvRecordReader::new_with_dialect(Cursor::new(sample_bytes), candidate_dialect);
+
+    let mut record_field_counts: Vec<usize> = Vec::new();
+    let mut sample_is_unparseable = false;
+
+    while record_field_counts.len() < CSV_DIALECT_SNIFF_SAMPLE_RECORDS {
+        match csv_record_reader.read_next_record() {
+            Ok(Some(csv_record)) => record_field_counts.push(csv_record.field_values.len()),
+            Ok(None) => {
+                // The final record of a cut-off sample may itself be cut off
+                if sample_was_truncated && record_field_counts.len() > 1 {
+                    record_field_counts.pop();
+                }
+                break;
+            }
+            Err(_) => {
+                // A cut-off sample may end inside a quote or a multi-byte character;
+                // a complete file that fails to parse rules this candidate out
+                sample_is_unparseable = !sample_was_truncated;
+                break;
+            }
+        }
+    }
+
+    let mut field_count_frequencies: Vec<(usize, usize)> = Vec::new();
+    for field_count in &record_field_counts {
+        match field_count_frequencies.iter_mut().find(|(count, _)| count == field_count) {
+            Some((_, frequency)) => *frequency += 1,
+            None => field_count_frequencies.push((*field_count, 1)),
+        }
+    }
+
+    let (modal_field_count, modal_frequency) = field_count_frequencies
+        .iter()
+        .copied()
+        .fold((0, 0), |best, current| if current.1 > best.1 { current } else { best });
+
+    let consistency_score = if sample_is_unparseable || modal_field_count < 2 || record_field_counts.is_empty() {
+        0.0
+    } else {
+        modal_frequency as f64 / record_field_counts.len() as f64
+    };
+
+    (
+        CsvDialectCandidateScore {
+            field_delimiter,
+            quote_character,
+            consistency_score,
+            modal_field_count,
+        },
+        record_field_counts.len(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper to sniff a complete in-memory file with the comma default
+    fn sniff_test_text(csv_text: &str) -> CsvDialectSniffingReport {
+        sniff_csv_dialect_from_sample(csv_text.as_bytes(), false, &CsvDialect::default())
+    }
+
+    #[test]
+    fn test_sniffs_semicolon_with_decimal_commas() {
+        let sniffing_report = sniff_test_text("name;price\nA;3,50\nB;4\nC;12,75\nD;1\n");
+
+        assert_eq!(sniffing_report.sniffed_dialect.field_delimiter, ';');
+        assert!(sniffing_report.confidence > 0.9);
+        assert_eq!(sniffing_report.sampled_record_count, 5);
+    }
+
+    #[test]
+    fn test_sniffs_tab_and_pipe_delimiters() {
+        let tab_report = sniff_test_text("a\tb\tc\n1\t2, 3\t4\n5\t6\t7\n");
+        assert_eq!(tab_report.sniffed_dialect.field_delimiter, '\t');
+
+        let pipe_report = sniff_test_text("id|note\n1|\"x|y\"\n2|z\n");
+        assert_eq!(pipe_report.sniffed_dialect.field_delimiter, '|');
+        assert_eq!(pipe_report.sniffed_dialect.quote_character, Some('"'));
+    }
+
+    #[test]
+    fn test_ambiguous_sample_reports_runner_up() {
+        let sniffing_report = sniff_test_text("a;1,5\nb;2,5\nc;3,5\nd;4,5\ne;5,5\n");
+
+        let runner_up = sniffing_report.runner_up_candidate.expect("runner-up expected");
+        assert_eq!(sniffing_report.sniffed_dialect.field_delimiter, ',');
+        assert_eq!(runner_up.field_delimiter, ';');
+        assert!(sniffing_report.confidence <= 0.5);
+    }
+
+    #[test]
+    fn test_single_column_keeps_base_dialect() {
+        let tsv_dialect = CsvDialect::default_for_file_extension("tsv");
+        let sniffing_report = sniff_csv_dialect_from_sample(b"only\none\ncolumn\n", false, &tsv_dialect);
+
+        assert!(sniffing_report.winning_candidate.is_none());
+        assert_eq!(sniffing_report.sniffed_dialect.field_delimiter, '\t');
+        assert_eq!(sniffing_report.confidence, 0.0);
+    }
+}
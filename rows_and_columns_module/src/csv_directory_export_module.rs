@@ -0,0 +1,538 @@
+// src/csv_directory_export_module.rs
+
+/// Export of a directory-stored dataset back to CSV for rows_and_columns
+///
+/// Walks a dataset made by the directory import
+/// (`rows_columns_data/csv_imports/dataset_NNN_name/`), puts each row back
+/// together from its column directories (through the dataset's storage
+/// backend, cell files or column segments), and writes it as CSV in the
+/// dialect recorded in the dataset's `csv_metadata.toml` (delimiter, quote
+/// character, escape style). Rows are read one at a time, so the dataset is
+/// never held in memory.
+///
+/// # Command Line
+/// ```text
+/// rows_and_columns --export <dataset> <output.csv> [--columns a,b,c] [--rows A..B] [--no-header]
+/// ```
+/// - `<dataset>` - a dataset directory name under `csv_imports` or a path to one
+/// - `--columns` - columns to write, in this order, by name, column directory
+///   name or 1-based column number (default: every imported column)
+/// - `--rows` - 1-based, inclusive row range; `A..` and `..B` leave one end open
+/// - `--no-header` - leave out the header line of column names
+///
+/// The output is UTF-8. An existing output file is never overwritten.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Import the dialect used to write the output
+use super::csv_record_reader_module::{CsvDialect, CsvEscapeStyle, CsvLineTerminator, CsvTrimPolicy};
+
+// Import the dataset layout written by the directory import
+use super::csv_directory_import_module::{
+    DATASET_METADATA_FILE_NAME,
+    ImportProgressRecord,
+    open_imported_dataset_reader,
+};
+
+// Import the column directory naming shared by the storage backends
+use super::dataset_storage_module::parse_column_directory_number;
+
+// Import metadata reading for the dialect and column names
+use super::metadata_manager_module::{
+    COLUMN_SECTION_NAME_PREFIX,
+    MetadataTomlDocument,
+    read_csv_dialect_from_metadata,
+    read_metadata_toml_file,
+};
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_configuration_error,
+    create_csv_processing_error,
+    create_file_system_error,
+};
+
+/// Inclusive, 1-based range of data rows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatasetRowRange {
+    /// First row to export
+    pub first_row_number: usize,
+
+    /// Last row to export (None exports to the end)
+    pub last_row_number: Option<usize>,
+}
+
+impl Default for DatasetRowRange {
+    fn default() -> Self {
+        DatasetRowRange { first_row_number: 1, last_row_number: None }
+    }
+}
+
+impl DatasetRowRange {
+    /// Parses `A..B`, `A..` or `..B`
+    ///
+    /// # Arguments
+    /// * `range_text` - The range as typed on the command line
+    ///
+    /// # Returns
+    /// * `Result<DatasetRowRange, String>` - The range, or a message explaining the problem
+    pub fn parse(range_text: &str) -> Result<DatasetRowRange, String> {
+        let (first_text, last_text) = range_text
+            .split_once("..")
+            .ok_or_else(|| format!("Row range must look like A..B, A.. or ..B: '{}'", range_text))?;
+        let parse_row_number = |row_number_text: &str| -> Result<Option<usize>, String> {
+            if row_number_text.is_empty() {
+                return Ok(None);
+            }
+            match row_number_text.parse::<usize>() {
+                Ok(row_number) if row_number >= 1 => Ok(Some(row_number)),
+                _ => Err(format!("Row numbers start at 1: '{}'", row_number_text)),
+            }
+        };
+
+        let row_range = DatasetRowRange {
+            first_row_number: parse_row_number(first_text)?.unwrap_or(1),
+            last_row_number: parse_row_number(last_text)?,
+        };
+        if let Some(last_row_number) = row_range.last_row_number
+            && last_row_number < row_range.first_row_number
+        {
+            return Err(format!("Row range ends before it starts: '{}'", range_text));
+        }
+
+        Ok(row_range)
+    }
+}
+
+/// What to export and how
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DatasetExportOptions {
+    /// Columns to write, in order (None writes every imported column)
+    pub column_selectors: Option<Vec<String>>,
+
+    /// Rows to write
+    pub row_range: DatasetRowRange,
+
+    /// Leave out the header line of column names
+    pub omit_header_row: bool,
+}
+
+/// A parsed `--export` command line
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetExportRequest {
+    /// Dataset directory name or path, as typed
+    pub dataset_argument: String,
+
+    /// The CSV file to create
+    pub output_file_path: PathBuf,
+
+    /// Column, row and header options
+    pub export_options: DatasetExportOptions,
+}
+
+/// Outcome of an export
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetExportSummary {
+    /// The CSV file written
+    pub output_file_path: PathBuf,
+
+    /// Names of the columns written, in order
+    pub exported_column_names: Vec<String>,
+
+    /// Number of data rows written
+    pub exported_row_count: usize,
+}
+
+/// One column directory of a dataset
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetColumn {
+    /// 1-based position of the column in the original CSV file
+    pub column_number: usize,
+
+    /// Column name from the dataset's metadata (or the directory name)
+    pub column_name: String,
+
+    /// Name of the column directory
+    pub column_directory_name: String,
+}
+
+/// Parses the arguments following `--export`
+///
+/// # Arguments
+/// * `export_arguments` - Command line arguments after `--export`
+///
+/// # Returns
+/// * `Result<DatasetExportRequest, String>` - The request, or a message explaining the problem
+pub fn parse_dataset_export_arguments(export_arguments: &[String]) -> Result<DatasetExportRequest, String> {
+    let mut positional_arguments = Vec::new();
+    let mut export_options = DatasetExportOptions::default();
+
+    let mut argument_iterator = export_arguments.iter();
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--columns" => {
+                let column_list = argument_iterator.next().ok_or("--columns needs a comma-separated list")?;
+                let column_selectors: Vec<String> = column_list
+                    .split(',')
+                    .map(|column_selector| column_selector.trim().to_string())
+                    .filter(|column_selector| !column_selector.is_empty())
+                    .collect();
+                if column_selectors.is_empty() {
+                    return Err("--columns needs at least one column".to_string());
+                }
+                export_options.column_selectors = Some(column_selectors);
+            }
+            "--rows" => {
+                let range_text = argument_iterator.next().ok_or("--rows needs a range such as 10..20")?;
+                export_options.row_range = DatasetRowRange::parse(range_text)?;
+            }
+            "--no-header" => export_options.omit_header_row = true,
+            _ if argument.starts_with("--") => return Err(format!("Unknown export option: {}", argument)),
+            _ => positional_arguments.push(argument.clone()),
+        }
+    }
+
+    match positional_arguments.as_slice() {
+        [dataset_argument, output_file_argument] => Ok(DatasetExportRequest {
+            dataset_argument: dataset_argument.clone(),
+            output_file_path: PathBuf::from(output_file_argument),
+            export_options,
+        }),
+        _ => Err("--export needs a dataset and an output file: --export <dataset> <output.csv>".to_string()),
+    }
+}
+
+/// Finds the dataset directory named on the command line
+///
+/// # Arguments
+/// * `dataset_argument` - A directory name under `csv_imports`, or a path
+/// * `csv_imports_directory` - The `rows_columns_data/csv_imports` directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - The dataset directory
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If no such dataset directory exists
+pub fn resolve_dataset_directory(dataset_argument: &str, csv_imports_directory: &Path) -> RowsAndColumnsResult<PathBuf> {
+    let dataset_path = PathBuf::from(dataset_argument);
+    if dataset_path.is_dir() {
+        return Ok(dataset_path);
+    }
+
+    let imported_dataset_path = csv_imports_directory.join(dataset_argument);
+    if imported_dataset_path.is_dir() {
+        return Ok(imported_dataset_path);
+    }
+
+    Err(create_configuration_error(&format!(
+        "No dataset directory '{}' (looked in the current directory and in {})",
+        dataset_argument,
+        csv_imports_directory.display()
+    )))
+}
+
+/// Writes a directory-stored dataset as a CSV file
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `output_file_path` - The CSV file to create (must not exist)
+/// * `export_options` - Columns, rows and header
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DatasetExportSummary>` - What was written
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the import is unfinished, a column is unknown,
+///   or the output file already exists
+/// * `RowsAndColumnsError::MetadataError` - If the dataset's metadata or progress file is invalid
+/// * `RowsAndColumnsError::CsvProcessingError` - If a value cannot be written in the recorded dialect
+/// * `RowsAndColumnsError::FileSystemError` - If a cell file cannot be read or the output cannot be written
+pub fn export_dataset_to_csv(
+    dataset_directory: &Path,
+    output_file_path: &Path,
+    export_options: &DatasetExportOptions,
+) -> RowsAndColumnsResult<DatasetExportSummary> {
+    let (import_progress_record, mut dataset_storage_reader) = open_imported_dataset_reader(dataset_directory)?;
+
+    // Dialect and column names come from the metadata copied in at import time
+    let metadata_file_path = dataset_directory.join(DATASET_METADATA_FILE_NAME);
+    let metadata_document = read_metadata_toml_file(&metadata_file_path)?;
+    let output_dialect = match &metadata_document {
+        Some(metadata_document) => read_csv_dialect_from_metadata(
+            metadata_document,
+            CsvDialect::default(),
+            &metadata_file_path.display().to_string(),
+        )?,
+        None => CsvDialect::default(),
+    };
+
+    let dataset_columns = list_dataset_columns(&import_progress_record, metadata_document.as_ref());
+    let exported_columns = match &export_options.column_selectors {
+        Some(column_selectors) => select_dataset_columns(&dataset_columns, column_selectors)
+            .map_err(|selection_problem| create_configuration_error(&selection_problem))?,
+        None => dataset_columns,
+    };
+
+    if output_file_path.exists() {
+        return Err(create_configuration_error(&format!(
+            "Output file already exists: {} (choose another name or remove it first)",
+            output_file_path.display()
+        )));
+    }
+    let output_file = File::create(output_file_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to create output file: {}", output_file_path.display()), io_error)
+    })?;
+    let mut output_writer = BufWriter::new(output_file);
+    let mut write_output_line = |field_values: &[String], row_number: Option<usize>| -> RowsAndColumnsResult<()> {
+        let csv_line = format_csv_line_in_dialect(field_values, &output_dialect).map_err(|format_problem| {
+            create_csv_processing_error(&format!("Cannot export row: {}", format_problem), row_number, None)
+        })?;
+        output_writer.write_all(csv_line.as_bytes()).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to write output file: {}", output_file_path.display()), io_error)
+        })
+    };
+
+    if !export_options.omit_header_row {
+        let header_values: Vec<String> = exported_columns.iter().map(|column| column.column_name.clone()).collect();
+        write_output_line(&header_values, None)?;
+    }
+
+    // Reassemble each row from its columns, one row at a time
+    let exported_column_indexes: Vec<usize> = exported_columns
+        .iter()
+        .map(|exported_column| exported_column.column_number.saturating_sub(1))
+        .collect();
+    let last_row_number = export_options.row_range.last_row_number
+        .unwrap_or(import_progress_record.imported_row_count)
+        .min(import_progress_record.imported_row_count);
+    let mut exported_row_count = 0;
+    for row_number in export_options.row_range.first_row_number..=last_row_number {
+        let row_values = dataset_storage_reader.read_row(row_number, &exported_column_indexes)?;
+        write_output_line(&row_values, Some(row_number))?;
+        exported_row_count += 1;
+    }
+
+    output_writer.flush().map_err(|io_error| {
+        create_file_system_error(&format!("Failed to write output file: {}", output_file_path.display()), io_error)
+    })?;
+
+    Ok(DatasetExportSummary {
+        output_file_path: output_file_path.to_path_buf(),
+        exported_column_names: exported_columns.into_iter().map(|column| column.column_name).collect(),
+        exported_row_count,
+    })
+}
+
+/// Displays the outcome of an export
+///
+/// # Arguments
+/// * `export_summary` - The export outcome
+pub fn display_dataset_export_summary(export_summary: &DatasetExportSummary) {
+    println!("✓ Exported {} rows to {}", export_summary.exported_row_count, export_summary.output_file_path.display());
+    println!("  Columns: {}", export_summary.exported_column_names.join(", "));
+}
+
+/// Lists the columns stored in a dataset, named from the dataset's metadata
+///
+/// # Arguments
+/// * `import_progress_record` - The dataset's progress record (column directories)
+/// * `metadata_document` - The metadata copied into the dataset, if present
+///
+/// # Returns
+/// * `Vec<DatasetColumn>` - One entry per column directory, in storage order
+pub fn list_dataset_columns(
+    import_progress_record: &ImportProgressRecord,
+    metadata_document: Option<&MetadataTomlDocument>,
+) -> Vec<DatasetColumn> {
+    import_progress_record.column_directory_names
+        .iter()
+        .map(|column_directory_name| {
+            let column_number = parse_column_directory_number(column_directory_name).unwrap_or(0);
+            let column_name = metadata_document
+                .and_then(|metadata_document| {
+                    metadata_document.get_string(&format!("{}{}", COLUMN_SECTION_NAME_PREFIX, column_number), "name")
+                })
+                .unwrap_or(column_directory_name)
+                .to_string();
+            DatasetColumn { column_number, column_name, column_directory_name: column_directory_name.clone() }
+        })
+        .collect()
+}
+
+/// Picks columns in the order given by `--columns`
+///
+/// A selector matches a column name first, then a column directory name,
+/// then a 1-based column number.
+///
+/// # Arguments
+/// * `dataset_columns` - Every column of the dataset
+/// * `column_selectors` - The requested columns
+///
+/// # Returns
+/// * `Result<Vec<DatasetColumn>, String>` - The selected columns, or a message naming the unknown one
+pub fn select_dataset_columns(
+    dataset_columns: &[DatasetColumn],
+    column_selectors: &[String],
+) -> Result<Vec<DatasetColumn>, String> {
+    column_selectors
+        .iter()
+        .map(|column_selector| {
+            dataset_columns
+                .iter()
+                .find(|column| column.column_name == *column_selector)
+                .or_else(|| dataset_columns.iter().find(|column| column.column_directory_name == *column_selector))
+                .or_else(|| {
+                    let column_number = column_selector.parse::<usize>().ok()?;
+                    dataset_columns.iter().find(|column| column.column_number == column_number)
+                })
+                .cloned()
+                .ok_or_else(|| {
+                    let available_names: Vec<&str> = dataset_columns.iter().map(|column| column.column_name.as_str()).collect();
+                    format!("Unknown column '{}'. Available columns: {}", column_selector, available_names.join(", "))
+                })
+        })
+        .collect()
+}
+
+/// Formats fields as one CSV line in the given dialect (including the line ending)
+///
+/// A field is quoted when it contains the delimiter, the quote character, a
+/// line break, a backslash (backslash escape style), surrounding whitespace
+/// the dialect would trim, or (first field only) the comment prefix. (With
+/// `trim_whitespace = "all"` the reader trims quoted fields too, but imported
+/// values were read that way and carry no such whitespace.) Quotes
+/// inside are doubled or backslash-escaped according to the escape style.
+///
+/// # Arguments
+/// * `field_values` - The fields to write
+/// * `csv_dialect` - The dialect to write in
+///
+/// # Returns
+/// * `Result<String, String>` - The CSV line, or a message if a field cannot be written in this dialect
+fn format_csv_line_in_dialect(field_values: &[String], csv_dialect: &CsvDialect) -> Result<String, String> {
+    let mut formatted_fields = Vec::with_capacity(field_values.len());
+
+    for (field_position, field_value) in field_values.iter().enumerate() {
+        let has_trimmable_whitespace = csv_dialect.trim_policy != CsvTrimPolicy::KeepWhitespace
+            && field_value.trim() != field_value;
+        let looks_like_comment = field_position == 0
+            && csv_dialect.comment_prefix.as_ref().is_some_and(|comment_prefix| field_value.starts_with(comment_prefix.as_str()));
+        let needs_quoting = field_value.contains(csv_dialect.field_delimiter)
+            || field_value.contains(['\n', '\r'])
+            || csv_dialect.quote_character.is_some_and(|quote_character| field_value.contains(quote_character))
+            || (csv_dialect.escape_style == CsvEscapeStyle::Backslash && field_value.contains('\\'))
+            || has_trimmable_whitespace
+            || looks_like_comment;
+
+        if !needs_quoting {
+            formatted_fields.push(field_value.clone());
+            continue;
+        }
+
+        let quote_character = csv_dialect.quote_character.ok_or_else(|| {
+            format!("value {:?} needs quoting but the dialect has no quote character", field_value)
+        })?;
+        let escaped_value = match csv_dialect.escape_style {
+            CsvEscapeStyle::DoubledQuote => field_value.replace(quote_character, &format!("{}{}", quote_character, quote_character)),
+            CsvEscapeStyle::Backslash => field_value
+                .replace('\\', "\\\\")
+                .replace(quote_character, &format!("\\{}", quote_character)),
+            CsvEscapeStyle::NoEscape => {
+                if field_value.contains(quote_character) {
+                    return Err(format!(
+                        "value {:?} contains the quote character but the dialect has no escape style",
+                        field_value
+                    ));
+                }
+                field_value.clone()
+            }
+        };
+        formatted_fields.push(format!("{}{}{}", quote_character, escaped_value, quote_character));
+    }
+
+    let line_ending = match csv_dialect.line_terminator {
+        CsvLineTerminator::Auto | CsvLineTerminator::Lf => "\n",
+        CsvLineTerminator::CrLf => "\r\n",
+        CsvLineTerminator::Cr => "\r",
+    };
+
+    Ok(format!("{}{}", formatted_fields.join(&csv_dialect.field_delimiter.to_string()), line_ending))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::csv_record_reader_module::CsvRecordReader;
+
+    /// Helper to build fields from string slices
+    fn test_fields(field_values: &[&str]) -> Vec<String> {
+        field_values.iter().map(|field_value| field_value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_formatted_line_reads_back_in_each_dialect() {
+        let field_values = test_fields(&["a;b", "say \"hi\"", " padded ", "c:\\temp", "two\nlines", ""]);
+        let comma_dialect = CsvDialect { trim_policy: CsvTrimPolicy::TrimUnquotedFields, ..CsvDialect::default() };
+        let semicolon_backslash_dialect = CsvDialect {
+            field_delimiter: ';',
+            escape_style: CsvEscapeStyle::Backslash,
+            line_terminator: CsvLineTerminator::CrLf,
+            ..comma_dialect.clone()
+        };
+
+        for csv_dialect in [comma_dialect, semicolon_backslash_dialect] {
+            let csv_line = format_csv_line_in_dialect(&field_values, &csv_dialect).expect("every field can be written");
+            let mut csv_record_reader = CsvRecordReader::new_with_dialect(csv_line.as_bytes(), csv_dialect);
+            let csv_record = csv_record_reader.read_next_record().expect("line parses").expect("one record");
+
+            assert_eq!(csv_record.field_values, field_values);
+        }
+    }
+
+    #[test]
+    fn test_unwritable_value_is_reported() {
+        let unquoted_dialect = CsvDialect { quote_character: None, ..CsvDialect::default() };
+
+        assert_eq!(format_csv_line_in_dialect(&test_fields(&["plain", "1"]), &unquoted_dialect), Ok("plain,1\n".to_string()));
+        assert!(format_csv_line_in_dialect(&test_fields(&["a,b"]), &unquoted_dialect).is_err());
+    }
+
+    #[test]
+    fn test_parse_export_arguments() {
+        let export_request = parse_dataset_export_arguments(&test_fields(&[
+            "dataset_001_sales", "out.csv", "--columns", "amount, 1", "--rows", "10..20", "--no-header",
+        ]))
+        .expect("valid arguments");
+
+        assert_eq!(export_request.dataset_argument, "dataset_001_sales");
+        assert_eq!(export_request.output_file_path, PathBuf::from("out.csv"));
+        assert_eq!(export_request.export_options.column_selectors, Some(test_fields(&["amount", "1"])));
+        assert_eq!(
+            export_request.export_options.row_range,
+            DatasetRowRange { first_row_number: 10, last_row_number: Some(20) }
+        );
+        assert!(export_request.export_options.omit_header_row);
+
+        assert_eq!(DatasetRowRange::parse("..5"), Ok(DatasetRowRange { first_row_number: 1, last_row_number: Some(5) }));
+        assert!(DatasetRowRange::parse("0..5").is_err());
+        assert!(DatasetRowRange::parse("9..5").is_err());
+        assert!(parse_dataset_export_arguments(&test_fields(&["only_dataset"])).is_err());
+    }
+
+    #[test]
+    fn test_select_columns_by_name_directory_or_number() {
+        let dataset_columns = vec![
+            DatasetColumn { column_number: 1, column_name: "id".to_string(), column_directory_name: "column_001_id".to_string() },
+            DatasetColumn { column_number: 3, column_name: "Amount".to_string(), column_directory_name: "column_003_amount".to_string() },
+        ];
+
+        let selected_columns = select_dataset_columns(&dataset_columns, &test_fields(&["Amount", "column_001_id", "3"]))
+            .expect("known columns");
+        let selected_numbers: Vec<usize> = selected_columns.iter().map(|column| column.column_number).collect();
+
+        assert_eq!(selected_numbers, vec![3, 1, 3]);
+        assert!(select_dataset_columns(&dataset_columns, &test_fields(&["2"])).is_err());
+    }
+}
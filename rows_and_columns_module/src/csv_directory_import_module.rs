@@ -0,0 +1,702 @@
+// src/csv_directory_import_module.rs
+
+/// Directory-per-column import for rows_and_columns
+///
+/// Option 2 of the post-analysis menu ("Load Data"). The CSV file is read
+/// once, as a stream, and every cell is written into its column's directory.
+/// With the default `cell_files` storage backend each cell gets its own file
+/// (see `dataset_storage_module` for the `column_segments` backend):
+///
+/// ```text
+/// rows_columns_data/csv_imports/
+/// └── dataset_001_customers/
+///     ├── csv_metadata.toml            # copy of the CSV's metadata file
+///     ├── import_progress.toml         # how far the import got
+///     ├── column_001_customer_id/
+///     │   ├── row_0001/value.txt
+///     │   └── row_0002/value.txt
+///     └── column_002_customer_name/
+/// ```
+///
+/// Each stored value is the cell exactly as read (after the dialect's trim
+/// policy), so blanks and null sentinels are kept as they were. Rows are
+/// numbered from 1 over the data records that the ragged row policy keeps;
+/// quarantined records are not imported. Columns marked `ignored` in the
+/// metadata file are left out, and column directories keep the column's
+/// position in the file so they match the `[column_N]` sections.
+///
+/// # Restarting an interrupted import
+/// Every thousand rows `import_progress.toml` records how many rows are fully
+/// written, together with the storage backend used. Running the import again
+/// finds the dataset directory made for the same CSV file (same path, size,
+/// modification time and columns), skips the rows already recorded and
+/// rewrites the rest. A dataset whose import completed is reported and left
+/// as it is; if the CSV file changed, the import goes into a new dataset
+/// directory.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// Import the analysis results and a reader over their data records
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    convert_csv_record_read_error,
+    open_analyzed_csv_data_record_reader,
+};
+
+// Import the ragged row policy handling shared with the analysis
+use super::csv_structure_validator_module::conform_record_fields_to_policy;
+
+// Import the storage backends that hold the cells
+use super::dataset_storage_module::{
+    COLUMN_DIRECTORY_PREFIX,
+    CELL_VALUE_FILE_NAME,
+    ROW_DIRECTORY_PREFIX,
+    DatasetStorageBackend,
+    DatasetStorageLayout,
+    DatasetStorageReader,
+    DatasetStorageSettings,
+    open_dataset_storage_reader,
+    open_dataset_storage_writer,
+};
+
+// Import TOML reading and writing helpers
+use super::metadata_manager_module::{
+    MetadataTomlDocument,
+    MetadataTomlValue,
+    escape_toml_basic_string,
+    format_toml_string_array,
+    parse_metadata_toml_content,
+};
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_configuration_error,
+    create_file_system_error,
+    create_metadata_error,
+};
+
+/// Prefix of dataset directories (`dataset_001_name`)
+const DATASET_DIRECTORY_PREFIX: &str = "dataset_";
+
+/// Copy of the CSV's metadata file inside the dataset directory
+pub const DATASET_METADATA_FILE_NAME: &str = "csv_metadata.toml";
+
+/// Progress record of the import inside the dataset directory
+const IMPORT_PROGRESS_FILE_NAME: &str = "import_progress.toml";
+
+/// Rows written between two progress records
+const IMPORT_CHECKPOINT_ROW_INTERVAL: usize = 1000;
+
+/// Rows written between two progress messages on screen
+const IMPORT_DISPLAY_ROW_INTERVAL: usize = 10_000;
+
+/// Fewest digits in a row directory number (`row_0001`)
+const MINIMUM_ROW_NUMBER_DIGITS: usize = 4;
+
+/// Outcome of an import into directory storage
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvDirectoryImportSummary {
+    /// The dataset directory holding the column directories
+    pub dataset_directory: PathBuf,
+
+    /// Number of data rows in the dataset
+    pub imported_row_count: usize,
+
+    /// Number of column directories (ignored columns are not imported)
+    pub imported_column_count: usize,
+
+    /// Backend storing the cells
+    pub storage_backend: DatasetStorageBackend,
+
+    /// Rows already written by an earlier, interrupted run (0 for a fresh import)
+    pub resumed_from_row_count: usize,
+
+    /// True if an earlier run had already completed this import
+    pub import_was_already_complete: bool,
+}
+
+/// Contents of `import_progress.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportProgressRecord {
+    /// Absolute path of the imported CSV file
+    pub source_csv_file: String,
+
+    /// Size of the CSV file when the import started
+    pub source_file_size_bytes: u64,
+
+    /// Modification time of the CSV file (seconds since the Unix epoch)
+    pub source_modified_seconds: u64,
+
+    /// Names of the column directories being written
+    pub column_directory_names: Vec<String>,
+
+    /// Digits in row directory numbers, fixed for the whole import
+    pub row_number_digits: usize,
+
+    /// Backend and segment size, fixed for the whole import
+    pub storage_settings: DatasetStorageSettings,
+
+    /// Data rows whose cells are all written
+    pub imported_row_count: usize,
+
+    /// True once every row is written
+    pub import_complete: bool,
+}
+
+impl ImportProgressRecord {
+    /// Checks whether a progress record belongs to the same CSV file and columns
+    ///
+    /// # Arguments
+    /// * `other_record` - The record to compare with
+    ///
+    /// # Returns
+    /// * `bool` - True if the source file and column directories are the same
+    fn describes_same_source(&self, other_record: &ImportProgressRecord) -> bool {
+        self.source_csv_file == other_record.source_csv_file
+            && self.source_file_size_bytes == other_record.source_file_size_bytes
+            && self.source_modified_seconds == other_record.source_modified_seconds
+            && self.column_directory_names == other_record.column_directory_names
+            && self.storage_settings == other_record.storage_settings
+    }
+
+    /// Describes where and how the dataset's cells are stored
+    ///
+    /// # Arguments
+    /// * `dataset_directory` - The dataset directory
+    ///
+    /// # Returns
+    /// * `DatasetStorageLayout` - The layout for opening a storage reader or writer
+    pub fn storage_layout(&self, dataset_directory: &Path) -> DatasetStorageLayout {
+        DatasetStorageLayout {
+            dataset_directory: dataset_directory.to_path_buf(),
+            column_directory_names: self.column_directory_names.clone(),
+            row_number_digits: self.row_number_digits,
+            storage_settings: self.storage_settings,
+        }
+    }
+
+    /// Formats the record as TOML
+    ///
+    /// # Returns
+    /// * `String` - The content of `import_progress.toml`
+    fn to_toml_content(&self) -> String {
+        let mut toml_content = String::new();
+        toml_content.push_str("# rows_and_columns directory import progress\n");
+        toml_content.push_str(&format!("source_csv_file = \"{}\"\n", escape_toml_basic_string(&self.source_csv_file)));
+        toml_content.push_str(&format!("source_file_size_bytes = {}\n", self.source_file_size_bytes));
+        toml_content.push_str(&format!("source_modified_seconds = {}\n", self.source_modified_seconds));
+        toml_content.push_str(&format!(
+            "column_directories = {}\n",
+            format_toml_string_array(&self.column_directory_names)
+        ));
+        toml_content.push_str(&format!("row_number_digits = {}\n", self.row_number_digits));
+        toml_content.push_str(&format!(
+            "storage_backend = \"{}\"\n",
+            self.storage_settings.storage_backend.to_toml_string()
+        ));
+        toml_content.push_str(&format!("segment_rows = {}\n", self.storage_settings.segment_row_count));
+        toml_content.push_str(&format!("imported_rows = {}\n", self.imported_row_count));
+        toml_content.push_str(&format!("import_complete = {}\n", self.import_complete));
+        toml_content
+    }
+
+    /// Reads a record from a parsed `import_progress.toml`
+    ///
+    /// # Arguments
+    /// * `progress_document` - The parsed file
+    ///
+    /// Records written before storage backends existed have no `storage_backend`
+    /// key; they used cell files.
+    ///
+    /// # Returns
+    /// * `Option<ImportProgressRecord>` - The record, or None if a key is missing or malformed
+    fn from_toml_document(progress_document: &MetadataTomlDocument) -> Option<ImportProgressRecord> {
+        let read_count = |key_name: &str| -> Option<u64> {
+            match progress_document.find_entry("", key_name).map(|entry| &entry.value) {
+                Some(MetadataTomlValue::Integer(integer_value)) => u64::try_from(*integer_value).ok(),
+                _ => None,
+            }
+        };
+        let column_directory_names = match progress_document.find_entry("", "column_directories").map(|entry| &entry.value) {
+            Some(MetadataTomlValue::StringArray(array_items)) => array_items.clone(),
+            _ => return None,
+        };
+        let import_complete = match progress_document.find_entry("", "import_complete").map(|entry| &entry.value) {
+            Some(MetadataTomlValue::Boolean(boolean_value)) => *boolean_value,
+            _ => return None,
+        };
+        let default_storage_settings = DatasetStorageSettings::default();
+        let storage_backend = match progress_document.get_string("", "storage_backend") {
+            Some(backend_text) => DatasetStorageBackend::from_toml_string(backend_text)?,
+            None => default_storage_settings.storage_backend,
+        };
+        let segment_row_count = match progress_document.find_entry("", "segment_rows") {
+            Some(_) => usize::try_from(read_count("segment_rows")?).ok().filter(|segment_row_count| *segment_row_count > 0)?,
+            None => default_storage_settings.segment_row_count,
+        };
+
+        Some(ImportProgressRecord {
+            source_csv_file: progress_document.get_string("", "source_csv_file")?.to_string(),
+            source_file_size_bytes: read_count("source_file_size_bytes")?,
+            source_modified_seconds: read_count("source_modified_seconds")?,
+            column_directory_names,
+            row_number_digits: usize::try_from(read_count("row_number_digits")?).ok()?,
+            storage_settings: DatasetStorageSettings { storage_backend, segment_row_count },
+            imported_row_count: usize::try_from(read_count("imported_rows")?).ok()?,
+            import_complete,
+        })
+    }
+}
+
+/// Imports an analyzed CSV file into directory-per-column storage
+///
+/// # Arguments
+/// * `analysis_results` - The completed analysis (file, dialect, header, ignored columns)
+/// * `csv_imports_directory` - The `rows_columns_data/csv_imports` directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvDirectoryImportSummary>` - Where the data went and how much was written
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If every column is ignored
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV file cannot be read or a directory or file cannot be written
+/// * `RowsAndColumnsError::CsvProcessingError` - If a record cannot be parsed
+pub fn import_csv_into_directory_storage(
+    analysis_results: &CsvAnalysisResults,
+    csv_imports_directory: &Path,
+) -> RowsAndColumnsResult<CsvDirectoryImportSummary> {
+    // Column directories, keyed by position in the file
+    let imported_columns: Vec<(usize, String)> = analysis_results.column_information_list
+        .iter()
+        .filter(|column_info| !column_info.column_ignored)
+        .map(|column_info| {
+            (
+                column_info.column_index,
+                format!(
+                    "{}{:03}_{}",
+                    COLUMN_DIRECTORY_PREFIX,
+                    column_info.column_index + 1,
+                    sanitize_directory_name_component(&column_info.column_name)
+                ),
+            )
+        })
+        .collect();
+    if imported_columns.is_empty() {
+        return Err(create_configuration_error(
+            "Every column is marked ignored; include at least one column before loading data"
+        ));
+    }
+
+    let (source_file_size_bytes, source_modified_seconds) = read_source_file_identity(&analysis_results.csv_file_path)?;
+    let mut import_progress_record = ImportProgressRecord {
+        source_csv_file: analysis_results.csv_file_path.display().to_string(),
+        source_file_size_bytes,
+        source_modified_seconds,
+        column_directory_names: imported_columns.iter().map(|(_, directory_name)| directory_name.clone()).collect(),
+        row_number_digits: analysis_results.total_data_row_count.to_string().len().max(MINIMUM_ROW_NUMBER_DIGITS),
+        storage_settings: analysis_results.dataset_storage_settings,
+        imported_row_count: 0,
+        import_complete: false,
+    };
+
+    // Resume or report an earlier import of the same file, or start a new dataset
+    let dataset_directory = match find_matching_dataset_directory(csv_imports_directory, &import_progress_record)? {
+        Some((existing_dataset_directory, existing_progress_record)) => {
+            if existing_progress_record.import_complete {
+                return Ok(CsvDirectoryImportSummary {
+                    dataset_directory: existing_dataset_directory,
+                    imported_row_count: existing_progress_record.imported_row_count,
+                    imported_column_count: imported_columns.len(),
+                    storage_backend: existing_progress_record.storage_settings.storage_backend,
+                    resumed_from_row_count: existing_progress_record.imported_row_count,
+                    import_was_already_complete: true,
+                });
+            }
+            import_progress_record = existing_progress_record;
+            existing_dataset_directory
+        }
+        None => {
+            let existing_directory_names = list_directory_entry_names(csv_imports_directory)?;
+            let dataset_name = analysis_results.csv_file_path
+                .file_stem()
+                .map(|file_stem| file_stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            csv_imports_directory.join(format!(
+                "{}{:03}_{}",
+                DATASET_DIRECTORY_PREFIX,
+                next_dataset_number(&existing_directory_names),
+                sanitize_directory_name_component(&dataset_name)
+            ))
+        }
+    };
+
+    let resumed_from_row_count = import_progress_record.imported_row_count;
+    create_directory(&dataset_directory)?;
+    fs::copy(&analysis_results.metadata_file_path, dataset_directory.join(DATASET_METADATA_FILE_NAME))
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to copy metadata file into {}", dataset_directory.display()),
+                io_error
+            )
+        })?;
+    write_import_progress_file(&dataset_directory, &import_progress_record)?;
+
+    if resumed_from_row_count > 0 {
+        println!("  Resuming import after row {}", resumed_from_row_count);
+    }
+
+    // Stream the data records once, writing the kept cells through the storage backend
+    let mut dataset_storage_writer = open_dataset_storage_writer(
+        &import_progress_record.storage_layout(&dataset_directory),
+        resumed_from_row_count,
+    )?;
+    let csv_record_reader = open_analyzed_csv_data_record_reader(analysis_results, "directory import")?;
+    let mut data_row_number = 0;
+    for record_result in csv_record_reader {
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during directory import", io_error)
+        })?;
+        let conformed_field_values = match conform_record_fields_to_policy(
+            csv_record.field_values,
+            analysis_results.total_column_count,
+            analysis_results.structural_validation_report.ragged_row_policy,
+        ) {
+            Some(conformed_field_values) => conformed_field_values,
+            None => continue,
+        };
+
+        data_row_number += 1;
+        if data_row_number <= resumed_from_row_count {
+            continue;
+        }
+
+        let row_values: Vec<String> = imported_columns
+            .iter()
+            .map(|(column_index, _)| conformed_field_values[*column_index].clone())
+            .collect();
+        dataset_storage_writer.write_row(data_row_number, &row_values)?;
+
+        if data_row_number % IMPORT_CHECKPOINT_ROW_INTERVAL == 0 {
+            dataset_storage_writer.flush_written_rows()?;
+            import_progress_record.imported_row_count = data_row_number;
+            write_import_progress_file(&dataset_directory, &import_progress_record)?;
+        }
+        if data_row_number % IMPORT_DISPLAY_ROW_INTERVAL == 0 {
+            println!("  ... {} rows imported", data_row_number);
+        }
+    }
+
+    dataset_storage_writer.flush_written_rows()?;
+    import_progress_record.imported_row_count = data_row_number;
+    import_progress_record.import_complete = true;
+    write_import_progress_file(&dataset_directory, &import_progress_record)?;
+
+    Ok(CsvDirectoryImportSummary {
+        dataset_directory,
+        imported_row_count: data_row_number,
+        imported_column_count: imported_columns.len(),
+        storage_backend: import_progress_record.storage_settings.storage_backend,
+        resumed_from_row_count,
+        import_was_already_complete: false,
+    })
+}
+
+/// Displays the outcome of an import
+///
+/// # Arguments
+/// * `import_summary` - The import outcome
+pub fn display_directory_import_summary(import_summary: &CsvDirectoryImportSummary) {
+    if import_summary.import_was_already_complete {
+        println!("✓ This CSV file was already imported; nothing to do.");
+    } else if import_summary.resumed_from_row_count > 0 {
+        println!(
+            "✓ Import finished ({} rows were already written by an earlier run)",
+            import_summary.resumed_from_row_count
+        );
+    } else {
+        println!("✓ Import finished");
+    }
+    println!("  Dataset directory: {}", import_summary.dataset_directory.display());
+    println!("  Rows: {}    Columns: {}", import_summary.imported_row_count, import_summary.imported_column_count);
+    match import_summary.storage_backend {
+        DatasetStorageBackend::CellFiles => {
+            println!("  Each cell is in <column directory>/{}<n>/{}", ROW_DIRECTORY_PREFIX, CELL_VALUE_FILE_NAME);
+        }
+        DatasetStorageBackend::ColumnSegments => {
+            println!("  Each column directory holds segment files with one value per line");
+        }
+    }
+}
+
+/// Looks for a dataset directory made for the same CSV file and columns
+///
+/// # Arguments
+/// * `csv_imports_directory` - The directory holding all datasets
+/// * `expected_progress_record` - Progress record describing the current file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<(PathBuf, ImportProgressRecord)>>` - The directory and its progress, if found
+fn find_matching_dataset_directory(
+    csv_imports_directory: &Path,
+    expected_progress_record: &ImportProgressRecord,
+) -> RowsAndColumnsResult<Option<(PathBuf, ImportProgressRecord)>> {
+    let mut dataset_directory_names: Vec<String> = list_directory_entry_names(csv_imports_directory)?
+        .into_iter()
+        .filter(|directory_name| directory_name.starts_with(DATASET_DIRECTORY_PREFIX))
+        .collect();
+    dataset_directory_names.sort();
+
+    for dataset_directory_name in dataset_directory_names {
+        // Directories without a readable progress record belong to nothing we can resume
+        let dataset_directory = csv_imports_directory.join(&dataset_directory_name);
+        if let Ok(progress_record) = read_import_progress_file(&dataset_directory)
+            && progress_record.describes_same_source(expected_progress_record)
+        {
+            return Ok(Some((dataset_directory, progress_record)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads `import_progress.toml` from a dataset directory
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ImportProgressRecord>` - The progress record
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read
+/// * `RowsAndColumnsError::MetadataError` - If the file is not a valid progress record
+pub fn read_import_progress_file(dataset_directory: &Path) -> RowsAndColumnsResult<ImportProgressRecord> {
+    let progress_file_path = dataset_directory.join(IMPORT_PROGRESS_FILE_NAME);
+    let progress_file_path_text = progress_file_path.display().to_string();
+    let progress_content = fs::read_to_string(&progress_file_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to read import progress file: {}", progress_file_path_text), io_error)
+    })?;
+    let progress_document = parse_metadata_toml_content(&progress_content, &progress_file_path_text)?;
+
+    ImportProgressRecord::from_toml_document(&progress_document).ok_or_else(|| {
+        create_metadata_error("Import progress file is missing a key or has a malformed value", &progress_file_path_text)
+    })
+}
+
+/// Opens a storage reader over a completely imported dataset
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(ImportProgressRecord, Box<dyn DatasetStorageReader>)>` - The progress record and the reader
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the import is unfinished
+/// * `RowsAndColumnsError::FileSystemError` / `MetadataError` - If the progress file cannot be read
+pub fn open_imported_dataset_reader(
+    dataset_directory: &Path,
+) -> RowsAndColumnsResult<(ImportProgressRecord, Box<dyn DatasetStorageReader>)> {
+    let import_progress_record = read_import_progress_file(dataset_directory)?;
+    if !import_progress_record.import_complete {
+        return Err(create_configuration_error(&format!(
+            "The import into {} is unfinished ({} rows); load the CSV file again to complete it",
+            dataset_directory.display(),
+            import_progress_record.imported_row_count
+        )));
+    }
+
+    let dataset_storage_reader = open_dataset_storage_reader(
+        &import_progress_record.storage_layout(dataset_directory),
+        import_progress_record.imported_row_count,
+    );
+    Ok((import_progress_record, dataset_storage_reader))
+}
+
+/// Reads the size and modification time that identify a CSV file's content
+///
+/// # Arguments
+/// * `csv_file_path` - The CSV file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(u64, u64)>` - Size in bytes and modification time in seconds since the Unix epoch
+fn read_source_file_identity(csv_file_path: &Path) -> RowsAndColumnsResult<(u64, u64)> {
+    let file_metadata = fs::metadata(csv_file_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to read file details: {}", csv_file_path.display()), io_error)
+    })?;
+    let modified_seconds = file_metadata
+        .modified()
+        .ok()
+        .and_then(|modified_time| modified_time.duration_since(UNIX_EPOCH).ok())
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or(0);
+
+    Ok((file_metadata.len(), modified_seconds))
+}
+
+/// Lists the names of the entries in a directory
+///
+/// # Arguments
+/// * `directory_path` - The directory to list
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<String>>` - Entry names (not sorted)
+fn list_directory_entry_names(directory_path: &Path) -> RowsAndColumnsResult<Vec<String>> {
+    let directory_entries = fs::read_dir(directory_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to list directory: {}", directory_path.display()), io_error)
+    })?;
+
+    let mut entry_names = Vec::new();
+    for directory_entry in directory_entries {
+        let directory_entry = directory_entry.map_err(|io_error| {
+            create_file_system_error(&format!("Failed to list directory: {}", directory_path.display()), io_error)
+        })?;
+        entry_names.push(directory_entry.file_name().to_string_lossy().to_string());
+    }
+
+    Ok(entry_names)
+}
+
+/// Picks the number of the next dataset directory
+///
+/// # Arguments
+/// * `existing_directory_names` - Names of the entries in the imports directory
+///
+/// # Returns
+/// * `usize` - One more than the highest existing dataset number (1 if there is none)
+fn next_dataset_number(existing_directory_names: &[String]) -> usize {
+    existing_directory_names
+        .iter()
+        .filter_map(|directory_name| directory_name.strip_prefix(DATASET_DIRECTORY_PREFIX))
+        .filter_map(|numbered_name| {
+            let number_text: String = numbered_name.chars().take_while(|character| character.is_ascii_digit()).collect();
+            number_text.parse::<usize>().ok()
+        })
+        .max()
+        .map_or(1, |highest_dataset_number| highest_dataset_number + 1)
+}
+
+/// Turns a column or file name into a safe directory name component
+///
+/// Letters and digits are lowercased and kept; every other run of characters
+/// becomes one underscore.
+///
+/// # Arguments
+/// * `raw_name` - The column or file name
+///
+/// # Returns
+/// * `String` - The name component (`unnamed` if nothing is left)
+pub fn sanitize_directory_name_component(raw_name: &str) -> String {
+    let mut sanitized_name = String::new();
+    for character in raw_name.chars() {
+        if character.is_alphanumeric() {
+            sanitized_name.extend(character.to_lowercase());
+        } else if !sanitized_name.is_empty() && !sanitized_name.ends_with('_') {
+            sanitized_name.push('_');
+        }
+    }
+
+    let sanitized_name = sanitized_name.trim_end_matches('_');
+    if sanitized_name.is_empty() {
+        "unnamed".to_string()
+    } else {
+        sanitized_name.to_string()
+    }
+}
+
+/// Creates a directory and any missing parents
+///
+/// # Arguments
+/// * `directory_path` - The directory to create
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn create_directory(directory_path: &Path) -> RowsAndColumnsResult<()> {
+    fs::create_dir_all(directory_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to create directory: {}", directory_path.display()), io_error)
+    })
+}
+
+/// Writes `import_progress.toml`, replacing the previous record in one step
+///
+/// The record goes to a temporary file first and is then renamed over the old
+/// one, so an interruption never leaves a half-written record behind.
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `import_progress_record` - The record to write
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn write_import_progress_file(
+    dataset_directory: &Path,
+    import_progress_record: &ImportProgressRecord,
+) -> RowsAndColumnsResult<()> {
+    let progress_file_path = dataset_directory.join(IMPORT_PROGRESS_FILE_NAME);
+    let temporary_progress_file_path = dataset_directory.join(format!("{}.tmp", IMPORT_PROGRESS_FILE_NAME));
+
+    fs::write(&temporary_progress_file_path, import_progress_record.to_toml_content()).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to write import progress file: {}", temporary_progress_file_path.display()),
+            io_error
+        )
+    })?;
+    fs::rename(&temporary_progress_file_path, &progress_file_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to replace import progress file: {}", progress_file_path.display()),
+            io_error
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_names() {
+        assert_eq!(sanitize_directory_name_component("Customer ID"), "customer_id");
+        assert_eq!(sanitize_directory_name_component("  total ($) "), "total");
+        assert_eq!(sanitize_directory_name_component("Straße/Nr."), "straße_nr");
+        assert_eq!(sanitize_directory_name_component("???"), "unnamed");
+    }
+
+    #[test]
+    fn test_next_dataset_number() {
+        assert_eq!(next_dataset_number(&[]), 1);
+
+        let existing_directory_names = vec![
+            "dataset_001_customers".to_string(),
+            "dataset_007_sales".to_string(),
+            "notes.txt".to_string(),
+        ];
+        assert_eq!(next_dataset_number(&existing_directory_names), 8);
+    }
+
+    #[test]
+    fn test_progress_record_round_trip() {
+        let import_progress_record = ImportProgressRecord {
+            source_csv_file: "/data/sales \"2024\".csv".to_string(),
+            source_file_size_bytes: 52_311,
+            source_modified_seconds: 1_760_000_000,
+            column_directory_names: vec!["column_001_id".to_string(), "column_003_amount".to_string()],
+            row_number_digits: 4,
+            storage_settings: DatasetStorageSettings {
+                storage_backend: DatasetStorageBackend::ColumnSegments,
+                segment_row_count: 50_000,
+            },
+            imported_row_count: 3000,
+            import_complete: false,
+        };
+
+        let progress_document = parse_metadata_toml_content(&import_progress_record.to_toml_content(), "test")
+            .expect("progress record should parse");
+        let read_back_record = ImportProgressRecord::from_toml_document(&progress_document)
+            .expect("progress record should have every key");
+
+        assert_eq!(read_back_record, import_progress_record);
+        assert!(read_back_record.describes_same_source(&import_progress_record));
+    }
+}
@@ -0,0 +1,425 @@
+// src/csv_header_detection_module.rs
+
+/// Header row detection for rows_and_columns
+///
+/// Decides whether the first record of a CSV file is a header by comparing each
+/// of its cells against the profile of the same column in the next sampled rows.
+/// Every column casts votes; the first record is a header if the total is positive.
+///
+/// # Column Votes
+/// - Type mismatch (+2): most sampled values in the column are numbers, booleans,
+///   or dates, but the first cell is not
+/// - Type match (-1): the first cell has the column's dominant type
+/// - Repeated value (-2): the first cell also appears further down the column,
+///   which a column name almost never does
+/// - Shape mismatch (+1): in a text column, the sampled values agree on a shape
+///   (capitalized, containing spaces, digits or underscores, or one fixed length)
+///   and the first cell does not
+///
+/// Files with only one or two data rows are too short for a shape profile; their
+/// columns vote on type alone, and only when every sampled value has the same type.
+///
+/// Sampled values that are blank or a configured null sentinel (e.g. `NA`) are
+/// left out of the column profiles, and only the configured true and false
+/// tokens count as booleans, as in type inference. A `has_header = true/false`
+/// key in the metadata file overrides the result.
+///
+/// # Design Philosophy
+/// - Whole-sample evidence instead of comparing two lines
+/// - Conservative: with no evidence either way, the first record is data
+use std::collections::HashSet;
+
+// Import the null sentinels left out of the column profiles
+use super::null_value_module::NullValueSettings;
+
+// Import the true and false tokens that make a value boolean
+use super::boolean_value_module::BooleanVocabulary;
+
+/// Share of sampled values that must agree for a column profile to count
+const HEADER_PROFILE_AGREEMENT_RATIO: f64 = 0.8;
+
+/// Minimum number of non-empty sampled values before a column's text shape is trusted
+const HEADER_PROFILE_MINIMUM_VALUES: usize = 3;
+
+/// Coarse value class used to build column type profiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SampleValueClass {
+    /// Empty or whitespace-only cell
+    Empty,
+
+    /// Integer or floating point number
+    Numeric,
+
+    /// A configured true or false token (any case)
+    Boolean,
+
+    /// Three numeric parts separated by `-`, `/` or `.`, like 2024-03-01 or 01/03/2024
+    DateLike,
+
+    /// Anything else
+    Text,
+}
+
+/// Result of header detection for one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvHeaderDetectionReport {
+    /// Whether the first record is treated as a header (after any override)
+    pub has_header_row: bool,
+
+    /// What the heuristic alone decided
+    pub heuristic_detected_header: bool,
+
+    /// Sum of all column votes (positive means header)
+    pub heuristic_score: i64,
+
+    /// Number of rows after the first record that were profiled
+    pub sampled_row_count: usize,
+
+    /// Whether `has_header` in the metadata file decided the result
+    pub overridden_by_metadata: bool,
+}
+
+/// Scores how likely the first record is a header
+///
+/// # Arguments
+/// * `first_record_fields` - Fields of the first record
+/// * `sampled_records` - Records following the first one
+/// * `null_value_settings` - Sentinels that mark missing sampled values
+/// * `boolean_vocabulary` - Tokens classified as booleans
+///
+/// # Returns
+/// * `CsvHeaderDetectionReport` - The heuristic decision (not yet overridden)
+pub fn detect_header_from_sample(
+    first_record_fields: &[String],
+    sampled_records: &[Vec<String>],
+    null_value_settings: &NullValueSettings,
+    boolean_vocabulary: &BooleanVocabulary,
+) -> CsvHeaderDetectionReport {
+    let mut heuristic_score: i64 = 0;
+
+    for (column_index, first_cell) in first_record_fields.iter().enumerate() {
+        let null_value_sentinels = null_value_settings.sentinels_for_column(column_index);
+        let column_sample_values: Vec<&str> = sampled_records
+            .iter()
+            .filter_map(|sampled_record| sampled_record.get(column_index))
+            .map(|sampled_value| sampled_value.trim())
+            .filter(|sampled_value| !null_value_sentinels.is_missing_value(sampled_value))
+            .collect();
+
+        heuristic_score += score_header_cell(first_cell.trim(), &column_sample_values, boolean_vocabulary);
+    }
+
+    // Column names are normally distinct; repeated names point to a data row
+    let non_empty_first_cells: Vec<&str> = first_record_fields
+        .iter()
+        .map(|first_cell| first_cell.trim())
+        .filter(|first_cell| !first_cell.is_empty())
+        .collect();
+    let distinct_first_cells: HashSet<&str> = non_empty_first_cells.iter().copied().collect();
+    if distinct_first_cells.len() < non_empty_first_cells.len() {
+        heuristic_score -= 1;
+    }
+
+    // A single record gives nothing to compare against: treat it as data
+    let heuristic_detected_header = !sampled_records.is_empty() && heuristic_score > 0;
+
+    CsvHeaderDetectionReport {
+        has_header_row: heuristic_detected_header,
+        heuristic_detected_header,
+        heuristic_score,
+        sampled_row_count: sampled_records.len(),
+        overridden_by_metadata: false,
+    }
+}
+
+/// Computes the votes one first-record cell casts for or against a header
+///
+/// # Arguments
+/// * `first_cell` - The trimmed first-record cell
+/// * `column_sample_values` - Trimmed, non-missing sampled values from the same column
+/// * `boolean_vocabulary` - Tokens classified as booleans
+///
+/// # Returns
+/// * `i64` - Positive for header evidence, negative for data evidence
+fn score_header_cell(first_cell: &str, column_sample_values: &[&str], boolean_vocabulary: &BooleanVocabulary) -> i64 {
+    if first_cell.is_empty() || column_sample_values.is_empty() {
+        return 0;
+    }
+
+    // Repeated value: a column name does not normally reappear as data
+    if column_sample_values.contains(&first_cell) {
+        return -2;
+    }
+
+    // With one or two values the agreement ratio demands that they all share a type
+    let first_cell_class = classify_sample_value(first_cell, boolean_vocabulary);
+    match dominant_value_class(column_sample_values, boolean_vocabulary) {
+        Some(dominant_class) if dominant_class != SampleValueClass::Text => {
+            if first_cell_class == dominant_class {
+                -1
+            } else {
+                2
+            }
+        }
+        _ => {
+            // Too few values to tell a shared shape from chance
+            if column_sample_values.len() >= HEADER_PROFILE_MINIMUM_VALUES
+                && first_cell_breaks_text_shape(first_cell, column_sample_values)
+            {
+                1
+            } else {
+                0
+            }
+        }
+    }
+}
+
+/// Classifies a trimmed value into a coarse value class
+///
+/// # Arguments
+/// * `trimmed_value` - The value to classify
+/// * `boolean_vocabulary` - Tokens classified as booleans
+///
+/// # Returns
+/// * `SampleValueClass` - The class of the value
+fn classify_sample_value(trimmed_value: &str, boolean_vocabulary: &BooleanVocabulary) -> SampleValueClass {
+    if trimmed_value.is_empty() {
+        return SampleValueClass::Empty;
+    }
+
+    if boolean_vocabulary.parse_boolean_value(trimmed_value).is_some() {
+        SampleValueClass::Boolean
+    } else if trimmed_value.parse::<f64>().is_ok() {
+        SampleValueClass::Numeric
+    } else if looks_like_date_value(trimmed_value) {
+        SampleValueClass::DateLike
+    } else {
+        SampleValueClass::Text
+    }
+}
+
+/// Checks whether a value has the shape of a calendar date
+///
+/// Accepts three all-digit parts separated by one repeated `-`, `/` or `.`,
+/// with a four-digit year first or last, optionally followed by a time after
+/// `T` or a space.
+///
+/// # Arguments
+/// * `trimmed_value` - The value to check
+///
+/// # Returns
+/// * `bool` - True if the value looks like a date
+fn looks_like_date_value(trimmed_value: &str) -> bool {
+    let date_portion = trimmed_value
+        .split(['T', ' '])
+        .next()
+        .unwrap_or(trimmed_value);
+
+    for date_separator in ['-', '/', '.'] {
+        let date_parts: Vec<&str> = date_portion.split(date_separator).collect();
+        if date_parts.len() != 3 {
+            continue;
+        }
+        let all_parts_are_digits = date_parts
+            .iter()
+            .all(|date_part| !date_part.is_empty() && date_part.chars().all(|character| character.is_ascii_digit()));
+        if !all_parts_are_digits {
+            continue;
+        }
+
+        let part_lengths = (date_parts[0].len(), date_parts[1].len(), date_parts[2].len());
+        return matches!(part_lengths, (4, 1..=2, 1..=2) | (1..=2, 1..=2, 4));
+    }
+
+    false
+}
+
+/// Finds the value class shared by most sampled values
+///
+/// # Arguments
+/// * `column_sample_values` - Trimmed, non-empty sampled values
+/// * `boolean_vocabulary` - Tokens classified as booleans
+///
+/// # Returns
+/// * `Option<SampleValueClass>` - The class held by at least the agreement ratio of values
+fn dominant_value_class(column_sample_values: &[&str], boolean_vocabulary: &BooleanVocabulary) -> Option<SampleValueClass> {
+    let mut class_counts: Vec<(SampleValueClass, usize)> = Vec::new();
+    for sample_value in column_sample_values {
+        let value_class = classify_sample_value(sample_value, boolean_vocabulary);
+        match class_counts.iter_mut().find(|(counted_class, _)| *counted_class == value_class) {
+            Some((_, class_count)) => *class_count += 1,
+            None => class_counts.push((value_class, 1)),
+        }
+    }
+
+    class_counts
+        .into_iter()
+        .find(|(_, class_count)| {
+            *class_count as f64 >= column_sample_values.len() as f64 * HEADER_PROFILE_AGREEMENT_RATIO
+        })
+        .map(|(value_class, _)| value_class)
+}
+
+/// Checks whether a text cell breaks a shape that the sampled text values agree on
+///
+/// # Arguments
+/// * `first_cell` - The trimmed first-record cell
+/// * `column_sample_values` - Trimmed, non-empty sampled values
+///
+/// # Returns
+/// * `bool` - True if the sampled values agree on a shape feature the first cell lacks (or vice versa)
+fn first_cell_breaks_text_shape(first_cell: &str, column_sample_values: &[&str]) -> bool {
+    let shape_features: [fn(&str) -> bool; 4] = [
+        |text_value| text_value.chars().next().is_some_and(|character| character.is_uppercase()),
+        |text_value| text_value.contains(' '),
+        |text_value| text_value.chars().any(|character| character.is_ascii_digit()),
+        |text_value| text_value.contains('_'),
+    ];
+
+    let sample_count = column_sample_values.len() as f64;
+    for shape_feature in shape_features {
+        let matching_share = column_sample_values
+            .iter()
+            .filter(|sample_value| shape_feature(sample_value))
+            .count() as f64
+            / sample_count;
+        let column_has_feature = matching_share >= HEADER_PROFILE_AGREEMENT_RATIO;
+        let column_lacks_feature = matching_share <= 1.0 - HEADER_PROFILE_AGREEMENT_RATIO;
+
+        if (column_has_feature || column_lacks_feature) && shape_feature(first_cell) != column_has_feature {
+            return true;
+        }
+    }
+
+    // Fixed-width codes (e.g. "AB12", "XY34") under a differently sized name
+    let first_sample_length = column_sample_values[0].chars().count();
+    let all_samples_share_length = column_sample_values
+        .iter()
+        .all(|sample_value| sample_value.chars().count() == first_sample_length);
+
+    all_samples_share_length && first_cell.chars().count() != first_sample_length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper to split comma-separated test lines into records
+    fn test_records(csv_lines: &[&str]) -> Vec<Vec<String>> {
+        csv_lines
+            .iter()
+            .map(|csv_line| csv_line.split(',').map(|field_value| field_value.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_header_over_numeric_and_boolean_columns() {
+        let records = test_records(&[
+            "id,name,age,is_indoor",
+            "1,Whiskers,3,TRUE",
+            "2,Mittens,5,FALSE",
+            "3,Shadow,2,TRUE",
+        ]);
+        let detection_report = detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default());
+
+        assert!(detection_report.has_header_row);
+        assert_eq!(detection_report.sampled_row_count, 3);
+    }
+
+    #[test]
+    fn test_header_uses_the_configured_boolean_vocabulary() {
+        let records = test_records(&["Actif,Note", "oui,a", "non,b", "oui,c"]);
+        let french_vocabulary = BooleanVocabulary {
+            true_tokens: vec!["oui".to_string()],
+            false_tokens: vec!["non".to_string()],
+        };
+
+        // Only the configured tokens make the column boolean (+2 against "Actif")
+        let french_report = detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &french_vocabulary);
+        assert!(french_report.has_header_row);
+        assert_eq!(french_report.heuristic_score, 3);
+        let default_report =
+            detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default());
+        assert_eq!(default_report.heuristic_score, 2);
+
+        // Single letters are not boolean unless listed
+        assert_eq!(classify_sample_value("y", &BooleanVocabulary::default()), SampleValueClass::Text);
+        assert_eq!(classify_sample_value("NON", &french_vocabulary), SampleValueClass::Boolean);
+    }
+
+    #[test]
+    fn test_header_over_all_text_columns() {
+        let records = test_records(&[
+            "name,breed,color,favorite_food",
+            "Whiskers,Siamese,Cream,Chicken",
+            "Mittens,Tabby,Gray,Tuna",
+            "Shadow,Black Cat,Black,Salmon",
+            "Luna,Persian,White,Dry Food",
+        ]);
+
+        assert!(detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default()).has_header_row);
+    }
+
+    #[test]
+    fn test_data_first_row_is_not_a_header() {
+        let records = test_records(&[
+            "1,Whiskers,3,2024-01-05",
+            "2,Mittens,5,2024-02-11",
+            "3,Shadow,2,2024-03-15",
+            "4,Whiskers,4,2024-04-20",
+        ]);
+
+        assert!(!detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default()).has_header_row);
+    }
+
+    #[test]
+    fn test_null_sentinels_do_not_break_numeric_profile() {
+        let records = test_records(&[
+            "id,score",
+            "1,NA",
+            "2,17",
+            "3,N/A",
+            "4,21",
+            "5,-",
+            "6,34",
+        ]);
+
+        let detection_report = detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default());
+        assert!(detection_report.has_header_row);
+        assert_eq!(detection_report.heuristic_score, 4);
+    }
+
+    #[test]
+    fn test_header_over_one_or_two_data_rows() {
+        for csv_lines in [&["id,name", "1,Ann"][..], &["id,name", "1,Ann", "2,Bob"][..]] {
+            let records = test_records(csv_lines);
+            let detection_report = detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default());
+            assert!(detection_report.has_header_row, "{:?}", csv_lines);
+            assert_eq!(detection_report.heuristic_score, 2);
+        }
+
+        // Two data-looking rows: the numbers match, and text alone is no evidence
+        let records = test_records(&["1,Ann", "2,Bob"]);
+        assert!(!detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default()).has_header_row);
+
+        // Mixed types in two values give no profile to compare against
+        let records = test_records(&["code,name", "7,Ann", "x,Bob"]);
+        assert_eq!(detect_header_from_sample(&records[0], &records[1..], &NullValueSettings::default(), &BooleanVocabulary::default()).heuristic_score, 0);
+    }
+
+    #[test]
+    fn test_single_record_is_data() {
+        let records = test_records(&["name,breed"]);
+
+        assert!(!detect_header_from_sample(&records[0], &[], &NullValueSettings::default(), &BooleanVocabulary::default()).has_header_row);
+    }
+
+    #[test]
+    fn test_date_shapes() {
+        assert!(looks_like_date_value("2024-03-01"));
+        assert!(looks_like_date_value("01/03/2024"));
+        assert!(looks_like_date_value("2024-03-01T10:15:00"));
+        assert!(!looks_like_date_value("3.14"));
+        assert!(!looks_like_date_value("1-2-3"));
+    }
+}
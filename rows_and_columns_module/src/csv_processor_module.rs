@@ -9,10 +9,19 @@
 /// 
 /// # Core Responsibilities
 /// - Parse CSV headers and detect column structure
+/// - Read the CSV dialect (delimiter, quoting, comments) from the metadata file,
+///   or sniff it from a sample of the file when none is configured
+/// - Detect the text encoding (UTF-8, UTF-16, Windows-1252, Latin-1) and
+///   transcode every pass to UTF-8
+/// - Validate that every record has the header's field count, and apply the
+///   ragged row policy (reject, pad, truncate, quarantine) in every pass
 /// - Analyze column data types (bool, int, float, string)
-/// - Create and manage CSV metadata TOML files
+/// - Create and manage CSV metadata TOML files, keeping hand-set column names
+///   and types and rejecting values that contradict a declared type
 /// - Validate CSV format and accessibility
 /// - Generate column analysis reports
+/// - Flag outliers in numerical columns (Tukey fences, z-score, modified
+///   z-score) and write the flagged rows to a side CSV for review
 /// 
 /// # Design Philosophy
 /// - Sample-based analysis: analyze first N rows for type detection
@@ -21,9 +30,9 @@
 /// - Fallback handling: graceful handling of missing headers or mixed types
 
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Write};
+use std::io::{self, BufWriter, Write};
 use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // Import our error handling system
 use super::error_types_module::{
@@ -31,10 +40,130 @@ use super::error_types_module::{
     RowsAndColumnsResult,
     create_file_system_error,
     create_csv_processing_error,
+    create_text_encoding_error,
     create_metadata_error,
+    create_statistical_analysis_error,
     create_configuration_error,
 };
 
+// Import the streaming RFC 4180 record reader and its dialect settings
+use super::csv_record_reader_module::{CsvDialect, CsvRecordReader};
+
+// Import dialect sniffing for files without a configured dialect
+use super::csv_dialect_sniffer_module::{CsvDialectSniffingReport, sniff_csv_dialect_from_file};
+
+// Import ragged record validation and policies
+use super::csv_structure_validator_module::{
+    CsvStructuralValidationReport,
+    QUARANTINE_FILE_SUFFIX,
+    RaggedRecordDiagnostic,
+    RaggedRowPolicy,
+    conform_record_fields_to_policy,
+    display_structural_validation_report,
+    format_rfc4180_csv_line,
+    write_quarantined_record,
+};
+
+// Import the sampled header row heuristic
+use super::csv_header_detection_module::{CsvHeaderDetectionReport, detect_header_from_sample};
+
+// Import null sentinels and the missing value counts kept per column
+use super::null_value_module::{MissingValueCounts, NullValueSentinels, NullValueSettings};
+
+// Import per-column type tallies and the inference mode
+use super::column_type_inference_module::{
+    ColumnTypeTally,
+    TypeInferenceMode,
+    TypeInferenceSettings,
+    TypeInferenceSummary,
+    TypeMismatchExample,
+    value_matches_data_type,
+};
+
+// Import the true/false vocabulary for boolean statistics and header detection
+use super::boolean_value_module::{BooleanValueCounts, BooleanVocabulary};
+
+// Import fixed-point decimal parsing for exact money statistics
+use super::decimal_value_module::{
+    FixedPointDecimal,
+    divide_rounding_half_away_from_zero,
+    parse_decimal_value,
+};
+
+// Import date and time parsing for temporal statistics
+use super::temporal_value_module::{
+    CalendarDate,
+    SECONDS_PER_DAY,
+    TemporalFormatSet,
+    TemporalHistogramGranularity,
+    format_duration_seconds,
+    format_seconds_since_midnight,
+    format_seconds_since_unix_epoch,
+    parse_date_value,
+    parse_datetime_value,
+    parse_time_value,
+};
+
+// Import encoding detection and the transcoding reader
+use super::text_encoding_module::{
+    CsvTextEncoding,
+    TextDecodingReader,
+    UndecodableTextError,
+    detect_text_encoding_of_file,
+};
+
+// Import the one-pass numerical statistics and the statistics mode
+use super::streaming_statistics_module::{
+    HyperLogLogCounter,
+    MODIFIED_Z_SCORE_OUTLIER_THRESHOLD,
+    MedianAbsoluteDeviationAccumulator,
+    NumericalStatisticsAccumulator,
+    OutlierDetectionRules,
+    PercentileInterpolation,
+    SpaceSavingTopValues,
+    StatisticsMode,
+    StatisticsSettings,
+    TukeyFences,
+    TukeyOutlierSeverity,
+    Z_SCORE_OUTLIER_THRESHOLD,
+    parse_numerical_field_value,
+};
+
+// Import the storage backends that imported datasets are read through
+use super::dataset_storage_module::{DatasetStorageReader, DatasetStorageSettings};
+
+// Import metadata TOML reading and the dialect section format
+use super::metadata_manager_module::{
+    DATA_TYPE_KEY_NAME,
+    DECIMAL_SCALE_KEY_NAME,
+    DETECTED_TYPE_KEY_NAME,
+    DIALECT_SECTION_NAME,
+    ENCODING_KEY_NAME,
+    HAS_HEADER_KEY_NAME,
+    DeclaredColumnSchema,
+    IGNORED_KEY_NAME,
+    MetadataTomlDocument,
+    read_metadata_toml_file,
+    read_csv_dialect_from_metadata,
+    read_ragged_row_policy_from_metadata,
+    read_has_header_override_from_metadata,
+    read_type_inference_settings_from_metadata,
+    read_null_value_settings_from_metadata,
+    read_declared_column_schema_from_metadata,
+    read_dataset_storage_settings_from_metadata,
+    read_statistics_settings_from_metadata,
+    format_null_values_toml_line,
+    format_csv_validation_toml_section,
+    format_csv_dialect_toml_section,
+    format_csv_dialect_sniffing_toml_section,
+    format_header_detection_toml_section,
+    format_type_inference_toml_section,
+    format_statistics_toml_section,
+    format_dataset_storage_toml_section,
+    format_column_type_tally_toml_lines,
+    escape_toml_basic_string,
+};
+
 // Import path management for metadata file operations
 use super::manage_absolute_executable_directory_relative_paths::{
     make_input_path_name_abs_executabledirectoryrelative_nocheck,
@@ -44,7 +173,16 @@ use super::manage_absolute_executable_directory_relative_paths::{
 
 /// Configuration constants for CSV processing
 const CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION: usize = 10;
+const CSV_SAMPLE_ROWS_FOR_HEADER_DETECTION: usize = 20;
+const TEMPORAL_HISTOGRAM_DISPLAY_LIMIT: usize = 24;
+const TYPE_CHANGE_PREVIEW_EXAMPLE_LIMIT: usize = 5;
+const TEMPORAL_HISTOGRAM_BAR_WIDTH: usize = 30;
 const METADATA_FILE_EXTENSION: &str = "csv_metadata.toml";
+const OUTLIER_ROWS_FILE_SUFFIX: &str = "outliers.csv";
+const OUTLIER_ROW_NUMBER_DISPLAY_LIMIT: usize = 10;
+
+/// Record reader over a CSV file transcoded to UTF-8
+pub type CsvFileRecordReader = CsvRecordReader<TextDecodingReader<File>>;
 
 /// Represents the detected data type for a CSV column
 /// 
@@ -52,7 +190,7 @@ const METADATA_FILE_EXTENSION: &str = "csv_metadata.toml";
 /// and handle in the directory-based storage system.
 #[derive(Debug, Clone, PartialEq)]
 pub enum CsvColumnDataType {
-    /// Boolean values (true/false, yes/no, 1/0)
+    /// Boolean values (true/false, yes/no, or a configured vocabulary)
     Boolean,
     
     /// Integer values (whole numbers)
@@ -61,6 +199,18 @@ pub enum CsvColumnDataType {
     /// Floating point values (decimal numbers)
     Float,
     
+    /// Fixed-point decimal values, including currency amounts like `$1,234.50`
+    Decimal,
+    
+    /// Calendar dates (ISO-8601 or a configured date format)
+    Date,
+    
+    /// Times of day without a date
+    Time,
+    
+    /// Dates with a time of day, optionally with a UTC offset
+    DateTime,
+    
     /// String/text values (fallback for anything not clearly typed)
     String,
 }
@@ -75,6 +225,10 @@ impl CsvColumnDataType {
             CsvColumnDataType::Boolean => "boolean",
             CsvColumnDataType::Integer => "integer", 
             CsvColumnDataType::Float => "float",
+            CsvColumnDataType::Decimal => "decimal",
+            CsvColumnDataType::Date => "date",
+            CsvColumnDataType::Time => "time",
+            CsvColumnDataType::DateTime => "datetime",
             CsvColumnDataType::String => "string",
         }
     }
@@ -90,7 +244,11 @@ impl CsvColumnDataType {
         match toml_string.to_lowercase().as_str() {
             "boolean" | "bool" => Some(CsvColumnDataType::Boolean),
             "integer" | "int" => Some(CsvColumnDataType::Integer),
-            "float" | "decimal" | "number" => Some(CsvColumnDataType::Float),
+            "float" | "number" => Some(CsvColumnDataType::Float),
+            "decimal" | "currency" | "money" => Some(CsvColumnDataType::Decimal),
+            "date" => Some(CsvColumnDataType::Date),
+            "time" => Some(CsvColumnDataType::Time),
+            "datetime" | "timestamp" => Some(CsvColumnDataType::DateTime),
             "string" | "text" | "str" => Some(CsvColumnDataType::String),
             _ => None,
         }
@@ -109,17 +267,32 @@ pub struct CsvColumnInformation {
     /// Name of the column (from header or generated)
     pub column_name: String,
     
-    /// Detected data type for this column
+    /// Data type used for this column (declared in the metadata file, or detected)
     pub detected_data_type: CsvColumnDataType,
     
-    /// Number of non-empty values found during analysis
+    /// Data type the inference picked, even when the metadata file declares another
+    pub inferred_data_type: CsvColumnDataType,
+    
+    /// Whether `detected_data_type` was declared by hand in the metadata file
+    pub data_type_declared_in_metadata: bool,
+    
+    /// First value that does not parse as the declared type (always None for detected types)
+    pub declared_type_conflict: Option<TypeMismatchExample>,
+    
+    /// Whether the metadata file marks the column `ignored` (no statistics, not imported)
+    pub column_ignored: bool,
+    
+    /// Number of values found during analysis that are neither blank nor a null sentinel
     pub non_empty_value_count: usize,
     
-    /// Number of empty/null values found during analysis
-    pub empty_value_count: usize,
+    /// Blank, null sentinel and type-invalid values found during analysis
+    pub missing_value_counts: MissingValueCounts,
     
     /// Sample values from this column (for user review)
     pub sample_values: Vec<String>,
+    
+    /// Per-type match counts and first mismatches behind the detected type
+    pub type_tally: ColumnTypeTally,
 }
 
 /// Complete analysis results for a CSV file
@@ -134,15 +307,42 @@ pub struct CsvAnalysisResults {
     /// Whether the CSV file has a header row
     pub has_header_row: bool,
     
+    /// Null sentinels from the metadata file (global list and per-column replacements)
+    pub null_value_settings: NullValueSettings,
+    
+    /// How the header decision was reached (heuristic score or metadata override)
+    pub header_detection_report: CsvHeaderDetectionReport,
+    
     /// Total number of columns detected
     pub total_column_count: usize,
     
-    /// Total number of data rows (excluding header)
+    /// Dialect used to read the file (from the metadata file or sniffed)
+    pub csv_dialect: CsvDialect,
+    
+    /// Text encoding of the file (from the metadata file or detected)
+    pub text_encoding: CsvTextEncoding,
+    
+    /// Dialect sniffing result, including whether it was applied
+    pub dialect_sniffing_report: CsvDialectSniffingReport,
+    
+    /// Total number of data rows (excluding header and quarantined records)
     pub total_data_row_count: usize,
     
+    /// Result of the ragged record validation pass, including the policy applied
+    pub structural_validation_report: CsvStructuralValidationReport,
+    
     /// Information about each column
     pub column_information_list: Vec<CsvColumnInformation>,
     
+    /// Whether column types came from a sample or from every row, and how many rows
+    pub type_inference_summary: TypeInferenceSummary,
+    
+    /// How the data import stores values (from the `[storage]` metadata section)
+    pub dataset_storage_settings: DatasetStorageSettings,
+    
+    /// Streaming or exact numerical statistics (from the `[statistics]` metadata section)
+    pub statistics_settings: StatisticsSettings,
+    
     /// Path to the metadata TOML file (existing or to-be-created)
     pub metadata_file_path: PathBuf,
     
@@ -169,250 +369,723 @@ pub struct CsvAnalysisResults {
 pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndColumnsResult<CsvAnalysisResults> {
     println!("🔍 Analyzing CSV file structure...");
     
+    // Step 0: Determine metadata file path and load the configured encoding and dialect
+    let metadata_file_path = determine_metadata_file_path(csv_file_path)?;
+    let metadata_file_already_existed = metadata_file_path.exists();
+    let existing_metadata_document = read_metadata_toml_file(&metadata_file_path)?;
+    
+    let text_encoding = load_csv_text_encoding_for_file(
+        csv_file_path,
+        existing_metadata_document.as_ref(),
+        &metadata_file_path,
+    )?;
+    
+    let (csv_dialect, dialect_sniffing_report) = load_csv_dialect_for_file(
+        csv_file_path,
+        text_encoding,
+        existing_metadata_document.as_ref(),
+        &metadata_file_path,
+    )?;
+    
+    println!("  ✓ Dialect: {}", csv_dialect.describe_for_display());
+    display_dialect_sniffing_summary(&dialect_sniffing_report);
+    
     // Step 1: Read and analyze the CSV file structure
-    let (has_header_row, column_count, data_row_count) = analyze_csv_basic_structure(csv_file_path)?;
+    let has_header_override = read_has_header_override_from_metadata(
+        existing_metadata_document.as_ref(),
+        &metadata_file_path.display().to_string(),
+    )?;
+    let null_value_settings = read_null_value_settings_from_metadata(
+        existing_metadata_document.as_ref(),
+        &metadata_file_path.display().to_string(),
+    )?;
+    let type_inference_settings = read_type_inference_settings_from_metadata(
+        existing_metadata_document.as_ref(),
+        &metadata_file_path.display().to_string(),
+    )?;
+    let (header_detection_report, column_count, all_data_row_count) = analyze_csv_basic_structure(
+        open_csv_record_reader(csv_file_path, text_encoding, &csv_dialect, "analysis")?,
+        has_header_override,
+        &null_value_settings,
+        &type_inference_settings.boolean_vocabulary,
+    )?;
+    let has_header_row = header_detection_report.has_header_row;
+    
+    // Step 1b: Check every record against the header width and apply the ragged row policy
+    let ragged_row_policy = read_ragged_row_policy_from_metadata(
+        existing_metadata_document.as_ref(),
+        &metadata_file_path.display().to_string(),
+    )?;
+    let structural_validation_report = validate_csv_record_structure(
+        csv_file_path,
+        text_encoding,
+        &csv_dialect,
+        column_count,
+        ragged_row_policy,
+    )?;
+    display_structural_validation_report(&structural_validation_report);
+    reject_unacceptable_ragged_records(&structural_validation_report, &metadata_file_path)?;
+    
+    let data_row_count = all_data_row_count - structural_validation_report.excluded_record_count();
     
     println!("  ✓ Basic structure detected:");
     println!("    Columns: {}", column_count);
     println!("    Data rows: {}", data_row_count);
-    println!("    Has header: {}", has_header_row);
+    display_header_detection_summary(&header_detection_report);
     
-    // Step 2: Analyze column data types and content
-    let column_information_list = analyze_csv_column_types_and_content(
-        csv_file_path, 
+    // Step 2: Analyze column data types and content (hand-set names and types in the metadata file win)
+    let declared_column_schema = read_declared_column_schema_from_metadata(
+        existing_metadata_document.as_ref(),
+        column_count,
+        &metadata_file_path.display().to_string(),
+    )?;
+    let (column_information_list, scanned_row_count) = analyze_csv_column_types_and_content(
+        open_csv_record_reader(csv_file_path, text_encoding, &csv_dialect, "type analysis")?,
+        ragged_row_policy,
+        &type_inference_settings,
+        &null_value_settings,
+        &declared_column_schema,
         has_header_row, 
         column_count
     )?;
+    reject_declared_type_conflicts(&column_information_list, &metadata_file_path)?;
     
-    println!("  ✓ Column types analyzed");
+    println!(
+        "  ✓ Column types analyzed ({} mode, {} rows scanned)",
+        type_inference_settings.inference_mode.to_toml_string(),
+        scanned_row_count
+    );
+    let declared_type_count = column_information_list
+        .iter()
+        .filter(|column_info| column_info.data_type_declared_in_metadata)
+        .count();
+    if declared_type_count > 0 {
+        println!("  ✓ {} column type(s) taken from the metadata file", declared_type_count);
+    }
     
-    // Step 3: Determine metadata file path and check if it exists
-    let metadata_file_path = determine_metadata_file_path(csv_file_path)?;
-    let metadata_file_already_existed = metadata_file_path.exists();
+    let type_inference_summary = TypeInferenceSummary {
+        inference_settings: type_inference_settings,
+        scanned_row_count,
+    };
     
+    let dataset_storage_settings = read_dataset_storage_settings_from_metadata(
+        existing_metadata_document.as_ref(),
+        &metadata_file_path.display().to_string(),
+    )?;
+    let statistics_settings = read_statistics_settings_from_metadata(
+        existing_metadata_document.as_ref(),
+        &metadata_file_path.display().to_string(),
+    )?;
+    
+    // Step 3: Report whether the metadata file already existed
     if metadata_file_already_existed {
         println!("  ✓ Found existing metadata file: {}", metadata_file_path.display());
     } else {
         println!("  ✓ Will create metadata file: {}", metadata_file_path.display());
     }
     
-    // Step 4: Create or update metadata file
-    create_or_update_metadata_file(&metadata_file_path, &column_information_list)?;
-    
-    println!("  ✓ Metadata file updated");
-    
-    // Return complete analysis results
-    Ok(CsvAnalysisResults {
+    let analysis_results = CsvAnalysisResults {
         csv_file_path: csv_file_path.clone(),
         has_header_row,
+        null_value_settings,
+        header_detection_report,
         total_column_count: column_count,
+        csv_dialect,
+        text_encoding,
+        dialect_sniffing_report,
         total_data_row_count: data_row_count,
+        structural_validation_report,
         column_information_list,
+        type_inference_summary,
+        dataset_storage_settings,
+        statistics_settings,
         metadata_file_path,
         metadata_file_already_existed,
-    })
+    };
+    
+    // Step 4: Create or update metadata file
+    create_or_update_metadata_file(&analysis_results)?;
+    
+    println!("  ✓ Metadata file updated");
+    
+    // Return complete analysis results
+    Ok(analysis_results)
 }
 
-/// Analyzes basic CSV file structure (row count, column count, header detection)
+/// Loads the text encoding for a file from its metadata file, or detects it
 /// 
-/// This function reads through the CSV file to determine fundamental structure
-/// without performing detailed type analysis.
+/// A top-level `encoding` key in the metadata file is authoritative, so a
+/// user can correct a wrong guess (e.g. a file whose first 64 KiB happen to be
+/// plain ASCII but which is Windows-1252 further down). Otherwise the encoding
+/// is detected from the byte order mark and a sample of the file.
 /// 
 /// # Arguments
-/// * `csv_file_path` - Path to the CSV file to analyze
+/// * `csv_file_path` - Path to the CSV file
+/// * `existing_metadata_document` - The parsed metadata file, if one exists
+/// * `metadata_file_path` - Path to the metadata TOML file (for error messages)
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<(bool, usize, usize)>` - (has_header, column_count, data_rows)
-fn analyze_csv_basic_structure(csv_file_path: &PathBuf) -> RowsAndColumnsResult<(bool, usize, usize)> {
-    let csv_file = File::open(csv_file_path)
-        .map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to open CSV file for analysis: {}", csv_file_path.display()),
-                io_error
-            )
-        })?;
+/// * `RowsAndColumnsResult<CsvTextEncoding>` - The encoding to decode the file with
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV sample cannot be read
+/// * `RowsAndColumnsError::MetadataError` - If the configured encoding is not supported
+fn load_csv_text_encoding_for_file(
+    csv_file_path: &Path,
+    existing_metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path: &Path,
+) -> RowsAndColumnsResult<CsvTextEncoding> {
+    let configured_encoding_name = existing_metadata_document
+        .and_then(|metadata_document| metadata_document.get_string("", ENCODING_KEY_NAME));
+    
+    match configured_encoding_name {
+        Some(encoding_name) => {
+            let configured_encoding = CsvTextEncoding::from_toml_string(encoding_name).ok_or_else(|| {
+                create_metadata_error(
+                    &format!(
+                        "Unsupported encoding \"{}\" (expected utf-8, utf-8-bom, utf-16le, utf-16be, windows-1252 or latin-1)",
+                        encoding_name
+                    ),
+                    &metadata_file_path.display().to_string(),
+                )
+            })?;
+            println!("  ✓ Encoding: {} (from metadata file)", configured_encoding.to_toml_string());
+            Ok(configured_encoding)
+        }
+        None => {
+            let detected_encoding = detect_text_encoding_of_file(csv_file_path)?;
+            println!("  ✓ Encoding: {} (detected)", detected_encoding.to_toml_string());
+            Ok(detected_encoding)
+        }
+    }
+}
+
+/// Loads the CSV dialect for a file from its metadata file, or sniffs it
+/// 
+/// The file is always sniffed so the metadata can report the guess. If the
+/// metadata file has a `[dialect]` section, that section is authoritative
+/// (missing keys fall back to the default for the file extension, where
+/// `.tsv` defaults to tabs) and the sniffed dialect is only reported.
+/// Otherwise the sniffed delimiter and quote character are used.
+/// 
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `text_encoding` - Encoding used to decode the sniffing sample
+/// * `existing_metadata_document` - The parsed metadata file, if one exists
+/// * `metadata_file_path` - Path to the metadata TOML file (for error messages)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<(CsvDialect, CsvDialectSniffingReport)>` - The dialect to read the file with and the sniffing report
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV sample cannot be read
+/// * `RowsAndColumnsError::MetadataError` - If the configured dialect is invalid
+fn load_csv_dialect_for_file(
+    csv_file_path: &Path,
+    text_encoding: CsvTextEncoding,
+    existing_metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path: &Path,
+) -> RowsAndColumnsResult<(CsvDialect, CsvDialectSniffingReport)> {
+    let file_extension = csv_file_path.extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("");
+    let extension_default_dialect = CsvDialect::default_for_file_extension(file_extension);
+    
+    let mut dialect_sniffing_report =
+        sniff_csv_dialect_from_file(csv_file_path, text_encoding, &extension_default_dialect)?;
+    
+    let configured_dialect = match existing_metadata_document {
+        Some(metadata_document) if metadata_document.has_section(DIALECT_SECTION_NAME) => {
+            Some(read_csv_dialect_from_metadata(
+                metadata_document,
+                extension_default_dialect,
+                &metadata_file_path.display().to_string(),
+            )?)
+        }
+        _ => None,
+    };
+    
+    match configured_dialect {
+        Some(csv_dialect) => {
+            dialect_sniffing_report.applied_to_analysis = false;
+            Ok((csv_dialect, dialect_sniffing_report))
+        }
+        None => Ok((dialect_sniffing_report.sniffed_dialect.clone(), dialect_sniffing_report)),
+    }
+}
+
+/// Prints the sniffed dialect, its confidence, and the runner-up
+/// 
+/// # Arguments
+/// * `dialect_sniffing_report` - The report produced while loading the dialect
+fn display_dialect_sniffing_summary(dialect_sniffing_report: &CsvDialectSniffingReport) {
+    let winner_description = match &dialect_sniffing_report.winning_candidate {
+        Some(winning_candidate) => format!("{:?}", winning_candidate.field_delimiter),
+        None => "no delimiter splits the sample".to_string(),
+    };
+    let usage_description = if dialect_sniffing_report.applied_to_analysis {
+        "applied"
+    } else {
+        "not applied, [dialect] in metadata file takes precedence"
+    };
     
-    let csv_reader = BufReader::new(csv_file);
-    let mut csv_lines = csv_reader.lines();
+    println!(
+        "    Sniffed delimiter: {} (confidence {:.0}%, {} records sampled, {})",
+        winner_description,
+        dialect_sniffing_report.confidence * 100.0,
+        dialect_sniffing_report.sampled_record_count,
+        usage_description
+    );
     
-    // Read the first line to determine column count
-    let first_line = csv_lines.next()
+    if let Some(runner_up_candidate) = &dialect_sniffing_report.runner_up_candidate {
+        println!(
+            "    Runner-up delimiter: {:?} ({:.0}% consistent field counts)",
+            runner_up_candidate.field_delimiter,
+            runner_up_candidate.consistency_score * 100.0
+        );
+    }
+}
+
+/// Prints the header decision and where it came from
+/// 
+/// # Arguments
+/// * `header_detection_report` - The report produced while analyzing the structure
+fn display_header_detection_summary(header_detection_report: &CsvHeaderDetectionReport) {
+    if header_detection_report.overridden_by_metadata {
+        println!(
+            "    Has header: {} (set by {} in metadata file; heuristic says {})",
+            header_detection_report.has_header_row,
+            HAS_HEADER_KEY_NAME,
+            header_detection_report.heuristic_detected_header
+        );
+    } else {
+        println!(
+            "    Has header: {} (score {:+} over {} sampled rows)",
+            header_detection_report.has_header_row,
+            header_detection_report.heuristic_score,
+            header_detection_report.sampled_row_count
+        );
+    }
+}
+
+/// Analyzes basic CSV file structure (row count, column count, header detection)
+/// 
+/// This function reads through the CSV file record by record to determine
+/// fundamental structure without performing detailed type analysis.
+/// 
+/// # Arguments
+/// * `csv_record_reader` - Reader positioned at the first record of the file
+/// * `has_header_override` - `has_header` from the metadata file, if set
+/// * `null_value_settings` - Sentinels left out of the header detection profiles
+/// * `boolean_vocabulary` - Tokens the header detection profiles count as booleans
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<(CsvHeaderDetectionReport, usize, usize)>` - (header detection, column_count, data_rows)
+fn analyze_csv_basic_structure(
+    mut csv_record_reader: CsvFileRecordReader,
+    has_header_override: Option<bool>,
+    null_value_settings: &NullValueSettings,
+    boolean_vocabulary: &BooleanVocabulary,
+) -> RowsAndColumnsResult<(CsvHeaderDetectionReport, usize, usize)> {
+    // Read the first record to determine column count
+    let first_record = csv_record_reader.read_next_record()
+        .map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read first record of CSV file", io_error)
+        })?
         .ok_or_else(|| {
             create_csv_processing_error(
                 "CSV file appears to be empty",
                 Some(1),
                 None
             )
-        })?
+        })?;
+    
+    let column_count = first_record.field_values.len();
+    
+    // Check if first record looks like a header by profiling the records after it
+    let (header_detection_report, sampled_record_count) = detect_csv_header_row(
+        &mut csv_record_reader,
+        &first_record.field_values,
+        has_header_override,
+        null_value_settings,
+        boolean_vocabulary,
+    )?;
+    
+    // Count total data rows (excluding header if present)
+    let remaining_records = count_remaining_csv_records(csv_record_reader)?;
+    let total_records = 1 + sampled_record_count + remaining_records;
+    let data_row_count = if header_detection_report.has_header_row { total_records - 1 } else { total_records };
+    
+    Ok((header_detection_report, column_count, data_row_count))
+}
+
+/// Opens a CSV file and wraps it in a transcoding, streaming record reader
+/// 
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file to open
+/// * `text_encoding` - Encoding used to decode the file
+/// * `csv_dialect` - Dialect used to split the file into records and fields
+/// * `purpose_description` - Short description of why the file is opened (for error messages)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<CsvFileRecordReader>` - Reader positioned at the first record
+fn open_csv_record_reader(
+    csv_file_path: &PathBuf,
+    text_encoding: CsvTextEncoding,
+    csv_dialect: &CsvDialect,
+    purpose_description: &str,
+) -> RowsAndColumnsResult<CsvFileRecordReader> {
+    let csv_file = File::open(csv_file_path)
         .map_err(|io_error| {
             create_file_system_error(
-                "Failed to read first line of CSV file",
+                &format!("Failed to open CSV file for {}: {}", purpose_description, csv_file_path.display()),
                 io_error
             )
         })?;
     
-    let column_count = count_csv_columns_in_line(&first_line);
-    
-    // Check if first line looks like a header by analyzing the second line
-    let has_header_row = detect_csv_header_row(&mut csv_lines, column_count, &first_line)?;
-    
-    // Count total data rows (excluding header if present)
-    let total_rows = count_remaining_csv_lines(csv_lines)?;
-    let data_row_count = if has_header_row { total_rows } else { total_rows + 1 };
+    Ok(CsvRecordReader::new_with_dialect(
+        TextDecodingReader::new(csv_file, text_encoding),
+        csv_dialect.clone(),
+    ))
+}
+
+/// Opens an analyzed CSV file positioned at its first data record
+/// 
+/// Uses the encoding and dialect found by the analysis and skips the header
+/// record if the file has one. Callers still conform each record to the
+/// ragged row policy with `conform_record_fields_to_policy`.
+/// 
+/// # Arguments
+/// * `analysis_results` - The completed analysis of the file
+/// * `purpose_description` - Short description of why the file is opened (for error messages)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<CsvFileRecordReader>` - Reader positioned at the first data record
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV file cannot be opened
+/// * `RowsAndColumnsError::CsvProcessingError` - If the header record cannot be parsed
+pub fn open_analyzed_csv_data_record_reader(
+    analysis_results: &CsvAnalysisResults,
+    purpose_description: &str,
+) -> RowsAndColumnsResult<CsvFileRecordReader> {
+    let mut csv_record_reader = open_csv_record_reader(
+        &analysis_results.csv_file_path,
+        analysis_results.text_encoding,
+        &analysis_results.csv_dialect,
+        purpose_description,
+    )?;
+    if analysis_results.has_header_row {
+        csv_record_reader.read_next_record().map_err(|io_error| {
+            convert_csv_record_read_error(
+                &format!("Failed to read header record during {}", purpose_description),
+                io_error,
+            )
+        })?;
+    }
     
-    Ok((has_header_row, column_count, data_row_count))
+    Ok(csv_record_reader)
 }
 
-/// Counts the number of CSV columns in a line by splitting on commas
+/// Converts a record reader error into the matching RowsAndColumnsError
 /// 
-/// This handles basic CSV comma separation. For MVP, we assume simple comma
-/// separation without escaped commas in quoted fields.
+/// Bytes that cannot be decoded in the file's encoding are reported as a text
+/// encoding error with their byte offsets. Other malformed CSV content (e.g.
+/// an unterminated quote) is reported as a CSV processing error; anything else
+/// is a genuine file system failure.
 /// 
 /// # Arguments
-/// * `csv_line` - The CSV line to analyze
+/// * `operation_description` - Description of the read operation that failed
+/// * `io_error` - The error returned by the record reader
 /// 
 /// # Returns
-/// * `usize` - Number of columns detected
-fn count_csv_columns_in_line(csv_line: &str) -> usize {
-    // Simple comma split for MVP - could be enhanced for quoted fields later
-    csv_line.split(',').count()
+/// * `RowsAndColumnsError` - A properly contextualized error
+pub fn convert_csv_record_read_error(operation_description: &str, io_error: io::Error) -> RowsAndColumnsError {
+    let undecodable_text_error = io_error.get_ref()
+        .and_then(|inner_error| inner_error.downcast_ref::<UndecodableTextError>());
+    
+    if let Some(undecodable_text_error) = undecodable_text_error {
+        create_text_encoding_error(
+            &format!(
+                "{} (set `{}` in the metadata file if the encoding was guessed wrong)",
+                operation_description, ENCODING_KEY_NAME
+            ),
+            undecodable_text_error.text_encoding_name,
+            undecodable_text_error.undecodable_byte_offset_start,
+            undecodable_text_error.undecodable_byte_offset_end,
+        )
+    } else if io_error.kind() == io::ErrorKind::InvalidData {
+        create_csv_processing_error(
+            &format!("{}: {}", operation_description, io_error),
+            None,
+            None
+        )
+    } else {
+        create_file_system_error(operation_description, io_error)
+    }
 }
+
 /// Detects whether the CSV file has a header row
 /// 
-/// This function uses heuristics to determine if the first row contains
-/// column headers rather than data.
+/// Reads up to `CSV_SAMPLE_ROWS_FOR_HEADER_DETECTION` records after the first
+/// one and compares the first record against their per-column type profile.
+/// A `has_header` override from the metadata file replaces the heuristic's verdict.
 /// 
 /// # Arguments
-/// * `remaining_lines` - Iterator of remaining lines after the first
-/// * `expected_column_count` - Expected number of columns
-/// * `first_line` - The first line content for analysis
+/// * `csv_record_reader` - Reader positioned just after the first record
+/// * `first_record_fields` - The fields of the first record for analysis
+/// * `has_header_override` - `has_header` from the metadata file, if set
+/// * `null_value_settings` - Sentinels left out of the column profiles
+/// * `boolean_vocabulary` - Tokens the column profiles count as booleans
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<bool>` - True if header row detected
+/// * `RowsAndColumnsResult<(CsvHeaderDetectionReport, usize)>` - (detection report, number of records consumed)
 fn detect_csv_header_row(
-    remaining_lines: &mut std::io::Lines<BufReader<File>>,
-    expected_column_count: usize,
-    first_line: &str,
-) -> RowsAndColumnsResult<bool> {
-    // Read the second line for comparison
-    let second_line = match remaining_lines.next() {
-        Some(line_result) => {
-            line_result.map_err(|io_error| {
-                create_file_system_error("Failed to read second line of CSV", io_error)
-            })?
-        }
-        None => {
-            // Only one line in file - assume it's data, not header
-            return Ok(false);
+    csv_record_reader: &mut CsvFileRecordReader,
+    first_record_fields: &[String],
+    has_header_override: Option<bool>,
+    null_value_settings: &NullValueSettings,
+    boolean_vocabulary: &BooleanVocabulary,
+) -> RowsAndColumnsResult<(CsvHeaderDetectionReport, usize)> {
+    let mut sampled_records = Vec::with_capacity(CSV_SAMPLE_ROWS_FOR_HEADER_DETECTION);
+    
+    while sampled_records.len() < CSV_SAMPLE_ROWS_FOR_HEADER_DETECTION {
+        match csv_record_reader.read_next_record() {
+            Ok(Some(csv_record)) => sampled_records.push(csv_record.field_values),
+            Ok(None) => break,
+            Err(io_error) => {
+                return Err(convert_csv_record_read_error("Failed to read CSV record for header detection", io_error));
+            }
         }
-    };
-    
-    // Split both lines into fields
-    let first_fields: Vec<&str> = first_line.split(',').collect();
-    let second_fields: Vec<&str> = second_line.split(',').collect();
+    }
     
-    // Check if field count matches expected column count
-    if first_fields.len() != expected_column_count || 
-       second_fields.len() != expected_column_count {
-        // Inconsistent column counts - this is suspicious but proceed
-        println!("  Warning: Inconsistent column counts detected");
+    let mut header_detection_report = detect_header_from_sample(
+        first_record_fields,
+        &sampled_records,
+        null_value_settings,
+        boolean_vocabulary,
+    );
+    if let Some(has_header_row) = has_header_override {
+        header_detection_report.has_header_row = has_header_row;
+        header_detection_report.overridden_by_metadata = true;
     }
     
-    // Heuristic: if first line contains non-numeric values and second line
-    // contains more numeric values, first line is likely a header
-    let first_line_numeric_fields = count_numeric_fields(&first_fields);
-    let second_line_numeric_fields = count_numeric_fields(&second_fields);
+    Ok((header_detection_report, sampled_records.len()))
+}
+
+/// Checks the field count of every record against the expected width
+/// 
+/// This is a full pass over the file. Every record whose field count differs
+/// from `expected_field_count` is listed in the report with its line number.
+/// Under the quarantine policy those records are also copied to the
+/// quarantine side file next to the CSV file.
+/// 
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `text_encoding` - Encoding used to decode the file
+/// * `csv_dialect` - Dialect used to split the file into records and fields
+/// * `expected_field_count` - Field count of the header (or first record)
+/// * `ragged_row_policy` - What to do with ragged records
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<CsvStructuralValidationReport>` - Every ragged record found
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If reading the CSV or writing the quarantine file fails
+/// * `RowsAndColumnsError::CsvProcessingError` - If a record cannot be parsed
+fn validate_csv_record_structure(
+    csv_file_path: &PathBuf,
+    text_encoding: CsvTextEncoding,
+    csv_dialect: &CsvDialect,
+    expected_field_count: usize,
+    ragged_row_policy: RaggedRowPolicy,
+) -> RowsAndColumnsResult<CsvStructuralValidationReport> {
+    let csv_record_reader = open_csv_record_reader(csv_file_path, text_encoding, csv_dialect, "structure validation")?;
+    let quarantine_file_path = determine_side_file_path(csv_file_path, QUARANTINE_FILE_SUFFIX)?;
+    
+    let mut checked_record_count = 0;
+    let mut ragged_record_diagnostics = Vec::new();
+    let mut quarantine_writer: Option<BufWriter<File>> = None;
+    
+    for record_result in csv_record_reader {
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during structure validation", io_error)
+        })?;
+        checked_record_count += 1;
+        
+        if csv_record.field_values.len() == expected_field_count {
+            continue;
+        }
+        
+        ragged_record_diagnostics.push(RaggedRecordDiagnostic {
+            starting_line_number: csv_record.starting_line_number,
+            actual_field_count: csv_record.field_values.len(),
+            expected_field_count,
+        });
+        
+        if ragged_row_policy == RaggedRowPolicy::Quarantine {
+            // Create the quarantine file only once there is something to put in it
+            if quarantine_writer.is_none() {
+                let quarantine_file = File::create(&quarantine_file_path).map_err(|io_error| {
+                    create_file_system_error(
+                        &format!("Failed to create quarantine file: {}", quarantine_file_path.display()),
+                        io_error
+                    )
+                })?;
+                quarantine_writer = Some(BufWriter::new(quarantine_file));
+            }
+            
+            if let Some(active_quarantine_writer) = quarantine_writer.as_mut() {
+                write_quarantined_record(
+                    active_quarantine_writer,
+                    csv_record.starting_line_number,
+                    &csv_record.field_values,
+                ).map_err(|io_error| {
+                    create_file_system_error(
+                        &format!("Failed to write quarantine file: {}", quarantine_file_path.display()),
+                        io_error
+                    )
+                })?;
+            }
+        }
+    }
     
-    // If first line has fewer numeric fields than second line, it's likely a header
-    let likely_header = first_line_numeric_fields < second_line_numeric_fields;
+    let quarantine_file_was_written = quarantine_writer.is_some();
+    if let Some(mut finished_quarantine_writer) = quarantine_writer {
+        finished_quarantine_writer.flush().map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to write quarantine file: {}", quarantine_file_path.display()),
+                io_error
+            )
+        })?;
+    }
     
-    Ok(likely_header)
+    Ok(CsvStructuralValidationReport {
+        ragged_row_policy,
+        expected_field_count,
+        checked_record_count,
+        ragged_record_diagnostics,
+        quarantine_file_path: if quarantine_file_was_written { Some(quarantine_file_path) } else { None },
+    })
 }
 
-/// Counts how many fields in a list appear to be numeric (int or float)
+/// Fails the analysis if the ragged row policy cannot handle every ragged record
 /// 
 /// # Arguments
-/// * `fields` - List of field values to analyze
+/// * `validation_report` - The report from the validation pass
+/// * `metadata_file_path` - Path to the metadata file (named in the error so the policy can be changed)
 /// 
 /// # Returns
-/// * `usize` - Number of fields that appear numeric
-fn count_numeric_fields(fields: &[&str]) -> usize {
-    fields.iter()
-        .filter(|field| {
-            let trimmed_field = field.trim();
-            // Try to parse as integer or float
-            trimmed_field.parse::<i64>().is_ok() || 
-            trimmed_field.parse::<f64>().is_ok()
-        })
-        .count()
+/// * `RowsAndColumnsResult<()>` - Success if every record is acceptable
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::CsvProcessingError` - Naming the first unacceptable record
+fn reject_unacceptable_ragged_records(
+    validation_report: &CsvStructuralValidationReport,
+    metadata_file_path: &Path,
+) -> RowsAndColumnsResult<()> {
+    let ragged_row_policy = validation_report.ragged_row_policy;
+    let first_unacceptable_record = validation_report.ragged_record_diagnostics
+        .iter()
+        .find(|diagnostic| {
+            !ragged_row_policy.accepts_field_count(diagnostic.actual_field_count, diagnostic.expected_field_count)
+        });
+    
+    match first_unacceptable_record {
+        None => Ok(()),
+        Some(first_unacceptable_record) => Err(create_csv_processing_error(
+            &format!(
+                "{} records do not have {} fields and ragged_row_policy is \"{}\" (first has {} fields); \
+                 set ragged_row_policy in the [validation] section of {} to pad, truncate or quarantine",
+                validation_report.unacceptable_record_count(),
+                first_unacceptable_record.expected_field_count,
+                ragged_row_policy.to_toml_string(),
+                first_unacceptable_record.actual_field_count,
+                metadata_file_path.display()
+            ),
+            Some(first_unacceptable_record.starting_line_number),
+            None
+        )),
+    }
 }
 
-/// Counts remaining lines in the CSV file
+/// Counts remaining records in the CSV file
+/// 
+/// A record may span several physical lines when a quoted field contains
+/// line breaks, so this counts logical records rather than lines.
 /// 
 /// # Arguments
-/// * `lines_iterator` - Iterator over remaining lines
+/// * `csv_record_reader` - Reader positioned at the first record to count
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<usize>` - Number of remaining lines or error
-fn count_remaining_csv_lines(
-    lines_iterator: std::io::Lines<BufReader<File>>
+/// * `RowsAndColumnsResult<usize>` - Number of remaining records or error
+fn count_remaining_csv_records(
+    csv_record_reader: CsvFileRecordReader
 ) -> RowsAndColumnsResult<usize> {
-    let mut line_count = 0;
+    let mut record_count = 0;
     
-    for line_result in lines_iterator {
-        line_result.map_err(|io_error| {
-            create_file_system_error("Failed to read CSV line during counting", io_error)
+    for record_result in csv_record_reader {
+        record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during counting", io_error)
         })?;
-        line_count += 1;
+        record_count += 1;
     }
     
-    Ok(line_count)
+    Ok(record_count)
 }
 
-/// Analyzes column data types and content by sampling CSV data
+/// Analyzes column data types and content by tallying CSV values
 /// 
-/// This function reads sample rows from the CSV to determine the most likely
-/// data type for each column based on the values found.
+/// This function reads data rows from the CSV (the first
+/// `CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION` in sample mode, all of them in full
+/// mode) and tallies, per column, how many values match each candidate type.
 /// 
 /// # Arguments
-/// * `csv_file_path` - Path to the CSV file
+/// * `csv_record_reader` - Reader positioned at the first record of the file
+/// * `ragged_row_policy` - How records with the wrong field count are conformed or skipped
+/// * `type_inference_settings` - Whether to stop after the sample rows, and the accepted date/time formats
+/// * `null_value_settings` - Sentinels that are counted as missing instead of tallied
+/// * `declared_column_schema` - Column names, hand-set types and decimal scales from the metadata
+///   file; declared types are checked against every row, even in sample mode
 /// * `has_header_row` - Whether the file has a header row to skip
 /// * `column_count` - Expected number of columns
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<Vec<CsvColumnInformation>>` - Column information list
+/// * `RowsAndColumnsResult<(Vec<CsvColumnInformation>, usize)>` - (column information list, data rows scanned)
 fn analyze_csv_column_types_and_content(
-    csv_file_path: &PathBuf,
+    mut csv_record_reader: CsvFileRecordReader,
+    ragged_row_policy: RaggedRowPolicy,
+    type_inference_settings: &TypeInferenceSettings,
+    null_value_settings: &NullValueSettings,
+    declared_column_schema: &BTreeMap<usize, DeclaredColumnSchema>,
     has_header_row: bool,
     column_count: usize,
-) -> RowsAndColumnsResult<Vec<CsvColumnInformation>> {
-    let csv_file = File::open(csv_file_path)
-        .map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to open CSV file for type analysis: {}", csv_file_path.display()),
-                io_error
-            )
-        })?;
-    
-    let csv_reader = BufReader::new(csv_file);
-    let mut csv_lines = csv_reader.lines();
-    
+) -> RowsAndColumnsResult<(Vec<CsvColumnInformation>, usize)> {
     // Initialize column information structures
     let mut column_info_list = Vec::new();
     let mut column_sample_values: Vec<Vec<String>> = vec![Vec::new(); column_count];
     let mut column_non_empty_counts = vec![0usize; column_count];
-    let mut column_empty_counts = vec![0usize; column_count];
+    let mut column_missing_value_counts = vec![MissingValueCounts::default(); column_count];
+    let mut column_type_tallies = vec![ColumnTypeTally::default(); column_count];
+    let mut declared_type_conflicts: Vec<Option<TypeMismatchExample>> = vec![None; column_count];
+    let declared_data_types: Vec<Option<&CsvColumnDataType>> = (0..column_count)
+        .map(|column_index| {
+            declared_column_schema
+                .get(&column_index)
+                .filter(|column_schema| !column_schema.column_ignored)
+                .and_then(|column_schema| column_schema.declared_data_type.as_ref())
+        })
+        .collect();
+    let has_declared_data_types = declared_data_types.iter().any(Option::is_some);
     
     // Read header row if it exists to get column names
     let column_names = if has_header_row {
-        match csv_lines.next() {
-            Some(header_line_result) => {
-                let header_line = header_line_result.map_err(|io_error| {
-                    create_file_system_error("Failed to read header line", io_error)
-                })?;
-                parse_csv_line_into_fields(&header_line)
+        match csv_record_reader.read_next_record() {
+            Ok(Some(header_record)) => header_record.field_values,
+            Err(io_error) => {
+                return Err(convert_csv_record_read_error("Failed to read header record", io_error));
             }
-            None => {
+            Ok(None) => {
                 return Err(create_csv_processing_error(
                     "CSV file appears empty when trying to read header",
                     Some(1),
@@ -427,34 +1100,61 @@ fn analyze_csv_column_types_and_content(
             .collect()
     };
     
-    // Sample data rows for type detection
+    // Tally data rows for type detection (sample mode stops early unless declared types need checking)
     let mut rows_processed = 0;
-    for (line_number, line_result) in csv_lines.enumerate() {
-        if rows_processed >= CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION {
+    for record_result in csv_record_reader {
+        let within_inference_rows = type_inference_settings.inference_mode == TypeInferenceMode::FullScan
+            || rows_processed < CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION;
+        if !within_inference_rows && !has_declared_data_types {
             break;
         }
         
-        let csv_line = line_result.map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to read CSV line {}", line_number + if has_header_row { 2 } else { 1 }),
-                io_error
-            )
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during type analysis", io_error)
         })?;
         
-        let field_values = parse_csv_line_into_fields(&csv_line);
+        // Bring ragged records to the header width, or skip quarantined ones
+        let conformed_field_values = match conform_record_fields_to_policy(
+            csv_record.field_values,
+            column_count,
+            ragged_row_policy,
+        ) {
+            Some(conformed_field_values) => conformed_field_values,
+            None => continue,
+        };
         
         // Process each field in this row
-        for (column_index, field_value) in field_values.iter().enumerate() {
-            if column_index >= column_count {
-                // More fields than expected - skip extras
-                continue;
+        for (column_index, field_value) in conformed_field_values.iter().enumerate() {
+            let trimmed_value = field_value.trim();
+            let missing_value_kind = null_value_settings
+                .sentinels_for_column(column_index)
+                .classify_missing_value(trimmed_value);
+            
+            // Remember the first value that contradicts a declared type
+            if let Some(declared_data_type) = declared_data_types[column_index]
+                && missing_value_kind.is_none()
+                && declared_type_conflicts[column_index].is_none()
+                && !value_matches_data_type(trimmed_value, declared_data_type, type_inference_settings)
+            {
+                declared_type_conflicts[column_index] = Some(TypeMismatchExample {
+                    offending_value: trimmed_value.to_string(),
+                    line_number: csv_record.starting_line_number,
+                });
             }
             
-            let trimmed_value = field_value.trim();
+            if !within_inference_rows {
+                continue;
+            }
             
-            if trimmed_value.is_empty() {
-                column_empty_counts[column_index] += 1;
+            // Blanks and null sentinels are counted, never tallied against a type
+            if let Some(missing_value_kind) = missing_value_kind {
+                column_missing_value_counts[column_index].record_missing_value(missing_value_kind);
             } else {
+                column_type_tallies[column_index].record_value(
+                    trimmed_value,
+                    csv_record.starting_line_number,
+                    type_inference_settings,
+                );
                 column_non_empty_counts[column_index] += 1;
                 
                 // Store sample values (limit to prevent memory issues)
@@ -464,110 +1164,165 @@ fn analyze_csv_column_types_and_content(
             }
         }
         
-        rows_processed += 1;
+        if within_inference_rows {
+            rows_processed += 1;
+        }
     }
     
-    // Analyze data types for each column based on samples
-    for column_index in 0..column_count {
-        let column_name = column_names.get(column_index)
-            .cloned()
+    // Decide data types for each column from its tally, unless the metadata file declares one
+    for (column_index, mut column_type_tally) in column_type_tallies.into_iter().enumerate() {
+        let column_schema = declared_column_schema.get(&column_index);
+        let column_name = column_schema
+            .and_then(|column_schema| column_schema.column_name.clone())
+            .or_else(|| column_names.get(column_index).cloned())
             .unwrap_or_else(|| format!("column_{}", column_index + 1));
         
-        let detected_data_type = detect_column_data_type(&column_sample_values[column_index]);
+        let inferred_data_type = column_type_tally.decide_data_type(type_inference_settings.inference_mode);
+        let column_ignored = column_schema.is_some_and(|column_schema| column_schema.column_ignored);
+        let declared_data_type = column_schema.and_then(|column_schema| column_schema.declared_data_type.clone());
+        let detected_data_type = declared_data_type
+            .clone()
+            .unwrap_or_else(|| inferred_data_type.clone());
+        
+        // A stored decimal scale covers values beyond the sample rows (exact sums start at the wider scale)
+        if let Some(declared_decimal_scale) = column_schema.and_then(|column_schema| column_schema.decimal_scale) {
+            column_type_tally.decimal_scale = column_type_tally.decimal_scale.max(declared_decimal_scale);
+        }
+        
+        let mut missing_value_counts = column_missing_value_counts[column_index];
+        missing_value_counts.type_invalid_count = column_type_tally.mismatched_value_count(&detected_data_type);
         
         let column_info = CsvColumnInformation {
             column_index,
             column_name,
             detected_data_type,
+            inferred_data_type,
+            data_type_declared_in_metadata: declared_data_type.is_some(),
+            declared_type_conflict: declared_type_conflicts[column_index].take(),
+            column_ignored,
             non_empty_value_count: column_non_empty_counts[column_index],
-            empty_value_count: column_empty_counts[column_index],
+            missing_value_counts,
             sample_values: column_sample_values[column_index].clone(),
+            type_tally: column_type_tally,
         };
         
         column_info_list.push(column_info);
     }
     
-    Ok(column_info_list)
+    Ok((column_info_list, rows_processed))
 }
 
-/// Parses a CSV line into individual field values
+/// Result of checking every value of a column against a candidate type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnTypeChangePreview {
+    /// Values checked (blanks and null sentinels are not checked)
+    pub checked_value_count: usize,
+    
+    /// Values that do not parse as the candidate type
+    pub failing_value_count: usize,
+    
+    /// The first few failing values with their line numbers
+    pub failing_value_examples: Vec<TypeMismatchExample>,
+}
+
+/// Checks every value of one column against a candidate type
 /// 
-/// For MVP, this uses simple comma splitting. Could be enhanced later
-/// for proper CSV parsing with quoted fields and escaped commas.
+/// Used by the column type review screen to show what a type change would
+/// reject before it is saved to the metadata file.
 /// 
 /// # Arguments
-/// * `csv_line` - The CSV line to parse
+/// * `analysis_results` - The completed analysis (file, dialect, null sentinels, formats)
+/// * `column_index` - 0-based index of the column to check
+/// * `candidate_data_type` - The type the user wants to give the column
 /// 
 /// # Returns
-/// * `Vec<String>` - List of field values
-fn parse_csv_line_into_fields(csv_line: &str) -> Vec<String> {
-    csv_line.split(',')
-        .map(|field| field.to_string())
-        .collect()
-}
-
-/// Detects the most likely data type for a column based on sample values
+/// * `RowsAndColumnsResult<ColumnTypeChangePreview>` - Checked and failing value counts
 /// 
-/// This function analyzes sample values and determines the most appropriate
-/// data type using heuristics and type parsing attempts.
-/// 
-/// # Arguments
-/// * `sample_values` - List of sample values from the column
-/// 
-/// # Returns
-/// * `CsvColumnDataType` - The detected data type
-fn detect_column_data_type(sample_values: &[String]) -> CsvColumnDataType {
-    if sample_values.is_empty() {
-        return CsvColumnDataType::String;
-    }
-    
-    let mut boolean_count = 0;
-    let mut integer_count = 0;
-    let mut float_count = 0;
-    let total_samples = sample_values.len();
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV file cannot be read
+/// * `RowsAndColumnsError::CsvProcessingError` - If a record cannot be parsed
+pub fn preview_column_type_change(
+    analysis_results: &CsvAnalysisResults,
+    column_index: usize,
+    candidate_data_type: &CsvColumnDataType,
+) -> RowsAndColumnsResult<ColumnTypeChangePreview> {
+    let csv_record_reader = open_analyzed_csv_data_record_reader(analysis_results, "type change preview")?;
+    
+    let null_value_sentinels = analysis_results.null_value_settings.sentinels_for_column(column_index);
+    let inference_settings = &analysis_results.type_inference_summary.inference_settings;
+    let mut type_change_preview = ColumnTypeChangePreview {
+        checked_value_count: 0,
+        failing_value_count: 0,
+        failing_value_examples: Vec::new(),
+    };
     
-    for sample_value in sample_values {
-        let trimmed_value = sample_value.trim().to_lowercase();
+    for record_result in csv_record_reader {
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during type change preview", io_error)
+        })?;
+        let conformed_field_values = match conform_record_fields_to_policy(
+            csv_record.field_values,
+            analysis_results.total_column_count,
+            analysis_results.structural_validation_report.ragged_row_policy,
+        ) {
+            Some(conformed_field_values) => conformed_field_values,
+            None => continue,
+        };
         
-        // Check if it's a boolean value
-        if is_boolean_value(&trimmed_value) {
-            boolean_count += 1;
+        let trimmed_value = conformed_field_values[column_index].trim();
+        if null_value_sentinels.is_missing_value(trimmed_value) {
+            continue;
         }
-        // Check if it's an integer
-        else if trimmed_value.parse::<i64>().is_ok() {
-            integer_count += 1;
-        }
-        // Check if it's a float
-        else if trimmed_value.parse::<f64>().is_ok() {
-            float_count += 1;
+        
+        type_change_preview.checked_value_count += 1;
+        if !value_matches_data_type(trimmed_value, candidate_data_type, inference_settings) {
+            type_change_preview.failing_value_count += 1;
+            if type_change_preview.failing_value_examples.len() < TYPE_CHANGE_PREVIEW_EXAMPLE_LIMIT {
+                type_change_preview.failing_value_examples.push(TypeMismatchExample {
+                    offending_value: trimmed_value.to_string(),
+                    line_number: csv_record.starting_line_number,
+                });
+            }
         }
-        // Otherwise it's a string
     }
     
-    // Determine type based on majority of samples
-    // Require at least 70% of samples to match a type
-    let threshold = (total_samples * 7) / 10; // 70% threshold
-    
-    if boolean_count >= threshold {
-        CsvColumnDataType::Boolean
-    } else if integer_count >= threshold {
-        CsvColumnDataType::Integer
-    } else if float_count >= threshold {
-        CsvColumnDataType::Float
-    } else {
-        CsvColumnDataType::String
-    }
+    Ok(type_change_preview)
 }
 
-/// Checks if a value represents a boolean
+/// Fails the analysis when a column holds a value its declared type cannot parse
 /// 
 /// # Arguments
-/// * `value` - The value to check (should be trimmed and lowercase)
+/// * `column_information_list` - The analyzed columns
+/// * `metadata_file_path` - Path to the metadata TOML file (for the error message)
 /// 
 /// # Returns
-/// * `bool` - True if the value appears to be boolean
-fn is_boolean_value(value: &str) -> bool {
-    matches!(value, "true" | "false" | "yes" | "no" | "1" | "0" | "t" | "f" | "y" | "n")
+/// * `RowsAndColumnsResult<()>` - Ok if every declared type fits its column
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - Naming the first conflicting column, value and line
+fn reject_declared_type_conflicts(
+    column_information_list: &[CsvColumnInformation],
+    metadata_file_path: &Path,
+) -> RowsAndColumnsResult<()> {
+    for column_info in column_information_list {
+        if let Some(declared_type_conflict) = &column_info.declared_type_conflict {
+            return Err(create_metadata_error(
+                &format!(
+                    "Column {} ('{}') is declared {} but line {} holds \"{}\" \
+                     (change its {} or add the value to null_values)",
+                    column_info.column_index + 1,
+                    column_info.column_name,
+                    column_info.detected_data_type.to_toml_string(),
+                    declared_type_conflict.line_number,
+                    declared_type_conflict.offending_value,
+                    DATA_TYPE_KEY_NAME,
+                ),
+                &metadata_file_path.display().to_string(),
+            ));
+        }
+    }
+    
+    Ok(())
 }
 
 /// Determines the path for the metadata TOML file based on CSV file path
@@ -578,6 +1333,21 @@ fn is_boolean_value(value: &str) -> bool {
 /// # Returns
 /// * `RowsAndColumnsResult<PathBuf>` - Path to metadata file or error
 fn determine_metadata_file_path(csv_file_path: &PathBuf) -> RowsAndColumnsResult<PathBuf> {
+    determine_side_file_path(csv_file_path, METADATA_FILE_EXTENSION)
+}
+
+/// Determines the path of a file that sits next to the CSV file
+/// 
+/// Side files share the CSV file's stem, e.g. `data.csv` gets
+/// `data.csv_metadata.toml` and `data.quarantine.csv`.
+/// 
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `side_file_suffix` - Suffix appended to the CSV file stem after a dot
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the side file or error
+fn determine_side_file_path(csv_file_path: &PathBuf, side_file_suffix: &str) -> RowsAndColumnsResult<PathBuf> {
     let csv_filename_stem = csv_file_path.file_stem()
         .and_then(|stem| stem.to_str())
         .ok_or_else(|| {
@@ -586,9 +1356,9 @@ fn determine_metadata_file_path(csv_file_path: &PathBuf) -> RowsAndColumnsResult
             )
         })?;
     
-    let metadata_filename = format!("{}.{}", csv_filename_stem, METADATA_FILE_EXTENSION);
+    let side_filename = format!("{}.{}", csv_filename_stem, side_file_suffix);
     
-    // Place metadata file in same directory as CSV file
+    // Place side file in same directory as CSV file
     let csv_directory = csv_file_path.parent()
         .ok_or_else(|| {
             create_configuration_error(
@@ -596,23 +1366,30 @@ fn determine_metadata_file_path(csv_file_path: &PathBuf) -> RowsAndColumnsResult
             )
         })?;
     
-    let metadata_file_path = csv_directory.join(metadata_filename);
-    
-    Ok(metadata_file_path)
+    Ok(csv_directory.join(side_filename))
 }
 
 /// Creates or updates the metadata TOML file with column information
 /// 
+/// Called after every analysis, and again when column types are edited in the
+/// review screen.
+/// 
+/// Besides the columns, the file records the encoding, the null sentinels, the dialect (`[dialect]`),
+/// the sniffing result (`[sniffing]`), the header decision (a hand-set
+/// `has_header` is kept; the heuristic goes to `[header_detection]`), the ragged
+/// record findings (`[validation]`) and the type inference mode (`[type_inference]`).
+/// 
 /// # Arguments
-/// * `metadata_file_path` - Path where metadata file should be created/updated
-/// * `column_information_list` - List of column information to store
+/// * `analysis_results` - The completed analysis, including the metadata file path
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or error
-fn create_or_update_metadata_file(
-    metadata_file_path: &PathBuf,
-    column_information_list: &[CsvColumnInformation],
+pub fn create_or_update_metadata_file(
+    analysis_results: &CsvAnalysisResults,
 ) -> RowsAndColumnsResult<()> {
+    let metadata_file_path = &analysis_results.metadata_file_path;
+    let header_detection_report = &analysis_results.header_detection_report;
+    
     // Prepare parent directories if needed
     if let Some(parent_dir) = metadata_file_path.parent() {
         if !parent_dir.exists() {
@@ -630,18 +1407,63 @@ fn create_or_update_metadata_file(
     toml_content.push_str("# CSV Metadata File\n");
     toml_content.push_str("# Generated by rows_and_columns\n\n");
     
-    toml_content.push_str(&format!("total_columns = {}\n", column_information_list.len()));
+    toml_content.push_str(&format!("total_columns = {}\n", analysis_results.column_information_list.len()));
+    toml_content.push_str(&format!("{} = \"{}\"\n", ENCODING_KEY_NAME, analysis_results.text_encoding.to_toml_string()));
+    toml_content.push_str(&format_null_values_toml_line(&analysis_results.null_value_settings.global_sentinels));
+    
+    // Keep a hand-set header override; otherwise show how to set one
+    if header_detection_report.overridden_by_metadata {
+        toml_content.push_str(&format!("{} = {}\n", HAS_HEADER_KEY_NAME, header_detection_report.has_header_row));
+    } else {
+        toml_content.push_str(&format!(
+            "# {} = {}   # uncomment to override header detection\n",
+            HAS_HEADER_KEY_NAME, header_detection_report.has_header_row
+        ));
+    }
     toml_content.push_str("\n");
     
+    // Add the dialect so users can correct it by hand
+    toml_content.push_str(&format_csv_dialect_toml_section(&analysis_results.csv_dialect));
+    toml_content.push_str(&format_csv_dialect_sniffing_toml_section(&analysis_results.dialect_sniffing_report));
+    toml_content.push_str(&format_header_detection_toml_section(header_detection_report));
+    toml_content.push_str(&format_csv_validation_toml_section(&analysis_results.structural_validation_report));
+    toml_content.push_str(&format_type_inference_toml_section(&analysis_results.type_inference_summary));
+    toml_content.push_str(&format_statistics_toml_section(&analysis_results.statistics_settings));
+    toml_content.push_str(&format_dataset_storage_toml_section(&analysis_results.dataset_storage_settings));
+    
     // Add column information
-    for column_info in column_information_list {
+    for column_info in &analysis_results.column_information_list {
         let column_section = format!("column_{}", column_info.column_index + 1);
         toml_content.push_str(&format!("[{}]\n", column_section));
-        toml_content.push_str(&format!("name = \"{}\"\n", column_info.column_name));
-        toml_content.push_str(&format!("data_type = \"{}\"\n", column_info.detected_data_type.to_toml_string()));
+        toml_content.push_str(&format!("name = \"{}\"\n", escape_toml_basic_string(&column_info.column_name)));
+        toml_content.push_str(&format!("{} = \"{}\"\n", DATA_TYPE_KEY_NAME, column_info.detected_data_type.to_toml_string()));
+        toml_content.push_str(&format!(
+            "{} = \"{}\"\n",
+            DETECTED_TYPE_KEY_NAME,
+            column_info.inferred_data_type.to_toml_string()
+        ));
         toml_content.push_str(&format!("column_index = {}\n", column_info.column_index));
+        if let Some(column_null_value_sentinels) = analysis_results.null_value_settings
+            .column_sentinel_overrides
+            .get(&column_info.column_index)
+        {
+            toml_content.push_str(&format_null_values_toml_line(column_null_value_sentinels));
+        }
+        if column_info.column_ignored {
+            toml_content.push_str(&format!("{} = true\n", IGNORED_KEY_NAME));
+        }
+        let missing_value_counts = &column_info.missing_value_counts;
         toml_content.push_str(&format!("non_empty_values = {}\n", column_info.non_empty_value_count));
-        toml_content.push_str(&format!("empty_values = {}\n", column_info.empty_value_count));
+        toml_content.push_str(&format!("empty_values = {}\n", missing_value_counts.blank_value_count));
+        toml_content.push_str(&format!("null_sentinel_values = {}\n", missing_value_counts.null_sentinel_count));
+        toml_content.push_str(&format!("type_invalid_values = {}\n", missing_value_counts.type_invalid_count));
+        if column_info.detected_data_type == CsvColumnDataType::Decimal {
+            toml_content.push_str(&format!("{} = {}\n", DECIMAL_SCALE_KEY_NAME, column_info.type_tally.decimal_scale));
+            if let Some(currency_symbol) = column_info.type_tally.currency_symbol {
+                toml_content.push_str(&format!("currency_symbol = \"{}\"\n", currency_symbol));
+            }
+        }
+        toml_content.push_str(&format_column_type_tally_toml_lines(&column_info.type_tally));
         toml_content.push_str("\n");
     }
     
@@ -681,11 +1503,66 @@ pub struct NumericalColumnStatistics {
     /// Mean (average) value
     pub mean_value: f64,
     
-    /// Standard deviation
+    /// Standard deviation (square root of `variance`)
     pub standard_deviation: f64,
     
+    /// Number of values that parsed as numbers (pandas `count`)
+    pub value_count: usize,
+    
+    /// Sum of the values
+    pub value_sum: f64,
+    
+    /// Variance dividing by count - ddof (NaN when there are too few values)
+    pub variance: f64,
+    
+    /// Delta degrees of freedom of `variance` (1: sample, as in pandas; 0: population)
+    pub delta_degrees_of_freedom: u32,
+    
+    /// Skewness (pandas' adjusted Fisher-Pearson coefficient when ddof is 1 or more)
+    pub skewness: f64,
+    
+    /// Excess kurtosis, 0 for a normal distribution (pandas' unbiased estimate when ddof is 1 or more)
+    pub excess_kurtosis: f64,
+    
+    /// Interquartile range (q3 - q1)
+    pub interquartile_range: f64,
+    
+    /// Range (max - min)
+    pub value_range: f64,
+    
+    /// Coefficient of variation (stdev / mean; None when the mean is zero)
+    pub coefficient_of_variation: Option<f64>,
+    
+    /// Values equal to zero
+    pub zero_value_count: usize,
+    
+    /// Values below zero
+    pub negative_value_count: usize,
+    
+    /// The `percentiles` requested in the `[statistics]` section, in that order
+    pub percentile_values: Vec<PercentileValue>,
+    
+    /// How the quartiles and percentiles between two ranks were computed
+    pub percentile_interpolation: PercentileInterpolation,
+    
+    /// Whether the quartiles and percentiles are P² estimates (streaming mode, past the exact buffer)
+    pub percentiles_are_estimates: bool,
+    
     /// Percentage of missing/empty values
     pub missing_percentage: f64,
+    
+    /// Blank, null sentinel and unparseable values behind the missing percentage
+    pub missing_value_counts: MissingValueCounts,
+}
+
+/// One requested percentile of a numerical column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercentileValue {
+    /// The percentile (0.0 to 100.0)
+    pub percentile: f64,
+    
+    /// The value at that percentile
+    pub percentile_value: f64,
 }
 
 /// Value frequency information for categorical columns
@@ -696,11 +1573,14 @@ pub struct CategoricalValueFrequency {
     /// The actual value/category
     pub value: String,
     
-    /// Number of times this value appears
+    /// Number of times this value appears (an upper bound when the frequencies are estimates)
     pub count: usize,
     
     /// Percentage of total non-empty values this represents
     pub percentage: f64,
+    
+    /// Largest possible overcount: the value appears at least `count - count_overestimate_bound` times
+    pub count_overestimate_bound: usize,
 }
 
 /// Enhanced statistical information for categorical columns
@@ -712,12 +1592,22 @@ pub struct CategoricalColumnStatistics {
     /// Total number of unique values/categories
     pub unique_value_count: usize,
     
+    /// Relative standard error of `unique_value_count` (None when counted exactly)
+    pub unique_value_count_standard_error: Option<f64>,
+    
     /// List of value frequencies (sorted by frequency, descending)
     pub value_frequencies: Vec<CategoricalValueFrequency>,
     
+    /// Whether the frequencies are the tracked top values of a bounded sketch
+    /// (only the most frequent values are listed and counts may be overstated)
+    pub value_frequencies_are_estimates: bool,
+    
     /// Percentage of missing/empty values
     pub missing_percentage: f64,
     
+    /// Blank and null sentinel values behind the missing percentage
+    pub missing_value_counts: MissingValueCounts,
+    
     /// Most common value (mode)
     pub mode_value: Option<String>,
     
@@ -725,6 +1615,91 @@ pub struct CategoricalColumnStatistics {
     pub mode_percentage: f64,
 }
 
+/// True, false and null shares of a boolean column
+/// 
+/// The three ratios are fractions of all values in the column; values outside
+/// the true/false vocabulary make up the rest.
+#[derive(Debug, Clone)]
+pub struct BooleanColumnStatistics {
+    /// Share of values read as true
+    pub true_ratio: f64,
+    
+    /// Share of values read as false
+    pub false_ratio: f64,
+    
+    /// Share of blank and null sentinel values
+    pub null_ratio: f64,
+    
+    /// The counts behind the ratios
+    pub boolean_value_counts: BooleanValueCounts,
+}
+
+/// Exact statistics for fixed-point decimal columns
+/// 
+/// Sums and means are computed on `i128` values in units of the column's
+/// smallest digit, so they carry no floating point rounding error.
+#[derive(Debug, Clone)]
+pub struct DecimalColumnStatistics {
+    /// Digits after the decimal point (the largest seen in the column)
+    pub decimal_scale: u32,
+    
+    /// Currency symbol used for display, if the column had one
+    pub currency_symbol: Option<char>,
+    
+    /// Number of values that parsed as decimals
+    pub parsed_value_count: usize,
+    
+    /// Exact sum of all values
+    pub exact_sum: FixedPointDecimal,
+    
+    /// Mean rounded half away from zero at the column's scale
+    pub rounded_mean: FixedPointDecimal,
+    
+    /// Smallest value
+    pub minimum_value: FixedPointDecimal,
+    
+    /// Largest value
+    pub maximum_value: FixedPointDecimal,
+}
+
+/// Number of values in one bucket of a date/time histogram
+#[derive(Debug, Clone)]
+pub struct TemporalHistogramBucket {
+    /// Bucket label, e.g. "2024-03" for a monthly bucket
+    pub bucket_label: String,
+    
+    /// Number of values falling in the bucket
+    pub value_count: usize,
+}
+
+/// Statistical information for date, time and datetime columns
+/// 
+/// Values are compared on their numeric scale (days for dates, seconds for
+/// times and datetimes) and reported back in ISO-8601 form.
+#[derive(Debug, Clone)]
+pub struct TemporalColumnStatistics {
+    /// Earliest value, ISO-8601 formatted
+    pub earliest_value_text: String,
+    
+    /// Latest value, ISO-8601 formatted
+    pub latest_value_text: String,
+    
+    /// Span between earliest and latest value, e.g. "41 days" or "08:15:00"
+    pub range_text: String,
+    
+    /// Bucket size of the histogram
+    pub histogram_granularity: TemporalHistogramGranularity,
+    
+    /// Histogram buckets in chronological order (empty buckets included)
+    pub histogram_buckets: Vec<TemporalHistogramBucket>,
+    
+    /// Percentage of missing/empty values
+    pub missing_percentage: f64,
+    
+    /// Blank, null sentinel and unparseable values behind the missing percentage
+    pub missing_value_counts: MissingValueCounts,
+}
+
 /// Field type classification for enhanced analysis
 /// 
 /// This enum distinguishes between different types of data for appropriate
@@ -736,6 +1711,9 @@ pub enum CsvFieldType {
     
     /// Continuous numerical data
     Continuous,
+    
+    /// Dates, times and datetimes
+    Temporal,
 }
 
 /// Enhanced column information with comprehensive statistics
@@ -755,12 +1733,24 @@ pub struct EnhancedCsvColumnInformation {
     
     /// Statistical information for categorical columns (None for numerical) 
     pub categorical_statistics: Option<CategoricalColumnStatistics>,
+    
+    /// Statistical information for date/time columns (None for other types)
+    pub temporal_statistics: Option<TemporalColumnStatistics>,
+    
+    /// Exact fixed-point statistics for decimal columns (None for other types)
+    pub decimal_statistics: Option<DecimalColumnStatistics>,
+    
+    /// True/false/null shares for boolean columns (None for other types)
+    pub boolean_statistics: Option<BooleanColumnStatistics>,
 }
 
 /// Performs enhanced statistical analysis on CSV columns
 /// 
 /// This function provides comprehensive pandas-style statistical analysis
 /// including appropriate measures for categorical and continuous data types.
+/// The file is read once and each value is fed to its column's accumulator,
+/// so numerical columns need a fixed amount of memory unless the `[statistics]`
+/// mode is `exact`.
 /// 
 /// # Arguments
 /// * `csv_file_path` - Path to the CSV file to analyze
@@ -774,307 +1764,1071 @@ pub fn perform_enhanced_statistical_analysis(
 ) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
     println!("📊 Performing enhanced statistical analysis...");
     
-    let mut enhanced_column_info_list = Vec::new();
-    
-    // Collect all data values for each column for comprehensive analysis
-    let all_column_values = collect_all_column_values(
+    let mut column_accumulators = create_column_statistics_accumulators(basic_analysis_results);
+    let mut csv_record_reader = open_csv_record_reader(
         csv_file_path,
-        basic_analysis_results.has_header_row,
-        basic_analysis_results.total_column_count,
+        basic_analysis_results.text_encoding,
+        &basic_analysis_results.csv_dialect,
+        "enhanced analysis",
     )?;
     
-    // Analyze each column with enhanced statistics
-    for basic_column_info in &basic_analysis_results.column_information_list {
-        let column_values = &all_column_values[basic_column_info.column_index];
-        
-        // Determine field type (categorical vs continuous)
-        let field_type = determine_field_type(&basic_column_info.detected_data_type);
-        
-        // Generate appropriate statistics based on field type
-        let (numerical_statistics, categorical_statistics) = match field_type {
-            CsvFieldType::Continuous => {
-                let numerical_stats = calculate_numerical_statistics(column_values)?;
-                (Some(numerical_stats), None)
-            }
-            CsvFieldType::Categorical => {
-                let categorical_stats = calculate_categorical_statistics(column_values)?;
-                (None, Some(categorical_stats))
-            }
-        };
+    // Skip header row if present
+    if basic_analysis_results.has_header_row {
+        csv_record_reader.read_next_record().map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read header record during enhanced analysis", io_error)
+        })?;
+    }
+    
+    // Feed every data row to the column accumulators
+    for record_result in csv_record_reader {
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during enhanced analysis", io_error)
+        })?;
         
-        let enhanced_column_info = EnhancedCsvColumnInformation {
-            basic_info: basic_column_info.clone(),
-            field_type,
-            numerical_statistics,
-            categorical_statistics,
+        // Bring ragged records to the header width, or skip quarantined ones
+        let conformed_field_values = match conform_record_fields_to_policy(
+            csv_record.field_values,
+            basic_analysis_results.total_column_count,
+            basic_analysis_results.structural_validation_report.ragged_row_policy,
+        ) {
+            Some(conformed_field_values) => conformed_field_values,
+            None => continue,
         };
         
-        enhanced_column_info_list.push(enhanced_column_info);
+        // (whitespace handling is already applied by the dialect's trim policy)
+        for column_accumulator in &mut column_accumulators {
+            column_accumulator.add_value(&conformed_field_values[column_accumulator.basic_column_info.column_index]);
+        }
     }
     
+    let enhanced_column_info_list = finish_column_statistics_accumulators(column_accumulators)?;
     println!("  ✓ Enhanced statistical analysis complete");
     
     Ok(enhanced_column_info_list)
 }
 
-/// Collects all values from CSV columns for comprehensive statistical analysis
+/// Performs enhanced statistical analysis on the columns of an imported dataset
 /// 
-/// This function reads through the entire CSV file to collect all values
-/// for detailed statistical calculations.
+/// Reads the rows through the dataset's storage backend instead of parsing the
+/// CSV file again, one row at a time. Every non-ignored column must be stored
+/// in the dataset.
 /// 
 /// # Arguments
-/// * `csv_file_path` - Path to the CSV file
-/// * `has_header_row` - Whether file has header row to skip
-/// * `column_count` - Number of columns expected
+/// * `basic_analysis_results` - Results from basic CSV structure analysis
+/// * `dataset_storage_reader` - Reader over the imported dataset
+/// * `stored_row_count` - Number of data rows in the dataset
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<Vec<Vec<String>>>` - All values for each column
-fn collect_all_column_values(
-    csv_file_path: &PathBuf,
-    has_header_row: bool,
-    column_count: usize,
-) -> RowsAndColumnsResult<Vec<Vec<String>>> {
-    let csv_file = File::open(csv_file_path)
-        .map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to open CSV file for enhanced analysis: {}", csv_file_path.display()),
-                io_error
-            )
-        })?;
+/// * `RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>>` - Enhanced column analysis
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If a stored value cannot be read
+pub fn perform_enhanced_statistical_analysis_from_dataset(
+    basic_analysis_results: &CsvAnalysisResults,
+    dataset_storage_reader: &mut dyn DatasetStorageReader,
+    stored_row_count: usize,
+) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
+    println!("📊 Performing enhanced statistical analysis from the imported dataset...");
     
-    let csv_reader = BufReader::new(csv_file);
-    let mut csv_lines = csv_reader.lines();
+    let mut column_accumulators = create_column_statistics_accumulators(basic_analysis_results);
+    let analyzed_column_indexes: Vec<usize> = column_accumulators
+        .iter()
+        .map(|column_accumulator| column_accumulator.basic_column_info.column_index)
+        .collect();
     
-    // Skip header row if present
-    if has_header_row {
-        csv_lines.next();
+    for row_number in 1..=stored_row_count {
+        let row_values = dataset_storage_reader.read_row(row_number, &analyzed_column_indexes)?;
+        for (column_accumulator, row_value) in column_accumulators.iter_mut().zip(&row_values) {
+            column_accumulator.add_value(row_value);
+        }
     }
     
-    // Initialize storage for all column values
-    let mut all_column_values: Vec<Vec<String>> = vec![Vec::new(); column_count];
+    let enhanced_column_info_list = finish_column_statistics_accumulators(column_accumulators)?;
+    println!("  ✓ Enhanced statistical analysis complete");
     
-    // Read all data rows
-    for line_result in csv_lines {
-        let csv_line = line_result.map_err(|io_error| {
-            create_file_system_error("Failed to read CSV line during enhanced analysis", io_error)
-        })?;
-        
-        let field_values = parse_csv_line_into_fields(&csv_line);
-        
-        // Store values for each column
-        for (column_index, field_value) in field_values.iter().enumerate() {
-            if column_index >= column_count {
-                continue; // Skip extra fields
+    Ok(enhanced_column_info_list)
+}
+
+/// Outlier analysis of one numerical column
+/// 
+/// Row numbers count data rows from 1, the way the directory import numbers
+/// them: the header and quarantined records are not rows.
+#[derive(Debug, Clone)]
+pub struct ColumnOutlierAnalysis {
+    /// Column name
+    pub column_name: String,
+    
+    /// Fences, mean, standard deviation, median and MAD the values were tested against
+    pub outlier_detection_rules: OutlierDetectionRules,
+    
+    /// Whether the quartiles, median and MAD are P² estimates (streaming mode, past the exact buffer)
+    pub rules_are_estimates: bool,
+    
+    /// Smallest value inside the inner fences (lower end of the box plot whisker)
+    pub lower_whisker_value: Option<f64>,
+    
+    /// Largest value inside the inner fences (upper end of the box plot whisker)
+    pub upper_whisker_value: Option<f64>,
+    
+    /// Rows outside the inner fences (1.5 × IQR) but inside the outer fences
+    pub mild_outlier_row_numbers: Vec<usize>,
+    
+    /// Rows outside the outer fences (3 × IQR)
+    pub extreme_outlier_row_numbers: Vec<usize>,
+    
+    /// Rows whose |z-score| is above the threshold
+    pub z_score_outlier_row_numbers: Vec<usize>,
+    
+    /// Rows whose |modified z-score| is above the threshold
+    pub modified_z_score_outlier_row_numbers: Vec<usize>,
+}
+
+impl ColumnOutlierAnalysis {
+    /// Tests one value of the column and records its row if it is flagged
+    ///
+    /// # Arguments
+    /// * `row_number` - 1-based data row number
+    /// * `numerical_value` - The parsed value
+    fn record_value(&mut self, row_number: usize, numerical_value: f64) {
+        let outlier_flags = self.outlier_detection_rules.classify_value(numerical_value);
+    
+        match outlier_flags.tukey_outlier_severity {
+            Some(TukeyOutlierSeverity::Mild) => self.mild_outlier_row_numbers.push(row_number),
+            Some(TukeyOutlierSeverity::Extreme) => self.extreme_outlier_row_numbers.push(row_number),
+            None => {
+                self.lower_whisker_value = Some(self.lower_whisker_value.map_or(numerical_value, |whisker_value| whisker_value.min(numerical_value)));
+                self.upper_whisker_value = Some(self.upper_whisker_value.map_or(numerical_value, |whisker_value| whisker_value.max(numerical_value)));
             }
-            
-            all_column_values[column_index].push(field_value.trim().to_string());
+        }
+        if outlier_flags.z_score_outlier {
+            self.z_score_outlier_row_numbers.push(row_number);
+        }
+        if outlier_flags.modified_z_score_outlier {
+            self.modified_z_score_outlier_row_numbers.push(row_number);
         }
     }
-    
-    Ok(all_column_values)
 }
 
-/// Determines field type based on detected data type
+/// Detects outliers in every numerical column of the CSV file
+/// 
+/// Uses the quartiles, mean and standard deviation of the enhanced analysis,
+/// then reads the file twice: once for the median absolute deviation (MAD),
+/// once to test every value with Tukey's fences (1.5 × IQR and 3 × IQR, not
+/// placed when the IQR is 0), the z-score and the modified z-score.
 /// 
 /// # Arguments
-/// * `detected_data_type` - The basic data type detected
+/// * `basic_analysis_results` - The basic analysis (file, dialect, null sentinels, statistics mode)
+/// * `enhanced_analysis_results` - The enhanced statistics of the same columns
 /// 
 /// # Returns
-/// * `CsvFieldType` - Categorical or Continuous classification
-fn determine_field_type(detected_data_type: &CsvColumnDataType) -> CsvFieldType {
-    match detected_data_type {
-        CsvColumnDataType::Integer | CsvColumnDataType::Float => CsvFieldType::Continuous,
-        CsvColumnDataType::Boolean | CsvColumnDataType::String => CsvFieldType::Categorical,
-    }
+/// * `RowsAndColumnsResult<Vec<ColumnOutlierAnalysis>>` - One analysis per numerical column
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV file cannot be read
+/// * `RowsAndColumnsError::CsvProcessingError` - If a record cannot be parsed
+pub fn perform_outlier_analysis(
+    basic_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+) -> RowsAndColumnsResult<Vec<ColumnOutlierAnalysis>> {
+    println!("🔎 Detecting outliers in numerical columns...");
+    
+    let numerical_columns: Vec<(&CsvColumnInformation, &NumericalColumnStatistics)> = enhanced_analysis_results
+        .iter()
+        .filter_map(|enhanced_column_info| {
+            enhanced_column_info.numerical_statistics
+                .as_ref()
+                .map(|numerical_statistics| (&enhanced_column_info.basic_info, numerical_statistics))
+        })
+        .collect();
+    
+    // First pass: the median absolute deviation of every column
+    let mut deviation_accumulators: Vec<MedianAbsoluteDeviationAccumulator> = numerical_columns
+        .iter()
+        .map(|(_, numerical_statistics)| {
+            MedianAbsoluteDeviationAccumulator::new(
+                numerical_statistics.q2_median_value,
+                basic_analysis_results.statistics_settings.statistics_mode,
+            )
+        })
+        .collect();
+    visit_numerical_column_values(basic_analysis_results, &numerical_columns, |column_position, _, numerical_value| {
+        deviation_accumulators[column_position].add_value(numerical_value);
+    })?;
+    
+    let mut outlier_analyses: Vec<ColumnOutlierAnalysis> = numerical_columns
+        .iter()
+        .zip(deviation_accumulators)
+        .map(|((basic_column_info, numerical_statistics), deviation_accumulator)| ColumnOutlierAnalysis {
+            column_name: basic_column_info.column_name.clone(),
+            outlier_detection_rules: OutlierDetectionRules {
+                tukey_fences: TukeyFences::from_quartiles(numerical_statistics.q1_value, numerical_statistics.q3_value),
+                mean_value: numerical_statistics.mean_value,
+                standard_deviation: numerical_statistics.standard_deviation,
+                median_value: numerical_statistics.q2_median_value,
+                median_absolute_deviation: deviation_accumulator.finish(),
+            },
+            rules_are_estimates: numerical_statistics.percentiles_are_estimates,
+            lower_whisker_value: None,
+            upper_whisker_value: None,
+            mild_outlier_row_numbers: Vec::new(),
+            extreme_outlier_row_numbers: Vec::new(),
+            z_score_outlier_row_numbers: Vec::new(),
+            modified_z_score_outlier_row_numbers: Vec::new(),
+        })
+        .collect();
+    
+    // Second pass: test every value
+    visit_numerical_column_values(basic_analysis_results, &numerical_columns, |column_position, row_number, numerical_value| {
+        outlier_analyses[column_position].record_value(row_number, numerical_value);
+    })?;
+    
+    println!("  ✓ Outlier analysis complete");
+    Ok(outlier_analyses)
 }
 
-/// Calculates comprehensive numerical statistics for continuous data
+/// Reads the CSV file once and hands every parsed value of the given columns to a visitor
+/// 
+/// Missing values (blank or null sentinel) and values that do not parse are skipped.
 /// 
 /// # Arguments
-/// * `column_values` - All values from the column
+/// * `basic_analysis_results` - The basic analysis (file, dialect, ragged row policy, null sentinels)
+/// * `numerical_columns` - The columns to read
+/// * `visit_value` - Called with (position in `numerical_columns`, 1-based data row number, value)
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<NumericalColumnStatistics>` - Complete numerical analysis
-fn calculate_numerical_statistics(column_values: &[String]) -> RowsAndColumnsResult<NumericalColumnStatistics> {
-    // Parse all numerical values, filtering out empty/invalid ones
-    let mut numerical_values = Vec::new();
-    let mut empty_count = 0;
+/// * `RowsAndColumnsResult<()>` - Success or read error
+fn visit_numerical_column_values(
+    basic_analysis_results: &CsvAnalysisResults,
+    numerical_columns: &[(&CsvColumnInformation, &NumericalColumnStatistics)],
+    mut visit_value: impl FnMut(usize, usize, f64),
+) -> RowsAndColumnsResult<()> {
+    let column_null_value_sentinels: Vec<&NullValueSentinels> = numerical_columns
+        .iter()
+        .map(|(basic_column_info, _)| basic_analysis_results.null_value_settings.sentinels_for_column(basic_column_info.column_index))
+        .collect();
+    let csv_record_reader = open_analyzed_csv_data_record_reader(basic_analysis_results, "outlier analysis")?;
+    let mut data_row_number = 0;
     
-    for value_string in column_values {
-        let trimmed_value = value_string.trim();
-        
-        if trimmed_value.is_empty() {
-            empty_count += 1;
-        } else {
-            // Try to parse as float (works for both integers and floats)
-            match trimmed_value.parse::<f64>() {
-                Ok(numerical_value) => numerical_values.push(numerical_value),
-                Err(_) => empty_count += 1, // Count unparseable as empty
+    for record_result in csv_record_reader {
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during outlier analysis", io_error)
+        })?;
+        let conformed_field_values = match conform_record_fields_to_policy(
+            csv_record.field_values,
+            basic_analysis_results.total_column_count,
+            basic_analysis_results.structural_validation_report.ragged_row_policy,
+        ) {
+            Some(conformed_field_values) => conformed_field_values,
+            None => continue,
+        };
+        data_row_number += 1;
+    
+        for (column_position, ((basic_column_info, _), null_value_sentinels)) in
+            numerical_columns.iter().zip(&column_null_value_sentinels).enumerate()
+        {
+            let trimmed_value = conformed_field_values[basic_column_info.column_index].trim();
+            if null_value_sentinels.classify_missing_value(trimmed_value).is_some() {
+                continue;
+            }
+            if let Some(numerical_value) = parse_numerical_field_value(trimmed_value, &basic_column_info.detected_data_type) {
+                visit_value(column_position, data_row_number, numerical_value);
             }
         }
     }
     
-    if numerical_values.is_empty() {
-        return Err(create_csv_processing_error(
-            "No valid numerical values found for statistical analysis",
-            None,
-            None
-        ));
-    }
-    
-    // Sort values for percentile calculations
-    numerical_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-    
-    // Calculate basic statistics
-    let min_value = numerical_values[0];
-    let max_value = numerical_values[numerical_values.len() - 1];
-    let mean_value = numerical_values.iter().sum::<f64>() / numerical_values.len() as f64;
-    
-    // Calculate percentiles
-    let q1_value = calculate_percentile(&numerical_values, 25.0);
-    let q2_median_value = calculate_percentile(&numerical_values, 50.0);
-    let q3_value = calculate_percentile(&numerical_values, 75.0);
-    
-    // Calculate standard deviation
-    let variance = numerical_values.iter()
-        .map(|value| (value - mean_value).powi(2))
-        .sum::<f64>() / numerical_values.len() as f64;
-    let standard_deviation = variance.sqrt();
-    
-    // Calculate missing percentage
-    let total_values = column_values.len();
-    let missing_percentage = if total_values > 0 {
-        (empty_count as f64 / total_values as f64) * 100.0
-    } else {
-        0.0
-    };
-    
-    Ok(NumericalColumnStatistics {
-        min_value,
-        q1_value,
-        q2_median_value,
-        q3_value,
-        max_value,
-        mean_value,
-        standard_deviation,
-        missing_percentage,
-    })
+    Ok(())
 }
 
-/// Calculates percentile value from sorted numerical data
+/// Lists the tests each flagged row failed
 /// 
 /// # Arguments
-/// * `sorted_values` - Sorted numerical values
-/// * `percentile` - Percentile to calculate (0.0 to 100.0)
+/// * `outlier_analyses` - The outlier analyses of the columns
 /// 
 /// # Returns
-/// * `f64` - The percentile value
-fn calculate_percentile(sorted_values: &[f64], percentile: f64) -> f64 {
-    if sorted_values.is_empty() {
-        return 0.0;
+/// * `BTreeMap<usize, Vec<String>>` - Flags such as `price:extreme` per row number, in row order
+fn collect_outlier_flags_by_row_number(outlier_analyses: &[ColumnOutlierAnalysis]) -> BTreeMap<usize, Vec<String>> {
+    let mut outlier_flags_by_row_number: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    
+    for outlier_analysis in outlier_analyses {
+        let flagged_row_lists = [
+            ("mild", &outlier_analysis.mild_outlier_row_numbers),
+            ("extreme", &outlier_analysis.extreme_outlier_row_numbers),
+            ("z", &outlier_analysis.z_score_outlier_row_numbers),
+            ("modified_z", &outlier_analysis.modified_z_score_outlier_row_numbers),
+        ];
+        for (flag_name, row_numbers) in flagged_row_lists {
+            for row_number in row_numbers {
+                outlier_flags_by_row_number
+                    .entry(*row_number)
+                    .or_default()
+                    .push(format!("{}:{}", outlier_analysis.column_name, flag_name));
+            }
+        }
     }
     
-    let index = (percentile / 100.0) * (sorted_values.len() - 1) as f64;
-    let lower_index = index.floor() as usize;
-    let upper_index = index.ceil() as usize;
-    
-    if lower_index == upper_index {
-        sorted_values[lower_index]
-    } else {
-        let weight = index - lower_index as f64;
-        sorted_values[lower_index] * (1.0 - weight) + sorted_values[upper_index] * weight
-    }
+    outlier_flags_by_row_number
 }
 
-/// Calculates comprehensive categorical statistics
+/// Counts the rows flagged by at least one test in at least one column
 /// 
 /// # Arguments
-/// * `column_values` - All values from the column
+/// * `outlier_analyses` - The outlier analyses of the columns
 /// 
-/// # Returns  
-/// * `RowsAndColumnsResult<CategoricalColumnStatistics>` - Complete categorical analysis
-fn calculate_categorical_statistics(column_values: &[String]) -> RowsAndColumnsResult<CategoricalColumnStatistics> {
-    let mut value_counts = HashMap::new();
-    let mut empty_count = 0;
-    
-    // Count occurrences of each value
-    for value_string in column_values {
-        let trimmed_value = value_string.trim();
-        
-        if trimmed_value.is_empty() {
-            empty_count += 1;
-        } else {
-            *value_counts.entry(trimmed_value.to_string()).or_insert(0) += 1;
-        }
-    }
-    
-    let total_non_empty_values: usize = value_counts.values().sum();
-    let unique_value_count = value_counts.len();
-    
-    // Create frequency list sorted by count (descending)
-    let mut value_frequencies: Vec<CategoricalValueFrequency> = value_counts
-        .into_iter()
-        .map(|(value, count)| {
-            let percentage = if total_non_empty_values > 0 {
-                (count as f64 / total_non_empty_values as f64) * 100.0
-            } else {
-                0.0
-            };
-            
-            CategoricalValueFrequency {
-                value,
-                count,
-                percentage,
-            }
-        })
-        .collect();
-    
-    // Sort by count (descending)
-    value_frequencies.sort_by(|a, b| b.count.cmp(&a.count));
-    
-    // Find mode (most common value)
-    let (mode_value, mode_percentage) = if let Some(most_frequent) = value_frequencies.first() {
-        (Some(most_frequent.value.clone()), most_frequent.percentage)
-    } else {
-        (None, 0.0)
-    };
-    
-    // Calculate missing percentage
-    let total_values = column_values.len();
-    let missing_percentage = if total_values > 0 {
-        (empty_count as f64 / total_values as f64) * 100.0
-    } else {
-        0.0
-    };
-    
-    Ok(CategoricalColumnStatistics {
-        unique_value_count,
-        value_frequencies,
-        missing_percentage,
-        mode_value,
-        mode_percentage,
-    })
+/// # Returns
+/// * `usize` - Number of distinct flagged rows
+pub fn count_flagged_outlier_rows(outlier_analyses: &[ColumnOutlierAnalysis]) -> usize {
+    collect_outlier_flags_by_row_number(outlier_analyses).len()
 }
 
-/// Displays enhanced CSV analysis results with comprehensive statistics
+/// Writes every flagged row to a side CSV file for review
 /// 
-/// This function shows detailed pandas-style statistical information
-/// appropriate for each column's data type.
+/// The file sits next to the CSV file (`data.outliers.csv`) and holds the
+/// header, then each flagged row in file order, as RFC 4180 CSV preceded by
+/// two columns: `row_number` and `outlier_flags` (e.g. `price:extreme; price:z`).
 /// 
 /// # Arguments
-/// * `enhanced_analysis_results` - The enhanced statistical analysis results
+/// * `basic_analysis_results` - The basic analysis (file, dialect, ragged row policy, column names)
+/// * `outlier_analyses` - The outlier analyses of the columns
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<()>` - Success or display error
-pub fn display_enhanced_csv_analysis_results(
-    enhanced_analysis_results: &[EnhancedCsvColumnInformation]
-) -> RowsAndColumnsResult<()> {
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("  Enhanced CSV Analysis Results");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
-    
-    for (display_index, enhanced_column_info) in enhanced_analysis_results.iter().enumerate() {
+/// * `RowsAndColumnsResult<(PathBuf, usize)>` - The side file and the number of rows written
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV file cannot be read or the side file cannot be written
+/// * `RowsAndColumnsError::CsvProcessingError` - If a record cannot be parsed
+pub fn write_outlier_rows_file(
+    basic_analysis_results: &CsvAnalysisResults,
+    outlier_analyses: &[ColumnOutlierAnalysis],
+) -> RowsAndColumnsResult<(PathBuf, usize)> {
+    let outlier_rows_file_path = determine_side_file_path(&basic_analysis_results.csv_file_path, OUTLIER_ROWS_FILE_SUFFIX)?;
+    let outlier_flags_by_row_number = collect_outlier_flags_by_row_number(outlier_analyses);
+    let convert_write_error = |io_error: io::Error| {
+        create_file_system_error(
+            &format!("Failed to write outlier rows file: {}", outlier_rows_file_path.display()),
+            io_error
+        )
+    };
+    
+    let outlier_rows_file = File::create(&outlier_rows_file_path).map_err(convert_write_error)?;
+    let mut outlier_rows_writer = BufWriter::new(outlier_rows_file);
+    let mut header_fields = vec!["row_number".to_string(), "outlier_flags".to_string()];
+    header_fields.extend(
+        basic_analysis_results.column_information_list
+            .iter()
+            .map(|column_info| column_info.column_name.clone())
+    );
+    outlier_rows_writer.write_all(format_rfc4180_csv_line(&header_fields).as_bytes()).map_err(convert_write_error)?;
+    
+    let csv_record_reader = open_analyzed_csv_data_record_reader(basic_analysis_results, "outlier row export")?;
+    let mut data_row_number = 0;
+    for record_result in csv_record_reader {
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during outlier row export", io_error)
+        })?;
+        let conformed_field_values = match conform_record_fields_to_policy(
+            csv_record.field_values,
+            basic_analysis_results.total_column_count,
+            basic_analysis_results.structural_validation_report.ragged_row_policy,
+        ) {
+            Some(conformed_field_values) => conformed_field_values,
+            None => continue,
+        };
+        data_row_number += 1;
+    
+        if let Some(outlier_flags) = outlier_flags_by_row_number.get(&data_row_number) {
+            let mut outlier_row_fields = vec![data_row_number.to_string(), outlier_flags.join("; ")];
+            outlier_row_fields.extend(conformed_field_values);
+            outlier_rows_writer.write_all(format_rfc4180_csv_line(&outlier_row_fields).as_bytes()).map_err(convert_write_error)?;
+        }
+    }
+    
+    outlier_rows_writer.flush().map_err(convert_write_error)?;
+    Ok((outlier_rows_file_path, outlier_flags_by_row_number.len()))
+}
+
+/// Creates one statistics accumulator per non-ignored column
+/// 
+/// # Arguments
+/// * `basic_analysis_results` - Column types, null sentinels and statistics settings
+/// 
+/// # Returns
+/// * `Vec<ColumnStatisticsAccumulator<'_>>` - The accumulators, in column order
+fn create_column_statistics_accumulators(
+    basic_analysis_results: &CsvAnalysisResults,
+) -> Vec<ColumnStatisticsAccumulator<'_>> {
+    basic_analysis_results
+        .column_information_list
+        .iter()
+        .filter(|basic_column_info| !basic_column_info.column_ignored)
+        .map(|basic_column_info| ColumnStatisticsAccumulator::new(basic_column_info, basic_analysis_results))
+        .collect()
+}
+
+/// Turns every column accumulator into the column's statistics
+/// 
+/// # Arguments
+/// * `column_accumulators` - The fed accumulators
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>>` - The columns with their statistics
+fn finish_column_statistics_accumulators(
+    column_accumulators: Vec<ColumnStatisticsAccumulator<'_>>,
+) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
+    column_accumulators
+        .into_iter()
+        .map(ColumnStatisticsAccumulator::finish)
+        .collect()
+}
+
+/// The accumulator matching a column's field type
+enum FieldStatisticsAccumulator {
+    /// Integer, float and decimal columns (boxed: the quartile estimators are large)
+    Continuous(Box<NumericalStatisticsAccumulator>),
+    
+    /// Boolean and string columns
+    Categorical(CategoricalStatisticsAccumulator),
+    
+    /// Date, time and date-time columns
+    Temporal(TemporalStatisticsAccumulator),
+}
+
+/// Accumulates the statistics that fit one column's type, one value at a time
+struct ColumnStatisticsAccumulator<'a> {
+    /// Detected type and evidence for the column
+    basic_column_info: &'a CsvColumnInformation,
+    
+    /// Values counted as missing in this column
+    null_value_sentinels: &'a NullValueSentinels,
+    
+    /// Temporal formats and boolean vocabulary used to parse values
+    inference_settings: &'a TypeInferenceSettings,
+    
+    /// Numerical, categorical or temporal statistics
+    field_statistics_accumulator: FieldStatisticsAccumulator,
+    
+    /// Exact fixed-point sums (decimal columns only)
+    decimal_statistics_accumulator: Option<DecimalStatisticsAccumulator>,
+    
+    /// True, false and null counts (boolean columns only)
+    boolean_value_counts: Option<BooleanValueCounts>,
+}
+
+impl<'a> ColumnStatisticsAccumulator<'a> {
+    /// Creates an empty accumulator for one column
+    /// 
+    /// # Arguments
+    /// * `basic_column_info` - Detected type and evidence for the column
+    /// * `basic_analysis_results` - Null sentinels, type inference and statistics settings
+    /// 
+    /// # Returns
+    /// * `ColumnStatisticsAccumulator` - An accumulator with no values
+    fn new(basic_column_info: &'a CsvColumnInformation, basic_analysis_results: &'a CsvAnalysisResults) -> Self {
+        let detected_data_type = basic_column_info.detected_data_type.clone();
+        
+        // Determine field type (categorical vs continuous)
+        let field_statistics_accumulator = match determine_field_type(&detected_data_type) {
+            CsvFieldType::Continuous => FieldStatisticsAccumulator::Continuous(Box::new(NumericalStatisticsAccumulator::new(
+                detected_data_type.clone(),
+                &basic_analysis_results.statistics_settings,
+            ))),
+            CsvFieldType::Categorical => FieldStatisticsAccumulator::Categorical(CategoricalStatisticsAccumulator::new(
+                &basic_analysis_results.statistics_settings,
+            )),
+            CsvFieldType::Temporal => FieldStatisticsAccumulator::Temporal(TemporalStatisticsAccumulator::default()),
+        };
+        
+        ColumnStatisticsAccumulator {
+            basic_column_info,
+            null_value_sentinels: basic_analysis_results.null_value_settings
+                .sentinels_for_column(basic_column_info.column_index),
+            inference_settings: &basic_analysis_results.type_inference_summary.inference_settings,
+            field_statistics_accumulator,
+            decimal_statistics_accumulator: (detected_data_type == CsvColumnDataType::Decimal)
+                .then(|| DecimalStatisticsAccumulator::new(basic_column_info.type_tally.decimal_scale)),
+            boolean_value_counts: (detected_data_type == CsvColumnDataType::Boolean).then(BooleanValueCounts::default),
+        }
+    }
+    
+    /// Adds one raw field value of the column
+    /// 
+    /// # Arguments
+    /// * `value_string` - The field value as read from the file or dataset
+    fn add_value(&mut self, value_string: &str) {
+        match &mut self.field_statistics_accumulator {
+            FieldStatisticsAccumulator::Continuous(numerical_accumulator) => {
+                numerical_accumulator.add_value(value_string, self.null_value_sentinels);
+            }
+            FieldStatisticsAccumulator::Categorical(categorical_accumulator) => {
+                categorical_accumulator.add_value(value_string, self.null_value_sentinels);
+            }
+            FieldStatisticsAccumulator::Temporal(temporal_accumulator) => temporal_accumulator.add_value(
+                value_string,
+                &self.basic_column_info.detected_data_type,
+                &self.inference_settings.temporal_formats,
+                self.null_value_sentinels,
+            ),
+        }
+        
+        if let Some(decimal_accumulator) = &mut self.decimal_statistics_accumulator {
+            decimal_accumulator.add_value(value_string, self.null_value_sentinels);
+        }
+        if let Some(boolean_value_counts) = &mut self.boolean_value_counts {
+            boolean_value_counts.record_value(
+                value_string,
+                &self.inference_settings.boolean_vocabulary,
+                self.null_value_sentinels,
+            );
+        }
+    }
+    
+    /// Produces the column with its statistics
+    /// 
+    /// # Returns
+    /// * `RowsAndColumnsResult<EnhancedCsvColumnInformation>` - The column with its statistics
+    fn finish(self) -> RowsAndColumnsResult<EnhancedCsvColumnInformation> {
+        let (field_type, numerical_statistics, categorical_statistics, temporal_statistics) =
+            match self.field_statistics_accumulator {
+                FieldStatisticsAccumulator::Continuous(numerical_accumulator) => {
+                    (CsvFieldType::Continuous, Some(numerical_accumulator.finish()?), None, None)
+                }
+                FieldStatisticsAccumulator::Categorical(categorical_accumulator) => {
+                    (CsvFieldType::Categorical, None, Some(categorical_accumulator.finish()), None)
+                }
+                FieldStatisticsAccumulator::Temporal(temporal_accumulator) => (
+                    CsvFieldType::Temporal,
+                    None,
+                    None,
+                    Some(temporal_accumulator.finish(&self.basic_column_info.detected_data_type)?),
+                ),
+            };
+        
+        // Decimal columns also get exact fixed-point sums and means
+        let decimal_statistics = match self.decimal_statistics_accumulator {
+            Some(decimal_accumulator) => Some(decimal_accumulator.finish(
+                &self.basic_column_info.column_name,
+                self.basic_column_info.type_tally.currency_symbol,
+            )?),
+            None => None,
+        };
+        
+        // Boolean columns also get true/false/null shares
+        let boolean_statistics = self.boolean_value_counts.map(calculate_boolean_statistics);
+        
+        Ok(EnhancedCsvColumnInformation {
+            basic_info: self.basic_column_info.clone(),
+            field_type,
+            numerical_statistics,
+            categorical_statistics,
+            temporal_statistics,
+            decimal_statistics,
+            boolean_statistics,
+        })
+    }
+}
+
+/// Determines field type based on detected data type
+/// 
+/// # Arguments
+/// * `detected_data_type` - The basic data type detected
+/// 
+/// # Returns
+/// * `CsvFieldType` - Categorical, Continuous or Temporal classification
+fn determine_field_type(detected_data_type: &CsvColumnDataType) -> CsvFieldType {
+    match detected_data_type {
+        CsvColumnDataType::Integer | CsvColumnDataType::Float | CsvColumnDataType::Decimal => CsvFieldType::Continuous,
+        CsvColumnDataType::Boolean | CsvColumnDataType::String => CsvFieldType::Categorical,
+        CsvColumnDataType::Date | CsvColumnDataType::Time | CsvColumnDataType::DateTime => CsvFieldType::Temporal,
+    }
+}
+
+/// How a categorical column's values are counted
+#[derive(Debug, Clone)]
+enum CategoricalValueCounter {
+    /// Every distinct value with its count (exact mode)
+    Exact(HashMap<String, usize>),
+    
+    /// Space-Saving top values and a HyperLogLog distinct count (streaming mode)
+    Bounded(SpaceSavingTopValues, Box<HyperLogLogCounter>),
+}
+
+/// Counts the values of a categorical column
+#[derive(Debug, Clone)]
+struct CategoricalStatisticsAccumulator {
+    /// Exact or bounded value counts
+    value_counter: CategoricalValueCounter,
+    
+    /// Number of non-missing values
+    non_missing_value_count: usize,
+    
+    /// Blank and null sentinel values
+    missing_value_counts: MissingValueCounts,
+    
+    /// Number of values fed
+    total_value_count: usize,
+}
+
+impl CategoricalStatisticsAccumulator {
+    /// Creates an empty accumulator
+    /// 
+    /// # Arguments
+    /// * `statistics_settings` - Exact or bounded counting, and how many values the bounded sketch tracks
+    /// 
+    /// # Returns
+    /// * `CategoricalStatisticsAccumulator` - An accumulator with no values
+    fn new(statistics_settings: &StatisticsSettings) -> Self {
+        let value_counter = match statistics_settings.statistics_mode {
+            StatisticsMode::Exact => CategoricalValueCounter::Exact(HashMap::new()),
+            StatisticsMode::Streaming => CategoricalValueCounter::Bounded(
+                SpaceSavingTopValues::new(statistics_settings.top_value_capacity),
+                Box::default(),
+            ),
+        };
+        
+        CategoricalStatisticsAccumulator {
+            value_counter,
+            non_missing_value_count: 0,
+            missing_value_counts: MissingValueCounts::default(),
+            total_value_count: 0,
+        }
+    }
+    
+    /// Adds one raw field value
+    /// 
+    /// # Arguments
+    /// * `value_string` - The field value
+    /// * `null_value_sentinels` - Values counted as missing instead of as a category
+    fn add_value(&mut self, value_string: &str, null_value_sentinels: &NullValueSentinels) {
+        self.total_value_count += 1;
+        let trimmed_value = value_string.trim();
+        
+        if let Some(missing_value_kind) = null_value_sentinels.classify_missing_value(trimmed_value) {
+            self.missing_value_counts.record_missing_value(missing_value_kind);
+            return;
+        }
+        
+        self.non_missing_value_count += 1;
+        match &mut self.value_counter {
+            CategoricalValueCounter::Exact(value_counts) => match value_counts.get_mut(trimmed_value) {
+                Some(value_count) => *value_count += 1,
+                None => {
+                    value_counts.insert(trimmed_value.to_string(), 1);
+                }
+            },
+            CategoricalValueCounter::Bounded(top_values, distinct_value_counter) => {
+                top_values.add_value(trimmed_value);
+                distinct_value_counter.add_value(trimmed_value);
+            }
+        }
+    }
+    
+    /// Calculates comprehensive categorical statistics
+    /// 
+    /// While the bounded sketch never had to replace a value its counts are
+    /// exact and are reported as such; after that, only the tracked values are
+    /// listed, their counts are upper bounds, and the unique value count is the
+    /// HyperLogLog estimate (never below the number of tracked values).
+    /// 
+    /// # Returns  
+    /// * `CategoricalColumnStatistics` - Complete categorical analysis
+    fn finish(self) -> CategoricalColumnStatistics {
+        let total_non_empty_values = self.non_missing_value_count;
+        
+        // Each value with (count, overestimate bound), and the unique value count with its error
+        let (counted_values, unique_value_count, unique_value_count_standard_error, value_frequencies_are_estimates) =
+            match self.value_counter {
+                CategoricalValueCounter::Exact(value_counts) => {
+                    let unique_value_count = value_counts.len();
+                    let counted_values: Vec<(String, usize, usize)> = value_counts
+                        .into_iter()
+                        .map(|(value, count)| (value, count, 0))
+                        .collect();
+                    (counted_values, unique_value_count, None, false)
+                }
+                CategoricalValueCounter::Bounded(top_values, distinct_value_counter) => {
+                    let counts_are_exact = top_values.is_exact();
+                    let tracked_value_count = top_values.tracked_value_count();
+                    let counted_values: Vec<(String, usize, usize)> = top_values
+                        .into_values_by_descending_count()
+                        .into_iter()
+                        .map(|(value, tracked_count)| {
+                            (value, tracked_count.estimated_count, tracked_count.overestimate_bound)
+                        })
+                        .collect();
+                    if counts_are_exact {
+                        (counted_values, tracked_value_count, None, false)
+                    } else {
+                        let estimated_unique_value_count = (distinct_value_counter.estimate().round() as usize)
+                            .max(tracked_value_count);
+                        (
+                            counted_values,
+                            estimated_unique_value_count,
+                            Some(distinct_value_counter.relative_standard_error()),
+                            true,
+                        )
+                    }
+                }
+            };
+        
+        // Create frequency list sorted by count (descending)
+        let mut value_frequencies: Vec<CategoricalValueFrequency> = counted_values
+            .into_iter()
+            .map(|(value, count, count_overestimate_bound)| {
+                let percentage = if total_non_empty_values > 0 {
+                    (count as f64 / total_non_empty_values as f64) * 100.0
+                } else {
+                    0.0
+                };
+                
+                CategoricalValueFrequency {
+                    value,
+                    count,
+                    percentage,
+                    count_overestimate_bound,
+                }
+            })
+            .collect();
+        
+        // Sort by count (descending)
+        value_frequencies.sort_by(|a, b| b.count.cmp(&a.count));
+        
+        // Find mode (most common value)
+        let (mode_value, mode_percentage) = if let Some(most_frequent) = value_frequencies.first() {
+            (Some(most_frequent.value.clone()), most_frequent.percentage)
+        } else {
+            (None, 0.0)
+        };
+        
+        CategoricalColumnStatistics {
+            unique_value_count,
+            unique_value_count_standard_error,
+            value_frequencies,
+            value_frequencies_are_estimates,
+            missing_percentage: self.missing_value_counts.missing_percentage(self.total_value_count),
+            missing_value_counts: self.missing_value_counts,
+            mode_value,
+            mode_percentage,
+        }
+    }
+}
+
+/// Calculates the true, false and null shares of a boolean column
+/// 
+/// # Arguments
+/// * `boolean_value_counts` - True, false and missing counts of the column
+/// 
+/// # Returns
+/// * `BooleanColumnStatistics` - The shares and the counts behind them
+fn calculate_boolean_statistics(boolean_value_counts: BooleanValueCounts) -> BooleanColumnStatistics {
+    let total_value_count = boolean_value_counts.total_value_count().max(1) as f64;
+    BooleanColumnStatistics {
+        true_ratio: boolean_value_counts.true_value_count as f64 / total_value_count,
+        false_ratio: boolean_value_counts.false_value_count as f64 / total_value_count,
+        null_ratio: boolean_value_counts.null_value_count() as f64 / total_value_count,
+        boolean_value_counts,
+    }
+}
+
+/// Running exact sum, minimum and maximum of a decimal column
+#[derive(Debug, Clone)]
+struct DecimalStatisticsAccumulator {
+    /// Scale every value is brought to before summing (grows when a wider value arrives)
+    decimal_scale: u32,
+    
+    /// Number of values that parsed as decimals
+    parsed_value_count: usize,
+    
+    /// Sum of the scaled values (None once a value or the sum left the 128-bit range)
+    exact_sum: Option<i128>,
+    
+    /// Smallest and largest scaled value
+    minimum_and_maximum: Option<(i128, i128)>,
+}
+
+impl DecimalStatisticsAccumulator {
+    /// Creates an empty accumulator
+    /// 
+    /// # Arguments
+    /// * `decimal_scale` - Starting scale (the widest seen during type detection or stored in metadata)
+    /// 
+    /// # Returns
+    /// * `DecimalStatisticsAccumulator` - An accumulator with no values
+    fn new(decimal_scale: u32) -> Self {
+        DecimalStatisticsAccumulator {
+            decimal_scale,
+            parsed_value_count: 0,
+            exact_sum: Some(0),
+            minimum_and_maximum: None,
+        }
+    }
+    
+    /// Adds one raw field value (missing and unparseable values are left out)
+    /// 
+    /// # Arguments
+    /// * `value_string` - The field value
+    /// * `null_value_sentinels` - Values left out of the sums
+    fn add_value(&mut self, value_string: &str, null_value_sentinels: &NullValueSentinels) {
+        let trimmed_value = value_string.trim();
+        if null_value_sentinels.is_missing_value(trimmed_value) {
+            return;
+        }
+        let Some(parsed_decimal_value) = parse_decimal_value(trimmed_value) else {
+            return;
+        };
+        
+        // A value with more fraction digits than seen so far (e.g. after the type sample) widens the scale
+        if parsed_decimal_value.decimal_value.scale > self.decimal_scale
+            && !self.widen_decimal_scale(parsed_decimal_value.decimal_value.scale)
+        {
+            self.exact_sum = None;
+            return;
+        }
+        let Some(scaled_value) = parsed_decimal_value.decimal_value.rescaled(self.decimal_scale) else {
+            self.exact_sum = None;
+            return;
+        };
+        let scaled_value = scaled_value.unscaled_value;
+        self.parsed_value_count += 1;
+        self.exact_sum = self.exact_sum.and_then(|running_sum| running_sum.checked_add(scaled_value));
+        self.minimum_and_maximum = Some(match self.minimum_and_maximum {
+            Some((minimum_value, maximum_value)) => (minimum_value.min(scaled_value), maximum_value.max(scaled_value)),
+            None => (scaled_value, scaled_value),
+        });
+    }
+    
+    /// Brings the running sum, minimum and maximum to a larger scale
+    /// 
+    /// # Arguments
+    /// * `wider_decimal_scale` - The new scale (larger than the current one)
+    /// 
+    /// # Returns
+    /// * `bool` - False if a running value left the 128-bit range (nothing is changed then)
+    fn widen_decimal_scale(&mut self, wider_decimal_scale: u32) -> bool {
+        let current_decimal_scale = self.decimal_scale;
+        let widen = |unscaled_value: i128| {
+            FixedPointDecimal { unscaled_value, scale: current_decimal_scale }
+                .rescaled(wider_decimal_scale)
+                .map(|rescaled_value| rescaled_value.unscaled_value)
+        };
+        
+        let Some(widened_sum) = self.exact_sum.and_then(widen) else {
+            return false;
+        };
+        let widened_minimum_and_maximum = match self.minimum_and_maximum {
+            Some((minimum_value, maximum_value)) => match (widen(minimum_value), widen(maximum_value)) {
+                (Some(widened_minimum), Some(widened_maximum)) => Some((widened_minimum, widened_maximum)),
+                _ => return false,
+            },
+            None => None,
+        };
+        
+        self.decimal_scale = wider_decimal_scale;
+        self.exact_sum = Some(widened_sum);
+        self.minimum_and_maximum = widened_minimum_and_maximum;
+        true
+    }
+    
+    /// Calculates exact sum, mean, minimum and maximum for the column
+    /// 
+    /// # Arguments
+    /// * `column_name` - Column name (for error messages)
+    /// * `currency_symbol` - Currency symbol used for display
+    /// 
+    /// # Returns
+    /// * `RowsAndColumnsResult<DecimalColumnStatistics>` - Exact decimal analysis
+    /// 
+    /// # Errors
+    /// * `RowsAndColumnsError::StatisticalAnalysisError` - If no value parses or the sum overflows
+    fn finish(self, column_name: &str, currency_symbol: Option<char>) -> RowsAndColumnsResult<DecimalColumnStatistics> {
+        let exact_sum = self.exact_sum.ok_or_else(|| {
+            create_statistical_analysis_error("Decimal values exceed the exact 128-bit range", column_name)
+        })?;
+        let Some((minimum_value, maximum_value)) = self.minimum_and_maximum else {
+            return Err(create_statistical_analysis_error("No valid decimal values found", column_name));
+        };
+        let rounded_mean = divide_rounding_half_away_from_zero(exact_sum, self.parsed_value_count as i128);
+        
+        let decimal_scale = self.decimal_scale;
+        let at_column_scale = |unscaled_value| FixedPointDecimal { unscaled_value, scale: decimal_scale };
+        Ok(DecimalColumnStatistics {
+            decimal_scale,
+            currency_symbol,
+            parsed_value_count: self.parsed_value_count,
+            exact_sum: at_column_scale(exact_sum),
+            rounded_mean: at_column_scale(rounded_mean),
+            minimum_value: at_column_scale(minimum_value),
+            maximum_value: at_column_scale(maximum_value),
+        })
+    }
+}
+
+/// Running extremes and per-day (or per-hour) counts of a date/time column
+#[derive(Debug, Clone, Default)]
+struct TemporalStatisticsAccumulator {
+    /// Earliest value as (position on the numeric scale, day since epoch or hour of day)
+    earliest_value: Option<(f64, i64)>,
+    
+    /// Latest value, like the earliest
+    latest_value: Option<(f64, i64)>,
+    
+    /// Values per day since the epoch (per hour of day for time columns)
+    value_counts_by_bucket_source: BTreeMap<i64, usize>,
+    
+    /// Blank, null sentinel and unparseable values
+    missing_value_counts: MissingValueCounts,
+    
+    /// Number of values fed
+    total_value_count: usize,
+}
+
+impl TemporalStatisticsAccumulator {
+    /// Adds one raw field value
+    /// 
+    /// # Arguments
+    /// * `value_string` - The field value
+    /// * `detected_data_type` - Date, Time or DateTime
+    /// * `temporal_formats` - Configured formats accepted besides ISO-8601
+    /// * `null_value_sentinels` - Values counted as missing instead of parsed
+    fn add_value(
+        &mut self,
+        value_string: &str,
+        detected_data_type: &CsvColumnDataType,
+        temporal_formats: &TemporalFormatSet,
+        null_value_sentinels: &NullValueSentinels,
+    ) {
+        self.total_value_count += 1;
+        let trimmed_value = value_string.trim();
+        
+        if let Some(missing_value_kind) = null_value_sentinels.classify_missing_value(trimmed_value) {
+            self.missing_value_counts.record_missing_value(missing_value_kind);
+            return;
+        }
+        
+        let parsed_temporal_value = match detected_data_type {
+            CsvColumnDataType::Date => parse_date_value(trimmed_value, temporal_formats).map(|calendar_date| {
+                let days_since_unix_epoch = calendar_date.days_since_unix_epoch();
+                (days_since_unix_epoch as f64, days_since_unix_epoch)
+            }),
+            CsvColumnDataType::Time => parse_time_value(trimmed_value, temporal_formats).map(|time_of_day| {
+                (time_of_day.seconds_since_midnight(), i64::from(time_of_day.hour))
+            }),
+            _ => parse_datetime_value(trimmed_value, temporal_formats).map(|calendar_date_time| {
+                let seconds_since_unix_epoch = calendar_date_time.seconds_since_unix_epoch();
+                (seconds_since_unix_epoch, (seconds_since_unix_epoch / SECONDS_PER_DAY).floor() as i64)
+            }),
+        };
+        
+        let Some(parsed_temporal_value) = parsed_temporal_value else {
+            self.missing_value_counts.type_invalid_count += 1;
+            return;
+        };
+        
+        if self.earliest_value.is_none_or(|earliest_value| parsed_temporal_value.0 < earliest_value.0) {
+            self.earliest_value = Some(parsed_temporal_value);
+        }
+        if self.latest_value.is_none_or(|latest_value| parsed_temporal_value.0 > latest_value.0) {
+            self.latest_value = Some(parsed_temporal_value);
+        }
+        *self.value_counts_by_bucket_source.entry(parsed_temporal_value.1).or_insert(0) += 1;
+    }
+    
+    /// Calculates earliest, latest, range and a period histogram for the column
+    /// 
+    /// The histogram bucket size follows the range: days for up to two months,
+    /// months for up to five years, years beyond that, and hours of the day for
+    /// time-only columns.
+    /// 
+    /// # Arguments
+    /// * `detected_data_type` - Date, Time or DateTime
+    /// 
+    /// # Returns
+    /// * `RowsAndColumnsResult<TemporalColumnStatistics>` - Complete temporal analysis
+    fn finish(self, detected_data_type: &CsvColumnDataType) -> RowsAndColumnsResult<TemporalColumnStatistics> {
+        let (Some((earliest_position, earliest_bucket_source)), Some((latest_position, latest_bucket_source))) =
+            (self.earliest_value, self.latest_value)
+        else {
+            return Err(create_csv_processing_error(
+                "No valid date or time values found for statistical analysis",
+                None,
+                None
+            ));
+        };
+        
+        // Format the extremes and the range on the column's own scale
+        let (earliest_value_text, latest_value_text, range_text) = match detected_data_type {
+            CsvColumnDataType::Date => {
+                let spanned_day_count = (latest_position - earliest_position) as i64;
+                (
+                    CalendarDate::from_days_since_unix_epoch(earliest_bucket_source).to_string(),
+                    CalendarDate::from_days_since_unix_epoch(latest_bucket_source).to_string(),
+                    format!("{} days", spanned_day_count),
+                )
+            }
+            CsvColumnDataType::Time => (
+                format_seconds_since_midnight(earliest_position),
+                format_seconds_since_midnight(latest_position),
+                format_duration_seconds(latest_position - earliest_position),
+            ),
+            _ => (
+                format_seconds_since_unix_epoch(earliest_position),
+                format_seconds_since_unix_epoch(latest_position),
+                format_duration_seconds(latest_position - earliest_position),
+            ),
+        };
+        
+        // Count values per bucket, keeping empty buckets between the extremes
+        let histogram_granularity = match detected_data_type {
+            CsvColumnDataType::Time => TemporalHistogramGranularity::HourOfDay,
+            _ => TemporalHistogramGranularity::for_spanned_days(latest_bucket_source - earliest_bucket_source),
+        };
+        let first_bucket_index = histogram_granularity.bucket_index_for_day(earliest_bucket_source);
+        let last_bucket_index = histogram_granularity.bucket_index_for_day(latest_bucket_source);
+        let mut bucket_counts = vec![0usize; (last_bucket_index - first_bucket_index + 1) as usize];
+        
+        for (bucket_source, value_count) in &self.value_counts_by_bucket_source {
+            let bucket_index = histogram_granularity.bucket_index_for_day(*bucket_source);
+            bucket_counts[(bucket_index - first_bucket_index) as usize] += value_count;
+        }
+        
+        let histogram_buckets = bucket_counts
+            .into_iter()
+            .enumerate()
+            .map(|(bucket_offset, value_count)| TemporalHistogramBucket {
+                bucket_label: histogram_granularity.bucket_label(first_bucket_index + bucket_offset as i64),
+                value_count,
+            })
+            .collect();
+        
+        Ok(TemporalColumnStatistics {
+            earliest_value_text,
+            latest_value_text,
+            range_text,
+            histogram_granularity,
+            histogram_buckets,
+            missing_percentage: self.missing_value_counts.missing_percentage(self.total_value_count),
+            missing_value_counts: self.missing_value_counts,
+        })
+    }
+}
+
+/// Displays enhanced CSV analysis results with comprehensive statistics
+/// 
+/// This function shows detailed pandas-style statistical information
+/// appropriate for each column's data type.
+/// 
+/// # Arguments
+/// * `enhanced_analysis_results` - The enhanced statistical analysis results
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or display error
+pub fn display_enhanced_csv_analysis_results(
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation]
+) -> RowsAndColumnsResult<()> {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Enhanced CSV Analysis Results");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!();
+    
+    for (display_index, enhanced_column_info) in enhanced_analysis_results.iter().enumerate() {
         let display_number = display_index + 1;
         let basic_info = &enhanced_column_info.basic_info;
         
@@ -1086,6 +2840,7 @@ pub fn display_enhanced_csv_analysis_results(
             match enhanced_column_info.field_type {
                 CsvFieldType::Categorical => "categorical",
                 CsvFieldType::Continuous => "continuous",
+                CsvFieldType::Temporal => "temporal",
             }
         );
         
@@ -1095,12 +2850,23 @@ pub fn display_enhanced_csv_analysis_results(
                 if let Some(numerical_stats) = &enhanced_column_info.numerical_statistics {
                     display_numerical_statistics(numerical_stats);
                 }
+                if let Some(decimal_stats) = &enhanced_column_info.decimal_statistics {
+                    display_decimal_statistics(decimal_stats);
+                }
             }
             CsvFieldType::Categorical => {
+                if let Some(boolean_stats) = &enhanced_column_info.boolean_statistics {
+                    display_boolean_statistics(boolean_stats);
+                }
                 if let Some(categorical_stats) = &enhanced_column_info.categorical_statistics {
                     display_categorical_statistics(categorical_stats);
                 }
             }
+            CsvFieldType::Temporal => {
+                if let Some(temporal_stats) = &enhanced_column_info.temporal_statistics {
+                    display_temporal_statistics(temporal_stats);
+                }
+            }
         }
         
         println!();
@@ -1118,6 +2884,12 @@ pub fn display_enhanced_csv_analysis_results(
 /// * `numerical_stats` - The numerical statistics to display
 fn display_numerical_statistics(numerical_stats: &NumericalColumnStatistics) {
     println!("   Field-type: continuous");
+    println!("   count: {}    sum: {:.3}    zeros: {}    negatives: {}",
+        numerical_stats.value_count,
+        numerical_stats.value_sum,
+        numerical_stats.zero_value_count,
+        numerical_stats.negative_value_count
+    );
     println!("   min: {:.3}    q1: {:.3}    q2: {:.3}    q3: {:.3}    max: {:.3}", 
         numerical_stats.min_value,
         numerical_stats.q1_value,
@@ -1125,11 +2897,93 @@ fn display_numerical_statistics(numerical_stats: &NumericalColumnStatistics) {
         numerical_stats.q3_value,
         numerical_stats.max_value
     );
-    println!("   mean: {:.3}    stdev: {:.3}", 
+    println!("   mean: {:.3}    stdev: {:.3}    variance: {:.3}    (ddof {})", 
         numerical_stats.mean_value,
-        numerical_stats.standard_deviation
+        numerical_stats.standard_deviation,
+        numerical_stats.variance,
+        numerical_stats.delta_degrees_of_freedom
+    );
+    let coefficient_of_variation_text = match numerical_stats.coefficient_of_variation {
+        Some(coefficient_of_variation) => format!("{:.3}", coefficient_of_variation),
+        None => "n/a (mean is 0)".to_string(),
+    };
+    println!("   IQR: {:.3}    range: {:.3}    CV: {}",
+        numerical_stats.interquartile_range,
+        numerical_stats.value_range,
+        coefficient_of_variation_text
+    );
+    println!("   skewness: {:.3}    excess kurtosis: {:.3}",
+        numerical_stats.skewness,
+        numerical_stats.excess_kurtosis
+    );
+    if !numerical_stats.percentile_values.is_empty() {
+        let percentile_texts: Vec<String> = numerical_stats.percentile_values
+            .iter()
+            .map(|percentile_value| format!("p{}: {:.3}", percentile_value.percentile, percentile_value.percentile_value))
+            .collect();
+        let percentile_method_text = if numerical_stats.percentiles_are_estimates {
+            "P² estimates".to_string()
+        } else {
+            format!("{} interpolation", numerical_stats.percentile_interpolation.to_toml_string())
+        };
+        println!("   {}    ({})", percentile_texts.join("    "), percentile_method_text);
+    }
+    display_missing_value_breakdown(numerical_stats.missing_percentage, &numerical_stats.missing_value_counts);
+}
+
+/// Displays the missing percentage with blanks, null sentinels and invalid values apart
+/// 
+/// # Arguments
+/// * `missing_percentage` - Share of the column without a usable value
+/// * `missing_value_counts` - The counts behind the percentage
+fn display_missing_value_breakdown(missing_percentage: f64, missing_value_counts: &MissingValueCounts) {
+    if missing_value_counts.total_missing_count() == 0 {
+        println!("   %missing: {:.1}%", missing_percentage);
+    } else {
+        println!("   %missing: {:.1}% (blank: {}, null sentinel: {}, type-invalid: {})",
+            missing_percentage,
+            missing_value_counts.blank_value_count,
+            missing_value_counts.null_sentinel_count,
+            missing_value_counts.type_invalid_count
+        );
+    }
+}
+
+/// Displays the true, false and null shares of a boolean column
+/// 
+/// # Arguments
+/// * `boolean_stats` - The boolean statistics to display
+fn display_boolean_statistics(boolean_stats: &BooleanColumnStatistics) {
+    let boolean_value_counts = &boolean_stats.boolean_value_counts;
+    println!("   true: {:.1}% ({})    false: {:.1}% ({})    null: {:.1}% ({})",
+        boolean_stats.true_ratio * 100.0,
+        boolean_value_counts.true_value_count,
+        boolean_stats.false_ratio * 100.0,
+        boolean_value_counts.false_value_count,
+        boolean_stats.null_ratio * 100.0,
+        boolean_value_counts.null_value_count()
+    );
+    if boolean_value_counts.missing_value_counts.type_invalid_count > 0 {
+        println!("   Not in the true/false vocabulary: {}", boolean_value_counts.missing_value_counts.type_invalid_count);
+    }
+}
+
+/// Displays exact decimal statistics
+/// 
+/// # Arguments
+/// * `decimal_stats` - The decimal statistics to display
+fn display_decimal_statistics(decimal_stats: &DecimalColumnStatistics) {
+    let currency_symbol = decimal_stats.currency_symbol;
+    println!("   exact (scale {}): sum: {}    mean: {}",
+        decimal_stats.decimal_scale,
+        decimal_stats.exact_sum.format_for_display(currency_symbol),
+        decimal_stats.rounded_mean.format_for_display(currency_symbol)
+    );
+    println!("   exact min: {}    exact max: {}    values: {}",
+        decimal_stats.minimum_value.format_for_display(currency_symbol),
+        decimal_stats.maximum_value.format_for_display(currency_symbol),
+        decimal_stats.parsed_value_count
     );
-    println!("   %missing: {:.1}%", numerical_stats.missing_percentage);
 }
 
 /// Displays categorical statistics with value distribution
@@ -1138,31 +2992,302 @@ fn display_numerical_statistics(numerical_stats: &NumericalColumnStatistics) {
 /// * `categorical_stats` - The categorical statistics to display
 fn display_categorical_statistics(categorical_stats: &CategoricalColumnStatistics) {
     println!("   Field-type: categorical");
-    println!("   Unique values: {}", categorical_stats.unique_value_count);
-    println!("   %missing: {:.1}%", categorical_stats.missing_percentage);
+    match categorical_stats.unique_value_count_standard_error {
+        Some(standard_error) => println!(
+            "   Unique values: ≈{} (estimate, ±{:.1}% standard error)",
+            categorical_stats.unique_value_count,
+            standard_error * 100.0
+        ),
+        None => println!("   Unique values: {}", categorical_stats.unique_value_count),
+    }
+    display_missing_value_breakdown(categorical_stats.missing_percentage, &categorical_stats.missing_value_counts);
     
+    let estimate_marker = if categorical_stats.value_frequencies_are_estimates { "≤" } else { "" };
     if let Some(mode_value) = &categorical_stats.mode_value {
-        println!("   Mode: {} ({:.1}%)", mode_value, categorical_stats.mode_percentage);
+        println!("   Mode: {} ({}{:.1}%)", mode_value, estimate_marker, categorical_stats.mode_percentage);
     }
     
-    println!("   Value Distribution:");
+    if categorical_stats.value_frequencies_are_estimates {
+        println!("   Value Distribution (estimated top values; each count may be overstated):");
+    } else {
+        println!("   Value Distribution:");
+    }
     
     // Show top values (limit to 5 for display)
     let display_limit = 5.min(categorical_stats.value_frequencies.len());
     for value_freq in categorical_stats.value_frequencies.iter().take(display_limit) {
-        println!("     {}: {:.1}% ({} values)", 
-            value_freq.value, 
-            value_freq.percentage, 
-            value_freq.count
-        );
+        if categorical_stats.value_frequencies_are_estimates {
+            println!("     {}: ≤{:.1}% ({} values, overstated by at most {})",
+                value_freq.value,
+                value_freq.percentage,
+                value_freq.count,
+                value_freq.count_overestimate_bound
+            );
+        } else {
+            println!("     {}: {:.1}% ({} values)", 
+                value_freq.value, 
+                value_freq.percentage, 
+                value_freq.count
+            );
+        }
     }
     
     // Show summary if there are more values
     if categorical_stats.value_frequencies.len() > display_limit {
         let remaining_count = categorical_stats.value_frequencies.len() - display_limit;
-        println!("     ... (showing top {} of {} unique values)", 
+        let unique_count_marker = if categorical_stats.unique_value_count_standard_error.is_some() { "≈" } else { "" };
+        println!("     ... (showing top {} of {}{} unique values)", 
             display_limit, 
+            unique_count_marker,
             categorical_stats.unique_value_count
         );
     }
-}
\ No newline at end of file
+}
+
+/// Displays date/time statistics with a histogram by period
+/// 
+/// # Arguments
+/// * `temporal_stats` - The temporal statistics to display
+fn display_temporal_statistics(temporal_stats: &TemporalColumnStatistics) {
+    println!("   Field-type: temporal");
+    println!("   earliest: {}    latest: {}", temporal_stats.earliest_value_text, temporal_stats.latest_value_text);
+    println!("   range: {}", temporal_stats.range_text);
+    display_missing_value_breakdown(temporal_stats.missing_percentage, &temporal_stats.missing_value_counts);
+    
+    println!("   Histogram by {}:", temporal_stats.histogram_granularity.display_name());
+    
+    // Show the first buckets (limit to keep the terminal readable)
+    let display_limit = TEMPORAL_HISTOGRAM_DISPLAY_LIMIT.min(temporal_stats.histogram_buckets.len());
+    let largest_bucket_count = temporal_stats.histogram_buckets
+        .iter()
+        .map(|histogram_bucket| histogram_bucket.value_count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    
+    for histogram_bucket in temporal_stats.histogram_buckets.iter().take(display_limit) {
+        let bar_length = (histogram_bucket.value_count * TEMPORAL_HISTOGRAM_BAR_WIDTH).div_ceil(largest_bucket_count);
+        println!("     {:<10} {:>6} {}",
+            histogram_bucket.bucket_label,
+            histogram_bucket.value_count,
+            "#".repeat(bar_length)
+        );
+    }
+    
+    if temporal_stats.histogram_buckets.len() > display_limit {
+        println!("     ... (showing first {} of {} buckets)",
+            display_limit,
+            temporal_stats.histogram_buckets.len()
+        );
+    }
+}
+
+/// Displays the outlier analysis of every numerical column
+/// 
+/// # Arguments
+/// * `outlier_analyses` - The outlier analyses to display
+pub fn display_outlier_analysis_results(outlier_analyses: &[ColumnOutlierAnalysis]) {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Outlier Analysis (rows are data rows, counted from 1)");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!();
+    
+    if outlier_analyses.is_empty() {
+        println!("  No numerical columns to test.");
+        println!();
+        return;
+    }
+    
+    for (display_index, outlier_analysis) in outlier_analyses.iter().enumerate() {
+        let outlier_detection_rules = &outlier_analysis.outlier_detection_rules;
+        println!("{}. {}{}",
+            display_index + 1,
+            outlier_analysis.column_name,
+            if outlier_analysis.rules_are_estimates { "    (quartiles, median and MAD are P² estimates)" } else { "" }
+        );
+        if let Some(tukey_fences) = &outlier_detection_rules.tukey_fences {
+            println!("   Tukey fences: inner {:.3} … {:.3}    outer {:.3} … {:.3}",
+                tukey_fences.lower_inner_fence,
+                tukey_fences.upper_inner_fence,
+                tukey_fences.lower_outer_fence,
+                tukey_fences.upper_outer_fence
+            );
+            if let (Some(lower_whisker_value), Some(upper_whisker_value)) =
+                (outlier_analysis.lower_whisker_value, outlier_analysis.upper_whisker_value)
+            {
+                println!("   whiskers: {:.3} … {:.3}", lower_whisker_value, upper_whisker_value);
+            }
+            display_outlier_row_numbers("mild (1.5×IQR)", &outlier_analysis.mild_outlier_row_numbers);
+            display_outlier_row_numbers("extreme (3×IQR)", &outlier_analysis.extreme_outlier_row_numbers);
+        } else {
+            println!("   Tukey fences: n/a (IQR is 0)");
+        }
+    
+        if outlier_detection_rules.standard_deviation > 0.0 {
+            display_outlier_row_numbers(
+                &format!("|z| > {} (mean {:.3}, stdev {:.3})",
+                    Z_SCORE_OUTLIER_THRESHOLD,
+                    outlier_detection_rules.mean_value,
+                    outlier_detection_rules.standard_deviation
+                ),
+                &outlier_analysis.z_score_outlier_row_numbers,
+            );
+        } else {
+            println!("   z-score: n/a (stdev is 0 or undefined)");
+        }
+    
+        if outlier_detection_rules.median_absolute_deviation > 0.0 {
+            display_outlier_row_numbers(
+                &format!("|modified z| > {} (median {:.3}, MAD {:.3})",
+                    MODIFIED_Z_SCORE_OUTLIER_THRESHOLD,
+                    outlier_detection_rules.median_value,
+                    outlier_detection_rules.median_absolute_deviation
+                ),
+                &outlier_analysis.modified_z_score_outlier_row_numbers,
+            );
+        } else {
+            println!("   modified z-score: n/a (MAD is 0)");
+        }
+        println!();
+    }
+    
+    println!("═══════════════════════════════════════════════════════════════");
+    println!();
+}
+
+/// Displays the rows flagged by one outlier test, shortening long lists
+/// 
+/// # Arguments
+/// * `test_description` - The test, e.g. "extreme (3×IQR)"
+/// * `row_numbers` - The flagged rows
+fn display_outlier_row_numbers(test_description: &str, row_numbers: &[usize]) {
+    if row_numbers.is_empty() {
+        println!("   {}: none", test_description);
+        return;
+    }
+    
+    let shown_row_numbers: Vec<String> = row_numbers
+        .iter()
+        .take(OUTLIER_ROW_NUMBER_DISPLAY_LIMIT)
+        .map(|row_number| row_number.to_string())
+        .collect();
+    let more_rows_text = if row_numbers.len() > OUTLIER_ROW_NUMBER_DISPLAY_LIMIT {
+        format!(", … (+{} more)", row_numbers.len() - OUTLIER_ROW_NUMBER_DISPLAY_LIMIT)
+    } else {
+        String::new()
+    };
+    println!("   {}: {} row(s): {}{}", test_description, row_numbers.len(), shown_row_numbers.join(", "), more_rows_text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_outlier_analysis_flags_rows_of_a_small_file() {
+        let test_directory = std::env::temp_dir().join(format!("{}_outlier_analysis_test", std::process::id()));
+        fs::create_dir_all(&test_directory).expect("test directory should be creatable");
+        let csv_file_path = test_directory.join("measurements.csv");
+        fs::write(
+            &csv_file_path,
+            "id,reading,constant\n1,10,5\n2,11,5\n3,12,5\n4,13,5\n5,14,5\n6,15,5\n7,100,5\n",
+        )
+        .expect("test CSV should be writable");
+
+        let basic_analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should work");
+        let enhanced_analysis_results =
+            perform_enhanced_statistical_analysis(&csv_file_path, &basic_analysis_results).expect("statistics should work");
+        let outlier_analyses =
+            perform_outlier_analysis(&basic_analysis_results, &enhanced_analysis_results).expect("outlier analysis should work");
+        let find_analysis = |column_name: &str| {
+            outlier_analyses
+                .iter()
+                .find(|outlier_analysis| outlier_analysis.column_name == column_name)
+                .expect("numerical column should be analyzed")
+        };
+
+        // reading: exact quartiles 11.5 and 14.5, so the outer fences are 2.5 and 23.5
+        let reading_analysis = find_analysis("reading");
+        assert!(!reading_analysis.rules_are_estimates);
+        assert!(reading_analysis.mild_outlier_row_numbers.is_empty());
+        assert_eq!(reading_analysis.extreme_outlier_row_numbers, vec![7]);
+        assert_eq!((reading_analysis.lower_whisker_value, reading_analysis.upper_whisker_value), (Some(10.0), Some(15.0)));
+
+        // id: evenly spread, nothing flagged; constant: IQR 0, so no fences at all
+        let id_analysis = find_analysis("id");
+        assert!(id_analysis.mild_outlier_row_numbers.is_empty() && id_analysis.extreme_outlier_row_numbers.is_empty());
+        let constant_analysis = find_analysis("constant");
+        assert_eq!(constant_analysis.outlier_detection_rules.tukey_fences, None);
+        assert!(constant_analysis.extreme_outlier_row_numbers.is_empty());
+
+        let (outlier_rows_file_path, written_row_count) =
+            write_outlier_rows_file(&basic_analysis_results, &outlier_analyses).expect("side file should be writable");
+        assert_eq!(written_row_count, 1);
+        let outlier_rows_text = fs::read_to_string(&outlier_rows_file_path).expect("side file should be readable");
+        let outlier_rows: Vec<&str> = outlier_rows_text.lines().collect();
+        assert_eq!(outlier_rows.len(), 2);
+        assert!(outlier_rows[1].starts_with("7,") && outlier_rows[1].contains("reading:extreme"), "{}", outlier_rows[1]);
+
+        fs::remove_dir_all(&test_directory).expect("test directory should be removable");
+    }
+
+    #[test]
+    fn test_decimal_scale_widens_after_the_type_sample() {
+        let test_directory = std::env::temp_dir().join(format!("{}_decimal_scale_widening_test", std::process::id()));
+        fs::create_dir_all(&test_directory).expect("test directory should be creatable");
+        let csv_file_path = test_directory.join("prices.csv");
+        let mut csv_text = String::from("price\n");
+        for whole_amount in 1..=12 {
+            csv_text.push_str(&format!("${}.50\n", whole_amount));
+        }
+        // Past the type detection sample, so the sampled scale is still 2
+        csv_text.push_str("$3.125\n");
+        fs::write(&csv_file_path, csv_text).expect("test CSV should be writable");
+
+        let basic_analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should work");
+        assert_eq!(basic_analysis_results.column_information_list[0].type_tally.decimal_scale, 2);
+        let enhanced_analysis_results =
+            perform_enhanced_statistical_analysis(&csv_file_path, &basic_analysis_results).expect("statistics should work");
+        let decimal_statistics = enhanced_analysis_results[0]
+            .decimal_statistics
+            .as_ref()
+            .expect("price should have decimal statistics");
+
+        assert_eq!(decimal_statistics.decimal_scale, 3);
+        assert_eq!(decimal_statistics.parsed_value_count, 13);
+        assert_eq!(decimal_statistics.exact_sum.to_string(), "87.125");
+        assert_eq!(decimal_statistics.rounded_mean.to_string(), "6.702");
+        assert_eq!(decimal_statistics.minimum_value.to_string(), "1.500");
+        assert_eq!(decimal_statistics.maximum_value.to_string(), "12.500");
+
+        fs::remove_dir_all(&test_directory).expect("test directory should be removable");
+    }
+
+    #[test]
+    fn test_decimal_scale_stored_in_metadata_is_the_starting_scale() {
+        let test_directory = std::env::temp_dir().join(format!("{}_stored_decimal_scale_test", std::process::id()));
+        fs::create_dir_all(&test_directory).expect("test directory should be creatable");
+        let csv_file_path = test_directory.join("amounts.csv");
+        fs::write(&csv_file_path, "amount\n1\n2\n3\n").expect("test CSV should be writable");
+        let metadata_file_path = determine_metadata_file_path(&csv_file_path).expect("metadata path should resolve");
+        fs::write(&metadata_file_path, "[column_1]\ndata_type = \"decimal\"\ndecimal_scale = 2\n")
+            .expect("test metadata should be writable");
+
+        let basic_analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should work");
+        let amount_column_info = &basic_analysis_results.column_information_list[0];
+        assert_eq!(amount_column_info.detected_data_type, CsvColumnDataType::Decimal);
+        assert_eq!(amount_column_info.type_tally.decimal_scale, 2);
+
+        let enhanced_analysis_results =
+            perform_enhanced_statistical_analysis(&csv_file_path, &basic_analysis_results).expect("statistics should work");
+        let decimal_statistics = enhanced_analysis_results[0]
+            .decimal_statistics
+            .as_ref()
+            .expect("amount should have decimal statistics");
+        assert_eq!(decimal_statistics.decimal_scale, 2);
+        assert_eq!(decimal_statistics.exact_sum.to_string(), "6.00");
+
+        fs::remove_dir_all(&test_directory).expect("test directory should be removable");
+    }
+}
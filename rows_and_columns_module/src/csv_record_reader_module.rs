@@ -0,0 +1,891 @@
+// src/csv_record_reader_module.rs
+
+/// RFC 4180 streaming CSV record reader for rows_and_columns
+///
+/// This module reads CSV data one logical record at a time from any `BufRead`
+/// source. A logical record may span several physical lines when a quoted field
+/// contains a line break, so splitting a file on newlines and then on commas is
+/// not enough to recover the real fields. This reader is a small state machine
+/// that follows RFC 4180 and reports where each record starts in the source.
+///
+/// The reader is driven by a `CsvDialect`, so the same state machine also reads
+/// TSV files, semicolon-separated European exports, and pipe-delimited dumps.
+///
+/// # Core Responsibilities
+/// - Yield one record at a time without reading ahead more than one line
+/// - Handle quoted fields containing delimiters, escaped quotes, and line breaks
+/// - Honor the dialect: delimiter, quote, escape style, comment lines,
+///   whitespace trimming, and LF / CRLF / CR line terminators
+/// - Report the 1-based physical line number and byte offset of every record
+///
+/// # Design Philosophy
+/// - No-Load: only the record currently being assembled is held in memory
+/// - Vanilla Rust: std-only and returns `io::Error`, so every crate can use it
+/// - Lenient where RFC 4180 is silent: a stray quote inside an unquoted field,
+///   or text following a closing quote, is kept as literal field content
+/// - Blank lines between records are skipped but still counted for line numbers
+use std::io::{self, BufRead};
+
+/// How a quote character can appear inside a quoted field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvEscapeStyle {
+    /// RFC 4180: a doubled quote `""` inside a quoted field is one literal quote
+    DoubledQuote,
+
+    /// A backslash makes the next character literal (`\"`, `\,`, `\\`);
+    /// `\n`, `\t` and `\r` produce a line feed, tab and carriage return
+    Backslash,
+
+    /// No escaping: the first quote after an opening quote closes the field
+    NoEscape,
+}
+
+impl CsvEscapeStyle {
+    /// Converts the escape style to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&str` - String representation of the escape style
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            CsvEscapeStyle::DoubledQuote => "doubled",
+            CsvEscapeStyle::Backslash => "backslash",
+            CsvEscapeStyle::NoEscape => "none",
+        }
+    }
+
+    /// Creates an escape style from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from the TOML file
+    ///
+    /// # Returns
+    /// * `Option<CsvEscapeStyle>` - The escape style or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<CsvEscapeStyle> {
+        match toml_string.to_lowercase().as_str() {
+            "doubled" | "double" | "rfc4180" => Some(CsvEscapeStyle::DoubledQuote),
+            "backslash" => Some(CsvEscapeStyle::Backslash),
+            "none" => Some(CsvEscapeStyle::NoEscape),
+            _ => None,
+        }
+    }
+}
+
+/// Which field values have surrounding whitespace removed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvTrimPolicy {
+    /// Keep every field exactly as written
+    KeepWhitespace,
+
+    /// Trim unquoted fields only; quoted fields keep their inner whitespace
+    TrimUnquotedFields,
+
+    /// Trim every field, quoted or not
+    TrimAllFields,
+}
+
+impl CsvTrimPolicy {
+    /// Converts the trim policy to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&str` - String representation of the trim policy
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            CsvTrimPolicy::KeepWhitespace => "none",
+            CsvTrimPolicy::TrimUnquotedFields => "unquoted",
+            CsvTrimPolicy::TrimAllFields => "all",
+        }
+    }
+
+    /// Creates a trim policy from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from the TOML file
+    ///
+    /// # Returns
+    /// * `Option<CsvTrimPolicy>` - The trim policy or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<CsvTrimPolicy> {
+        match toml_string.to_lowercase().as_str() {
+            "none" | "keep" => Some(CsvTrimPolicy::KeepWhitespace),
+            "unquoted" => Some(CsvTrimPolicy::TrimUnquotedFields),
+            "all" => Some(CsvTrimPolicy::TrimAllFields),
+            _ => None,
+        }
+    }
+}
+
+/// Which byte sequences end a physical line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvLineTerminator {
+    /// Accept LF, CRLF, and a lone CR
+    Auto,
+
+    /// Only LF ends a line (a CR before it is field content)
+    Lf,
+
+    /// Only CRLF ends a line (a lone LF is field content)
+    CrLf,
+
+    /// Only CR ends a line (classic Mac OS exports)
+    Cr,
+}
+
+impl CsvLineTerminator {
+    /// Converts the line terminator to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&str` - String representation of the line terminator
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            CsvLineTerminator::Auto => "auto",
+            CsvLineTerminator::Lf => "lf",
+            CsvLineTerminator::CrLf => "crlf",
+            CsvLineTerminator::Cr => "cr",
+        }
+    }
+
+    /// Creates a line terminator from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from the TOML file
+    ///
+    /// # Returns
+    /// * `Option<CsvLineTerminator>` - The line terminator or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<CsvLineTerminator> {
+        match toml_string.to_lowercase().as_str() {
+            "auto" => Some(CsvLineTerminator::Auto),
+            "lf" => Some(CsvLineTerminator::Lf),
+            "crlf" => Some(CsvLineTerminator::CrLf),
+            "cr" => Some(CsvLineTerminator::Cr),
+            _ => None,
+        }
+    }
+}
+
+/// Complete description of how a delimited text file is written
+///
+/// The default dialect is RFC 4180 (comma, double quote, doubled-quote escapes)
+/// with every field trimmed and any common line terminator accepted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvDialect {
+    /// Character separating fields (`,` `;` `\t` `|` ...)
+    pub field_delimiter: char,
+
+    /// Character that starts and ends a quoted field (None disables quoting)
+    pub quote_character: Option<char>,
+
+    /// How quotes are escaped inside quoted fields
+    pub escape_style: CsvEscapeStyle,
+
+    /// Lines starting with this text between records are skipped (None disables comments)
+    pub comment_prefix: Option<String>,
+
+    /// Which fields have surrounding whitespace removed
+    pub trim_policy: CsvTrimPolicy,
+
+    /// Which byte sequences end a physical line
+    pub line_terminator: CsvLineTerminator,
+}
+
+impl Default for CsvDialect {
+    /// Creates the RFC 4180 comma-separated dialect
+    ///
+    /// # Returns
+    /// * `CsvDialect` - Comma delimiter, double quote, doubled-quote escapes
+    fn default() -> Self {
+        CsvDialect {
+            field_delimiter: ',',
+            quote_character: Some('"'),
+            escape_style: CsvEscapeStyle::DoubledQuote,
+            comment_prefix: None,
+            trim_policy: CsvTrimPolicy::TrimAllFields,
+            line_terminator: CsvLineTerminator::Auto,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// Chooses a starting dialect from the file extension
+    ///
+    /// `.tsv` files default to a tab delimiter; everything else defaults to
+    /// the RFC 4180 comma dialect.
+    ///
+    /// # Arguments
+    /// * `file_extension` - The file extension without the dot (any case)
+    ///
+    /// # Returns
+    /// * `CsvDialect` - The default dialect for that kind of file
+    pub fn default_for_file_extension(file_extension: &str) -> CsvDialect {
+        match file_extension.to_lowercase().as_str() {
+            "tsv" | "tab" => CsvDialect {
+                field_delimiter: '\t',
+                ..CsvDialect::default()
+            },
+            _ => CsvDialect::default(),
+        }
+    }
+
+    /// Produces a one-line human readable description of the dialect
+    ///
+    /// # Returns
+    /// * `String` - e.g. `delimiter ',' | quote '"' | escape doubled | ...`
+    pub fn describe_for_display(&self) -> String {
+        let quote_description = match self.quote_character {
+            Some(quote_character) => format!("{:?}", quote_character),
+            None => "none".to_string(),
+        };
+        let comment_description = match &self.comment_prefix {
+            Some(comment_prefix) => format!("{:?}", comment_prefix),
+            None => "none".to_string(),
+        };
+
+        format!(
+            "delimiter {:?} | quote {} | escape {} | comment {} | trim {} | line ending {}",
+            self.field_delimiter,
+            quote_description,
+            self.escape_style.to_toml_string(),
+            comment_description,
+            self.trim_policy.to_toml_string(),
+            self.line_terminator.to_toml_string()
+        )
+    }
+
+    /// Checks whether a character is the quote character of this dialect
+    ///
+    /// # Arguments
+    /// * `candidate_character` - The character to test
+    ///
+    /// # Returns
+    /// * `bool` - True if quoting is enabled and the character is the quote
+    fn is_quote_character(&self, candidate_character: char) -> bool {
+        self.quote_character == Some(candidate_character)
+    }
+
+    /// Checks whether a character starts a backslash escape in this dialect
+    ///
+    /// # Arguments
+    /// * `candidate_character` - The character to test
+    ///
+    /// # Returns
+    /// * `bool` - True if backslash escaping is enabled and the character is `\`
+    fn is_escape_character(&self, candidate_character: char) -> bool {
+        self.escape_style == CsvEscapeStyle::Backslash && candidate_character == '\\'
+    }
+
+    /// Checks whether leading/trailing whitespace around quotes may be skipped
+    ///
+    /// # Arguments
+    /// * `candidate_character` - The character to test
+    ///
+    /// # Returns
+    /// * `bool` - True if trimming is enabled and the character is whitespace
+    fn is_skippable_whitespace(&self, candidate_character: char) -> bool {
+        self.trim_policy != CsvTrimPolicy::KeepWhitespace
+            && candidate_character != self.field_delimiter
+            && (candidate_character == ' ' || candidate_character == '\t')
+    }
+}
+
+/// One logical CSV record together with its position in the source
+///
+/// The position information lets callers produce precise diagnostics
+/// (e.g. "line 42") and lets other tools seek straight back to a record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRecord {
+    /// Field values in column order, with surrounding quotes and escapes removed
+    pub field_values: Vec<String>,
+
+    /// Physical line number (1-based) on which this record begins
+    pub starting_line_number: usize,
+
+    /// Byte offset from the start of the source at which this record begins
+    pub starting_byte_offset: u64,
+
+    /// Number of physical lines this record occupies
+    /// (greater than 1 when a quoted field contains line breaks)
+    pub physical_line_count: usize,
+}
+
+/// Parser state while scanning the characters of one record
+///
+/// Each variant describes what the previous character left us expecting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CsvFieldParseState {
+    /// At the beginning of a field (start of record or just after a delimiter)
+    AtFieldStart,
+
+    /// Inside a field that did not begin with a quote
+    InUnquotedContent,
+
+    /// Inside a quoted field, where delimiters and line breaks are literal
+    InQuotedContent,
+
+    /// Just saw a quote inside a quoted field: either an escaped `""` or the closing quote
+    QuoteSeenInQuotedContent,
+
+    /// Just saw a backslash inside an unquoted field (backslash escape style only)
+    EscapeInUnquotedContent,
+
+    /// Just saw a backslash inside a quoted field (backslash escape style only)
+    EscapeInQuotedContent,
+}
+
+/// Streaming reader that yields one `CsvRecord` at a time
+///
+/// # Examples
+/// ```rust
+/// let csv_file = File::open("data.csv")?;
+/// let mut record_reader = CsvRecordReader::new_with_dialect(BufReader::new(csv_file), CsvDialect::default());
+/// while let Some(csv_record) = record_reader.read_next_record()? {
+///     println!("line {}: {:?}", csv_record.starting_line_number, csv_record.field_values);
+/// }
+/// ```
+pub struct CsvRecordReader<R: BufRead> {
+    /// The underlying buffered byte source
+    source_reader: R,
+
+    /// Dialect describing delimiter, quoting, comments, trimming and line endings
+    csv_dialect: CsvDialect,
+
+    /// Physical line number (1-based) of the next line to be read
+    next_physical_line_number: usize,
+
+    /// Byte offset of the next line to be read
+    next_byte_offset: u64,
+
+    /// Reusable buffer for the raw bytes of one physical line
+    raw_line_buffer: Vec<u8>,
+}
+
+impl<R: BufRead> CsvRecordReader<R> {
+    /// Creates a reader for a specific dialect positioned at the start of the source
+    ///
+    /// # Arguments
+    /// * `source_reader` - Any buffered reader (file, stdin, in-memory cursor)
+    /// * `csv_dialect` - How the source is delimited, quoted and terminated
+    ///
+    /// # Returns
+    /// * `CsvRecordReader<R>` - A reader whose first record starts at line 1, byte 0
+    pub fn new_with_dialect(source_reader: R, csv_dialect: CsvDialect) -> Self {
+        CsvRecordReader {
+            source_reader,
+            csv_dialect,
+            next_physical_line_number: 1,
+            next_byte_offset: 0,
+            raw_line_buffer: Vec::new(),
+        }
+    }
+
+    /// Reads the next logical record from the source
+    ///
+    /// Blank physical lines and comment lines between records are skipped. A
+    /// quoted field that contains line breaks (or an escaped line break with the
+    /// backslash escape style) causes additional physical lines to be consumed.
+    ///
+    /// # Returns
+    /// * `io::Result<Option<CsvRecord>>` - The next record, `None` at end of input, or an error
+    ///
+    /// # Errors
+    /// * `io::ErrorKind::InvalidData` - If a line is not valid UTF-8, or the input
+    ///   ends inside a quoted field or escape sequence
+    /// * Any other `io::Error` from the underlying source
+    pub fn read_next_record(&mut self) -> io::Result<Option<CsvRecord>> {
+        let mut completed_field_values: Vec<String> = Vec::new();
+        let mut current_field_value = String::new();
+        let mut current_field_was_quoted = false;
+        let mut parse_state = CsvFieldParseState::AtFieldStart;
+
+        let mut record_starting_line_number = self.next_physical_line_number;
+        let mut record_starting_byte_offset = self.next_byte_offset;
+        let mut record_has_started = false;
+        let mut physical_lines_in_record = 0;
+
+        loop {
+            // Remember where this physical line begins before consuming it
+            let line_starting_line_number = self.next_physical_line_number;
+            let line_starting_byte_offset = self.next_byte_offset;
+
+            let (line_content, line_terminator) = match self.read_next_physical_line()? {
+                Some(line_parts) => line_parts,
+                None => {
+                    // End of input between records is the normal way to finish
+                    if !record_has_started {
+                        return Ok(None);
+                    }
+
+                    // A started record only continues past a line break inside
+                    // quotes or after an escape, so reaching the end here means
+                    // the quote (or escape) never closed
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "Unterminated quoted field in record starting at line {} (byte offset {})",
+                            record_starting_line_number, record_starting_byte_offset
+                        ),
+                    ));
+                }
+            };
+
+            if !record_has_started {
+                // Skip blank lines that sit between records
+                if line_content.is_empty() {
+                    continue;
+                }
+
+                // Skip comment lines that sit between records
+                if let Some(comment_prefix) = &self.csv_dialect.comment_prefix
+                    && !comment_prefix.is_empty()
+                    && line_content.starts_with(comment_prefix.as_str())
+                {
+                    continue;
+                }
+
+                record_has_started = true;
+                record_starting_line_number = line_starting_line_number;
+                record_starting_byte_offset = line_starting_byte_offset;
+            }
+            physical_lines_in_record += 1;
+
+            // Feed every character of this physical line through the state machine
+            for current_character in line_content.chars() {
+                parse_state = match parse_state {
+                    CsvFieldParseState::AtFieldStart => {
+                        if self.csv_dialect.is_quote_character(current_character) {
+                            current_field_was_quoted = true;
+                            CsvFieldParseState::InQuotedContent
+                        } else if current_character == self.csv_dialect.field_delimiter {
+                            self.finish_current_field(
+                                &mut completed_field_values,
+                                &mut current_field_value,
+                                &mut current_field_was_quoted,
+                            );
+                            CsvFieldParseState::AtFieldStart
+                        } else if self.csv_dialect.is_skippable_whitespace(current_character) {
+                            // Leading whitespace is dropped so ` "quoted"` is still quoted
+                            CsvFieldParseState::AtFieldStart
+                        } else if self.csv_dialect.is_escape_character(current_character) {
+                            CsvFieldParseState::EscapeInUnquotedContent
+                        } else {
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InUnquotedContent
+                        }
+                    }
+                    CsvFieldParseState::InUnquotedContent => {
+                        if current_character == self.csv_dialect.field_delimiter {
+                            self.finish_current_field(
+                                &mut completed_field_values,
+                                &mut current_field_value,
+                                &mut current_field_was_quoted,
+                            );
+                            CsvFieldParseState::AtFieldStart
+                        } else if self.csv_dialect.is_escape_character(current_character) {
+                            CsvFieldParseState::EscapeInUnquotedContent
+                        } else {
+                            // Stray quotes in unquoted fields are kept literally
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InUnquotedContent
+                        }
+                    }
+                    CsvFieldParseState::InQuotedContent => {
+                        if self.csv_dialect.is_escape_character(current_character) {
+                            CsvFieldParseState::EscapeInQuotedContent
+                        } else if self.csv_dialect.is_quote_character(current_character) {
+                            CsvFieldParseState::QuoteSeenInQuotedContent
+                        } else {
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InQuotedContent
+                        }
+                    }
+                    CsvFieldParseState::QuoteSeenInQuotedContent => {
+                        if self.csv_dialect.is_quote_character(current_character)
+                            && self.csv_dialect.escape_style == CsvEscapeStyle::DoubledQuote
+                        {
+                            // Escaped quote: "" inside a quoted field means one literal "
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InQuotedContent
+                        } else if current_character == self.csv_dialect.field_delimiter {
+                            self.finish_current_field(
+                                &mut completed_field_values,
+                                &mut current_field_value,
+                                &mut current_field_was_quoted,
+                            );
+                            CsvFieldParseState::AtFieldStart
+                        } else if self.csv_dialect.is_skippable_whitespace(current_character) {
+                            // Trailing whitespace between the closing quote and the delimiter
+                            CsvFieldParseState::QuoteSeenInQuotedContent
+                        } else {
+                            // Text after a closing quote is kept as literal content
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InUnquotedContent
+                        }
+                    }
+                    CsvFieldParseState::EscapeInUnquotedContent => {
+                        current_field_value.push(translate_backslash_escape(current_character));
+                        CsvFieldParseState::InUnquotedContent
+                    }
+                    CsvFieldParseState::EscapeInQuotedContent => {
+                        current_field_value.push(translate_backslash_escape(current_character));
+                        CsvFieldParseState::InQuotedContent
+                    }
+                };
+            }
+
+            // A line break inside a quoted field, or directly after a backslash,
+            // belongs to the field value and the record continues on the next line
+            match parse_state {
+                CsvFieldParseState::InQuotedContent | CsvFieldParseState::EscapeInQuotedContent => {
+                    current_field_value.push_str(&line_terminator);
+                    parse_state = CsvFieldParseState::InQuotedContent;
+                    continue;
+                }
+                CsvFieldParseState::EscapeInUnquotedContent => {
+                    current_field_value.push_str(&line_terminator);
+                    parse_state = CsvFieldParseState::InUnquotedContent;
+                    continue;
+                }
+                _ => {}
+            }
+
+            // Otherwise the line break ends the record
+            self.finish_current_field(
+                &mut completed_field_values,
+                &mut current_field_value,
+                &mut current_field_was_quoted,
+            );
+            return Ok(Some(CsvRecord {
+                field_values: completed_field_values,
+                starting_line_number: record_starting_line_number,
+                starting_byte_offset: record_starting_byte_offset,
+                physical_line_count: physical_lines_in_record,
+            }));
+        }
+    }
+
+    /// Applies the trim policy to the field being built and appends it to the record
+    ///
+    /// # Arguments
+    /// * `completed_field_values` - Fields already completed in this record
+    /// * `current_field_value` - The field just finished (left empty afterwards)
+    /// * `current_field_was_quoted` - Whether the field began with a quote (reset afterwards)
+    fn finish_current_field(
+        &self,
+        completed_field_values: &mut Vec<String>,
+        current_field_value: &mut String,
+        current_field_was_quoted: &mut bool,
+    ) {
+        let finished_field_value = std::mem::take(current_field_value);
+        let should_trim_field = match self.csv_dialect.trim_policy {
+            CsvTrimPolicy::KeepWhitespace => false,
+            CsvTrimPolicy::TrimUnquotedFields => !*current_field_was_quoted,
+            CsvTrimPolicy::TrimAllFields => true,
+        };
+
+        if should_trim_field {
+            completed_field_values.push(finished_field_value.trim().to_string());
+        } else {
+            completed_field_values.push(finished_field_value);
+        }
+        *current_field_was_quoted = false;
+    }
+
+    /// Reads one physical line and splits off its line terminator
+    ///
+    /// # Returns
+    /// * `io::Result<Option<(String, String)>>` - (line content, line terminator), or `None` at end of input
+    fn read_next_physical_line(&mut self) -> io::Result<Option<(String, String)>> {
+        self.raw_line_buffer.clear();
+        let bytes_read = self.read_raw_physical_line_bytes()?;
+
+        if bytes_read == 0 {
+            return Ok(None);
+        }
+
+        let line_number_being_read = self.next_physical_line_number;
+        let byte_offset_being_read = self.next_byte_offset;
+        self.next_physical_line_number += 1;
+        self.next_byte_offset += bytes_read as u64;
+
+        // Split the terminator away from the content
+        let raw_line_bytes = &self.raw_line_buffer[..];
+        let terminator_length = match self.csv_dialect.line_terminator {
+            CsvLineTerminator::Auto => {
+                if raw_line_bytes.ends_with(b"\r\n") {
+                    2
+                } else if raw_line_bytes.ends_with(b"\n") || raw_line_bytes.ends_with(b"\r") {
+                    1
+                } else {
+                    0
+                }
+            }
+            CsvLineTerminator::Lf => usize::from(raw_line_bytes.ends_with(b"\n")),
+            CsvLineTerminator::CrLf => {
+                if raw_line_bytes.ends_with(b"\r\n") {
+                    2
+                } else {
+                    0
+                }
+            }
+            CsvLineTerminator::Cr => usize::from(raw_line_bytes.ends_with(b"\r")),
+        };
+        let content_length = raw_line_bytes.len() - terminator_length;
+
+        let line_content = std::str::from_utf8(&raw_line_bytes[..content_length])
+            .map_err(|utf8_error| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Invalid UTF-8 data at line {} (byte offset {}): {}",
+                        line_number_being_read,
+                        byte_offset_being_read + utf8_error.valid_up_to() as u64,
+                        utf8_error
+                    ),
+                )
+            })?
+            .to_string();
+
+        let line_terminator = String::from_utf8_lossy(&raw_line_bytes[content_length..]).to_string();
+
+        Ok(Some((line_content, line_terminator)))
+    }
+
+    /// Reads the raw bytes of one physical line, terminator included, into the line buffer
+    ///
+    /// # Returns
+    /// * `io::Result<usize>` - Number of bytes consumed from the source (0 at end of input)
+    fn read_raw_physical_line_bytes(&mut self) -> io::Result<usize> {
+        match self.csv_dialect.line_terminator {
+            CsvLineTerminator::Lf => self.source_reader.read_until(b'\n', &mut self.raw_line_buffer),
+            CsvLineTerminator::Cr => self.source_reader.read_until(b'\r', &mut self.raw_line_buffer),
+            CsvLineTerminator::CrLf => {
+                // A lone LF is content, so keep reading until the line ends with CRLF
+                let mut total_bytes_read = 0;
+                loop {
+                    let bytes_read = self.source_reader.read_until(b'\n', &mut self.raw_line_buffer)?;
+                    total_bytes_read += bytes_read;
+                    if bytes_read == 0 || self.raw_line_buffer.ends_with(b"\r\n") {
+                        return Ok(total_bytes_read);
+                    }
+                }
+            }
+            CsvLineTerminator::Auto => {
+                // Stop at the first LF or CR; a CR directly followed by LF is one terminator
+                let mut total_bytes_read = 0;
+                loop {
+                    let available_bytes = self.source_reader.fill_buf()?;
+                    if available_bytes.is_empty() {
+                        return Ok(total_bytes_read);
+                    }
+
+                    match available_bytes.iter().position(|byte| *byte == b'\n' || *byte == b'\r') {
+                        Some(terminator_position) => {
+                            let terminator_byte = available_bytes[terminator_position];
+                            self.raw_line_buffer
+                                .extend_from_slice(&available_bytes[..=terminator_position]);
+                            self.source_reader.consume(terminator_position + 1);
+                            total_bytes_read += terminator_position + 1;
+
+                            if terminator_byte == b'\r' {
+                                let following_bytes = self.source_reader.fill_buf()?;
+                                if following_bytes.first() == Some(&b'\n') {
+                                    self.raw_line_buffer.push(b'\n');
+                                    self.source_reader.consume(1);
+                                    total_bytes_read += 1;
+                                }
+                            }
+                            return Ok(total_bytes_read);
+                        }
+                        None => {
+                            let available_length = available_bytes.len();
+                            self.raw_line_buffer.extend_from_slice(available_bytes);
+                            self.source_reader.consume(available_length);
+                            total_bytes_read += available_length;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Translates the character following a backslash into the character it stands for
+///
+/// # Arguments
+/// * `escaped_character` - The character directly after the backslash
+///
+/// # Returns
+/// * `char` - Line feed, tab or carriage return for `n` `t` `r`; otherwise the character itself
+fn translate_backslash_escape(escaped_character: char) -> char {
+    match escaped_character {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        other_character => other_character,
+    }
+}
+
+impl<R: BufRead> Iterator for CsvRecordReader<R> {
+    type Item = io::Result<CsvRecord>;
+
+    /// Yields the next record, so the reader can be used in `for` loops
+    ///
+    /// # Returns
+    /// * `Option<io::Result<CsvRecord>>` - Next record or error, `None` at end of input
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Helper to read every record from an in-memory string
+    fn read_all_test_records(csv_text: &str) -> io::Result<Vec<CsvRecord>> {
+        CsvRecordReader::new_with_dialect(Cursor::new(csv_text.as_bytes().to_vec()), CsvDialect::default()).collect()
+    }
+
+    #[test]
+    fn test_simple_records_and_positions() {
+        let records = read_all_test_records("a,b,c\n1,2,3\n").expect("valid CSV");
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].field_values, vec!["a", "b", "c"]);
+        assert_eq!(records[1].field_values, vec!["1", "2", "3"]);
+        assert_eq!(records[1].starting_line_number, 2);
+        assert_eq!(records[1].starting_byte_offset, 6);
+    }
+
+    #[test]
+    fn test_quoted_field_with_comma_and_escaped_quote() {
+        let records = read_all_test_records("name,note\n\"Smith, J\",\"said \"\"hi\"\"\"\n")
+            .expect("valid CSV");
+
+        assert_eq!(records[1].field_values, vec!["Smith, J", "said \"hi\""]);
+    }
+
+    #[test]
+    fn test_quoted_field_spanning_lines() {
+        let records = read_all_test_records("id,text\n1,\"first\nsecond\"\n2,plain\n")
+            .expect("valid CSV");
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].field_values, vec!["1", "first\nsecond"]);
+        assert_eq!(records[1].physical_line_count, 2);
+        assert_eq!(records[2].starting_line_number, 4);
+        assert_eq!(records[2].starting_byte_offset, 25);
+    }
+
+    #[test]
+    fn test_crlf_line_endings_and_blank_lines() {
+        let records = read_all_test_records("a,b\r\n\r\n1,\r\n").expect("valid CSV");
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].field_values, vec!["1", ""]);
+        assert_eq!(records[1].starting_line_number, 3);
+    }
+
+    #[test]
+    fn test_unterminated_quote_is_an_error() {
+        let read_result = read_all_test_records("a,b\n1,\"never closed\n");
+
+        match read_result {
+            Err(io_error) => {
+                assert_eq!(io_error.kind(), io::ErrorKind::InvalidData);
+                assert!(io_error.to_string().contains("line 2"));
+            }
+            Ok(_) => panic!("Expected an unterminated quote error"),
+        }
+    }
+
+    /// Helper to read every record with a specific dialect
+    fn read_all_dialect_test_records(csv_text: &str, csv_dialect: CsvDialect) -> io::Result<Vec<CsvRecord>> {
+        CsvRecordReader::new_with_dialect(Cursor::new(csv_text.as_bytes().to_vec()), csv_dialect).collect()
+    }
+
+    #[test]
+    fn test_tab_and_semicolon_delimiters() {
+        let tsv_records = read_all_dialect_test_records(
+            "a\tb\n1,5\t\"x\ty\"\n",
+            CsvDialect::default_for_file_extension("TSV"),
+        )
+        .expect("valid TSV");
+        assert_eq!(tsv_records[1].field_values, vec!["1,5", "x\ty"]);
+
+        let semicolon_dialect = CsvDialect {
+            field_delimiter: ';',
+            ..CsvDialect::default()
+        };
+        let semicolon_records =
+            read_all_dialect_test_records("price;qty\n3,50; 2\n", semicolon_dialect).expect("valid CSV");
+        assert_eq!(semicolon_records[1].field_values, vec!["3,50", "2"]);
+    }
+
+    #[test]
+    fn test_backslash_escapes_and_comment_lines() {
+        let pipe_dialect = CsvDialect {
+            field_delimiter: '|',
+            escape_style: CsvEscapeStyle::Backslash,
+            comment_prefix: Some("#".to_string()),
+            ..CsvDialect::default()
+        };
+        let records = read_all_dialect_test_records(
+            "# exported nightly\nid|note\n1|a\\|b\n2|\"say \\\"hi\\\"\"\n",
+            pipe_dialect,
+        )
+        .expect("valid pipe-delimited data");
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].starting_line_number, 2);
+        assert_eq!(records[1].field_values, vec!["1", "a|b"]);
+        assert_eq!(records[2].field_values, vec!["2", "say \"hi\""]);
+    }
+
+    #[test]
+    fn test_trim_policies() {
+        let csv_text = "  a , \" b \" \n";
+
+        let trim_all = read_all_dialect_test_records(csv_text, CsvDialect::default()).expect("valid CSV");
+        assert_eq!(trim_all[0].field_values, vec!["a", "b"]);
+
+        let trim_unquoted_dialect = CsvDialect {
+            trim_policy: CsvTrimPolicy::TrimUnquotedFields,
+            ..CsvDialect::default()
+        };
+        let trim_unquoted = read_all_dialect_test_records(csv_text, trim_unquoted_dialect).expect("valid CSV");
+        assert_eq!(trim_unquoted[0].field_values, vec!["a", " b "]);
+
+        let keep_dialect = CsvDialect {
+            trim_policy: CsvTrimPolicy::KeepWhitespace,
+            ..CsvDialect::default()
+        };
+        let kept = read_all_dialect_test_records(csv_text, keep_dialect).expect("valid CSV");
+        assert_eq!(kept[0].field_values, vec!["  a ", " \" b \" "]);
+    }
+
+    #[test]
+    fn test_cr_only_and_strict_crlf_line_terminators() {
+        let auto_records = read_all_test_records("a,b\r1,2\r").expect("valid CSV");
+        assert_eq!(auto_records.len(), 2);
+        assert_eq!(auto_records[1].starting_byte_offset, 4);
+
+        let crlf_dialect = CsvDialect {
+            line_terminator: CsvLineTerminator::CrLf,
+            ..CsvDialect::default()
+        };
+        let crlf_records = read_all_dialect_test_records("a,b\r\n1,two\nlines\r\n", crlf_dialect)
+            .expect("valid CSV");
+        assert_eq!(crlf_records.len(), 2);
+        assert_eq!(crlf_records[1].field_values, vec!["1", "two\nlines"]);
+    }
+
+    #[test]
+    fn test_dialect_toml_round_trip() {
+        assert_eq!(CsvEscapeStyle::from_toml_string("backslash"), Some(CsvEscapeStyle::Backslash));
+        assert_eq!(
+            CsvTrimPolicy::from_toml_string(CsvTrimPolicy::TrimUnquotedFields.to_toml_string()),
+            Some(CsvTrimPolicy::TrimUnquotedFields)
+        );
+        assert_eq!(CsvLineTerminator::from_toml_string("CRLF"), Some(CsvLineTerminator::CrLf));
+        assert_eq!(CsvLineTerminator::from_toml_string("unix"), None);
+    }
+}
@@ -0,0 +1,309 @@
+// src/csv_structure_validator_module.rs
+
+/// Structural validation of CSV records for rows_and_columns
+///
+/// A "ragged" record has a different number of fields than the header (or,
+/// for files without a header, than the first record). This module describes
+/// what the validation pass found and what to do with ragged records. The
+/// pass itself lives in `csv_processor_module`, which reads every record once
+/// and builds a `CsvStructuralValidationReport`.
+///
+/// # Ragged Row Policies
+/// The policy is chosen with `ragged_row_policy` in the `[validation]` section
+/// of the metadata file:
+/// - `reject` - any ragged record stops the analysis with an error
+/// - `pad` - short records are padded with empty fields; long records are an error
+/// - `truncate` - long records lose their extra fields, short records are padded
+///   (the default, matching what earlier versions did silently)
+/// - `quarantine` - ragged records are left out of the analysis and copied to a
+///   side file next to the CSV file
+///
+/// # Quarantine File Format
+/// The quarantine file is RFC 4180 CSV in UTF-8. The first field of each line is
+/// the source line number of the record, followed by the record's own fields.
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// File name suffix of the quarantine side file (`<stem>.quarantine.csv`)
+pub const QUARANTINE_FILE_SUFFIX: &str = "quarantine.csv";
+
+/// Maximum number of ragged records printed to the terminal
+const RAGGED_RECORD_DISPLAY_LIMIT: usize = 25;
+
+/// What to do with a record whose field count differs from the header
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RaggedRowPolicy {
+    /// Stop the analysis with an error
+    Reject,
+
+    /// Pad short records with empty fields; long records are an error
+    PadWithEmpty,
+
+    /// Cut long records to the header width; pad short records
+    Truncate,
+
+    /// Leave ragged records out and copy them to the quarantine file
+    Quarantine,
+}
+
+impl RaggedRowPolicy {
+    /// Converts the policy to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&str` - String representation of the policy
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            RaggedRowPolicy::Reject => "reject",
+            RaggedRowPolicy::PadWithEmpty => "pad",
+            RaggedRowPolicy::Truncate => "truncate",
+            RaggedRowPolicy::Quarantine => "quarantine",
+        }
+    }
+
+    /// Creates a policy from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from the TOML file
+    ///
+    /// # Returns
+    /// * `Option<RaggedRowPolicy>` - The policy or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<RaggedRowPolicy> {
+        match toml_string.to_lowercase().as_str() {
+            "reject" => Some(RaggedRowPolicy::Reject),
+            "pad" => Some(RaggedRowPolicy::PadWithEmpty),
+            "truncate" => Some(RaggedRowPolicy::Truncate),
+            "quarantine" => Some(RaggedRowPolicy::Quarantine),
+            _ => None,
+        }
+    }
+
+    /// Checks whether this policy can turn a record of the given width into a usable row
+    ///
+    /// # Arguments
+    /// * `actual_field_count` - Number of fields in the record
+    /// * `expected_field_count` - Number of fields in the header
+    ///
+    /// # Returns
+    /// * `bool` - False if the policy treats this record as an error
+    pub fn accepts_field_count(self, actual_field_count: usize, expected_field_count: usize) -> bool {
+        match self {
+            RaggedRowPolicy::Reject => actual_field_count == expected_field_count,
+            RaggedRowPolicy::PadWithEmpty => actual_field_count <= expected_field_count,
+            RaggedRowPolicy::Truncate | RaggedRowPolicy::Quarantine => true,
+        }
+    }
+}
+
+/// Diagnostic for one record whose field count differs from the header
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaggedRecordDiagnostic {
+    /// Line number (1-based) where the record starts
+    pub starting_line_number: usize,
+
+    /// Number of fields the record actually has
+    pub actual_field_count: usize,
+
+    /// Number of fields the header has
+    pub expected_field_count: usize,
+}
+
+/// Result of the structural validation pass over every record
+#[derive(Debug, Clone)]
+pub struct CsvStructuralValidationReport {
+    /// Policy applied to ragged records
+    pub ragged_row_policy: RaggedRowPolicy,
+
+    /// Field count every record is compared against
+    pub expected_field_count: usize,
+
+    /// Number of records checked (header included)
+    pub checked_record_count: usize,
+
+    /// Every ragged record, in file order
+    pub ragged_record_diagnostics: Vec<RaggedRecordDiagnostic>,
+
+    /// Side file holding quarantined records (only when records were quarantined)
+    pub quarantine_file_path: Option<PathBuf>,
+}
+
+impl CsvStructuralValidationReport {
+    /// Counts the records the analysis leaves out under this report's policy
+    ///
+    /// # Returns
+    /// * `usize` - Number of quarantined records (0 for every other policy)
+    pub fn excluded_record_count(&self) -> usize {
+        match self.ragged_row_policy {
+            RaggedRowPolicy::Quarantine => self.ragged_record_diagnostics.len(),
+            _ => 0,
+        }
+    }
+
+    /// Counts the ragged records this report's policy cannot repair
+    ///
+    /// # Returns
+    /// * `usize` - Number of records that make the analysis fail
+    pub fn unacceptable_record_count(&self) -> usize {
+        self.ragged_record_diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                !self
+                    .ragged_row_policy
+                    .accepts_field_count(diagnostic.actual_field_count, diagnostic.expected_field_count)
+            })
+            .count()
+    }
+}
+
+/// Brings a record to the header width according to the ragged row policy
+///
+/// # Arguments
+/// * `field_values` - The record's fields
+/// * `expected_field_count` - Number of fields in the header
+/// * `ragged_row_policy` - What to do with ragged records
+///
+/// # Returns
+/// * `Option<Vec<String>>` - The record with exactly `expected_field_count` fields,
+///   or None if the record is quarantined and must be skipped
+pub fn conform_record_fields_to_policy(
+    mut field_values: Vec<String>,
+    expected_field_count: usize,
+    ragged_row_policy: RaggedRowPolicy,
+) -> Option<Vec<String>> {
+    if field_values.len() == expected_field_count {
+        return Some(field_values);
+    }
+
+    if ragged_row_policy == RaggedRowPolicy::Quarantine {
+        return None;
+    }
+
+    // Reject and pad stop the analysis during validation, before any pass gets here
+    field_values.resize(expected_field_count, String::new());
+    Some(field_values)
+}
+
+/// Formats fields as one RFC 4180 CSV line (including the trailing newline)
+///
+/// Fields containing a comma, quote, or line break are quoted, and quotes
+/// inside them are doubled.
+///
+/// # Arguments
+/// * `field_values` - The fields to write
+///
+/// # Returns
+/// * `String` - The CSV line
+pub fn format_rfc4180_csv_line(field_values: &[String]) -> String {
+    let formatted_fields: Vec<String> = field_values
+        .iter()
+        .map(|field_value| {
+            if field_value.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field_value.replace('"', "\"\""))
+            } else {
+                field_value.clone()
+            }
+        })
+        .collect();
+
+    format!("{}\n", formatted_fields.join(","))
+}
+
+/// Writes one quarantined record to the quarantine file
+///
+/// # Arguments
+/// * `quarantine_writer` - Destination for quarantined records
+/// * `starting_line_number` - Source line number of the record
+/// * `field_values` - The record's fields
+///
+/// # Returns
+/// * `io::Result<()>` - Success or write error
+pub fn write_quarantined_record<W: Write>(
+    quarantine_writer: &mut W,
+    starting_line_number: usize,
+    field_values: &[String],
+) -> io::Result<()> {
+    let mut quarantine_fields = Vec::with_capacity(field_values.len() + 1);
+    quarantine_fields.push(starting_line_number.to_string());
+    quarantine_fields.extend(field_values.iter().cloned());
+
+    quarantine_writer.write_all(format_rfc4180_csv_line(&quarantine_fields).as_bytes())
+}
+
+/// Prints the structural validation summary and the ragged records
+///
+/// # Arguments
+/// * `validation_report` - The report from the validation pass
+pub fn display_structural_validation_report(validation_report: &CsvStructuralValidationReport) {
+    let ragged_record_count = validation_report.ragged_record_diagnostics.len();
+
+    if ragged_record_count == 0 {
+        println!(
+            "  ✓ Structure validated: all {} records have {} fields",
+            validation_report.checked_record_count, validation_report.expected_field_count
+        );
+        return;
+    }
+
+    println!(
+        "  ⚠ Structure validated: {} of {} records have a field count other than {} (policy: {})",
+        ragged_record_count,
+        validation_report.checked_record_count,
+        validation_report.expected_field_count,
+        validation_report.ragged_row_policy.to_toml_string()
+    );
+
+    for ragged_record in validation_report
+        .ragged_record_diagnostics
+        .iter()
+        .take(RAGGED_RECORD_DISPLAY_LIMIT)
+    {
+        println!(
+            "    Line {}: {} fields (expected {})",
+            ragged_record.starting_line_number, ragged_record.actual_field_count, ragged_record.expected_field_count
+        );
+    }
+
+    if ragged_record_count > RAGGED_RECORD_DISPLAY_LIMIT {
+        println!("    ... and {} more ragged records", ragged_record_count - RAGGED_RECORD_DISPLAY_LIMIT);
+    }
+
+    if let Some(quarantine_file_path) = &validation_report.quarantine_file_path {
+        println!("    Quarantined records written to: {}", quarantine_file_path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper to build a record from string slices
+    fn test_record(field_values: &[&str]) -> Vec<String> {
+        field_values.iter().map(|field_value| field_value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_conform_record_fields_to_policy() {
+        assert_eq!(
+            conform_record_fields_to_policy(test_record(&["1"]), 3, RaggedRowPolicy::PadWithEmpty),
+            Some(test_record(&["1", "", ""]))
+        );
+        assert_eq!(
+            conform_record_fields_to_policy(test_record(&["1", "2", "3", "4"]), 3, RaggedRowPolicy::Truncate),
+            Some(test_record(&["1", "2", "3"]))
+        );
+        assert_eq!(conform_record_fields_to_policy(test_record(&["1"]), 3, RaggedRowPolicy::Quarantine), None);
+        assert!(!RaggedRowPolicy::PadWithEmpty.accepts_field_count(4, 3));
+        assert!(RaggedRowPolicy::PadWithEmpty.accepts_field_count(2, 3));
+    }
+
+    #[test]
+    fn test_quarantined_record_is_rfc4180_with_line_number() {
+        let mut quarantine_output = Vec::new();
+        write_quarantined_record(&mut quarantine_output, 14, &test_record(&["a,b", "say \"hi\"", "x"]))
+            .expect("in-memory write");
+
+        assert_eq!(
+            String::from_utf8(quarantine_output).expect("UTF-8 output"),
+            "14,\"a,b\",\"say \"\"hi\"\"\",x\n"
+        );
+    }
+}
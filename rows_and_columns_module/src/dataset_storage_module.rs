@@ -0,0 +1,863 @@
+// src/dataset_storage_module.rs
+
+/// Storage backends for imported datasets in rows_and_columns
+///
+/// An imported dataset keeps one directory per column. How the cells of a
+/// column are stored inside that directory is up to the storage backend,
+/// chosen with `backend` in the `[storage]` section of the metadata file:
+///
+/// - `cell_files` (the default) - one `row_NNNN/value.txt` per cell. Easy to
+///   browse, but a 10M-row by 20-column file becomes 200M directories.
+/// - `column_segments` - one plain text segment file per `segment_rows` rows
+///   (`segment_000001.txt`, one value per line), plus a small offset index
+///   (`segment_000001.index`) holding the byte offset of every 100th line.
+///
+/// In a segment file a backslash is written as `\\`, a line feed as `\n` and
+/// a carriage return as `\r`, so every value stays on its own line. The index
+/// is plain text too: line k is the byte offset of row `100 * k` within the
+/// segment (counting from 0).
+///
+/// The import writes through `DatasetStorageWriter`; the export and the
+/// statistics read through `DatasetStorageReader`, so neither cares which
+/// backend a dataset uses.
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+    create_configuration_error,
+};
+
+/// Prefix of column directories (`column_001_name`)
+pub const COLUMN_DIRECTORY_PREFIX: &str = "column_";
+
+/// Prefix of row directories in the `cell_files` backend (`row_0001`)
+pub const ROW_DIRECTORY_PREFIX: &str = "row_";
+
+/// File holding one cell value in the `cell_files` backend
+pub const CELL_VALUE_FILE_NAME: &str = "value.txt";
+
+/// Prefix of segment files in the `column_segments` backend
+const SEGMENT_FILE_PREFIX: &str = "segment_";
+
+/// Extension of segment value files
+const SEGMENT_VALUES_FILE_EXTENSION: &str = "txt";
+
+/// Extension of segment offset index files
+const SEGMENT_INDEX_FILE_EXTENSION: &str = "index";
+
+/// Rows between two entries of a segment offset index
+const SEGMENT_INDEX_ROW_INTERVAL: usize = 100;
+
+/// Rows per segment file when the metadata file does not set `segment_rows`
+pub const DEFAULT_SEGMENT_ROW_COUNT: usize = 100_000;
+
+/// How the cells of an imported dataset are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetStorageBackend {
+    /// One `row_NNNN/value.txt` per cell
+    CellFiles,
+
+    /// One segment file per column per `segment_rows` rows, with an offset index
+    ColumnSegments,
+}
+
+impl DatasetStorageBackend {
+    /// Converts the backend to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&str` - String representation of the backend
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            DatasetStorageBackend::CellFiles => "cell_files",
+            DatasetStorageBackend::ColumnSegments => "column_segments",
+        }
+    }
+
+    /// Creates a backend from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from the TOML file
+    ///
+    /// # Returns
+    /// * `Option<DatasetStorageBackend>` - The backend or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<DatasetStorageBackend> {
+        match toml_string.to_lowercase().as_str() {
+            "cell_files" | "cells" => Some(DatasetStorageBackend::CellFiles),
+            "column_segments" | "segments" => Some(DatasetStorageBackend::ColumnSegments),
+            _ => None,
+        }
+    }
+}
+
+/// The `[storage]` settings of a dataset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatasetStorageSettings {
+    /// Which backend stores the cells
+    pub storage_backend: DatasetStorageBackend,
+
+    /// Rows per segment file (`column_segments` only)
+    pub segment_row_count: usize,
+}
+
+impl Default for DatasetStorageSettings {
+    fn default() -> Self {
+        DatasetStorageSettings {
+            storage_backend: DatasetStorageBackend::CellFiles,
+            segment_row_count: DEFAULT_SEGMENT_ROW_COUNT,
+        }
+    }
+}
+
+/// Where and how a dataset's columns are stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetStorageLayout {
+    /// The dataset directory
+    pub dataset_directory: PathBuf,
+
+    /// Column directory names, in the order row values are written
+    pub column_directory_names: Vec<String>,
+
+    /// Digits in row directory numbers (`cell_files` only)
+    pub row_number_digits: usize,
+
+    /// Backend and segment size
+    pub storage_settings: DatasetStorageSettings,
+}
+
+impl DatasetStorageLayout {
+    /// Finds the position of a CSV column among the stored columns
+    ///
+    /// # Arguments
+    /// * `column_index` - 0-based index of the column in the CSV file
+    ///
+    /// # Returns
+    /// * `Option<usize>` - Position in `column_directory_names`, or None if the column is not stored
+    pub fn column_position(&self, column_index: usize) -> Option<usize> {
+        self.column_directory_names
+            .iter()
+            .position(|column_directory_name| parse_column_directory_number(column_directory_name) == Some(column_index + 1))
+    }
+}
+
+/// Writes the rows of a dataset, in row order
+pub trait DatasetStorageWriter {
+    /// Writes one data row
+    ///
+    /// # Arguments
+    /// * `row_number` - 1-based data row number (one more than the previous call)
+    /// * `row_values` - One value per stored column, in layout order
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<()>` - Success or error
+    fn write_row(&mut self, row_number: usize, row_values: &[String]) -> RowsAndColumnsResult<()>;
+
+    /// Makes every row written so far complete on disk, before a progress checkpoint
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<()>` - Success or error
+    fn flush_written_rows(&mut self) -> RowsAndColumnsResult<()>;
+}
+
+/// Reads the cells of a dataset
+pub trait DatasetStorageReader {
+    /// Checks whether a CSV column is stored in the dataset
+    ///
+    /// # Arguments
+    /// * `column_index` - 0-based index of the column in the CSV file
+    ///
+    /// # Returns
+    /// * `bool` - True if the column was imported
+    fn stores_column(&self, column_index: usize) -> bool;
+
+    /// Reads some cells of one row
+    ///
+    /// # Arguments
+    /// * `row_number` - 1-based data row number
+    /// * `column_indexes` - 0-based CSV column indexes, in the order wanted
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Vec<String>>` - One value per requested column
+    fn read_row(&mut self, row_number: usize, column_indexes: &[usize]) -> RowsAndColumnsResult<Vec<String>>;
+
+    /// Reads every value of one column, in row order
+    ///
+    /// # Arguments
+    /// * `column_index` - 0-based index of the column in the CSV file
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Vec<String>>` - The column's values
+    fn read_column_values(&mut self, column_index: usize) -> RowsAndColumnsResult<Vec<String>>;
+}
+
+/// Opens a writer for a dataset's backend
+///
+/// # Arguments
+/// * `storage_layout` - Where and how the columns are stored
+/// * `resume_after_row_count` - Rows already written by an interrupted import (0 for a fresh one)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Box<dyn DatasetStorageWriter>>` - The writer, positioned after those rows
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If a directory cannot be created or a segment cannot be reopened
+pub fn open_dataset_storage_writer(
+    storage_layout: &DatasetStorageLayout,
+    resume_after_row_count: usize,
+) -> RowsAndColumnsResult<Box<dyn DatasetStorageWriter>> {
+    for column_directory_name in &storage_layout.column_directory_names {
+        create_directory(&storage_layout.dataset_directory.join(column_directory_name))?;
+    }
+
+    match storage_layout.storage_settings.storage_backend {
+        DatasetStorageBackend::CellFiles => Ok(Box::new(CellFileStorage {
+            storage_layout: storage_layout.clone(),
+            stored_row_count: resume_after_row_count,
+        })),
+        DatasetStorageBackend::ColumnSegments => Ok(Box::new(ColumnSegmentWriter::open(
+            storage_layout,
+            resume_after_row_count,
+        )?)),
+    }
+}
+
+/// Opens a reader for a dataset's backend
+///
+/// # Arguments
+/// * `storage_layout` - Where and how the columns are stored
+/// * `stored_row_count` - Number of data rows in the dataset
+///
+/// # Returns
+/// * `Box<dyn DatasetStorageReader>` - The reader
+pub fn open_dataset_storage_reader(
+    storage_layout: &DatasetStorageLayout,
+    stored_row_count: usize,
+) -> Box<dyn DatasetStorageReader> {
+    match storage_layout.storage_settings.storage_backend {
+        DatasetStorageBackend::CellFiles => Box::new(CellFileStorage {
+            storage_layout: storage_layout.clone(),
+            stored_row_count,
+        }),
+        DatasetStorageBackend::ColumnSegments => Box::new(ColumnSegmentReader {
+            storage_layout: storage_layout.clone(),
+            stored_row_count,
+            column_cursors: storage_layout.column_directory_names.iter().map(|_| None).collect(),
+        }),
+    }
+}
+
+/// Reads the column number from a column directory name (`column_003_amount` → 3)
+///
+/// # Arguments
+/// * `column_directory_name` - The directory name
+///
+/// # Returns
+/// * `Option<usize>` - The 1-based column number
+pub fn parse_column_directory_number(column_directory_name: &str) -> Option<usize> {
+    let numbered_name = column_directory_name.strip_prefix(COLUMN_DIRECTORY_PREFIX)?;
+    let number_text: String = numbered_name.chars().take_while(|character| character.is_ascii_digit()).collect();
+    number_text.parse::<usize>().ok()
+}
+
+/// Formats a row directory name with a fixed number of digits
+///
+/// # Arguments
+/// * `data_row_number` - 1-based data row number
+/// * `row_number_digits` - Digits to pad the number to
+///
+/// # Returns
+/// * `String` - e.g. `row_0042`
+pub fn format_row_directory_name(data_row_number: usize, row_number_digits: usize) -> String {
+    format!("{}{:0width$}", ROW_DIRECTORY_PREFIX, data_row_number, width = row_number_digits)
+}
+
+/// The `cell_files` backend: one `row_NNNN/value.txt` per cell
+struct CellFileStorage {
+    /// Where the columns are stored
+    storage_layout: DatasetStorageLayout,
+
+    /// Number of data rows in the dataset
+    stored_row_count: usize,
+}
+
+impl CellFileStorage {
+    /// Path of one cell file
+    ///
+    /// # Arguments
+    /// * `column_position` - Position of the column in the layout
+    /// * `row_number` - 1-based data row number
+    ///
+    /// # Returns
+    /// * `PathBuf` - `<column directory>/row_NNNN/value.txt`
+    fn cell_value_file_path(&self, column_position: usize, row_number: usize) -> PathBuf {
+        self.storage_layout.dataset_directory
+            .join(&self.storage_layout.column_directory_names[column_position])
+            .join(format_row_directory_name(row_number, self.storage_layout.row_number_digits))
+            .join(CELL_VALUE_FILE_NAME)
+    }
+
+    /// Reads one cell file
+    ///
+    /// # Arguments
+    /// * `column_position` - Position of the column in the layout
+    /// * `row_number` - 1-based data row number
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<String>` - The cell value
+    fn read_cell_value(&self, column_position: usize, row_number: usize) -> RowsAndColumnsResult<String> {
+        let cell_value_file_path = self.cell_value_file_path(column_position, row_number);
+        fs::read_to_string(&cell_value_file_path).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to read cell file: {}", cell_value_file_path.display()), io_error)
+        })
+    }
+}
+
+impl DatasetStorageWriter for CellFileStorage {
+    fn write_row(&mut self, row_number: usize, row_values: &[String]) -> RowsAndColumnsResult<()> {
+        for (column_position, cell_value) in row_values.iter().enumerate() {
+            let cell_value_file_path = self.cell_value_file_path(column_position, row_number);
+            if let Some(row_directory) = cell_value_file_path.parent() {
+                create_directory(row_directory)?;
+            }
+            fs::write(&cell_value_file_path, cell_value).map_err(|io_error| {
+                create_file_system_error(&format!("Failed to write cell file: {}", cell_value_file_path.display()), io_error)
+            })?;
+        }
+
+        self.stored_row_count = row_number;
+        Ok(())
+    }
+
+    fn flush_written_rows(&mut self) -> RowsAndColumnsResult<()> {
+        // Every cell file is complete as soon as it is written
+        Ok(())
+    }
+}
+
+impl DatasetStorageReader for CellFileStorage {
+    fn stores_column(&self, column_index: usize) -> bool {
+        self.storage_layout.column_position(column_index).is_some()
+    }
+
+    fn read_row(&mut self, row_number: usize, column_indexes: &[usize]) -> RowsAndColumnsResult<Vec<String>> {
+        column_indexes
+            .iter()
+            .map(|column_index| {
+                let column_position = find_column_position(&self.storage_layout, *column_index)?;
+                self.read_cell_value(column_position, row_number)
+            })
+            .collect()
+    }
+
+    fn read_column_values(&mut self, column_index: usize) -> RowsAndColumnsResult<Vec<String>> {
+        let column_position = find_column_position(&self.storage_layout, column_index)?;
+        (1..=self.stored_row_count)
+            .map(|row_number| self.read_cell_value(column_position, row_number))
+            .collect()
+    }
+}
+
+/// The segment file one column is currently appending to
+struct OpenColumnSegment {
+    /// 1-based segment number
+    segment_number: usize,
+
+    /// Value lines of the segment
+    values_writer: BufWriter<File>,
+
+    /// Offset index of the segment
+    index_writer: BufWriter<File>,
+
+    /// Bytes written to the values file so far
+    written_byte_count: u64,
+}
+
+/// Writer for the `column_segments` backend
+struct ColumnSegmentWriter {
+    /// Where the columns are stored
+    storage_layout: DatasetStorageLayout,
+
+    /// The open segment of each column, in layout order
+    open_segments: Vec<Option<OpenColumnSegment>>,
+}
+
+impl ColumnSegmentWriter {
+    /// Opens the writer, cutting back a segment an interrupted import wrote past its checkpoint
+    ///
+    /// # Arguments
+    /// * `storage_layout` - Where the columns are stored
+    /// * `resume_after_row_count` - Rows already written (0 for a fresh import)
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<ColumnSegmentWriter>` - The writer
+    fn open(storage_layout: &DatasetStorageLayout, resume_after_row_count: usize) -> RowsAndColumnsResult<ColumnSegmentWriter> {
+        let segment_row_count = storage_layout.storage_settings.segment_row_count;
+        let mut open_segments = Vec::with_capacity(storage_layout.column_directory_names.len());
+
+        for column_directory_name in &storage_layout.column_directory_names {
+            let kept_row_count = resume_after_row_count % segment_row_count;
+            if kept_row_count == 0 {
+                // The next row starts a new segment file
+                open_segments.push(None);
+                continue;
+            }
+
+            let segment_number = resume_after_row_count / segment_row_count + 1;
+            let column_directory = storage_layout.dataset_directory.join(column_directory_name);
+            open_segments.push(Some(reopen_column_segment(&column_directory, segment_number, kept_row_count)?));
+        }
+
+        Ok(ColumnSegmentWriter { storage_layout: storage_layout.clone(), open_segments })
+    }
+}
+
+impl DatasetStorageWriter for ColumnSegmentWriter {
+    fn write_row(&mut self, row_number: usize, row_values: &[String]) -> RowsAndColumnsResult<()> {
+        let segment_row_count = self.storage_layout.storage_settings.segment_row_count;
+        let segment_number = (row_number - 1) / segment_row_count + 1;
+        let row_within_segment = (row_number - 1) % segment_row_count;
+
+        for (column_position, cell_value) in row_values.iter().enumerate() {
+            let column_directory = self.storage_layout.dataset_directory
+                .join(&self.storage_layout.column_directory_names[column_position]);
+            let open_segment = &mut self.open_segments[column_position];
+
+            // Move on to a new segment file when the row belongs to the next one
+            if open_segment.as_ref().map(|segment| segment.segment_number) != Some(segment_number) {
+                if let Some(finished_segment) = open_segment.as_mut() {
+                    flush_column_segment(finished_segment, &column_directory)?;
+                }
+                *open_segment = Some(create_column_segment(&column_directory, segment_number)?);
+            }
+
+            if let Some(current_segment) = open_segment.as_mut() {
+                let segment_values_path = segment_file_path(&column_directory, segment_number, SEGMENT_VALUES_FILE_EXTENSION);
+                if row_within_segment.is_multiple_of(SEGMENT_INDEX_ROW_INTERVAL) {
+                    writeln!(current_segment.index_writer, "{}", current_segment.written_byte_count).map_err(|io_error| {
+                        create_file_system_error(
+                            &format!("Failed to write segment index for: {}", segment_values_path.display()),
+                            io_error
+                        )
+                    })?;
+                }
+
+                let value_line = format!("{}\n", escape_segment_value(cell_value));
+                current_segment.values_writer.write_all(value_line.as_bytes()).map_err(|io_error| {
+                    create_file_system_error(&format!("Failed to write segment file: {}", segment_values_path.display()), io_error)
+                })?;
+                current_segment.written_byte_count += value_line.len() as u64;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn flush_written_rows(&mut self) -> RowsAndColumnsResult<()> {
+        for (column_position, open_segment) in self.open_segments.iter_mut().enumerate() {
+            if let Some(current_segment) = open_segment.as_mut() {
+                let column_directory = self.storage_layout.dataset_directory
+                    .join(&self.storage_layout.column_directory_names[column_position]);
+                flush_column_segment(current_segment, &column_directory)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Where the reader of one column currently is
+struct ColumnSegmentCursor {
+    /// 1-based segment number of the open file
+    segment_number: usize,
+
+    /// Data row number of the next line the reader returns
+    next_row_number: usize,
+
+    /// The open values file
+    values_reader: BufReader<File>,
+}
+
+/// Reader for the `column_segments` backend
+struct ColumnSegmentReader {
+    /// Where the columns are stored
+    storage_layout: DatasetStorageLayout,
+
+    /// Number of data rows in the dataset
+    stored_row_count: usize,
+
+    /// The open position of each column, so rows read in order need no seeking
+    column_cursors: Vec<Option<ColumnSegmentCursor>>,
+}
+
+impl ColumnSegmentReader {
+    /// Reads the value of one row of one column
+    ///
+    /// # Arguments
+    /// * `column_position` - Position of the column in the layout
+    /// * `row_number` - 1-based data row number
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<String>` - The cell value
+    fn read_cell_value(&mut self, column_position: usize, row_number: usize) -> RowsAndColumnsResult<String> {
+        let segment_row_count = self.storage_layout.storage_settings.segment_row_count;
+        let segment_number = (row_number - 1) / segment_row_count + 1;
+        let column_directory = self.storage_layout.dataset_directory
+            .join(&self.storage_layout.column_directory_names[column_position]);
+        let segment_values_path = segment_file_path(&column_directory, segment_number, SEGMENT_VALUES_FILE_EXTENSION);
+
+        // Keep reading forward when the row is just ahead; otherwise seek using the index
+        let cursor_is_usable = self.column_cursors[column_position].as_ref().is_some_and(|column_cursor| {
+            column_cursor.segment_number == segment_number
+                && column_cursor.next_row_number <= row_number
+                && row_number - column_cursor.next_row_number < SEGMENT_INDEX_ROW_INTERVAL
+        });
+        if !cursor_is_usable {
+            self.column_cursors[column_position] = Some(seek_column_segment(
+                &column_directory,
+                segment_number,
+                segment_row_count,
+                row_number,
+            )?);
+        }
+
+        let column_cursor = self.column_cursors[column_position].as_mut().ok_or_else(|| {
+            create_configuration_error(&format!("No open segment for {}", segment_values_path.display()))
+        })?;
+        let mut value_line = String::new();
+        while column_cursor.next_row_number <= row_number {
+            value_line.clear();
+            let read_byte_count = column_cursor.values_reader.read_line(&mut value_line).map_err(|io_error| {
+                create_file_system_error(&format!("Failed to read segment file: {}", segment_values_path.display()), io_error)
+            })?;
+            if read_byte_count == 0 {
+                return Err(create_configuration_error(&format!(
+                    "Segment file ends before row {}: {}",
+                    row_number,
+                    segment_values_path.display()
+                )));
+            }
+            column_cursor.next_row_number += 1;
+        }
+
+        Ok(unescape_segment_value(value_line.trim_end_matches('\n')))
+    }
+}
+
+impl DatasetStorageReader for ColumnSegmentReader {
+    fn stores_column(&self, column_index: usize) -> bool {
+        self.storage_layout.column_position(column_index).is_some()
+    }
+
+    fn read_row(&mut self, row_number: usize, column_indexes: &[usize]) -> RowsAndColumnsResult<Vec<String>> {
+        let mut row_values = Vec::with_capacity(column_indexes.len());
+        for column_index in column_indexes {
+            let column_position = find_column_position(&self.storage_layout, *column_index)?;
+            row_values.push(self.read_cell_value(column_position, row_number)?);
+        }
+
+        Ok(row_values)
+    }
+
+    fn read_column_values(&mut self, column_index: usize) -> RowsAndColumnsResult<Vec<String>> {
+        let column_position = find_column_position(&self.storage_layout, column_index)?;
+        let mut column_values = Vec::with_capacity(self.stored_row_count);
+        for row_number in 1..=self.stored_row_count {
+            column_values.push(self.read_cell_value(column_position, row_number)?);
+        }
+
+        // Columns are read one after another; close this one's file
+        self.column_cursors[column_position] = None;
+        Ok(column_values)
+    }
+}
+
+/// Finds a CSV column among the stored columns or explains that it is missing
+///
+/// # Arguments
+/// * `storage_layout` - Where the columns are stored
+/// * `column_index` - 0-based index of the column in the CSV file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<usize>` - Position in the layout
+fn find_column_position(storage_layout: &DatasetStorageLayout, column_index: usize) -> RowsAndColumnsResult<usize> {
+    storage_layout.column_position(column_index).ok_or_else(|| {
+        create_configuration_error(&format!(
+            "Column {} is not stored in dataset {}",
+            column_index + 1,
+            storage_layout.dataset_directory.display()
+        ))
+    })
+}
+
+/// Path of a segment's values or index file
+///
+/// # Arguments
+/// * `column_directory` - The column directory
+/// * `segment_number` - 1-based segment number
+/// * `file_extension` - `txt` or `index`
+///
+/// # Returns
+/// * `PathBuf` - e.g. `<column directory>/segment_000001.txt`
+fn segment_file_path(column_directory: &Path, segment_number: usize, file_extension: &str) -> PathBuf {
+    column_directory.join(format!("{}{:06}.{}", SEGMENT_FILE_PREFIX, segment_number, file_extension))
+}
+
+/// Creates (or empties) a segment's values and index files
+///
+/// # Arguments
+/// * `column_directory` - The column directory
+/// * `segment_number` - 1-based segment number
+///
+/// # Returns
+/// * `RowsAndColumnsResult<OpenColumnSegment>` - The open, empty segment
+fn create_column_segment(column_directory: &Path, segment_number: usize) -> RowsAndColumnsResult<OpenColumnSegment> {
+    let create_segment_file = |file_extension: &str| -> RowsAndColumnsResult<BufWriter<File>> {
+        let segment_path = segment_file_path(column_directory, segment_number, file_extension);
+        File::create(&segment_path).map(BufWriter::new).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to create segment file: {}", segment_path.display()), io_error)
+        })
+    };
+
+    Ok(OpenColumnSegment {
+        segment_number,
+        values_writer: create_segment_file(SEGMENT_VALUES_FILE_EXTENSION)?,
+        index_writer: create_segment_file(SEGMENT_INDEX_FILE_EXTENSION)?,
+        written_byte_count: 0,
+    })
+}
+
+/// Reopens a segment for appending, keeping only its first rows
+///
+/// Lines past `kept_row_count` were written after the last progress
+/// checkpoint; they are cut off and the index is rebuilt for the kept lines.
+///
+/// # Arguments
+/// * `column_directory` - The column directory
+/// * `segment_number` - 1-based segment number
+/// * `kept_row_count` - Rows of the segment to keep
+///
+/// # Returns
+/// * `RowsAndColumnsResult<OpenColumnSegment>` - The segment, positioned after the kept rows
+fn reopen_column_segment(
+    column_directory: &Path,
+    segment_number: usize,
+    kept_row_count: usize,
+) -> RowsAndColumnsResult<OpenColumnSegment> {
+    let segment_values_path = segment_file_path(column_directory, segment_number, SEGMENT_VALUES_FILE_EXTENSION);
+    let segment_index_path = segment_file_path(column_directory, segment_number, SEGMENT_INDEX_FILE_EXTENSION);
+    let segment_read_error = |io_error| {
+        create_file_system_error(&format!("Failed to reopen segment file: {}", segment_values_path.display()), io_error)
+    };
+
+    // Find the byte offset after the kept rows, noting index offsets on the way
+    let mut values_reader = BufReader::new(File::open(&segment_values_path).map_err(segment_read_error)?);
+    let mut index_offsets = Vec::new();
+    let mut kept_byte_count: u64 = 0;
+    let mut value_line = Vec::new();
+    for row_within_segment in 0..kept_row_count {
+        if row_within_segment.is_multiple_of(SEGMENT_INDEX_ROW_INTERVAL) {
+            index_offsets.push(kept_byte_count);
+        }
+        value_line.clear();
+        let read_byte_count = values_reader.read_until(b'\n', &mut value_line).map_err(segment_read_error)?;
+        if read_byte_count == 0 {
+            return Err(create_configuration_error(&format!(
+                "Segment file has fewer rows than the import progress records: {}",
+                segment_values_path.display()
+            )));
+        }
+        kept_byte_count += read_byte_count as u64;
+    }
+
+    // In append mode every write lands at the (new) end of the file
+    let values_file = OpenOptions::new().append(true).open(&segment_values_path).map_err(segment_read_error)?;
+    values_file.set_len(kept_byte_count).map_err(segment_read_error)?;
+
+    let index_content: String = index_offsets.iter().map(|index_offset| format!("{}\n", index_offset)).collect();
+    fs::write(&segment_index_path, index_content).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to rebuild segment index: {}", segment_index_path.display()), io_error)
+    })?;
+    let index_file = OpenOptions::new().append(true).open(&segment_index_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to reopen segment index: {}", segment_index_path.display()), io_error)
+    })?;
+
+    Ok(OpenColumnSegment {
+        segment_number,
+        values_writer: BufWriter::new(values_file),
+        index_writer: BufWriter::new(index_file),
+        written_byte_count: kept_byte_count,
+    })
+}
+
+/// Flushes a segment's buffered lines to disk
+///
+/// # Arguments
+/// * `open_segment` - The segment
+/// * `column_directory` - The column directory (for error messages)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn flush_column_segment(open_segment: &mut OpenColumnSegment, column_directory: &Path) -> RowsAndColumnsResult<()> {
+    let segment_values_path = segment_file_path(column_directory, open_segment.segment_number, SEGMENT_VALUES_FILE_EXTENSION);
+    open_segment.values_writer.flush()
+        .and_then(|_| open_segment.index_writer.flush())
+        .map_err(|io_error| {
+            create_file_system_error(&format!("Failed to write segment file: {}", segment_values_path.display()), io_error)
+        })
+}
+
+/// Opens a segment positioned at the indexed row at or before the wanted row
+///
+/// # Arguments
+/// * `column_directory` - The column directory
+/// * `segment_number` - 1-based segment number
+/// * `segment_row_count` - Rows per segment
+/// * `row_number` - 1-based data row number wanted
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ColumnSegmentCursor>` - Cursor at or shortly before the row
+fn seek_column_segment(
+    column_directory: &Path,
+    segment_number: usize,
+    segment_row_count: usize,
+    row_number: usize,
+) -> RowsAndColumnsResult<ColumnSegmentCursor> {
+    let segment_values_path = segment_file_path(column_directory, segment_number, SEGMENT_VALUES_FILE_EXTENSION);
+    let segment_index_path = segment_file_path(column_directory, segment_number, SEGMENT_INDEX_FILE_EXTENSION);
+    let first_row_of_segment = (segment_number - 1) * segment_row_count + 1;
+    let index_entry_number = (row_number - first_row_of_segment) / SEGMENT_INDEX_ROW_INTERVAL;
+
+    let index_content = fs::read_to_string(&segment_index_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to read segment index: {}", segment_index_path.display()), io_error)
+    })?;
+    let byte_offset = index_content
+        .lines()
+        .nth(index_entry_number)
+        .and_then(|index_line| index_line.trim().parse::<u64>().ok())
+        .ok_or_else(|| {
+            create_configuration_error(&format!(
+                "Segment index has no entry for row {}: {}",
+                row_number,
+                segment_index_path.display()
+            ))
+        })?;
+
+    let mut values_file = File::open(&segment_values_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to open segment file: {}", segment_values_path.display()), io_error)
+    })?;
+    values_file.seek(SeekFrom::Start(byte_offset)).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to seek in segment file: {}", segment_values_path.display()), io_error)
+    })?;
+
+    Ok(ColumnSegmentCursor {
+        segment_number,
+        next_row_number: first_row_of_segment + index_entry_number * SEGMENT_INDEX_ROW_INTERVAL,
+        values_reader: BufReader::new(values_file),
+    })
+}
+
+/// Escapes a value so it fits on one segment line
+///
+/// # Arguments
+/// * `cell_value` - The value
+///
+/// # Returns
+/// * `String` - The value with `\`, line feeds and carriage returns escaped
+pub fn escape_segment_value(cell_value: &str) -> String {
+    let mut escaped_value = String::with_capacity(cell_value.len());
+    for character in cell_value.chars() {
+        match character {
+            '\\' => escaped_value.push_str("\\\\"),
+            '\n' => escaped_value.push_str("\\n"),
+            '\r' => escaped_value.push_str("\\r"),
+            _ => escaped_value.push(character),
+        }
+    }
+    escaped_value
+}
+
+/// Reverses `escape_segment_value`
+///
+/// # Arguments
+/// * `escaped_value` - One segment line without its line feed
+///
+/// # Returns
+/// * `String` - The original value
+pub fn unescape_segment_value(escaped_value: &str) -> String {
+    let mut cell_value = String::with_capacity(escaped_value.len());
+    let mut characters = escaped_value.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            cell_value.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('n') => cell_value.push('\n'),
+            Some('r') => cell_value.push('\r'),
+            Some(escaped_character) => cell_value.push(escaped_character),
+            None => cell_value.push('\\'),
+        }
+    }
+    cell_value
+}
+
+/// Creates a directory and any missing parents
+///
+/// # Arguments
+/// * `directory_path` - The directory to create
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn create_directory(directory_path: &Path) -> RowsAndColumnsResult<()> {
+    fs::create_dir_all(directory_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to create directory: {}", directory_path.display()), io_error)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_value_escaping_round_trip() {
+        for cell_value in ["plain", "", "two\nlines", "c:\\temp\\new", "cr\r\nlf", "trailing\\"] {
+            let escaped_value = escape_segment_value(cell_value);
+
+            assert!(!escaped_value.contains('\n'));
+            assert_eq!(unescape_segment_value(&escaped_value), cell_value);
+        }
+    }
+
+    #[test]
+    fn test_column_directory_numbers() {
+        let storage_layout = DatasetStorageLayout {
+            dataset_directory: PathBuf::from("dataset_001_sales"),
+            column_directory_names: vec!["column_001_id".to_string(), "column_003_amount".to_string()],
+            row_number_digits: 4,
+            storage_settings: DatasetStorageSettings::default(),
+        };
+
+        assert_eq!(parse_column_directory_number("column_012_total_2024"), Some(12));
+        assert_eq!(parse_column_directory_number("notes"), None);
+        assert_eq!(storage_layout.column_position(2), Some(1));
+        assert_eq!(storage_layout.column_position(1), None);
+        assert_eq!(format_row_directory_name(42, 4), "row_0042");
+        assert_eq!(format_row_directory_name(123_456, 6), "row_123456");
+    }
+
+    #[test]
+    fn test_backend_names() {
+        assert_eq!(
+            DatasetStorageBackend::from_toml_string("Column_Segments"),
+            Some(DatasetStorageBackend::ColumnSegments)
+        );
+        assert_eq!(
+            DatasetStorageBackend::from_toml_string(DatasetStorageBackend::CellFiles.to_toml_string()),
+            Some(DatasetStorageBackend::CellFiles)
+        );
+        assert_eq!(DatasetStorageBackend::from_toml_string("sqlite"), None);
+    }
+}
@@ -0,0 +1,287 @@
+// src/decimal_value_module.rs
+
+/// Fixed-point decimal and currency values for rows_and_columns
+///
+/// Money columns such as `$1,234.50`, `(12.30)` or `-€7.05` are parsed into an
+/// `i128` count of the smallest unit (the "unscaled value") plus a scale, the
+/// number of digits after the decimal point. Sums and means are computed on the
+/// unscaled values, so no binary floating point rounding is involved.
+///
+/// # Recognised Forms
+/// - An optional currency symbol (`$ € £ ¥ ₹ ₩ ₽ ₺ ¢`) before or after the number
+/// - A leading `-` or `+` (before or after a leading currency symbol)
+/// - Parentheses for negatives, as used in accounting: `(1,234.50)`
+/// - Thousands separators `,` in groups of three: `1,234,567.89`
+/// - `.` as the decimal point, with up to `MAXIMUM_DECIMAL_SCALE` fraction digits
+use std::fmt;
+
+/// Largest number of digits after the decimal point
+pub const MAXIMUM_DECIMAL_SCALE: u32 = 18;
+
+/// Largest number of significant digits (keeps sums of many values inside `i128`)
+const MAXIMUM_DECIMAL_DIGITS: usize = 30;
+
+/// Currency symbols accepted before or after a number
+const CURRENCY_SYMBOLS: [char; 9] = ['$', '€', '£', '¥', '₹', '₩', '₽', '₺', '¢'];
+
+/// A fixed-point decimal number: `unscaled_value / 10^scale`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedPointDecimal {
+    /// The number multiplied by `10^scale`
+    pub unscaled_value: i128,
+
+    /// Number of digits after the decimal point
+    pub scale: u32,
+}
+
+impl FixedPointDecimal {
+    /// Expresses the number with more digits after the decimal point
+    ///
+    /// # Arguments
+    /// * `target_scale` - The new scale (must not be smaller than the current one)
+    ///
+    /// # Returns
+    /// * `Option<FixedPointDecimal>` - The same number at the new scale, or None on overflow
+    pub fn rescaled(self, target_scale: u32) -> Option<FixedPointDecimal> {
+        let scale_increase = target_scale.checked_sub(self.scale)?;
+        let unscaled_value = self.unscaled_value.checked_mul(10_i128.checked_pow(scale_increase)?)?;
+
+        Some(FixedPointDecimal { unscaled_value, scale: target_scale })
+    }
+
+    /// Converts to the nearest `f64` (for quartiles and standard deviation)
+    ///
+    /// # Returns
+    /// * `f64` - Approximate value
+    pub fn to_f64(self) -> f64 {
+        self.unscaled_value as f64 / 10_f64.powi(self.scale as i32)
+    }
+
+    /// Formats the number with thousands separators and an optional currency symbol
+    ///
+    /// # Arguments
+    /// * `currency_symbol` - Symbol to place before the digits
+    ///
+    /// # Returns
+    /// * `String` - e.g. "-$1,234.50"
+    pub fn format_for_display(self, currency_symbol: Option<char>) -> String {
+        let plain_text = self.to_string();
+        let (sign_text, unsigned_text) = match plain_text.strip_prefix('-') {
+            Some(unsigned_text) => ("-", unsigned_text),
+            None => ("", plain_text.as_str()),
+        };
+        let (integer_text, fraction_text) = match unsigned_text.split_once('.') {
+            Some((integer_text, fraction_text)) => (integer_text, Some(fraction_text)),
+            None => (unsigned_text, None),
+        };
+
+        let mut grouped_integer_text = String::with_capacity(integer_text.len() + integer_text.len() / 3);
+        for (digit_index, digit) in integer_text.chars().enumerate() {
+            if digit_index > 0 && (integer_text.len() - digit_index) % 3 == 0 {
+                grouped_integer_text.push(',');
+            }
+            grouped_integer_text.push(digit);
+        }
+
+        let currency_text = currency_symbol.map(String::from).unwrap_or_default();
+        match fraction_text {
+            Some(fraction_text) => format!("{}{}{}.{}", sign_text, currency_text, grouped_integer_text, fraction_text),
+            None => format!("{}{}{}", sign_text, currency_text, grouped_integer_text),
+        }
+    }
+}
+
+impl fmt::Display for FixedPointDecimal {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign_text = if self.unscaled_value < 0 { "-" } else { "" };
+        let digit_text = self.unscaled_value.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(formatter, "{}{}", sign_text, digit_text);
+        }
+
+        let padded_digit_text = format!("{:0>width$}", digit_text, width = scale + 1);
+        let (integer_text, fraction_text) = padded_digit_text.split_at(padded_digit_text.len() - scale);
+        write!(formatter, "{}{}.{}", sign_text, integer_text, fraction_text)
+    }
+}
+
+/// A parsed decimal value together with the currency symbol it was written with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParsedDecimalValue {
+    /// The number
+    pub decimal_value: FixedPointDecimal,
+
+    /// Currency symbol found before or after the number
+    pub currency_symbol: Option<char>,
+}
+
+/// Parses a money or plain decimal value
+///
+/// # Arguments
+/// * `trimmed_value` - The value to parse
+///
+/// # Returns
+/// * `Option<ParsedDecimalValue>` - The value, or None if it is not a decimal number
+pub fn parse_decimal_value(trimmed_value: &str) -> Option<ParsedDecimalValue> {
+    let mut remaining_text = trimmed_value;
+    let mut is_negative = false;
+    let mut currency_symbol = None;
+
+    // Accounting negatives: (1,234.50)
+    if let Some(parenthesized_text) = remaining_text.strip_prefix('(').and_then(|text| text.strip_suffix(')')) {
+        is_negative = true;
+        remaining_text = parenthesized_text.trim();
+    }
+
+    // Sign, then currency symbol, then possibly a sign again: -$12.30 or $-12.30
+    for _ in 0..2 {
+        if let Some(unsigned_text) = remaining_text.strip_prefix('-') {
+            if is_negative {
+                return None;
+            }
+            is_negative = true;
+            remaining_text = unsigned_text.trim_start();
+        } else if let Some(unsigned_text) = remaining_text.strip_prefix('+') {
+            remaining_text = unsigned_text.trim_start();
+        }
+
+        if currency_symbol.is_none()
+            && let Some(first_character) = remaining_text.chars().next()
+            && CURRENCY_SYMBOLS.contains(&first_character)
+        {
+            currency_symbol = Some(first_character);
+            remaining_text = remaining_text[first_character.len_utf8()..].trim_start();
+        }
+    }
+
+    // Trailing currency symbol: 12.30 €
+    if currency_symbol.is_none()
+        && let Some(last_character) = remaining_text.chars().last()
+        && CURRENCY_SYMBOLS.contains(&last_character)
+    {
+        currency_symbol = Some(last_character);
+        remaining_text = remaining_text[..remaining_text.len() - last_character.len_utf8()].trim_end();
+    }
+
+    let (integer_text, fraction_text) = match remaining_text.split_once('.') {
+        Some((integer_text, fraction_text)) => (integer_text, fraction_text),
+        None => (remaining_text, ""),
+    };
+    let has_decimal_point = remaining_text.contains('.');
+
+    let integer_digits = strip_thousands_separators(integer_text)?;
+    let fraction_is_valid = fraction_text.chars().all(|character| character.is_ascii_digit())
+        && (!has_decimal_point || !fraction_text.is_empty());
+    if !fraction_is_valid || (integer_digits.is_empty() && fraction_text.is_empty()) {
+        return None;
+    }
+
+    let scale = fraction_text.len() as u32;
+    let all_digits = format!("{}{}", integer_digits, fraction_text);
+    if scale > MAXIMUM_DECIMAL_SCALE || all_digits.len() > MAXIMUM_DECIMAL_DIGITS {
+        return None;
+    }
+
+    let magnitude = all_digits.parse::<i128>().ok()?;
+    Some(ParsedDecimalValue {
+        decimal_value: FixedPointDecimal {
+            unscaled_value: if is_negative { -magnitude } else { magnitude },
+            scale,
+        },
+        currency_symbol,
+    })
+}
+
+/// Divides, rounding halves away from zero
+///
+/// # Arguments
+/// * `numerator` - The dividend
+/// * `denominator` - The divisor (must be positive)
+///
+/// # Returns
+/// * `i128` - The rounded quotient
+pub fn divide_rounding_half_away_from_zero(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+
+    if remainder.unsigned_abs() * 2 >= denominator.unsigned_abs() {
+        quotient + numerator.signum()
+    } else {
+        quotient
+    }
+}
+
+/// Removes `,` thousands separators after checking their grouping
+///
+/// # Arguments
+/// * `integer_text` - Digits before the decimal point
+///
+/// # Returns
+/// * `Option<String>` - Digits only, or None if the grouping is wrong or a non-digit appears
+fn strip_thousands_separators(integer_text: &str) -> Option<String> {
+    let digit_groups: Vec<&str> = integer_text.split(',').collect();
+    let all_groups_are_digits = digit_groups
+        .iter()
+        .all(|digit_group| digit_group.chars().all(|character| character.is_ascii_digit()));
+    if !all_groups_are_digits {
+        return None;
+    }
+
+    if digit_groups.len() > 1 {
+        let first_group_is_valid = (1..=3).contains(&digit_groups[0].len());
+        let later_groups_are_valid = digit_groups[1..].iter().all(|digit_group| digit_group.len() == 3);
+        if !first_group_is_valid || !later_groups_are_valid {
+            return None;
+        }
+    }
+
+    Some(digit_groups.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper to parse a value that is expected to be a decimal
+    fn parsed(value_text: &str) -> Option<(i128, u32, Option<char>)> {
+        parse_decimal_value(value_text).map(|parsed_value| {
+            (parsed_value.decimal_value.unscaled_value, parsed_value.decimal_value.scale, parsed_value.currency_symbol)
+        })
+    }
+
+    #[test]
+    fn test_currency_forms() {
+        assert_eq!(parsed("$1,234.50"), Some((123_450, 2, Some('$'))));
+        assert_eq!(parsed("-12.30"), Some((-1_230, 2, None)));
+        assert_eq!(parsed("(1,234.50)"), Some((-123_450, 2, None)));
+        assert_eq!(parsed("($7.05)"), Some((-705, 2, Some('$'))));
+        assert_eq!(parsed("$-7.05"), Some((-705, 2, Some('$'))));
+        assert_eq!(parsed("12.30 €"), Some((1_230, 2, Some('€'))));
+        assert_eq!(parsed("1,234,567"), Some((1_234_567, 0, None)));
+    }
+
+    #[test]
+    fn test_rejected_forms() {
+        for rejected_text in ["", "$", "12,34.00", "1,2345", "--5", "(-5)", "1.2.3", "12.", "1e5", "abc"] {
+            assert_eq!(parse_decimal_value(rejected_text), None, "{}", rejected_text);
+        }
+    }
+
+    #[test]
+    fn test_formatting_and_rounding() {
+        let negative_amount = FixedPointDecimal { unscaled_value: -123_456_789, scale: 2 };
+
+        assert_eq!(negative_amount.to_string(), "-1234567.89");
+        assert_eq!(negative_amount.format_for_display(Some('$')), "-$1,234,567.89");
+        assert_eq!(FixedPointDecimal { unscaled_value: 5, scale: 3 }.to_string(), "0.005");
+        assert_eq!(
+            FixedPointDecimal { unscaled_value: 15, scale: 1 }.rescaled(3),
+            Some(FixedPointDecimal { unscaled_value: 1_500, scale: 3 })
+        );
+        assert_eq!(divide_rounding_half_away_from_zero(5, 2), 3);
+        assert_eq!(divide_rounding_half_away_from_zero(-5, 2), -3);
+        assert_eq!(divide_rounding_half_away_from_zero(7, 3), 2);
+    }
+}
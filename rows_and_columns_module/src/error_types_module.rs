@@ -34,6 +34,18 @@ pub enum RowsAndColumnsError {
         csv_column_identifier: Option<String>,
     },
     
+    /// Input bytes that cannot be decoded in the file's text encoding
+    TextEncodingError {
+        /// Description of the decoding operation that failed
+        encoding_operation_description: String,
+        /// Name of the text encoding being decoded (e.g. "utf-8", "utf-16le")
+        text_encoding_name: String,
+        /// Byte offset in the source file where the undecodable data starts
+        undecodable_byte_offset_start: u64,
+        /// Byte offset in the source file just past the undecodable data
+        undecodable_byte_offset_end: u64,
+    },
+    
     /// Metadata file (TOML) parsing or validation errors
     MetadataError {
         /// Description of the metadata operation that failed
@@ -116,6 +128,22 @@ impl fmt::Display for RowsAndColumnsError {
                 )
             }
             
+            RowsAndColumnsError::TextEncodingError { 
+                encoding_operation_description, 
+                text_encoding_name, 
+                undecodable_byte_offset_start, 
+                undecodable_byte_offset_end 
+            } => {
+                write!(
+                    formatter,
+                    "Text decoding failed: {} - Bytes {}..{} are not valid {}",
+                    encoding_operation_description,
+                    undecodable_byte_offset_start,
+                    undecodable_byte_offset_end,
+                    text_encoding_name
+                )
+            }
+            
             RowsAndColumnsError::MetadataError { 
                 metadata_operation_description, 
                 metadata_file_path 
@@ -241,6 +269,30 @@ pub fn create_csv_processing_error(
     }
 }
 
+/// Helper function to create text encoding errors with context
+/// 
+/// # Arguments
+/// * `encoding_operation_description` - Description of the decoding operation that failed
+/// * `text_encoding_name` - Name of the text encoding being decoded
+/// * `undecodable_byte_offset_start` - Source byte offset where the bad data starts
+/// * `undecodable_byte_offset_end` - Source byte offset just past the bad data
+/// 
+/// # Returns
+/// * `RowsAndColumnsError` - A properly contextualized text encoding error
+pub fn create_text_encoding_error(
+    encoding_operation_description: &str,
+    text_encoding_name: &str,
+    undecodable_byte_offset_start: u64,
+    undecodable_byte_offset_end: u64
+) -> RowsAndColumnsError {
+    RowsAndColumnsError::TextEncodingError {
+        encoding_operation_description: encoding_operation_description.to_string(),
+        text_encoding_name: text_encoding_name.to_string(),
+        undecodable_byte_offset_start,
+        undecodable_byte_offset_end,
+    }
+}
+
 /// Helper function to create metadata errors with context
 /// 
 /// # Arguments
@@ -379,6 +431,16 @@ mod tests {
         }
     }
 
+    /// Test error display formatting for text encoding errors
+    #[test]
+    fn test_text_encoding_error_display() {
+        let encoding_error = create_text_encoding_error("Reading CSV record", "utf-8", 1041, 1042);
+
+        let error_string = encoding_error.to_string();
+        assert!(error_string.contains("1041..1042"));
+        assert!(error_string.contains("utf-8"));
+    }
+
     /// Test helper function for creating configuration errors
     #[test]
     fn test_create_configuration_error_helper() {
@@ -0,0 +1,360 @@
+// src/lookup_crate_generator_module.rs
+
+/// Standalone lookup program generation for rows_and_columns
+///
+/// Turns one column of an imported dataset into a tiny, dependency-free Rust
+/// crate whose binary is a precompiled value-to-row table: every distinct
+/// value with the rows that hold it, as a sorted static array searched by
+/// binary search. The crate can be copied anywhere and built with plain
+/// `cargo build`; it never reads the dataset again.
+///
+/// # Generated Crate
+/// ```text
+/// <output directory>/
+/// ├── Cargo.toml                      # no dependencies
+/// └── src/
+///     ├── main.rs                     # the table, the lookup and main()
+///     └── original_column_values.rs   # the column in row order (tests only)
+/// ```
+/// The binary looks up each command line argument, or each line of standard
+/// input when there are no arguments, and prints the value, a tab, and the
+/// comma-separated 1-based row numbers. `cargo test` in the generated crate
+/// checks every key and every row against the original column values.
+///
+/// # Command Line
+/// ```text
+/// rows_and_columns --generate-lookup <dataset> <column> <output directory>
+/// ```
+/// The output directory must not exist yet (or be empty).
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Import dataset column selection from the lookup indexes
+use super::column_lookup_index_module::open_dataset_column;
+
+// Import the name sanitizing used for dataset and column directories
+use super::csv_directory_import_module::sanitize_directory_name_component;
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_configuration_error,
+    create_file_system_error,
+};
+
+/// Source of the generated `src/main.rs`; the `@NAME@` markers are filled in
+const LOOKUP_MAIN_SOURCE_TEMPLATE: &str = r#"//! Lookup table for column `@COLUMN_NAME@` of dataset `@DATASET_NAME@`
+//!
+//! Generated by rows_and_columns (@ROW_COUNT@ rows, @DISTINCT_VALUE_COUNT@ distinct values).
+//! The table is a snapshot: generate the crate again when the dataset changes.
+//!
+//! Usage:
+//!   @CRATE_NAME@ <value> [<value> ...]   look up each argument
+//!   @CRATE_NAME@ < values.txt            look up each line of standard input
+//!
+//! Each lookup prints the value, a tab, and the 1-based numbers of the rows
+//! holding it (comma-separated, empty when the value does not occur). The exit
+//! status is 1 when any value was not found.
+
+use std::io::{self, BufRead, Write};
+use std::process;
+
+#[cfg(test)]
+mod original_column_values;
+
+/// Every distinct value of the column with the rows holding it, sorted by value
+static LOOKUP_ENTRIES: &[(&str, &[usize])] = &[
+@LOOKUP_ENTRIES@];
+
+/// Finds the rows holding a value
+///
+/// # Arguments
+/// * `looked_up_value` - The value, matched exactly
+///
+/// # Returns
+/// * `Option<&'static [usize]>` - The 1-based row numbers, or None if the value does not occur
+fn look_up_rows(looked_up_value: &str) -> Option<&'static [usize]> {
+    LOOKUP_ENTRIES
+        .binary_search_by(|(entry_value, _)| (*entry_value).cmp(looked_up_value))
+        .ok()
+        .map(|entry_position| LOOKUP_ENTRIES[entry_position].1)
+}
+
+/// Prints one lookup as `value<TAB>rows`
+///
+/// # Arguments
+/// * `output` - Where to print
+/// * `looked_up_value` - The value to look up
+///
+/// # Returns
+/// * `io::Result<bool>` - Whether the value was found
+fn print_lookup(output: &mut impl Write, looked_up_value: &str) -> io::Result<bool> {
+    let row_numbers = look_up_rows(looked_up_value).unwrap_or(&[]);
+    let row_number_texts: Vec<String> = row_numbers.iter().map(|row_number| row_number.to_string()).collect();
+    writeln!(output, "{}\t{}", looked_up_value, row_number_texts.join(","))?;
+    Ok(!row_numbers.is_empty())
+}
+
+/// Looks up the arguments, or the lines of standard input when there are none
+///
+/// # Returns
+/// * `io::Result<bool>` - Whether every value was found
+fn run_lookups() -> io::Result<bool> {
+    let lookup_arguments: Vec<String> = std::env::args().skip(1).collect();
+    let standard_output = io::stdout();
+    let mut output = standard_output.lock();
+    let mut every_value_found = true;
+
+    if lookup_arguments.is_empty() {
+        for input_line in io::stdin().lock().lines() {
+            let input_line = input_line?;
+            every_value_found &= print_lookup(&mut output, input_line.trim_end_matches('\r'))?;
+        }
+    } else {
+        for lookup_argument in &lookup_arguments {
+            every_value_found &= print_lookup(&mut output, lookup_argument)?;
+        }
+    }
+
+    output.flush()?;
+    Ok(every_value_found)
+}
+
+fn main() {
+    match run_lookups() {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(io_error) => {
+            eprintln!("Error: {}", io_error);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::original_column_values::ORIGINAL_COLUMN_VALUES;
+
+    #[test]
+    fn test_every_key_matches_the_original_data() {
+        // The table must be strictly sorted for the binary search
+        assert!(LOOKUP_ENTRIES.windows(2).all(|entry_pair| entry_pair[0].0 < entry_pair[1].0));
+
+        // Every row is found under its own value
+        for (row_position, original_value) in ORIGINAL_COLUMN_VALUES.iter().enumerate() {
+            let row_numbers = look_up_rows(original_value).expect("every original value should be a key");
+            assert!(row_numbers.contains(&(row_position + 1)), "row {} missing under {:?}", row_position + 1, original_value);
+        }
+
+        // Every listed row holds the value it is listed under, and each row is listed once
+        let mut listed_row_count = 0;
+        for (entry_value, row_numbers) in LOOKUP_ENTRIES {
+            for row_number in row_numbers.iter() {
+                assert_eq!(ORIGINAL_COLUMN_VALUES[row_number - 1], *entry_value, "row {}", row_number);
+            }
+            listed_row_count += row_numbers.len();
+        }
+        assert_eq!(listed_row_count, ORIGINAL_COLUMN_VALUES.len());
+    }
+}
+"#;
+
+/// What the lookup crate generation wrote
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupCrateGenerationSummary {
+    /// The generated crate's directory
+    pub crate_directory: PathBuf,
+
+    /// The generated crate's package and binary name
+    pub crate_name: String,
+
+    /// Name of the column the table was made from
+    pub column_name: String,
+
+    /// Number of rows in the table
+    pub row_count: usize,
+
+    /// Number of distinct values (table entries)
+    pub distinct_value_count: usize,
+}
+
+/// Generates a standalone lookup crate for one dataset column
+///
+/// # Arguments
+/// * `dataset_directory` - The dataset directory
+/// * `column_selector` - Column name, column directory name or 1-based column number
+/// * `crate_directory` - Where to create the crate (must not exist or be empty)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<LookupCrateGenerationSummary>` - What was written
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the import is unfinished, the column is unknown,
+///   or the output directory is not empty
+/// * `RowsAndColumnsError::FileSystemError` - If the column cannot be read or the crate cannot be written
+pub fn generate_column_lookup_crate(
+    dataset_directory: &Path,
+    column_selector: &str,
+    crate_directory: &Path,
+) -> RowsAndColumnsResult<LookupCrateGenerationSummary> {
+    let (_, mut dataset_storage_reader, dataset_column) = open_dataset_column(dataset_directory, column_selector)?;
+
+    let output_directory_has_entries = fs::read_dir(crate_directory)
+        .map(|mut directory_entries| directory_entries.next().is_some())
+        .unwrap_or(false);
+    if output_directory_has_entries {
+        return Err(create_configuration_error(&format!(
+            "Output directory is not empty: {} (choose another directory)",
+            crate_directory.display()
+        )));
+    }
+
+    let column_values = dataset_storage_reader.read_column_values(dataset_column.column_number.saturating_sub(1))?;
+    let mut rows_by_value: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (value_position, column_value) in column_values.iter().enumerate() {
+        rows_by_value.entry(column_value.as_str()).or_default().push(value_position + 1);
+    }
+
+    let dataset_name = dataset_directory
+        .file_name()
+        .map(|directory_name| directory_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "dataset".to_string());
+    let crate_name = format!(
+        "{}_{}_lookup",
+        sanitize_directory_name_component(&dataset_name),
+        sanitize_directory_name_component(&dataset_column.column_name)
+    );
+
+    let main_source = LOOKUP_MAIN_SOURCE_TEMPLATE
+        .replace("@COLUMN_NAME@", &dataset_column.column_name.replace('`', "'"))
+        .replace("@DATASET_NAME@", &dataset_name.replace('`', "'"))
+        .replace("@ROW_COUNT@", &column_values.len().to_string())
+        .replace("@DISTINCT_VALUE_COUNT@", &rows_by_value.len().to_string())
+        .replace("@CRATE_NAME@", &crate_name)
+        .replace("@LOOKUP_ENTRIES@", &format_lookup_entry_lines(&rows_by_value));
+
+    let source_directory = crate_directory.join("src");
+    fs::create_dir_all(&source_directory).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to create directory: {}", source_directory.display()), io_error)
+    })?;
+    write_generated_file(&crate_directory.join("Cargo.toml"), &format_lookup_crate_manifest(&crate_name))?;
+    write_generated_file(&source_directory.join("main.rs"), &main_source)?;
+    write_generated_file(
+        &source_directory.join("original_column_values.rs"),
+        &format_original_column_values_source(&column_values),
+    )?;
+
+    Ok(LookupCrateGenerationSummary {
+        crate_directory: crate_directory.to_path_buf(),
+        crate_name,
+        column_name: dataset_column.column_name,
+        row_count: column_values.len(),
+        distinct_value_count: rows_by_value.len(),
+    })
+}
+
+/// Displays a summary of a generated lookup crate
+///
+/// # Arguments
+/// * `generation_summary` - The summary returned by the generation
+pub fn display_lookup_crate_generation_summary(generation_summary: &LookupCrateGenerationSummary) {
+    println!("✓ Lookup crate written: {}", generation_summary.crate_directory.display());
+    println!("  Crate: {}", generation_summary.crate_name);
+    println!("  Column: {}", generation_summary.column_name);
+    println!(
+        "  Table: {} rows, {} distinct values",
+        generation_summary.row_count, generation_summary.distinct_value_count
+    );
+    println!("  Build and check it with: cargo test && cargo build --release");
+}
+
+/// Formats the entries of the static lookup table, one per line
+///
+/// Values are written with Rust's string escaping (`{:?}`), which always gives
+/// a valid string literal.
+///
+/// # Arguments
+/// * `rows_by_value` - Row numbers per value, in value order
+///
+/// # Returns
+/// * `String` - The entry lines, each ending with a comma and a line feed
+fn format_lookup_entry_lines(rows_by_value: &BTreeMap<&str, Vec<usize>>) -> String {
+    let mut entry_lines = String::new();
+    for (column_value, row_numbers) in rows_by_value {
+        let row_number_texts: Vec<String> = row_numbers.iter().map(|row_number| row_number.to_string()).collect();
+        entry_lines.push_str(&format!("    ({:?}, &[{}]),\n", column_value, row_number_texts.join(", ")));
+    }
+    entry_lines
+}
+
+/// Formats the generated `src/original_column_values.rs`
+///
+/// # Arguments
+/// * `column_values` - The column's values in row order
+///
+/// # Returns
+/// * `String` - The module source
+fn format_original_column_values_source(column_values: &[String]) -> String {
+    let mut module_source = String::new();
+    module_source.push_str("//! The column's values in row order, as read from the dataset (test data)\n\n");
+    module_source.push_str("pub static ORIGINAL_COLUMN_VALUES: &[&str] = &[\n");
+    for column_value in column_values {
+        module_source.push_str(&format!("    {:?},\n", column_value));
+    }
+    module_source.push_str("];\n");
+    module_source
+}
+
+/// Formats the generated `Cargo.toml`
+///
+/// # Arguments
+/// * `crate_name` - Package and binary name
+///
+/// # Returns
+/// * `String` - The manifest
+fn format_lookup_crate_manifest(crate_name: &str) -> String {
+    format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\n",
+        crate_name
+    )
+}
+
+/// Writes one generated file
+///
+/// # Arguments
+/// * `file_path` - The file to create
+/// * `file_content` - Its content
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or a file system error
+fn write_generated_file(file_path: &Path, file_content: &str) -> RowsAndColumnsResult<()> {
+    fs::write(file_path, file_content).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to write generated file: {}", file_path.display()), io_error)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_entries_are_valid_sorted_literals() {
+        let column_values: Vec<String> = ["tabby", "siamese", "a \"quoted\"\nline", "siamese"]
+            .iter()
+            .map(|value| value.to_string())
+            .collect();
+        let mut rows_by_value: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (value_position, column_value) in column_values.iter().enumerate() {
+            rows_by_value.entry(column_value.as_str()).or_default().push(value_position + 1);
+        }
+
+        assert_eq!(
+            format_lookup_entry_lines(&rows_by_value),
+            "    (\"a \\\"quoted\\\"\\nline\", &[3]),\n    (\"siamese\", &[2, 4]),\n    (\"tabby\", &[1]),\n"
+        );
+        assert!(format_original_column_values_source(&column_values).contains("    \"a \\\"quoted\\\"\\nline\",\n"));
+        assert!(LOOKUP_MAIN_SOURCE_TEMPLATE.contains("@LOOKUP_ENTRIES@"));
+    }
+}
@@ -24,6 +24,24 @@ mod error_types_module;
 mod manage_absolute_executable_directory_relative_paths;
 mod rows_and_columns_module;
 mod csv_processor_module;
+mod csv_record_reader_module;
+mod text_encoding_module;
+mod csv_dialect_sniffer_module;
+mod csv_header_detection_module;
+mod column_type_inference_module;
+mod temporal_value_module;
+mod decimal_value_module;
+mod boolean_value_module;
+mod null_value_module;
+mod csv_structure_validator_module;
+mod metadata_manager_module;
+mod column_type_review_module;
+mod dataset_storage_module;
+mod csv_directory_import_module;
+mod csv_directory_export_module;
+mod column_lookup_index_module;
+mod lookup_crate_generator_module;
+mod streaming_statistics_module;
 use rows_and_columns_module::run_rows_and_columns_application;
 
 /// Application entry point - delegates to primary module
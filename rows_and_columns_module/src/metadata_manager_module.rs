@@ -0,0 +1,1818 @@
+// src/metadata_manager_module.rs
+
+/// TOML metadata file reading for rows_and_columns
+///
+/// This module reads the `*.csv_metadata.toml` files that sit next to each CSV
+/// file. Users are expected to open these files in a text editor and adjust
+/// settings (for example the CSV dialect), so every analysis pass reads the
+/// file back before it is rewritten. Only the subset of TOML that the metadata
+/// format uses is supported, implemented with the standard library only.
+///
+/// # Supported TOML Subset
+/// - `# comments` on their own line or after a value
+/// - `[section]` and `[dotted.section]` table headers
+/// - `key = value` pairs with bare keys
+/// - Values: basic strings `"..."` (with `\" \\ \t \n \r \uXXXX` escapes),
+///   literal strings `'...'`, integers, floats, `true` / `false`, and
+///   single-line arrays of strings `["...", '...']`
+///
+/// # The `[dialect]` Section
+/// ```toml
+/// [dialect]
+/// delimiter = ";"            # any single character, e.g. "\t" or "|"
+/// quote_character = "\""     # "" disables quoting
+/// escape_style = "doubled"   # doubled | backslash | none
+/// comment_prefix = "#"       # "" disables comment lines
+/// trim_whitespace = "all"    # all | unquoted | none
+/// line_terminator = "auto"   # auto | lf | crlf | cr
+/// ```
+/// Missing keys fall back to the default dialect for the file extension.
+/// When the section is absent the dialect is sniffed from the file, and the
+/// sniffing result (confidence and runner-up) is written to `[sniffing]`.
+/// Delete the `[dialect]` section to have the file sniffed again.
+///
+/// # Header Detection
+/// The top-level `has_header = true` or `has_header = false` key overrides the
+/// header heuristic. The analysis only writes it back when it was set by hand;
+/// the heuristic's own verdict and score go to `[header_detection]`.
+///
+/// # Null Values
+/// The top-level `null_values = ["", "NA", "N/A", "null", "-", "?"]` array lists
+/// the sentinels that mark a missing value. A `null_values` array inside a
+/// `[column_N]` section replaces the global list for that column.
+///
+/// # The `[type_inference]` Section
+/// `mode = "sample"` (the default) infers column types from the first data rows;
+/// `mode = "full"` reads every row. Each `[column_N]` section then lists how
+/// many values matched each candidate type and the first value that did not.
+/// `date_formats`, `time_formats` and `datetime_formats` list extra formats
+/// (e.g. `["%m/%d/%Y"]`) accepted besides ISO-8601. `true_values` and
+/// `false_values` set the boolean vocabulary (e.g. `["true", "yes", "oui"]`).
+///
+/// # Column Sections
+/// Each `[column_N]` section (N counts from 1) describes one column. Its `name`
+/// replaces the header name. Its `data_type` is the column's declared type
+/// whenever it differs from the `detected_type` written next to it (or when
+/// there is no `detected_type`, as in a hand-written file): a declared type is
+/// used instead of detection, and every value of the column must parse as it.
+/// `ignored = true` leaves the column out of the statistics and the data import.
+/// `decimal_scale` is read back as the smallest scale of a decimal column's
+/// exact sums, so digits seen beyond the sampled rows are kept.
+/// The other keys are written by the analysis.
+///
+/// # The `[validation]` Section
+/// `ragged_row_policy` (reject | pad | truncate | quarantine) chooses what
+/// happens to records whose field count differs from the header. The other
+/// keys in the section are written by the analysis and report its findings.
+///
+/// # The `[storage]` Section
+/// `backend` chooses how the data import stores values: `cell_files` (the
+/// default) writes one `row_NNNN/value.txt` per cell, `column_segments` packs
+/// `segment_rows` values per column into plain text segment files.
+///
+/// # The `[statistics]` Section
+/// `mode` chooses how columns are summarized: `streaming` (the default) keeps
+/// fixed memory per column, estimating the quartiles and, for categorical
+/// columns with more than `top_values` distinct values, the value counts;
+/// `exact` keeps every value in memory. `ddof` is the delta degrees of
+/// freedom of the variance: 1 (the default, as in pandas) for sample
+/// statistics, 0 for population statistics. `percentiles` lists extra
+/// percentiles to report, e.g. `[1, 5, 95, 99]`, and `interpolation`
+/// (linear | lower | higher | nearest | midpoint) how values between two
+/// ranks are computed, as in numpy.
+///
+/// # Design Philosophy
+/// - Vanilla Rust: no third party TOML crate
+/// - Clear errors: every parse failure names the file and the line number
+/// - Order preserving: entries are kept in file order for predictable lookups
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Import the column data types a column section can declare
+use super::csv_processor_module::CsvColumnDataType;
+
+// Import the dialect types understood by the record reader
+use super::csv_record_reader_module::{
+    CsvDialect,
+    CsvEscapeStyle,
+    CsvLineTerminator,
+    CsvTrimPolicy,
+};
+
+// Import the dialect sniffing report written to the [sniffing] section
+use super::csv_dialect_sniffer_module::CsvDialectSniffingReport;
+
+// Import the type inference mode and per-column evidence
+use super::column_type_inference_module::{
+    ColumnTypeTally,
+    TypeInferenceMode,
+    TypeInferenceSettings,
+    TypeInferenceSummary,
+};
+
+// Import the configurable true/false vocabulary
+use super::boolean_value_module::BooleanVocabulary;
+
+// Import the largest scale a decimal column can have
+use super::decimal_value_module::MAXIMUM_DECIMAL_SCALE;
+
+// Import the configurable date and time formats
+use super::temporal_value_module::{TemporalFormatSet, validate_temporal_format};
+
+// Import the null value sentinels (global and per column)
+use super::null_value_module::{NullValueSentinels, NullValueSettings};
+
+// Import the header detection report written to the [header_detection] section
+use super::csv_header_detection_module::CsvHeaderDetectionReport;
+
+// Import the structural validation report written to the [validation] section
+use super::csv_structure_validator_module::{CsvStructuralValidationReport, RaggedRowPolicy};
+
+// Import the storage backend settings written to the [storage] section
+use super::dataset_storage_module::{DatasetStorageBackend, DatasetStorageSettings};
+
+// Import the statistics settings written to the [statistics] section
+use super::streaming_statistics_module::{PercentileInterpolation, StatisticsMode, StatisticsSettings};
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsError,
+    RowsAndColumnsResult,
+    create_file_system_error,
+    create_metadata_error,
+};
+
+/// A single value read from a metadata TOML file
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataTomlValue {
+    /// Basic or literal string value
+    String(String),
+
+    /// Whole number value
+    Integer(i64),
+
+    /// Floating point value
+    Float(f64),
+
+    /// `true` or `false`
+    Boolean(bool),
+
+    /// Single-line array of strings
+    StringArray(Vec<String>),
+
+    /// Single-line array of numbers (integers are widened to floats)
+    NumberArray(Vec<f64>),
+}
+
+/// Column name and type set in a `[column_N]` section
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeclaredColumnSchema {
+    /// `name`, used instead of the header name
+    pub column_name: Option<String>,
+
+    /// `data_type`, when it was set by hand (it differs from `detected_type`)
+    pub declared_data_type: Option<CsvColumnDataType>,
+
+    /// `ignored = true`
+    pub column_ignored: bool,
+
+    /// `decimal_scale`, the scale a decimal column's exact sums start at
+    pub decimal_scale: Option<u32>,
+}
+
+/// One `key = value` entry together with the table it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataTomlEntry {
+    /// Name of the enclosing `[section]` (empty string for top-level keys)
+    pub section_name: String,
+
+    /// The key on the left of `=`
+    pub key_name: String,
+
+    /// The parsed value on the right of `=`
+    pub value: MetadataTomlValue,
+
+    /// Line number (1-based) where the entry was found, for diagnostics
+    pub line_number: usize,
+}
+
+/// A parsed metadata TOML document
+///
+/// Entries are kept in file order. Lookups return the first matching entry.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataTomlDocument {
+    /// All key/value entries in the order they appear in the file
+    pub entries: Vec<MetadataTomlEntry>,
+}
+
+impl MetadataTomlDocument {
+    /// Looks up an entry by section and key
+    ///
+    /// # Arguments
+    /// * `section_name` - Section name, or `""` for top-level keys
+    /// * `key_name` - Key to look up
+    ///
+    /// # Returns
+    /// * `Option<&MetadataTomlEntry>` - The entry, or None if absent
+    pub fn find_entry(&self, section_name: &str, key_name: &str) -> Option<&MetadataTomlEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.section_name == section_name && entry.key_name == key_name)
+    }
+
+    /// Checks whether any entry belongs to the given section
+    ///
+    /// # Arguments
+    /// * `section_name` - Section name to look for
+    ///
+    /// # Returns
+    /// * `bool` - True if at least one key is defined in that section
+    pub fn has_section(&self, section_name: &str) -> bool {
+        self.entries.iter().any(|entry| entry.section_name == section_name)
+    }
+
+    /// Looks up a string value by section and key
+    ///
+    /// # Arguments
+    /// * `section_name` - Section name, or `""` for top-level keys
+    /// * `key_name` - Key to look up
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The string value, or None if absent or not a string
+    pub fn get_string(&self, section_name: &str, key_name: &str) -> Option<&str> {
+        match self.find_entry(section_name, key_name) {
+            Some(MetadataTomlEntry { value: MetadataTomlValue::String(string_value), .. }) => {
+                Some(string_value.as_str())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Top-level metadata key holding the text encoding of the CSV file
+pub const ENCODING_KEY_NAME: &str = "encoding";
+
+/// Top-level metadata key overriding header row detection
+pub const HAS_HEADER_KEY_NAME: &str = "has_header";
+
+/// Metadata key (top-level or in a `[column_N]` section) listing null sentinels
+pub const NULL_VALUES_KEY_NAME: &str = "null_values";
+
+/// Prefix of the metadata tables describing single columns (`[column_1]`, ...)
+pub const COLUMN_SECTION_NAME_PREFIX: &str = "column_";
+
+/// Column section key holding the type used for the column
+pub const DATA_TYPE_KEY_NAME: &str = "data_type";
+
+/// Column section key holding the type the analysis detected
+pub const DETECTED_TYPE_KEY_NAME: &str = "detected_type";
+
+/// Column section key that leaves a column out of statistics and import
+pub const IGNORED_KEY_NAME: &str = "ignored";
+
+/// Column section key holding the number of digits after the decimal point
+pub const DECIMAL_SCALE_KEY_NAME: &str = "decimal_scale";
+
+/// Name of the metadata table holding the header detection result
+pub const HEADER_DETECTION_SECTION_NAME: &str = "header_detection";
+
+/// Name of the metadata table holding the CSV dialect
+pub const DIALECT_SECTION_NAME: &str = "dialect";
+
+/// Name of the metadata table holding the dialect sniffing result
+pub const SNIFFING_SECTION_NAME: &str = "sniffing";
+
+/// Name of the metadata table holding the type inference mode
+pub const TYPE_INFERENCE_SECTION_NAME: &str = "type_inference";
+
+/// Name of the metadata table holding the ragged row policy and validation findings
+pub const VALIDATION_SECTION_NAME: &str = "validation";
+
+/// Name of the metadata table holding the data import storage backend
+pub const STORAGE_SECTION_NAME: &str = "storage";
+
+/// Name of the metadata table holding the enhanced statistics mode
+pub const STATISTICS_SECTION_NAME: &str = "statistics";
+
+/// Reads and parses a metadata TOML file if it exists
+///
+/// # Arguments
+/// * `metadata_file_path` - Path to the `*.csv_metadata.toml` file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<MetadataTomlDocument>>` - Parsed document, or None if no file exists
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file exists but cannot be read
+/// * `RowsAndColumnsError::MetadataError` - If the file content is not valid metadata TOML
+pub fn read_metadata_toml_file(metadata_file_path: &Path) -> RowsAndColumnsResult<Option<MetadataTomlDocument>> {
+    if !metadata_file_path.exists() {
+        return Ok(None);
+    }
+
+    let toml_content = std::fs::read_to_string(metadata_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to read metadata file: {}", metadata_file_path.display()),
+                io_error
+            )
+        })?;
+
+    let parsed_document = parse_metadata_toml_content(
+        &toml_content,
+        &metadata_file_path.display().to_string()
+    )?;
+
+    Ok(Some(parsed_document))
+}
+
+/// Parses metadata TOML text into an ordered list of entries
+///
+/// # Arguments
+/// * `toml_content` - The full text of the TOML file
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<MetadataTomlDocument>` - Parsed document or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If any line cannot be parsed
+pub fn parse_metadata_toml_content(
+    toml_content: &str,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<MetadataTomlDocument> {
+    let mut parsed_document = MetadataTomlDocument::default();
+    let mut current_section_name = String::new();
+
+    for (line_index, raw_line) in toml_content.lines().enumerate() {
+        let line_number = line_index + 1;
+        let trimmed_line = raw_line.trim();
+
+        // Skip blank lines and whole-line comments
+        if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
+            continue;
+        }
+
+        // Section header: [name] or [dotted.name]
+        if trimmed_line.starts_with('[') {
+            let header_without_comment = strip_trailing_toml_comment(trimmed_line);
+            if !header_without_comment.ends_with(']') || header_without_comment.starts_with("[[") {
+                return Err(create_metadata_error(
+                    &format!("Invalid section header at line {}: {}", line_number, trimmed_line),
+                    metadata_file_path_for_errors
+                ));
+            }
+
+            current_section_name = header_without_comment[1..header_without_comment.len() - 1]
+                .trim()
+                .to_string();
+            continue;
+        }
+
+        // Key/value pair
+        let equals_position = trimmed_line.find('=').ok_or_else(|| {
+            create_metadata_error(
+                &format!("Expected 'key = value' at line {}: {}", line_number, trimmed_line),
+                metadata_file_path_for_errors
+            )
+        })?;
+
+        let key_name = trimmed_line[..equals_position].trim().to_string();
+        if key_name.is_empty() {
+            return Err(create_metadata_error(
+                &format!("Missing key name at line {}", line_number),
+                metadata_file_path_for_errors
+            ));
+        }
+
+        let value_text = trimmed_line[equals_position + 1..].trim();
+        let parsed_value = parse_metadata_toml_value(value_text).map_err(|value_problem| {
+            create_metadata_error(
+                &format!("Invalid value for '{}' at line {}: {}", key_name, line_number, value_problem),
+                metadata_file_path_for_errors
+            )
+        })?;
+
+        parsed_document.entries.push(MetadataTomlEntry {
+            section_name: current_section_name.clone(),
+            key_name,
+            value: parsed_value,
+            line_number,
+        });
+    }
+
+    Ok(parsed_document)
+}
+
+/// Parses the text to the right of `=` into a value
+///
+/// # Arguments
+/// * `value_text` - Trimmed value text, possibly followed by a `# comment`
+///
+/// # Returns
+/// * `Result<MetadataTomlValue, String>` - Parsed value or a description of the problem
+fn parse_metadata_toml_value(value_text: &str) -> Result<MetadataTomlValue, String> {
+    if let Some(array_body_text) = value_text.strip_prefix('[') {
+        if !array_body_text.trim_start().starts_with(['"', '\'', ']']) {
+            let (array_numbers, remaining_text) = parse_toml_number_array(value_text)?;
+            ensure_only_comment_remains(remaining_text)?;
+            return Ok(MetadataTomlValue::NumberArray(array_numbers));
+        }
+        let (array_items, remaining_text) = parse_toml_string_array(value_text)?;
+        ensure_only_comment_remains(remaining_text)?;
+        return Ok(MetadataTomlValue::StringArray(array_items));
+    }
+
+    if value_text.starts_with('"') {
+        let (string_value, remaining_text) = parse_toml_basic_string(value_text)?;
+        ensure_only_comment_remains(remaining_text)?;
+        return Ok(MetadataTomlValue::String(string_value));
+    }
+
+    if let Some(literal_body) = value_text.strip_prefix('\'') {
+        let closing_quote_position = literal_body
+            .find('\'')
+            .ok_or_else(|| "unterminated literal string".to_string())?;
+        ensure_only_comment_remains(&literal_body[closing_quote_position + 1..])?;
+        return Ok(MetadataTomlValue::String(literal_body[..closing_quote_position].to_string()));
+    }
+
+    let bare_value = strip_trailing_toml_comment(value_text);
+    match bare_value {
+        "" => Err("missing value".to_string()),
+        "true" => Ok(MetadataTomlValue::Boolean(true)),
+        "false" => Ok(MetadataTomlValue::Boolean(false)),
+        _ => {
+            let number_text = bare_value.replace('_', "");
+            if let Ok(integer_value) = number_text.parse::<i64>() {
+                Ok(MetadataTomlValue::Integer(integer_value))
+            } else if let Ok(float_value) = number_text.parse::<f64>() {
+                Ok(MetadataTomlValue::Float(float_value))
+            } else {
+                Err(format!("unrecognized value '{}'", bare_value))
+            }
+        }
+    }
+}
+
+/// Parses a single-line array of strings starting at the opening bracket
+///
+/// # Arguments
+/// * `array_text` - Text starting with `[`
+///
+/// # Returns
+/// * `Result<(Vec<String>, &str), String>` - (array items, text after the closing bracket) or error
+fn parse_toml_string_array(array_text: &str) -> Result<(Vec<String>, &str), String> {
+    let mut array_items = Vec::new();
+    let mut remaining_text = array_text[1..].trim_start();
+
+    loop {
+        if let Some(text_after_array) = remaining_text.strip_prefix(']') {
+            return Ok((array_items, text_after_array));
+        }
+
+        if remaining_text.starts_with('"') {
+            let (string_value, text_after_item) = parse_toml_basic_string(remaining_text)?;
+            array_items.push(string_value);
+            remaining_text = text_after_item.trim_start();
+        } else if let Some(literal_body) = remaining_text.strip_prefix('\'') {
+            let closing_quote_position = literal_body
+                .find('\'')
+                .ok_or_else(|| "unterminated literal string".to_string())?;
+            array_items.push(literal_body[..closing_quote_position].to_string());
+            remaining_text = literal_body[closing_quote_position + 1..].trim_start();
+        } else {
+            return Err("arrays may only contain strings and must close on the same line".to_string());
+        }
+
+        if let Some(text_after_comma) = remaining_text.strip_prefix(',') {
+            remaining_text = text_after_comma.trim_start();
+        } else if !remaining_text.starts_with(']') {
+            return Err("expected ',' or ']' after array item".to_string());
+        }
+    }
+}
+
+/// Parses a single-line array of numbers starting at the opening bracket
+///
+/// # Arguments
+/// * `array_text` - Text starting with `[`
+///
+/// # Returns
+/// * `Result<(Vec<f64>, &str), String>` - (array numbers, text after the closing bracket) or error
+fn parse_toml_number_array(array_text: &str) -> Result<(Vec<f64>, &str), String> {
+    let closing_bracket_position = array_text
+        .find(']')
+        .ok_or_else(|| "arrays must close on the same line".to_string())?;
+    let array_numbers = array_text[1..closing_bracket_position]
+        .split(',')
+        .map(str::trim)
+        .filter(|item_text| !item_text.is_empty())
+        .map(|item_text| {
+            item_text
+                .replace('_', "")
+                .parse::<f64>()
+                .map_err(|_| format!("arrays may not mix numbers and '{}'", item_text))
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+
+    Ok((array_numbers, &array_text[closing_bracket_position + 1..]))
+}
+
+/// Formats strings as a single-line TOML array
+///
+/// # Arguments
+/// * `array_items` - The strings to write
+///
+/// # Returns
+/// * `String` - e.g. `["%m/%d/%Y", "%d.%m.%Y"]`
+pub fn format_toml_string_array(array_items: &[String]) -> String {
+    let quoted_items: Vec<String> = array_items
+        .iter()
+        .map(|array_item| format!("\"{}\"", escape_toml_basic_string(array_item)))
+        .collect();
+
+    format!("[{}]", quoted_items.join(", "))
+}
+
+/// Parses a TOML basic string starting at the opening quote
+///
+/// # Arguments
+/// * `quoted_text` - Text starting with `"`
+///
+/// # Returns
+/// * `Result<(String, &str), String>` - (decoded string, text after the closing quote) or error
+fn parse_toml_basic_string(quoted_text: &str) -> Result<(String, &str), String> {
+    let mut decoded_string = String::new();
+    let mut character_iterator = quoted_text.char_indices().skip(1);
+
+    while let Some((character_position, current_character)) = character_iterator.next() {
+        match current_character {
+            '"' => {
+                return Ok((decoded_string, &quoted_text[character_position + 1..]));
+            }
+            '\\' => {
+                let escaped_character = match character_iterator.next() {
+                    Some((_, escape_code)) => escape_code,
+                    None => return Err("unterminated escape sequence".to_string()),
+                };
+                match escaped_character {
+                    '"' => decoded_string.push('"'),
+                    '\\' => decoded_string.push('\\'),
+                    't' => decoded_string.push('\t'),
+                    'n' => decoded_string.push('\n'),
+                    'r' => decoded_string.push('\r'),
+                    'u' => {
+                        let mut hex_digits = String::new();
+                        for _ in 0..4 {
+                            match character_iterator.next() {
+                                Some((_, hex_digit)) => hex_digits.push(hex_digit),
+                                None => return Err("incomplete \\u escape".to_string()),
+                            }
+                        }
+                        let code_point = u32::from_str_radix(&hex_digits, 16)
+                            .map_err(|_| format!("invalid \\u escape '{}'", hex_digits))?;
+                        let unicode_character = char::from_u32(code_point)
+                            .ok_or_else(|| format!("invalid unicode code point '{}'", hex_digits))?;
+                        decoded_string.push(unicode_character);
+                    }
+                    other_character => {
+                        return Err(format!("unsupported escape sequence '\\{}'", other_character));
+                    }
+                }
+            }
+            other_character => decoded_string.push(other_character),
+        }
+    }
+
+    Err("unterminated string".to_string())
+}
+
+/// Removes a trailing `# comment` from an unquoted value or header
+///
+/// # Arguments
+/// * `text` - Text that contains no string literal
+///
+/// # Returns
+/// * `&str` - The text before any `#`, trimmed
+fn strip_trailing_toml_comment(text: &str) -> &str {
+    match text.find('#') {
+        Some(comment_position) => text[..comment_position].trim(),
+        None => text.trim(),
+    }
+}
+
+/// Verifies that nothing but whitespace or a comment follows a value
+///
+/// # Arguments
+/// * `remaining_text` - Text after the end of a value
+///
+/// # Returns
+/// * `Result<(), String>` - Ok if only a comment or whitespace remains
+fn ensure_only_comment_remains(remaining_text: &str) -> Result<(), String> {
+    let trimmed_remainder = remaining_text.trim();
+    if trimmed_remainder.is_empty() || trimmed_remainder.starts_with('#') {
+        Ok(())
+    } else {
+        Err(format!("unexpected text after value: '{}'", trimmed_remainder))
+    }
+}
+
+/// Escapes a string for writing as a TOML basic string (without the surrounding quotes)
+///
+/// # Arguments
+/// * `raw_string` - The string to escape
+///
+/// # Returns
+/// * `String` - Escaped string safe to place between `"` quotes
+pub fn escape_toml_basic_string(raw_string: &str) -> String {
+    let mut escaped_string = String::with_capacity(raw_string.len());
+
+    for current_character in raw_string.chars() {
+        match current_character {
+            '"' => escaped_string.push_str("\\\""),
+            '\\' => escaped_string.push_str("\\\\"),
+            '\t' => escaped_string.push_str("\\t"),
+            '\n' => escaped_string.push_str("\\n"),
+            '\r' => escaped_string.push_str("\\r"),
+            control_character if control_character.is_control() => {
+                escaped_string.push_str(&format!("\\u{:04X}", control_character as u32));
+            }
+            other_character => escaped_string.push(other_character),
+        }
+    }
+
+    escaped_string
+}
+
+/// Builds the CSV dialect from the `[dialect]` section of a metadata document
+///
+/// Every key is optional; keys that are absent keep the value from
+/// `fallback_dialect`, so a user may override only the delimiter.
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document
+/// * `fallback_dialect` - Dialect used for keys that are not present
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvDialect>` - The configured dialect
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If a dialect key has an invalid value
+pub fn read_csv_dialect_from_metadata(
+    metadata_document: &MetadataTomlDocument,
+    fallback_dialect: CsvDialect,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<CsvDialect> {
+    let mut configured_dialect = fallback_dialect;
+
+    if let Some(delimiter_text) = read_dialect_string(metadata_document, "delimiter", metadata_file_path_for_errors)? {
+        configured_dialect.field_delimiter = parse_single_dialect_character(
+            metadata_document,
+            "delimiter",
+            &delimiter_text,
+            metadata_file_path_for_errors,
+        )?;
+    }
+
+    if let Some(quote_text) = read_dialect_string(metadata_document, "quote_character", metadata_file_path_for_errors)? {
+        configured_dialect.quote_character = if quote_text.is_empty() {
+            None
+        } else {
+            Some(parse_single_dialect_character(
+                metadata_document,
+                "quote_character",
+                &quote_text,
+                metadata_file_path_for_errors,
+            )?)
+        };
+    }
+
+    if let Some(escape_text) = read_dialect_string(metadata_document, "escape_style", metadata_file_path_for_errors)? {
+        configured_dialect.escape_style = CsvEscapeStyle::from_toml_string(&escape_text).ok_or_else(|| {
+            create_invalid_dialect_value_error(
+                metadata_document,
+                "escape_style",
+                &escape_text,
+                "doubled, backslash or none",
+                metadata_file_path_for_errors,
+            )
+        })?;
+    }
+
+    if let Some(comment_text) = read_dialect_string(metadata_document, "comment_prefix", metadata_file_path_for_errors)? {
+        configured_dialect.comment_prefix = if comment_text.is_empty() { None } else { Some(comment_text) };
+    }
+
+    if let Some(trim_text) = read_dialect_string(metadata_document, "trim_whitespace", metadata_file_path_for_errors)? {
+        configured_dialect.trim_policy = CsvTrimPolicy::from_toml_string(&trim_text).ok_or_else(|| {
+            create_invalid_dialect_value_error(
+                metadata_document,
+                "trim_whitespace",
+                &trim_text,
+                "all, unquoted or none",
+                metadata_file_path_for_errors,
+            )
+        })?;
+    }
+
+    if let Some(terminator_text) = read_dialect_string(metadata_document, "line_terminator", metadata_file_path_for_errors)? {
+        configured_dialect.line_terminator = CsvLineTerminator::from_toml_string(&terminator_text).ok_or_else(|| {
+            create_invalid_dialect_value_error(
+                metadata_document,
+                "line_terminator",
+                &terminator_text,
+                "auto, lf, crlf or cr",
+                metadata_file_path_for_errors,
+            )
+        })?;
+    }
+
+    if configured_dialect.quote_character == Some(configured_dialect.field_delimiter) {
+        return Err(create_metadata_error(
+            "Dialect delimiter and quote_character must be different characters",
+            metadata_file_path_for_errors,
+        ));
+    }
+
+    Ok(configured_dialect)
+}
+
+/// Formats a CSV dialect as a `[dialect]` TOML section
+///
+/// # Arguments
+/// * `csv_dialect` - The dialect to write
+///
+/// # Returns
+/// * `String` - The section text, ending with a blank line
+pub fn format_csv_dialect_toml_section(csv_dialect: &CsvDialect) -> String {
+    let quote_text = csv_dialect.quote_character.map(String::from).unwrap_or_default();
+    let comment_text = csv_dialect.comment_prefix.clone().unwrap_or_default();
+
+    let mut section_text = String::new();
+    section_text.push_str(&format!("[{}]\n", DIALECT_SECTION_NAME));
+    section_text.push_str(&format!(
+        "delimiter = \"{}\"\n",
+        escape_toml_basic_string(&csv_dialect.field_delimiter.to_string())
+    ));
+    section_text.push_str(&format!("quote_character = \"{}\"\n", escape_toml_basic_string(&quote_text)));
+    section_text.push_str(&format!("escape_style = \"{}\"\n", csv_dialect.escape_style.to_toml_string()));
+    section_text.push_str(&format!("comment_prefix = \"{}\"\n", escape_toml_basic_string(&comment_text)));
+    section_text.push_str(&format!("trim_whitespace = \"{}\"\n", csv_dialect.trim_policy.to_toml_string()));
+    section_text.push_str(&format!("line_terminator = \"{}\"\n", csv_dialect.line_terminator.to_toml_string()));
+    section_text.push('\n');
+    section_text
+}
+
+/// Formats a dialect sniffing report as a `[sniffing]` TOML section
+///
+/// # Arguments
+/// * `sniffing_report` - The report to write
+///
+/// # Returns
+/// * `String` - The section text, ending with a blank line
+pub fn format_csv_dialect_sniffing_toml_section(sniffing_report: &CsvDialectSniffingReport) -> String {
+    let mut section_text = String::new();
+    section_text.push_str(&format!("[{}]\n", SNIFFING_SECTION_NAME));
+    section_text.push_str(&format!("applied = {}\n", sniffing_report.applied_to_analysis));
+    section_text.push_str(&format!("confidence = {:.3}\n", sniffing_report.confidence));
+    section_text.push_str(&format!("sampled_records = {}\n", sniffing_report.sampled_record_count));
+
+    if let Some(winning_candidate) = &sniffing_report.winning_candidate {
+        section_text.push_str(&format!(
+            "delimiter = \"{}\"\n",
+            escape_toml_basic_string(&winning_candidate.field_delimiter.to_string())
+        ));
+        section_text.push_str(&format!("consistency = {:.3}\n", winning_candidate.consistency_score));
+    }
+
+    if let Some(runner_up_candidate) = &sniffing_report.runner_up_candidate {
+        let runner_up_quote_text = runner_up_candidate.quote_character.map(String::from).unwrap_or_default();
+        section_text.push_str(&format!(
+            "runner_up_delimiter = \"{}\"\n",
+            escape_toml_basic_string(&runner_up_candidate.field_delimiter.to_string())
+        ));
+        section_text.push_str(&format!(
+            "runner_up_quote_character = \"{}\"\n",
+            escape_toml_basic_string(&runner_up_quote_text)
+        ));
+        section_text.push_str(&format!("runner_up_consistency = {:.3}\n", runner_up_candidate.consistency_score));
+    }
+
+    section_text.push('\n');
+    section_text
+}
+
+/// Reads the top-level `has_header` override
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document, if a metadata file exists
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<bool>>` - The configured value, or None to use the heuristic
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If the value is not `true` or `false`
+pub fn read_has_header_override_from_metadata(
+    metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<Option<bool>> {
+    let has_header_entry = metadata_document
+        .and_then(|metadata_document| metadata_document.find_entry("", HAS_HEADER_KEY_NAME));
+
+    match has_header_entry {
+        None => Ok(None),
+        Some(MetadataTomlEntry { value: MetadataTomlValue::Boolean(has_header_row), .. }) => Ok(Some(*has_header_row)),
+        Some(invalid_entry) => Err(create_metadata_error(
+            &format!(
+                "Invalid {} at line {} (expected true or false without quotes)",
+                HAS_HEADER_KEY_NAME, invalid_entry.line_number
+            ),
+            metadata_file_path_for_errors,
+        )),
+    }
+}
+
+/// Formats a header detection report as a `[header_detection]` TOML section
+///
+/// # Arguments
+/// * `detection_report` - The report to write
+///
+/// # Returns
+/// * `String` - The section text, ending with a blank line
+pub fn format_header_detection_toml_section(detection_report: &CsvHeaderDetectionReport) -> String {
+    let mut section_text = String::new();
+    section_text.push_str(&format!("[{}]\n", HEADER_DETECTION_SECTION_NAME));
+    section_text.push_str(&format!("applied = {}\n", !detection_report.overridden_by_metadata));
+    section_text.push_str(&format!("detected_header = {}\n", detection_report.heuristic_detected_header));
+    section_text.push_str(&format!("score = {}\n", detection_report.heuristic_score));
+    section_text.push_str(&format!("sampled_rows = {}\n", detection_report.sampled_row_count));
+    section_text.push('\n');
+    section_text
+}
+
+/// Reads the global and per-column `null_values` lists
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document, if a metadata file exists
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<NullValueSettings>` - The configured sentinels; the default
+///   list when no top-level `null_values` is set
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If a `null_values` entry is not an array of strings
+pub fn read_null_value_settings_from_metadata(
+    metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<NullValueSettings> {
+    let metadata_document = match metadata_document {
+        Some(metadata_document) => metadata_document,
+        None => return Ok(NullValueSettings::default()),
+    };
+
+    let mut null_value_settings = NullValueSettings::default();
+    for metadata_entry in &metadata_document.entries {
+        if metadata_entry.key_name != NULL_VALUES_KEY_NAME {
+            continue;
+        }
+
+        let sentinel_values = match &metadata_entry.value {
+            MetadataTomlValue::StringArray(sentinel_values) => sentinel_values.clone(),
+            _ => {
+                return Err(create_metadata_error(
+                    &format!(
+                        "'{}' at line {} must be an array of strings",
+                        NULL_VALUES_KEY_NAME, metadata_entry.line_number
+                    ),
+                    metadata_file_path_for_errors,
+                ));
+            }
+        };
+        let null_value_sentinels = NullValueSentinels { sentinel_values };
+
+        // Top-level list, or a list in a [column_N] section (other sections are ignored)
+        if metadata_entry.section_name.is_empty() {
+            null_value_settings.global_sentinels = null_value_sentinels;
+        } else if let Some(column_index) = parse_column_section_index(&metadata_entry.section_name) {
+            null_value_settings.column_sentinel_overrides.insert(column_index, null_value_sentinels);
+        }
+    }
+
+    Ok(null_value_settings)
+}
+
+/// Reads the column names, hand-set column types and decimal scales from the `[column_N]` sections
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document, if a metadata file exists
+/// * `column_count` - Number of columns in the CSV file
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<BTreeMap<usize, DeclaredColumnSchema>>` - Schema by 0-based column index
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If a section names a column the file does not
+///   have, or a `name`, `data_type` or `decimal_scale` value is invalid
+pub fn read_declared_column_schema_from_metadata(
+    metadata_document: Option<&MetadataTomlDocument>,
+    column_count: usize,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<BTreeMap<usize, DeclaredColumnSchema>> {
+    let mut declared_column_schema: BTreeMap<usize, DeclaredColumnSchema> = BTreeMap::new();
+    let metadata_document = match metadata_document {
+        Some(metadata_document) => metadata_document,
+        None => return Ok(declared_column_schema),
+    };
+
+    for metadata_entry in &metadata_document.entries {
+        let column_index = match parse_column_section_index(&metadata_entry.section_name) {
+            Some(column_index) => column_index,
+            None => continue,
+        };
+        if column_index >= column_count {
+            return Err(create_metadata_error(
+                &format!(
+                    "[{}] at line {} describes a column the CSV file does not have ({} columns)",
+                    metadata_entry.section_name, metadata_entry.line_number, column_count
+                ),
+                metadata_file_path_for_errors,
+            ));
+        }
+
+        let invalid_value_error = |expected_description: &str| {
+            create_metadata_error(
+                &format!(
+                    "Invalid {} in [{}] at line {} (expected {})",
+                    metadata_entry.key_name, metadata_entry.section_name, metadata_entry.line_number,
+                    expected_description
+                ),
+                metadata_file_path_for_errors,
+            )
+        };
+
+        match (metadata_entry.key_name.as_str(), &metadata_entry.value) {
+            ("name", MetadataTomlValue::String(column_name)) => {
+                declared_column_schema.entry(column_index).or_default().column_name = Some(column_name.clone());
+            }
+            ("name", _) => return Err(invalid_value_error("a string")),
+            (DATA_TYPE_KEY_NAME, MetadataTomlValue::String(data_type_text)) => {
+                let declared_data_type = CsvColumnDataType::from_toml_string(data_type_text).ok_or_else(|| {
+                    invalid_value_error("boolean, integer, float, decimal, date, time, datetime or string")
+                })?;
+
+                // A data_type still equal to the detected type was written by the analysis, not by hand
+                let detected_data_type = metadata_document
+                    .get_string(&metadata_entry.section_name, DETECTED_TYPE_KEY_NAME)
+                    .and_then(CsvColumnDataType::from_toml_string);
+                if detected_data_type.as_ref() != Some(&declared_data_type) {
+                    declared_column_schema.entry(column_index).or_default().declared_data_type =
+                        Some(declared_data_type);
+                }
+            }
+            (DATA_TYPE_KEY_NAME, _) => return Err(invalid_value_error("a quoted type name")),
+            (IGNORED_KEY_NAME, MetadataTomlValue::Boolean(column_ignored)) => {
+                declared_column_schema.entry(column_index).or_default().column_ignored = *column_ignored;
+            }
+            (IGNORED_KEY_NAME, _) => return Err(invalid_value_error("true or false without quotes")),
+            (DECIMAL_SCALE_KEY_NAME, MetadataTomlValue::Integer(decimal_scale))
+                if (0..=MAXIMUM_DECIMAL_SCALE as i64).contains(decimal_scale) =>
+            {
+                declared_column_schema.entry(column_index).or_default().decimal_scale = Some(*decimal_scale as u32);
+            }
+            (DECIMAL_SCALE_KEY_NAME, _) => {
+                return Err(invalid_value_error(&format!("a whole number from 0 to {}", MAXIMUM_DECIMAL_SCALE)));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(declared_column_schema)
+}
+
+/// Reads the 0-based column index from a `column_N` section name
+///
+/// # Arguments
+/// * `section_name` - The section name
+///
+/// # Returns
+/// * `Option<usize>` - The column index, or None if this is not a column section
+fn parse_column_section_index(section_name: &str) -> Option<usize> {
+    let column_number = section_name
+        .strip_prefix(COLUMN_SECTION_NAME_PREFIX)?
+        .parse::<usize>()
+        .ok()?;
+    column_number.checked_sub(1)
+}
+
+/// Formats a `null_values = [...]` line
+///
+/// # Arguments
+/// * `null_value_sentinels` - The sentinels to write
+///
+/// # Returns
+/// * `String` - The line, ending with a newline
+pub fn format_null_values_toml_line(null_value_sentinels: &NullValueSentinels) -> String {
+    format!(
+        "{} = {}\n",
+        NULL_VALUES_KEY_NAME,
+        format_toml_string_array(&null_value_sentinels.sentinel_values)
+    )
+}
+
+/// Reads the ragged row policy from the `[validation]` section
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document, if a metadata file exists
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<RaggedRowPolicy>` - The configured policy, or `truncate` if none is set
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If the policy value is not supported
+pub fn read_ragged_row_policy_from_metadata(
+    metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<RaggedRowPolicy> {
+    let policy_entry = metadata_document
+        .and_then(|metadata_document| metadata_document.find_entry(VALIDATION_SECTION_NAME, "ragged_row_policy"));
+
+    match policy_entry {
+        None => Ok(RaggedRowPolicy::Truncate),
+        Some(policy_entry) => {
+            let policy = match &policy_entry.value {
+                MetadataTomlValue::String(policy_text) => RaggedRowPolicy::from_toml_string(policy_text),
+                _ => None,
+            };
+            policy.ok_or_else(|| {
+                create_metadata_error(
+                    &format!(
+                        "Invalid ragged_row_policy at line {} (expected reject, pad, truncate or quarantine)",
+                        policy_entry.line_number
+                    ),
+                    metadata_file_path_for_errors,
+                )
+            })
+        }
+    }
+}
+
+/// Reads the data import storage backend from the `[storage]` section
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata document, if the file exists
+/// * `metadata_file_path_for_errors` - Path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DatasetStorageSettings>` - The settings (defaults when the section is absent)
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If `backend` or `segment_rows` is invalid
+pub fn read_dataset_storage_settings_from_metadata(
+    metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<DatasetStorageSettings> {
+    let mut storage_settings = DatasetStorageSettings::default();
+    let metadata_document = match metadata_document {
+        Some(metadata_document) => metadata_document,
+        None => return Ok(storage_settings),
+    };
+
+    if let Some(backend_entry) = metadata_document.find_entry(STORAGE_SECTION_NAME, "backend") {
+        let storage_backend = match &backend_entry.value {
+            MetadataTomlValue::String(backend_text) => DatasetStorageBackend::from_toml_string(backend_text),
+            _ => None,
+        };
+        storage_settings.storage_backend = storage_backend.ok_or_else(|| {
+            create_metadata_error(
+                &format!(
+                    "Invalid storage backend at line {} (expected cell_files or column_segments)",
+                    backend_entry.line_number
+                ),
+                metadata_file_path_for_errors,
+            )
+        })?;
+    }
+
+    if let Some(segment_rows_entry) = metadata_document.find_entry(STORAGE_SECTION_NAME, "segment_rows") {
+        storage_settings.segment_row_count = match segment_rows_entry.value {
+            MetadataTomlValue::Integer(segment_row_count) if segment_row_count > 0 => segment_row_count as usize,
+            _ => {
+                return Err(create_metadata_error(
+                    &format!(
+                        "Invalid segment_rows at line {} (expected a positive integer)",
+                        segment_rows_entry.line_number
+                    ),
+                    metadata_file_path_for_errors,
+                ));
+            }
+        };
+    }
+
+    Ok(storage_settings)
+}
+
+/// Formats the data import storage settings as a `[storage]` TOML section
+///
+/// # Arguments
+/// * `storage_settings` - The settings to write
+///
+/// # Returns
+/// * `String` - The section text, ending with a blank line
+pub fn format_dataset_storage_toml_section(storage_settings: &DatasetStorageSettings) -> String {
+    let mut section_text = String::new();
+    section_text.push_str(&format!("[{}]\n", STORAGE_SECTION_NAME));
+    section_text.push_str(&format!(
+        "backend = \"{}\"\n",
+        storage_settings.storage_backend.to_toml_string()
+    ));
+    section_text.push_str(&format!("segment_rows = {}\n", storage_settings.segment_row_count));
+    section_text.push('\n');
+    section_text
+}
+
+/// Reads the enhanced statistics mode, top value capacity, ddof and percentiles from the `[statistics]` section
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata document, if the file exists
+/// * `metadata_file_path_for_errors` - Path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<StatisticsSettings>` - The settings (defaults when the section is absent)
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If `mode`, `top_values`, `ddof`, `percentiles` or
+///   `interpolation` is invalid
+pub fn read_statistics_settings_from_metadata(
+    metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<StatisticsSettings> {
+    let mut statistics_settings = StatisticsSettings::default();
+    let metadata_document = match metadata_document {
+        Some(metadata_document) => metadata_document,
+        None => return Ok(statistics_settings),
+    };
+
+    if let Some(mode_entry) = metadata_document.find_entry(STATISTICS_SECTION_NAME, "mode") {
+        let statistics_mode = match &mode_entry.value {
+            MetadataTomlValue::String(mode_text) => StatisticsMode::from_toml_string(mode_text),
+            _ => None,
+        };
+        statistics_settings.statistics_mode = statistics_mode.ok_or_else(|| {
+            create_metadata_error(
+                &format!(
+                    "Invalid statistics mode at line {} (expected streaming or exact)",
+                    mode_entry.line_number
+                ),
+                metadata_file_path_for_errors,
+            )
+        })?;
+    }
+
+    if let Some(top_values_entry) = metadata_document.find_entry(STATISTICS_SECTION_NAME, "top_values") {
+        statistics_settings.top_value_capacity = match top_values_entry.value {
+            MetadataTomlValue::Integer(top_value_capacity) if top_value_capacity > 0 => top_value_capacity as usize,
+            _ => {
+                return Err(create_metadata_error(
+                    &format!(
+                        "Invalid top_values at line {} (expected a positive integer)",
+                        top_values_entry.line_number
+                    ),
+                    metadata_file_path_for_errors,
+                ));
+            }
+        };
+    }
+
+    if let Some(ddof_entry) = metadata_document.find_entry(STATISTICS_SECTION_NAME, "ddof") {
+        statistics_settings.delta_degrees_of_freedom = match ddof_entry.value {
+            MetadataTomlValue::Integer(delta_degrees_of_freedom) if (0..=i64::from(u32::MAX)).contains(&delta_degrees_of_freedom) => {
+                delta_degrees_of_freedom as u32
+            }
+            _ => {
+                return Err(create_metadata_error(
+                    &format!("Invalid ddof at line {} (expected 0 or a positive integer)", ddof_entry.line_number),
+                    metadata_file_path_for_errors,
+                ));
+            }
+        };
+    }
+
+    if let Some(percentiles_entry) = metadata_document.find_entry(STATISTICS_SECTION_NAME, "percentiles") {
+        statistics_settings.requested_percentiles = match &percentiles_entry.value {
+            MetadataTomlValue::NumberArray(requested_percentiles)
+                if requested_percentiles.iter().all(|percentile| (0.0..=100.0).contains(percentile)) =>
+            {
+                requested_percentiles.clone()
+            }
+            MetadataTomlValue::StringArray(array_items) if array_items.is_empty() => Vec::new(),
+            _ => {
+                return Err(create_metadata_error(
+                    &format!(
+                        "Invalid percentiles at line {} (expected numbers from 0 to 100, e.g. [1, 5, 95, 99])",
+                        percentiles_entry.line_number
+                    ),
+                    metadata_file_path_for_errors,
+                ));
+            }
+        };
+    }
+
+    if let Some(interpolation_entry) = metadata_document.find_entry(STATISTICS_SECTION_NAME, "interpolation") {
+        let percentile_interpolation = match &interpolation_entry.value {
+            MetadataTomlValue::String(interpolation_text) => PercentileInterpolation::from_toml_string(interpolation_text),
+            _ => None,
+        };
+        statistics_settings.percentile_interpolation = percentile_interpolation.ok_or_else(|| {
+            create_metadata_error(
+                &format!(
+                    "Invalid interpolation at line {} (expected linear, lower, higher, nearest or midpoint)",
+                    interpolation_entry.line_number
+                ),
+                metadata_file_path_for_errors,
+            )
+        })?;
+    }
+
+    Ok(statistics_settings)
+}
+
+/// Formats the enhanced statistics settings as a `[statistics]` TOML section
+///
+/// # Arguments
+/// * `statistics_settings` - The settings to write
+///
+/// # Returns
+/// * `String` - The section text, ending with a blank line
+pub fn format_statistics_toml_section(statistics_settings: &StatisticsSettings) -> String {
+    let mut section_text = String::new();
+    section_text.push_str(&format!("[{}]\n", STATISTICS_SECTION_NAME));
+    section_text.push_str(&format!("mode = \"{}\"\n", statistics_settings.statistics_mode.to_toml_string()));
+    section_text.push_str(&format!("top_values = {}\n", statistics_settings.top_value_capacity));
+    section_text.push_str(&format!("ddof = {}\n", statistics_settings.delta_degrees_of_freedom));
+    let percentile_texts: Vec<String> = statistics_settings
+        .requested_percentiles
+        .iter()
+        .map(|percentile| percentile.to_string())
+        .collect();
+    section_text.push_str(&format!("percentiles = [{}]\n", percentile_texts.join(", ")));
+    section_text.push_str(&format!(
+        "interpolation = \"{}\"\n",
+        statistics_settings.percentile_interpolation.to_toml_string()
+    ));
+    section_text.push('\n');
+    section_text
+}
+
+/// Formats a structural validation report as a `[validation]` TOML section
+///
+/// # Arguments
+/// * `validation_report` - The report to write
+///
+/// # Returns
+/// * `String` - The section text, ending with a blank line
+pub fn format_csv_validation_toml_section(validation_report: &CsvStructuralValidationReport) -> String {
+    let mut section_text = String::new();
+    section_text.push_str(&format!("[{}]\n", VALIDATION_SECTION_NAME));
+    section_text.push_str(&format!(
+        "ragged_row_policy = \"{}\"\n",
+        validation_report.ragged_row_policy.to_toml_string()
+    ));
+    section_text.push_str(&format!("expected_fields = {}\n", validation_report.expected_field_count));
+    section_text.push_str(&format!("checked_records = {}\n", validation_report.checked_record_count));
+    section_text.push_str(&format!("ragged_records = {}\n", validation_report.ragged_record_diagnostics.len()));
+
+    if let Some(first_ragged_record) = validation_report.ragged_record_diagnostics.first() {
+        section_text.push_str(&format!("first_ragged_line = {}\n", first_ragged_record.starting_line_number));
+    }
+    if let Some(quarantine_file_path) = &validation_report.quarantine_file_path {
+        section_text.push_str(&format!(
+            "quarantine_file = \"{}\"\n",
+            escape_toml_basic_string(&quarantine_file_path.display().to_string())
+        ));
+    }
+
+    section_text.push('\n');
+    section_text
+}
+
+/// Reads the type inference settings from the `[type_inference]` section
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document, if a metadata file exists
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<TypeInferenceSettings>` - The configured settings, or the defaults
+///   (`sample` mode, ISO-8601 only, true/yes and false/no) for anything not set
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If the mode or a format is not supported,
+///   or a token is listed as both true and false
+pub fn read_type_inference_settings_from_metadata(
+    metadata_document: Option<&MetadataTomlDocument>,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<TypeInferenceSettings> {
+    let metadata_document = match metadata_document {
+        Some(metadata_document) => metadata_document,
+        None => return Ok(TypeInferenceSettings::default()),
+    };
+
+    let inference_mode = match metadata_document.find_entry(TYPE_INFERENCE_SECTION_NAME, "mode") {
+        None => TypeInferenceMode::Sample,
+        Some(mode_entry) => {
+            let inference_mode = match &mode_entry.value {
+                MetadataTomlValue::String(mode_text) => TypeInferenceMode::from_toml_string(mode_text),
+                _ => None,
+            };
+            inference_mode.ok_or_else(|| {
+                create_metadata_error(
+                    &format!(
+                        "Invalid type inference mode at line {} (expected sample or full)",
+                        mode_entry.line_number
+                    ),
+                    metadata_file_path_for_errors,
+                )
+            })?
+        }
+    };
+
+    let default_boolean_vocabulary = BooleanVocabulary::default();
+    let boolean_vocabulary = BooleanVocabulary {
+        true_tokens: read_type_inference_string_list(metadata_document, "true_values", metadata_file_path_for_errors)?
+            .unwrap_or(default_boolean_vocabulary.true_tokens),
+        false_tokens: read_type_inference_string_list(metadata_document, "false_values", metadata_file_path_for_errors)?
+            .unwrap_or(default_boolean_vocabulary.false_tokens),
+    };
+    if let Some(conflicting_token) = boolean_vocabulary.find_conflicting_token() {
+        return Err(create_metadata_error(
+            &format!("\"{}\" is listed in both true_values and false_values", conflicting_token),
+            metadata_file_path_for_errors,
+        ));
+    }
+
+    Ok(TypeInferenceSettings {
+        inference_mode,
+        temporal_formats: TemporalFormatSet {
+            date_formats: read_temporal_format_list(metadata_document, "date_formats", metadata_file_path_for_errors)?,
+            time_formats: read_temporal_format_list(metadata_document, "time_formats", metadata_file_path_for_errors)?,
+            datetime_formats: read_temporal_format_list(
+                metadata_document,
+                "datetime_formats",
+                metadata_file_path_for_errors,
+            )?,
+        },
+        boolean_vocabulary,
+    })
+}
+
+/// Reads an optional list of strings from `[type_inference]`
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document
+/// * `key_name` - The list to read
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<Vec<String>>>` - The list (a single string counts as a
+///   one-item list), or None if the key is absent
+fn read_type_inference_string_list(
+    metadata_document: &MetadataTomlDocument,
+    key_name: &str,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<Option<Vec<String>>> {
+    let list_entry = match metadata_document.find_entry(TYPE_INFERENCE_SECTION_NAME, key_name) {
+        Some(list_entry) => list_entry,
+        None => return Ok(None),
+    };
+
+    match &list_entry.value {
+        MetadataTomlValue::StringArray(list_items) => Ok(Some(list_items.clone())),
+        MetadataTomlValue::String(single_item) => Ok(Some(vec![single_item.clone()])),
+        _ => Err(create_metadata_error(
+            &format!("'{}' at line {} must be an array of strings", key_name, list_entry.line_number),
+            metadata_file_path_for_errors,
+        )),
+    }
+}
+
+/// Reads and validates one list of date or time formats from `[type_inference]`
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document
+/// * `key_name` - The list to read (`date_formats`, `time_formats` or `datetime_formats`)
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<String>>` - The formats, or an empty list if the key is absent
+fn read_temporal_format_list(
+    metadata_document: &MetadataTomlDocument,
+    key_name: &str,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<Vec<String>> {
+    let format_entry = match metadata_document.find_entry(TYPE_INFERENCE_SECTION_NAME, key_name) {
+        Some(format_entry) => format_entry,
+        None => return Ok(Vec::new()),
+    };
+
+    let temporal_formats = read_type_inference_string_list(metadata_document, key_name, metadata_file_path_for_errors)?
+        .unwrap_or_default();
+
+    for temporal_format in &temporal_formats {
+        validate_temporal_format(temporal_format).map_err(|format_problem| {
+            create_metadata_error(
+                &format!(
+                    "Invalid format \"{}\" in '{}' at line {}: {}",
+                    temporal_format, key_name, format_entry.line_number, format_problem
+                ),
+                metadata_file_path_for_errors,
+            )
+        })?;
+    }
+
+    Ok(temporal_formats)
+}
+
+/// Formats the `[type_inference]` TOML section
+///
+/// # Arguments
+/// * `inference_summary` - The settings the column types were inferred with and the rows scanned
+///
+/// # Returns
+/// * `String` - The section text, ending with a blank line
+pub fn format_type_inference_toml_section(inference_summary: &TypeInferenceSummary) -> String {
+    let inference_settings = &inference_summary.inference_settings;
+    let temporal_formats = &inference_settings.temporal_formats;
+
+    let mut section_text = String::new();
+    section_text.push_str(&format!("[{}]\n", TYPE_INFERENCE_SECTION_NAME));
+    section_text.push_str(&format!("mode = \"{}\"\n", inference_settings.inference_mode.to_toml_string()));
+    section_text.push_str(&format!("date_formats = {}\n", format_toml_string_array(&temporal_formats.date_formats)));
+    section_text.push_str(&format!("time_formats = {}\n", format_toml_string_array(&temporal_formats.time_formats)));
+    section_text.push_str(&format!(
+        "datetime_formats = {}\n",
+        format_toml_string_array(&temporal_formats.datetime_formats)
+    ));
+    section_text.push_str(&format!(
+        "true_values = {}\n",
+        format_toml_string_array(&inference_settings.boolean_vocabulary.true_tokens)
+    ));
+    section_text.push_str(&format!(
+        "false_values = {}\n",
+        format_toml_string_array(&inference_settings.boolean_vocabulary.false_tokens)
+    ));
+    section_text.push_str(&format!("scanned_rows = {}\n", inference_summary.scanned_row_count));
+    section_text.push('\n');
+    section_text
+}
+
+/// Formats a column's type evidence as `key = value` lines for its `[column_N]` section
+///
+/// For each candidate type this writes `<type>_matches`, and when some value
+/// did not match, `first_non_<type>` and `first_non_<type>_line`.
+///
+/// # Arguments
+/// * `column_type_tally` - The column's per-type match counts
+///
+/// # Returns
+/// * `String` - The lines, each ending with a newline
+pub fn format_column_type_tally_toml_lines(column_type_tally: &ColumnTypeTally) -> String {
+    let mut tally_lines = String::new();
+    tally_lines.push_str(&format!("checked_values = {}\n", column_type_tally.checked_value_count));
+
+    for type_match_count in &column_type_tally.type_match_counts {
+        let type_name = type_match_count.data_type.to_toml_string();
+        tally_lines.push_str(&format!("{}_matches = {}\n", type_name, type_match_count.matched_value_count));
+
+        if let Some(first_mismatch) = &type_match_count.first_mismatch {
+            tally_lines.push_str(&format!(
+                "first_non_{} = \"{}\"\n",
+                type_name,
+                escape_toml_basic_string(&first_mismatch.offending_value)
+            ));
+            tally_lines.push_str(&format!("first_non_{}_line = {}\n", type_name, first_mismatch.line_number));
+        }
+    }
+
+    tally_lines
+}
+
+/// Reads an optional string key from the `[dialect]` section
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document
+/// * `key_name` - Dialect key to read
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<String>>` - The string value, or None if the key is absent
+fn read_dialect_string(
+    metadata_document: &MetadataTomlDocument,
+    key_name: &str,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<Option<String>> {
+    if let Some(string_value) = metadata_document.get_string(DIALECT_SECTION_NAME, key_name) {
+        return Ok(Some(string_value.to_string()));
+    }
+
+    match metadata_document.find_entry(DIALECT_SECTION_NAME, key_name) {
+        None => Ok(None),
+        Some(non_string_entry) => Err(create_metadata_error(
+            &format!(
+                "Dialect key '{}' at line {} must be a string",
+                key_name, non_string_entry.line_number
+            ),
+            metadata_file_path_for_errors,
+        )),
+    }
+}
+
+/// Parses a dialect value that must be exactly one character
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document (for the line number)
+/// * `key_name` - Dialect key being parsed
+/// * `value_text` - The string value
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<char>` - The single character
+fn parse_single_dialect_character(
+    metadata_document: &MetadataTomlDocument,
+    key_name: &str,
+    value_text: &str,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsResult<char> {
+    let mut value_characters = value_text.chars();
+    match (value_characters.next(), value_characters.next()) {
+        (Some(single_character), None) if single_character != '\n' && single_character != '\r' => {
+            Ok(single_character)
+        }
+        _ => Err(create_invalid_dialect_value_error(
+            metadata_document,
+            key_name,
+            value_text,
+            "a single character other than a line break",
+            metadata_file_path_for_errors,
+        )),
+    }
+}
+
+/// Creates the error for a dialect key with an unsupported value
+///
+/// # Arguments
+/// * `metadata_document` - Parsed metadata TOML document (for the line number)
+/// * `key_name` - Dialect key with the bad value
+/// * `value_text` - The rejected value
+/// * `expected_description` - What values are accepted
+/// * `metadata_file_path_for_errors` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsError` - A metadata error naming the key, line and accepted values
+fn create_invalid_dialect_value_error(
+    metadata_document: &MetadataTomlDocument,
+    key_name: &str,
+    value_text: &str,
+    expected_description: &str,
+    metadata_file_path_for_errors: &str,
+) -> RowsAndColumnsError {
+    let line_number = metadata_document
+        .find_entry(DIALECT_SECTION_NAME, key_name)
+        .map(|entry| entry.line_number)
+        .unwrap_or(0);
+
+    create_metadata_error(
+        &format!(
+            "Invalid dialect {} {:?} at line {} (expected {})",
+            key_name, value_text, line_number, expected_description
+        ),
+        metadata_file_path_for_errors,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sections_and_scalar_values() {
+        let toml_text = "# header\ntotal_columns = 7\n\n[dialect]\ndelimiter = \"\\t\" # tab\nquote_character = '\"'\n\n[column_1]\nname = \"id\"\nflag = true\nratio = 0.5\n";
+        let parsed_document = parse_metadata_toml_content(toml_text, "test.toml").expect("valid TOML");
+
+        assert_eq!(
+            parsed_document.find_entry("", "total_columns").map(|entry| entry.value.clone()),
+            Some(MetadataTomlValue::Integer(7))
+        );
+        assert_eq!(parsed_document.get_string("dialect", "delimiter"), Some("\t"));
+        assert_eq!(parsed_document.get_string("dialect", "quote_character"), Some("\""));
+        assert_eq!(parsed_document.get_string("column_1", "name"), Some("id"));
+        assert_eq!(
+            parsed_document.find_entry("column_1", "flag").map(|entry| entry.value.clone()),
+            Some(MetadataTomlValue::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_parse_string_arrays() {
+        let toml_text = "[type_inference]\ndate_formats = [\"%m/%d/%Y\", '%d.%m.%Y'] # two\ntime_formats = []\n";
+        let parsed_document = parse_metadata_toml_content(toml_text, "test.toml").expect("valid TOML");
+        let date_formats = vec!["%m/%d/%Y".to_string(), "%d.%m.%Y".to_string()];
+
+        assert_eq!(
+            parsed_document.find_entry("type_inference", "date_formats").map(|entry| entry.value.clone()),
+            Some(MetadataTomlValue::StringArray(date_formats.clone()))
+        );
+        assert_eq!(
+            parsed_document.find_entry("type_inference", "time_formats").map(|entry| entry.value.clone()),
+            Some(MetadataTomlValue::StringArray(Vec::new()))
+        );
+        assert!(parse_metadata_toml_content("formats = [\"a\", 1]\n", "test.toml").is_err());
+        assert!(parse_metadata_toml_content("formats = [1, \"a\"]\n", "test.toml").is_err());
+        let number_document = parse_metadata_toml_content("percentiles = [1, 2.5, 1_000] # three\n", "test.toml")
+            .expect("number arrays should parse");
+        assert_eq!(
+            number_document.find_entry("", "percentiles").map(|entry| entry.value.clone()),
+            Some(MetadataTomlValue::NumberArray(vec![1.0, 2.5, 1000.0]))
+        );
+        assert_eq!(format_toml_string_array(&date_formats), "[\"%m/%d/%Y\", \"%d.%m.%Y\"]");
+    }
+
+    #[test]
+    fn test_parse_error_reports_line_number() {
+        let parse_result = parse_metadata_toml_content("a = 1\nthis line is wrong\n", "test.toml");
+
+        match parse_result {
+            Err(metadata_error) => assert!(metadata_error.to_string().contains("line 2")),
+            Ok(_) => panic!("Expected a metadata parse error"),
+        }
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        let original_value = "say \"hi\"\tnow\\";
+        let toml_line = format!("value = \"{}\"", escape_toml_basic_string(original_value));
+        let parsed_document = parse_metadata_toml_content(&toml_line, "test.toml").expect("valid TOML");
+
+        assert_eq!(parsed_document.get_string("", "value"), Some(original_value));
+    }
+
+    #[test]
+    fn test_dialect_section_round_trip_and_partial_override() {
+        let pipe_dialect = CsvDialect {
+            field_delimiter: '|',
+            quote_character: None,
+            escape_style: CsvEscapeStyle::Backslash,
+            comment_prefix: Some("#".to_string()),
+            trim_policy: CsvTrimPolicy::KeepWhitespace,
+            line_terminator: CsvLineTerminator::CrLf,
+        };
+        let section_text = format_csv_dialect_toml_section(&pipe_dialect);
+        let parsed_document = parse_metadata_toml_content(&section_text, "test.toml").expect("valid TOML");
+        let read_back_dialect = read_csv_dialect_from_metadata(&parsed_document, CsvDialect::default(), "test.toml")
+            .expect("valid dialect");
+        assert_eq!(read_back_dialect, pipe_dialect);
+
+        let partial_document = parse_metadata_toml_content("[dialect]\ndelimiter = \"\\t\"\n", "test.toml")
+            .expect("valid TOML");
+        let partial_dialect = read_csv_dialect_from_metadata(&partial_document, CsvDialect::default(), "test.toml")
+            .expect("valid dialect");
+        assert_eq!(partial_dialect.field_delimiter, '\t');
+        assert_eq!(partial_dialect.quote_character, Some('"'));
+    }
+
+    #[test]
+    fn test_invalid_dialect_value_is_metadata_error() {
+        let parsed_document = parse_metadata_toml_content("[dialect]\ndelimiter = \";;\"\n", "test.toml")
+            .expect("valid TOML");
+        let dialect_result = read_csv_dialect_from_metadata(&parsed_document, CsvDialect::default(), "test.toml");
+
+        match dialect_result {
+            Err(metadata_error) => assert!(metadata_error.to_string().contains("line 2")),
+            Ok(_) => panic!("Expected an invalid dialect error"),
+        }
+    }
+
+    #[test]
+    fn test_null_values_global_and_per_column() {
+        let metadata_document = parse_metadata_toml_content(
+            "null_values = [\"\", \"missing\"]\n[column_2]\nnull_values = []\n[dialect]\nnull_values = [\"x\"]\n",
+            "test.toml",
+        )
+        .expect("null values should parse");
+        let null_value_settings = read_null_value_settings_from_metadata(Some(&metadata_document), "test.toml")
+            .expect("null values should be valid");
+
+        assert!(null_value_settings.sentinels_for_column(0).is_missing_value("missing"));
+        assert!(!null_value_settings.sentinels_for_column(0).is_missing_value("NA"));
+        assert!(!null_value_settings.sentinels_for_column(1).is_missing_value("missing"));
+        assert!(null_value_settings.sentinels_for_column(1).is_missing_value(" "));
+        assert_eq!(null_value_settings.column_sentinel_overrides.len(), 1);
+
+        let invalid_document = parse_metadata_toml_content("null_values = \"NA\"\n", "test.toml")
+            .expect("string value should parse");
+        assert!(read_null_value_settings_from_metadata(Some(&invalid_document), "test.toml").is_err());
+    }
+
+    #[test]
+    fn test_declared_column_schema_ignores_untouched_types() {
+        let metadata_document = parse_metadata_toml_content(
+            "[column_1]\nname = \"id\"\ndata_type = \"integer\"\ndetected_type = \"integer\"\n\
+             [column_2]\nname = \"zip\"\ndata_type = \"string\"\ndetected_type = \"integer\"\n\
+             [column_3]\ndata_type = \"date\"\nignored = true\ndecimal_scale = 4\n",
+            "test.toml",
+        )
+        .expect("column sections should parse");
+        let declared_column_schema = read_declared_column_schema_from_metadata(Some(&metadata_document), 3, "test.toml")
+            .expect("column sections should be valid");
+
+        assert_eq!(declared_column_schema[&0].declared_data_type, None);
+        assert_eq!(declared_column_schema[&0].column_name.as_deref(), Some("id"));
+        assert_eq!(declared_column_schema[&1].declared_data_type, Some(CsvColumnDataType::String));
+        assert_eq!(declared_column_schema[&2].declared_data_type, Some(CsvColumnDataType::Date));
+        assert!(declared_column_schema[&2].column_ignored);
+        assert!(!declared_column_schema[&1].column_ignored);
+        assert_eq!((declared_column_schema[&1].decimal_scale, declared_column_schema[&2].decimal_scale), (None, Some(4)));
+
+        assert!(read_declared_column_schema_from_metadata(Some(&metadata_document), 2, "test.toml").is_err());
+        let invalid_document = parse_metadata_toml_content("[column_1]\ndata_type = \"bogus\"\n", "test.toml")
+            .expect("column section should parse");
+        assert!(read_declared_column_schema_from_metadata(Some(&invalid_document), 1, "test.toml").is_err());
+        let invalid_document = parse_metadata_toml_content("[column_1]\ndecimal_scale = 19\n", "test.toml")
+            .expect("column section should parse");
+        assert!(read_declared_column_schema_from_metadata(Some(&invalid_document), 1, "test.toml").is_err());
+    }
+
+    #[test]
+    fn test_storage_section_round_trip_and_invalid_values() {
+        assert_eq!(
+            read_dataset_storage_settings_from_metadata(None, "test.toml").expect("defaults should apply"),
+            DatasetStorageSettings::default()
+        );
+
+        let storage_settings = DatasetStorageSettings {
+            storage_backend: DatasetStorageBackend::ColumnSegments,
+            segment_row_count: 250,
+        };
+        let metadata_document = parse_metadata_toml_content(&format_dataset_storage_toml_section(&storage_settings), "test.toml")
+            .expect("storage section should parse");
+        assert_eq!(
+            read_dataset_storage_settings_from_metadata(Some(&metadata_document), "test.toml")
+                .expect("storage section should be valid"),
+            storage_settings
+        );
+
+        for invalid_section in ["[storage]\nbackend = \"zip\"\n", "[storage]\nsegment_rows = 0\n"] {
+            let invalid_document = parse_metadata_toml_content(invalid_section, "test.toml")
+                .expect("storage section should parse");
+            assert!(read_dataset_storage_settings_from_metadata(Some(&invalid_document), "test.toml").is_err());
+        }
+    }
+
+    #[test]
+    fn test_statistics_section_round_trip_and_invalid_mode() {
+        assert_eq!(
+            read_statistics_settings_from_metadata(None, "test.toml").expect("defaults should apply"),
+            StatisticsSettings {
+                statistics_mode: StatisticsMode::Streaming,
+                top_value_capacity: 1000,
+                delta_degrees_of_freedom: 1,
+                requested_percentiles: Vec::new(),
+                percentile_interpolation: PercentileInterpolation::Linear,
+            }
+        );
+
+        let statistics_settings = StatisticsSettings {
+            statistics_mode: StatisticsMode::Exact,
+            top_value_capacity: 50,
+            delta_degrees_of_freedom: 0,
+            requested_percentiles: vec![1.0, 2.5, 99.0],
+            percentile_interpolation: PercentileInterpolation::Nearest,
+        };
+        let metadata_document = parse_metadata_toml_content(&format_statistics_toml_section(&statistics_settings), "test.toml")
+            .expect("statistics section should parse");
+        assert_eq!(
+            read_statistics_settings_from_metadata(Some(&metadata_document), "test.toml")
+                .expect("statistics section should be valid"),
+            statistics_settings
+        );
+
+        let invalid_sections = [
+            "[statistics]\nmode = \"approximate\"\n",
+            "[statistics]\ntop_values = 0\n",
+            "[statistics]\nddof = -1\n",
+            "[statistics]\npercentiles = [5, 150]\n",
+            "[statistics]\npercentiles = [\"p5\"]\n",
+            "[statistics]\ninterpolation = \"cubic\"\n",
+        ];
+        for invalid_section in invalid_sections {
+            let invalid_document = parse_metadata_toml_content(invalid_section, "test.toml")
+                .expect("statistics section should parse");
+            assert!(read_statistics_settings_from_metadata(Some(&invalid_document), "test.toml").is_err());
+        }
+    }
+}
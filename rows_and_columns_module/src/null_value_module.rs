@@ -0,0 +1,195 @@
+// src/null_value_module.rs
+
+/// Missing value sentinels for rows_and_columns
+///
+/// Besides truly blank cells, many CSV files mark missing data with a sentinel
+/// such as `NA`, `N/A`, `null`, `-` or `?`. The metadata file lists these in a
+/// top-level `null_values` array, and any `[column_N]` section can set its own
+/// `null_values` to replace the global list for that column (for example
+/// `null_values = [""]` where `-` is a real value).
+///
+/// Sentinels are compared with the trimmed cell exactly, including case.
+/// Every analysis pass (header detection, type inference and the column
+/// statistics) skips sentinel values the same way it skips blanks, and the
+/// missing value counts keep three kinds apart:
+/// - blank: the cell is empty or whitespace only
+/// - null sentinel: the cell is one of the configured sentinels
+/// - type-invalid: the cell has a value that does not parse as the column type
+use std::collections::BTreeMap;
+
+/// Sentinels used when the metadata file does not set `null_values`
+pub const DEFAULT_NULL_VALUE_SENTINELS: [&str; 6] = ["", "NA", "N/A", "null", "-", "?"];
+
+/// Why a cell counts as missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingValueKind {
+    /// Empty or whitespace-only cell
+    Blank,
+
+    /// Cell equal to one of the configured null sentinels
+    NullSentinel,
+}
+
+/// The null sentinels that apply to one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct NullValueSentinels {
+    /// Sentinel values, compared with the trimmed cell exactly
+    pub sentinel_values: Vec<String>,
+}
+
+impl Default for NullValueSentinels {
+    fn default() -> Self {
+        NullValueSentinels {
+            sentinel_values: DEFAULT_NULL_VALUE_SENTINELS
+                .iter()
+                .map(|sentinel_value| sentinel_value.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl NullValueSentinels {
+    /// Decides whether a cell is missing, and why
+    ///
+    /// # Arguments
+    /// * `field_value` - The raw or trimmed cell value
+    ///
+    /// # Returns
+    /// * `Option<MissingValueKind>` - The kind of missing value, or None for a present value
+    pub fn classify_missing_value(&self, field_value: &str) -> Option<MissingValueKind> {
+        let trimmed_value = field_value.trim();
+        if trimmed_value.is_empty() {
+            Some(MissingValueKind::Blank)
+        } else if self.sentinel_values.iter().any(|sentinel_value| sentinel_value == trimmed_value) {
+            Some(MissingValueKind::NullSentinel)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether a cell is blank or a null sentinel
+    ///
+    /// # Arguments
+    /// * `field_value` - The raw or trimmed cell value
+    ///
+    /// # Returns
+    /// * `bool` - True if the cell is missing
+    pub fn is_missing_value(&self, field_value: &str) -> bool {
+        self.classify_missing_value(field_value).is_some()
+    }
+}
+
+/// Null sentinels for a whole file: the global list and per-column replacements
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NullValueSettings {
+    /// Top-level `null_values` (or the defaults)
+    pub global_sentinels: NullValueSentinels,
+
+    /// `null_values` set in `[column_N]` sections, keyed by 0-based column index
+    pub column_sentinel_overrides: BTreeMap<usize, NullValueSentinels>,
+}
+
+impl NullValueSettings {
+    /// Returns the sentinels that apply to a column
+    ///
+    /// # Arguments
+    /// * `column_index` - 0-based column index
+    ///
+    /// # Returns
+    /// * `&NullValueSentinels` - The column's own list, or the global list
+    pub fn sentinels_for_column(&self, column_index: usize) -> &NullValueSentinels {
+        self.column_sentinel_overrides
+            .get(&column_index)
+            .unwrap_or(&self.global_sentinels)
+    }
+}
+
+/// Missing value counts for one column, kept apart by kind
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MissingValueCounts {
+    /// Empty or whitespace-only cells
+    pub blank_value_count: usize,
+
+    /// Cells equal to a null sentinel
+    pub null_sentinel_count: usize,
+
+    /// Present cells that do not parse as the column type
+    pub type_invalid_count: usize,
+}
+
+impl MissingValueCounts {
+    /// Counts one blank or sentinel cell
+    ///
+    /// # Arguments
+    /// * `missing_value_kind` - Why the cell is missing
+    pub fn record_missing_value(&mut self, missing_value_kind: MissingValueKind) {
+        match missing_value_kind {
+            MissingValueKind::Blank => self.blank_value_count += 1,
+            MissingValueKind::NullSentinel => self.null_sentinel_count += 1,
+        }
+    }
+
+    /// Total number of cells without a usable value
+    ///
+    /// # Returns
+    /// * `usize` - Blanks, sentinels and type-invalid values together
+    pub fn total_missing_count(&self) -> usize {
+        self.blank_value_count + self.null_sentinel_count + self.type_invalid_count
+    }
+
+    /// Share of cells without a usable value
+    ///
+    /// # Arguments
+    /// * `total_value_count` - Number of cells in the column
+    ///
+    /// # Returns
+    /// * `f64` - Percentage from 0 to 100 (0 for an empty column)
+    pub fn missing_percentage(&self, total_value_count: usize) -> f64 {
+        if total_value_count > 0 {
+            (self.total_missing_count() as f64 / total_value_count as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blank_sentinel_and_present_values() {
+        let null_value_sentinels = NullValueSentinels::default();
+
+        assert_eq!(null_value_sentinels.classify_missing_value("   "), Some(MissingValueKind::Blank));
+        assert_eq!(null_value_sentinels.classify_missing_value(" N/A "), Some(MissingValueKind::NullSentinel));
+        assert_eq!(null_value_sentinels.classify_missing_value("-"), Some(MissingValueKind::NullSentinel));
+        assert_eq!(null_value_sentinels.classify_missing_value("NULL"), None);
+        assert_eq!(null_value_sentinels.classify_missing_value("-5"), None);
+    }
+
+    #[test]
+    fn test_column_override_replaces_global_list() {
+        let mut null_value_settings = NullValueSettings::default();
+        null_value_settings.column_sentinel_overrides.insert(
+            1,
+            NullValueSentinels { sentinel_values: vec![String::new()] },
+        );
+
+        assert!(null_value_settings.sentinels_for_column(0).is_missing_value("-"));
+        assert!(!null_value_settings.sentinels_for_column(1).is_missing_value("-"));
+        assert!(null_value_settings.sentinels_for_column(1).is_missing_value(""));
+    }
+
+    #[test]
+    fn test_missing_percentage_counts_every_kind() {
+        let mut missing_value_counts = MissingValueCounts::default();
+        missing_value_counts.record_missing_value(MissingValueKind::Blank);
+        missing_value_counts.record_missing_value(MissingValueKind::NullSentinel);
+        missing_value_counts.type_invalid_count = 2;
+
+        assert_eq!(missing_value_counts.total_missing_count(), 4);
+        assert_eq!(missing_value_counts.missing_percentage(8), 50.0);
+        assert_eq!(MissingValueCounts::default().missing_percentage(0), 0.0);
+    }
+}
@@ -25,7 +25,7 @@
 /// - No pre-loading: on-demand data processing for scalability
 /// - Clear error handling with comprehensive user feedback
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::io::{self, Write};
 
 use crate::csv_processor_module::EnhancedCsvColumnInformation;
@@ -35,7 +35,45 @@ use super::csv_processor_module::{
     analyze_csv_file_structure_and_types,
     CsvAnalysisResults,
     perform_enhanced_statistical_analysis,
+    perform_enhanced_statistical_analysis_from_dataset,
     display_enhanced_csv_analysis_results,
+    ColumnOutlierAnalysis,
+    perform_outlier_analysis,
+    display_outlier_analysis_results,
+    count_flagged_outlier_rows,
+    write_outlier_rows_file,
+};
+
+// Import the column type review screen (menu option 1)
+use super::column_type_review_module::run_column_type_review_screen;
+
+// Import the directory-per-column data import (menu option 2)
+use super::csv_directory_import_module::{
+    import_csv_into_directory_storage,
+    open_imported_dataset_reader,
+    display_directory_import_summary,
+};
+
+// Import the CSV export of directory-stored datasets (--export)
+use super::csv_directory_export_module::{
+    parse_dataset_export_arguments,
+    resolve_dataset_directory,
+    export_dataset_to_csv,
+    display_dataset_export_summary,
+};
+
+// Import the value-to-row lookup indexes (--index-column, --lookup)
+use super::column_lookup_index_module::{
+    build_column_lookup_index,
+    look_up_rows_by_value,
+    display_column_lookup_index_summary,
+    display_column_lookup_result,
+};
+
+// Import the standalone lookup crate generator (--generate-lookup)
+use super::lookup_crate_generator_module::{
+    generate_column_lookup_crate,
+    display_lookup_crate_generation_summary,
 };
 
 // Import our custom error types for comprehensive error handling
@@ -67,6 +105,7 @@ const ANALYSIS_CACHE_SUBDIRECTORY_NAME: &str = "analysis_cache";
 /// # Command Line Usage
 /// * `rows_and_columns` - Interactive mode (future implementation)
 /// * `rows_and_columns <csv_file_path>` - Process specific CSV file
+/// * `rows_and_columns --export <dataset> <output.csv> [options]` - Write an imported dataset as CSV
 /// * `rows_and_columns --help` - Show usage information
 /// 
 /// # Returns
@@ -107,6 +146,18 @@ pub fn run_rows_and_columns_application() -> RowsAndColumnsResult<()> {
                 display_usage_help_information();
                 return Ok(());
             }
+            "--export" => {
+                return export_dataset_from_command_line(&command_line_arguments[2..], &directory_paths);
+            }
+            "--index-column" => {
+                return build_lookup_index_from_command_line(&command_line_arguments[2..], &directory_paths);
+            }
+            "--lookup" => {
+                return look_up_value_from_command_line(&command_line_arguments[2..], &directory_paths);
+            }
+            "--generate-lookup" => {
+                return generate_lookup_crate_from_command_line(&command_line_arguments[2..], &directory_paths);
+            }
             _ => {
                 // Treat the first argument as a CSV file path
                 let csv_file_path = &command_line_arguments[1];
@@ -129,12 +180,26 @@ pub fn run_rows_and_columns_application() -> RowsAndColumnsResult<()> {
 fn display_usage_help_information() {
     println!("USAGE:");
     println!("  rows_and_columns <csv_file_path>     Process a specific CSV file");
+    println!("  rows_and_columns --export <dataset> <output.csv> [--columns a,b] [--rows A..B] [--no-header]");
+    println!("                                       Write an imported dataset back to CSV");
+    println!("  rows_and_columns --index-column <dataset> <column>");
+    println!("                                       Build a value-to-row lookup index for a column");
+    println!("  rows_and_columns --lookup <dataset> <column> <value>");
+    println!("                                       List the rows holding exactly a value (case-sensitive)");
+    println!("  rows_and_columns --generate-lookup <dataset> <column> <output_directory>");
+    println!("                                       Write a standalone Rust lookup program for a column");
     println!("  rows_and_columns --help              Show this help information");
     println!();
     println!("EXAMPLES:");
     println!("  rows_and_columns data/customers.csv");
     println!("  rows_and_columns /home/user/sales_data.csv");
     println!("  rows_and_columns ../reports/quarterly.csv");
+    println!("  rows_and_columns --export dataset_001_customers customers_copy.csv");
+    println!("  rows_and_columns --export dataset_001_customers top.csv --columns name,total --rows 1..100");
+    println!("  rows_and_columns --index-column dataset_001_cats breed");
+    println!("  rows_and_columns --lookup dataset_001_cats breed Siamese");
+    println!("  rows_and_columns --lookup dataset_001_cats breed \"Black Cat\"");
+    println!("  rows_and_columns --generate-lookup dataset_001_cats breed ./breed_lookup");
     println!();
     println!("FEATURES:");
     println!("  • Directory-based CSV data storage for scalability");
@@ -440,20 +505,23 @@ fn validate_user_provided_csv_file_path(user_file_path: &str) -> Result<String,
 /// has been accomplished so far (CSV analysis) and what can be done next.
 /// 
 /// # Arguments
-/// * `csv_analysis_results` - The basic CSV analysis results
-/// * `enhanced_analysis_results` - The enhanced statistical analysis results
+/// * `csv_analysis_results` - The basic CSV analysis results (updated by the type review)
+/// * `enhanced_analysis_results` - The enhanced statistical analysis results (recomputed after type changes)
 /// * `directory_paths` - Application directory structure
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or error
 fn launch_interactive_post_analysis_menu(
-    csv_analysis_results: &CsvAnalysisResults,
-    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    csv_analysis_results: &mut CsvAnalysisResults,
+    enhanced_analysis_results: &mut Vec<EnhancedCsvColumnInformation>,
     directory_paths: &ApplicationDirectoryPaths,
 ) -> RowsAndColumnsResult<()> {
     println!("Analysis complete! Choose your next step:");
     println!();
     
+    // Set once the data is loaded; statistics are then recomputed from the stored dataset
+    let mut loaded_dataset_directory: Option<PathBuf> = None;
+    
     loop {
         // Display menu options
         display_post_analysis_main_menu();
@@ -464,15 +532,24 @@ fn launch_interactive_post_analysis_menu(
         // Process user choice
         match user_selection.to_lowercase().as_str() {
             "1" | "review" | "types" | "edit" => {
-                println!("🔧 Column data type review selected.");
-                println!("This will allow you to verify and modify detected column types.");
-                println!("(Implementation coming in next step)");
+                let saved_change_count = run_column_type_review_screen(csv_analysis_results)?;
+                if saved_change_count > 0 {
+                    *enhanced_analysis_results = recompute_enhanced_statistical_analysis(
+                        csv_analysis_results,
+                        loaded_dataset_directory.as_deref(),
+                    )?;
+                    println!("✓ {} column change(s) saved; statistics updated.", saved_change_count);
+                }
                 println!();
             }
             "2" | "load" | "import" | "directory" => {
-                println!("📂 Data loading into directory structure selected.");
-                println!("This will create the scalable directory-based storage system.");
-                println!("(Implementation coming in next step)");
+                println!("📂 Loading data into directory-based storage...");
+                let import_summary = import_csv_into_directory_storage(
+                    csv_analysis_results,
+                    &directory_paths.csv_imports_directory,
+                )?;
+                display_directory_import_summary(&import_summary);
+                loaded_dataset_directory = Some(import_summary.dataset_directory);
                 println!();
             }
             "3" | "export" | "report" | "save" => {
@@ -481,7 +558,13 @@ fn launch_interactive_post_analysis_menu(
                 println!("(Implementation coming in next step)");
                 println!();
             }
-            "4" | "quit" | "exit" | "q" => {
+            "4" | "outliers" | "outlier" => {
+                let outlier_analyses = perform_outlier_analysis(csv_analysis_results, enhanced_analysis_results)?;
+                display_outlier_analysis_results(&outlier_analyses);
+                offer_outlier_rows_file(csv_analysis_results, &outlier_analyses)?;
+                println!();
+            }
+            "5" | "quit" | "exit" | "q" => {
                 println!("Thank you for using rows_and_columns!");
                 println!("Your analysis results and metadata have been saved.");
                 return Ok(());
@@ -490,18 +573,82 @@ fn launch_interactive_post_analysis_menu(
                 display_post_analysis_menu_help();
             }
             "" => {
-                println!("Please enter a selection (1-4) or 'help' for assistance.");
+                println!("Please enter a selection (1-5) or 'help' for assistance.");
                 println!();
             }
             _ => {
                 println!("Invalid selection: '{}'", user_selection);
-                println!("Please choose 1-4, or type 'help' for assistance.");
+                println!("Please choose 1-5, or type 'help' for assistance.");
                 println!();
             }
         }
     }
 }
 
+/// Offers to write the rows flagged by the outlier analysis to a side CSV file
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - The basic CSV analysis results
+/// * `outlier_analyses` - The outlier analyses from menu option 4
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn offer_outlier_rows_file(
+    csv_analysis_results: &CsvAnalysisResults,
+    outlier_analyses: &[ColumnOutlierAnalysis],
+) -> RowsAndColumnsResult<()> {
+    let flagged_row_count = count_flagged_outlier_rows(outlier_analyses);
+    if flagged_row_count == 0 {
+        println!("No outliers flagged.");
+        return Ok(());
+    }
+    
+    let user_answer = get_user_menu_selection(&format!(
+        "Write the {} flagged row(s) to a side CSV for review? (y/n)",
+        flagged_row_count
+    ))?;
+    if matches!(user_answer.to_lowercase().as_str(), "y" | "yes") {
+        let (outlier_rows_file_path, written_row_count) = write_outlier_rows_file(csv_analysis_results, outlier_analyses)?;
+        println!("✓ {} flagged row(s) written to {}", written_row_count, outlier_rows_file_path.display());
+    }
+    Ok(())
+}
+
+/// Recomputes the enhanced statistics after the column types were changed
+/// 
+/// Reads the values from the loaded dataset when it stores every analyzed
+/// column, and from the CSV file otherwise (for example when a column left out
+/// of the import was included again).
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - The basic CSV analysis results with the new column types
+/// * `loaded_dataset_directory` - The dataset loaded by menu option 2, if any
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>>` - The new enhanced analysis
+fn recompute_enhanced_statistical_analysis(
+    csv_analysis_results: &CsvAnalysisResults,
+    loaded_dataset_directory: Option<&Path>,
+) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
+    if let Some(dataset_directory) = loaded_dataset_directory {
+        let (import_progress_record, mut dataset_storage_reader) = open_imported_dataset_reader(dataset_directory)?;
+        let dataset_stores_every_column = csv_analysis_results
+            .column_information_list
+            .iter()
+            .filter(|column_info| !column_info.column_ignored)
+            .all(|column_info| dataset_storage_reader.stores_column(column_info.column_index));
+        if dataset_stores_every_column {
+            return perform_enhanced_statistical_analysis_from_dataset(
+                csv_analysis_results,
+                dataset_storage_reader.as_mut(),
+                import_progress_record.imported_row_count,
+            );
+        }
+    }
+    
+    perform_enhanced_statistical_analysis(&csv_analysis_results.csv_file_path, csv_analysis_results)
+}
+
 /// Displays the main post-analysis menu options
 /// 
 /// This shows users what they can do next after CSV analysis is complete,
@@ -513,7 +660,8 @@ fn display_post_analysis_main_menu() {
     println!("  1. Review/Edit Column Data Types");
     println!("  2. 'Load' Data into No-Load DataFrame (not in active memory)");
     println!("  3. Export Current Analysis Report");
-    println!("  4. Quit");
+    println!("  4. Detect Outliers (Tukey fences, z-score, MAD)");
+    println!("  5. Quit");
     println!();
     println!("  💡 Tip: Data loading (option 2) enables visualizations and advanced analysis");
     println!("  Type 'help' for detailed descriptions of each option.");
@@ -534,7 +682,7 @@ fn display_post_analysis_main_menu() {
 /// 
 /// # Errors
 /// * `RowsAndColumnsError::FileSystemError` - If input/output operations fail
-fn get_user_menu_selection(prompt_text: &str) -> RowsAndColumnsResult<String> {
+pub fn get_user_menu_selection(prompt_text: &str) -> RowsAndColumnsResult<String> {
     print!("{}: ", prompt_text);
     
     // Ensure the prompt is displayed immediately
@@ -564,15 +712,17 @@ fn display_post_analysis_menu_help() {
     
     println!("1. Review/Edit Column Data Types");
     println!("   • Verify that automatic type detection was accurate");
-    println!("   • Change data types if needed (boolean → string, etc.)");
-    println!("   • Essential step before loading data for analysis");
-    println!("   • Example: Change 'age' from string to integer");
+    println!("   • Shows how many values matched each candidate type");
+    println!("   • Change a type, rename a column, or ignore a column");
+    println!("   • Type changes are checked against every value before saving");
+    println!("   • Changes are saved to the metadata file and used on the next run");
     println!();
     
     println!("2. 'Load' Data into No-Load DataFrame (not in active memory)");
     println!("   • Creates scalable directory-based storage system");
     println!("   • Each column becomes a directory with individual cell files");
     println!("   • Enables memory-efficient processing of large datasets");
+    println!("   • Ignored columns are left out; an interrupted load resumes where it stopped");
     println!("   • Required before visualizations and advanced analysis");
     println!();
     
@@ -583,7 +733,14 @@ fn display_post_analysis_menu_help() {
     println!("   • Can be done before or after data loading");
     println!();
     
-    println!("4. Quit");
+    println!("4. Detect Outliers (Tukey fences, z-score, MAD)");
+    println!("   • Flags values outside Tukey's fences (1.5 × IQR mild, 3 × IQR extreme)");
+    println!("   • Also flags |z-score| > 3 and |modified z-score| > 3.5 (median and MAD)");
+    println!("   • Shows the fences, box plot whiskers, counts and flagged row numbers");
+    println!("   • Flagged rows can be written to a side CSV (data.outliers.csv) for review");
+    println!();
+    
+    println!("5. Quit");
     println!("   • Exit the application safely");
     println!("   • Analysis results and metadata files are preserved");
     println!("   • You can restart analysis later with the same CSV file");
@@ -597,6 +754,130 @@ fn display_post_analysis_menu_help() {
     println!();
 }
 
+/// Exports an imported dataset to a CSV file as requested on the command line
+/// 
+/// # Arguments
+/// * `export_arguments` - The command line arguments after `--export`
+/// * `directory_paths` - The application directory structure (for the csv_imports directory)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or detailed error information
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the arguments, dataset or columns are invalid
+/// * `RowsAndColumnsError::FileSystemError` - If reading the dataset or writing the output fails
+fn export_dataset_from_command_line(
+    export_arguments: &[String],
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    let export_request = parse_dataset_export_arguments(export_arguments)
+        .map_err(|argument_problem| create_configuration_error(&argument_problem))?;
+    let dataset_directory = resolve_dataset_directory(
+        &export_request.dataset_argument,
+        &directory_paths.csv_imports_directory,
+    )?;
+    
+    println!("Exporting dataset: {}", dataset_directory.display());
+    let export_summary = export_dataset_to_csv(
+        &dataset_directory,
+        &export_request.output_file_path,
+        &export_request.export_options,
+    )?;
+    display_dataset_export_summary(&export_summary);
+    
+    Ok(())
+}
+
+/// Builds the lookup index of a dataset column as requested on the command line
+/// 
+/// # Arguments
+/// * `index_arguments` - The command line arguments after `--index-column`
+/// * `directory_paths` - The application directory structure (for the csv_imports directory)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or detailed error information
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the arguments, dataset or column are invalid
+/// * `RowsAndColumnsError::FileSystemError` - If reading the column or writing the index fails
+fn build_lookup_index_from_command_line(
+    index_arguments: &[String],
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    let [dataset_argument, column_selector] = index_arguments else {
+        return Err(create_configuration_error("Usage: --index-column <dataset> <column>"));
+    };
+    let dataset_directory = resolve_dataset_directory(dataset_argument, &directory_paths.csv_imports_directory)?;
+    
+    println!("Indexing column '{}' of dataset: {}", column_selector, dataset_directory.display());
+    let index_summary = build_column_lookup_index(&dataset_directory, column_selector)?;
+    display_column_lookup_index_summary(&index_summary);
+    
+    Ok(())
+}
+
+/// Looks up the rows holding a value as requested on the command line
+/// 
+/// # Arguments
+/// * `lookup_arguments` - The command line arguments after `--lookup`
+/// * `directory_paths` - The application directory structure (for the csv_imports directory)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or detailed error information
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the arguments, dataset or column are invalid,
+///   or the column has no lookup index
+/// * `RowsAndColumnsError::FileSystemError` - If the index cannot be read or rebuilt
+fn look_up_value_from_command_line(
+    lookup_arguments: &[String],
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    let [dataset_argument, column_selector, looked_up_value] = lookup_arguments else {
+        return Err(create_configuration_error("Usage: --lookup <dataset> <column> <value>"));
+    };
+    let dataset_directory = resolve_dataset_directory(dataset_argument, &directory_paths.csv_imports_directory)?;
+    
+    let lookup_result = look_up_rows_by_value(&dataset_directory, column_selector, looked_up_value)?;
+    display_column_lookup_result(&lookup_result);
+    
+    Ok(())
+}
+
+/// Generates a standalone lookup crate for a dataset column as requested on the command line
+/// 
+/// # Arguments
+/// * `generation_arguments` - The command line arguments after `--generate-lookup`
+/// * `directory_paths` - The application directory structure (for the csv_imports directory)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or detailed error information
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the arguments, dataset, column or output directory are invalid
+/// * `RowsAndColumnsError::FileSystemError` - If reading the column or writing the crate fails
+fn generate_lookup_crate_from_command_line(
+    generation_arguments: &[String],
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    let [dataset_argument, column_selector, output_directory] = generation_arguments else {
+        return Err(create_configuration_error(
+            "Usage: --generate-lookup <dataset> <column> <output_directory>",
+        ));
+    };
+    let dataset_directory = resolve_dataset_directory(dataset_argument, &directory_paths.csv_imports_directory)?;
+    
+    println!("Generating a lookup crate for column '{}' of dataset: {}", column_selector, dataset_directory.display());
+    let generation_summary = generate_column_lookup_crate(
+        &dataset_directory,
+        column_selector,
+        &PathBuf::from(output_directory),
+    )?;
+    display_lookup_crate_generation_summary(&generation_summary);
+    
+    Ok(())
+}
+
 /// Processes a CSV file specified via command line argument with enhanced statistical analysis
 /// 
 /// This function validates the provided CSV file path, performs comprehensive analysis including
@@ -627,10 +908,10 @@ fn process_csv_file_from_command_line(
     display_csv_file_processing_information(&csv_file_absolute_path)?;
     
     // Step 3: Analyze CSV structure and column types (basic analysis)
-    let csv_analysis_results = analyze_csv_file_structure_and_types(&csv_file_absolute_path)?;
+    let mut csv_analysis_results = analyze_csv_file_structure_and_types(&csv_file_absolute_path)?;
     
     // Step 4: Perform enhanced statistical analysis
-    let enhanced_analysis_results = perform_enhanced_statistical_analysis(
+    let mut enhanced_analysis_results = perform_enhanced_statistical_analysis(
         &csv_file_absolute_path,
         &csv_analysis_results
     )?;
@@ -642,7 +923,7 @@ fn process_csv_file_from_command_line(
     display_enhanced_csv_processing_completion_status(&csv_analysis_results, directory_paths);
     
     // Step 7: Launch interactive menu for next steps
-    launch_interactive_post_analysis_menu(&csv_analysis_results, &enhanced_analysis_results, directory_paths)?;
+    launch_interactive_post_analysis_menu(&mut csv_analysis_results, &mut enhanced_analysis_results, directory_paths)?;
 
     Ok(())
 }
@@ -670,13 +951,18 @@ fn display_enhanced_csv_processing_completion_status(
     // Count field types for summary
     let mut continuous_count = 0;
     let mut categorical_count = 0;
+    let mut temporal_count = 0;
     
     for column_info in &analysis_results.column_information_list {
         match column_info.detected_data_type {
             super::csv_processor_module::CsvColumnDataType::Integer | 
-            super::csv_processor_module::CsvColumnDataType::Float => continuous_count += 1,
+            super::csv_processor_module::CsvColumnDataType::Float |
+            super::csv_processor_module::CsvColumnDataType::Decimal => continuous_count += 1,
             super::csv_processor_module::CsvColumnDataType::Boolean | 
             super::csv_processor_module::CsvColumnDataType::String => categorical_count += 1,
+            super::csv_processor_module::CsvColumnDataType::Date |
+            super::csv_processor_module::CsvColumnDataType::Time |
+            super::csv_processor_module::CsvColumnDataType::DateTime => temporal_count += 1,
         }
     }
     
@@ -686,6 +972,9 @@ fn display_enhanced_csv_processing_completion_status(
     if categorical_count > 0 {
         println!("    - {} categorical columns: value distributions, mode, uniqueness", categorical_count);
     }
+    if temporal_count > 0 {
+        println!("    - {} date/time columns: earliest, latest, range, histogram by period", temporal_count);
+    }
     
     println!("  • Metadata TOML file created/updated");
     println!("  • Ready for directory-based storage and visualization");
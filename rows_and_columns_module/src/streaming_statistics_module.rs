@@ -0,0 +1,1575 @@
+// src/streaming_statistics_module.rs
+
+/// One-pass, bounded-memory numerical statistics for rows_and_columns
+///
+/// The enhanced analysis reads each value once and feeds it to an accumulator
+/// instead of collecting whole columns, so memory does not grow with the
+/// number of rows. Count, sum, minimum and maximum are exact; mean, variance,
+/// skewness and kurtosis use one-pass central moment updates (Welford, with
+/// Terriberry's extension to the third and fourth moment), which are exact up
+/// to floating point rounding. Quartiles and percentiles are exact while a
+/// column has at most `EXACT_QUANTILE_BUFFER_CAPACITY` values; beyond that they
+/// come from P² quantile estimators (Jain & Chlamtac, 1985), which keep five
+/// markers per quantile.
+///
+/// Categorical columns track at most `top_values` distinct values with the
+/// Space-Saving algorithm (Metwally et al., 2005) and count distinct values
+/// with a HyperLogLog sketch. While a column has no more distinct values than
+/// that, both are exact; beyond it, the reported counts are flagged as
+/// estimates with their error bounds.
+///
+/// # The `[statistics]` Section
+/// ```toml
+/// [statistics]
+/// mode = "streaming"   # streaming (default) | exact
+/// top_values = 1000    # distinct values tracked per categorical column
+/// ddof = 1             # delta degrees of freedom of variance and standard deviation
+/// percentiles = [1, 5, 10, 90, 95, 99]   # reported besides the quartiles
+/// interpolation = "linear"   # linear (default) | lower | higher | nearest | midpoint
+/// ```
+/// `ddof = 1` (the default, as in pandas) gives the sample variance together
+/// with pandas' bias-corrected skewness and excess kurtosis; `ddof = 0` gives
+/// the population variance, skewness and kurtosis.
+/// `exact` keeps every parsed number of a numerical column and every distinct
+/// value of a categorical column in memory, giving exact quartiles and counts
+/// at the cost of memory proportional to the row count.
+/// `interpolation` chooses the value between two ranks like numpy and pandas
+/// do; it applies to the exact mode and to streaming columns that fit the
+/// exact buffer, since the P² estimates beyond it are not taken at ranks.
+///
+/// # Design Philosophy
+/// - No-Load: fixed memory per column in the default mode
+/// - Vanilla Rust: no statistics crates
+/// - Exact when small: up to 4096 values per column the quantiles interpolate
+///   the sorted values, exactly like the exact mode
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+// Import the column types and the statistics structures the accumulator fills
+use super::csv_processor_module::{CsvColumnDataType, NumericalColumnStatistics, PercentileValue};
+
+// Import currency-aware decimal parsing for decimal columns
+use super::decimal_value_module::parse_decimal_value;
+
+// Import missing value classification
+use super::null_value_module::{MissingValueCounts, NullValueSentinels};
+
+// Import our error handling system
+use super::error_types_module::{RowsAndColumnsResult, create_csv_processing_error};
+
+/// Number of markers a P² estimator keeps
+const P2_MARKER_COUNT: usize = 5;
+
+/// Values per column kept for exact quantiles before switching to P² estimates
+pub const EXACT_QUANTILE_BUFFER_CAPACITY: usize = 4096;
+
+/// Default number of distinct values a categorical column tracks in streaming mode
+pub const DEFAULT_TOP_VALUE_CAPACITY: usize = 1000;
+
+/// Default delta degrees of freedom (sample statistics, like pandas)
+pub const DEFAULT_DELTA_DEGREES_OF_FREEDOM: u32 = 1;
+
+/// Tukey's inner fence distance in IQRs (the ends of the box plot whiskers)
+pub const TUKEY_INNER_FENCE_MULTIPLIER: f64 = 1.5;
+
+/// Tukey's outer fence distance in IQRs (extreme outliers lie beyond)
+pub const TUKEY_OUTER_FENCE_MULTIPLIER: f64 = 3.0;
+
+/// |z-score| above which a value is an outlier
+pub const Z_SCORE_OUTLIER_THRESHOLD: f64 = 3.0;
+
+/// |modified z-score| above which a value is an outlier (Iglewicz & Hoaglin)
+pub const MODIFIED_Z_SCORE_OUTLIER_THRESHOLD: f64 = 3.5;
+
+/// Scale of the modified z-score (the 0.75 quantile of the standard normal distribution)
+const MODIFIED_Z_SCORE_SCALE: f64 = 0.6745;
+
+/// Hash bits choosing a HyperLogLog register (2^14 registers, about 0.8% standard error)
+const HYPERLOGLOG_PRECISION_BITS: u32 = 14;
+
+/// How numerical columns are summarized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatisticsMode {
+    /// Bounded memory: Welford moments and P² quartile estimates
+    #[default]
+    Streaming,
+
+    /// Keep and sort every value for exact quartiles
+    Exact,
+}
+
+impl StatisticsMode {
+    /// Converts the mode to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&'static str` - "streaming" or "exact"
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            StatisticsMode::Streaming => "streaming",
+            StatisticsMode::Exact => "exact",
+        }
+    }
+
+    /// Parses a mode from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string from the metadata file
+    ///
+    /// # Returns
+    /// * `Option<StatisticsMode>` - The mode or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<StatisticsMode> {
+        match toml_string.to_lowercase().as_str() {
+            "streaming" => Some(StatisticsMode::Streaming),
+            "exact" => Some(StatisticsMode::Exact),
+            _ => None,
+        }
+    }
+}
+
+/// How a percentile falling between two ranks is computed (numpy/pandas `interpolation`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PercentileInterpolation {
+    /// Weighted between the lower and the higher value
+    #[default]
+    Linear,
+
+    /// The lower value
+    Lower,
+
+    /// The higher value
+    Higher,
+
+    /// The value at the nearest rank (the even rank when exactly halfway)
+    Nearest,
+
+    /// The mean of the lower and the higher value
+    Midpoint,
+}
+
+impl PercentileInterpolation {
+    /// Converts the interpolation to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&'static str` - "linear", "lower", "higher", "nearest" or "midpoint"
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            PercentileInterpolation::Linear => "linear",
+            PercentileInterpolation::Lower => "lower",
+            PercentileInterpolation::Higher => "higher",
+            PercentileInterpolation::Nearest => "nearest",
+            PercentileInterpolation::Midpoint => "midpoint",
+        }
+    }
+
+    /// Parses an interpolation from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string from the metadata file
+    ///
+    /// # Returns
+    /// * `Option<PercentileInterpolation>` - The interpolation or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<PercentileInterpolation> {
+        match toml_string.to_lowercase().as_str() {
+            "linear" => Some(PercentileInterpolation::Linear),
+            "lower" => Some(PercentileInterpolation::Lower),
+            "higher" => Some(PercentileInterpolation::Higher),
+            "nearest" => Some(PercentileInterpolation::Nearest),
+            "midpoint" => Some(PercentileInterpolation::Midpoint),
+            _ => None,
+        }
+    }
+}
+
+/// Settings of the enhanced statistical analysis (from the `[statistics]` section)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatisticsSettings {
+    /// Streaming (bounded memory) or exact statistics
+    pub statistics_mode: StatisticsMode,
+
+    /// Distinct values tracked per categorical column in streaming mode
+    pub top_value_capacity: usize,
+
+    /// Subtracted from the count when dividing for the variance (1: sample, 0: population)
+    pub delta_degrees_of_freedom: u32,
+
+    /// Percentiles (0.0 to 100.0) reported besides the quartiles, in the order given
+    pub requested_percentiles: Vec<f64>,
+
+    /// How quartiles and percentiles between two ranks are computed
+    pub percentile_interpolation: PercentileInterpolation,
+}
+
+impl Default for StatisticsSettings {
+    fn default() -> Self {
+        StatisticsSettings {
+            statistics_mode: StatisticsMode::default(),
+            top_value_capacity: DEFAULT_TOP_VALUE_CAPACITY,
+            delta_degrees_of_freedom: DEFAULT_DELTA_DEGREES_OF_FREEDOM,
+            requested_percentiles: Vec::new(),
+            percentile_interpolation: PercentileInterpolation::default(),
+        }
+    }
+}
+
+/// Running count, sum, central moments, minimum and maximum (Welford's method)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningMoments {
+    /// Number of values added
+    pub value_count: usize,
+
+    /// Sum of the values added
+    pub value_sum: f64,
+
+    /// Mean of the values added so far
+    pub running_mean: f64,
+
+    /// Sum of squared deviations from the running mean
+    pub squared_deviation_sum: f64,
+
+    /// Sum of cubed deviations from the running mean
+    pub cubed_deviation_sum: f64,
+
+    /// Sum of fourth powers of the deviations from the running mean
+    pub fourth_power_deviation_sum: f64,
+
+    /// Smallest value added
+    pub minimum_value: f64,
+
+    /// Largest value added
+    pub maximum_value: f64,
+}
+
+impl Default for RunningMoments {
+    fn default() -> Self {
+        RunningMoments {
+            value_count: 0,
+            value_sum: 0.0,
+            running_mean: 0.0,
+            squared_deviation_sum: 0.0,
+            cubed_deviation_sum: 0.0,
+            fourth_power_deviation_sum: 0.0,
+            minimum_value: f64::INFINITY,
+            maximum_value: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl RunningMoments {
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value (must not be NaN)
+    pub fn add_value(&mut self, numerical_value: f64) {
+        let previous_count = self.value_count as f64;
+        self.value_count += 1;
+        let value_count = self.value_count as f64;
+
+        let deviation_before_update = numerical_value - self.running_mean;
+        let scaled_deviation = deviation_before_update / value_count;
+        let scaled_deviation_squared = scaled_deviation * scaled_deviation;
+        let second_moment_increment = deviation_before_update * scaled_deviation * previous_count;
+
+        // The higher moments are updated first: they use the previous lower moments
+        self.fourth_power_deviation_sum += second_moment_increment
+            * scaled_deviation_squared
+            * (value_count * value_count - 3.0 * value_count + 3.0)
+            + 6.0 * scaled_deviation_squared * self.squared_deviation_sum
+            - 4.0 * scaled_deviation * self.cubed_deviation_sum;
+        self.cubed_deviation_sum += second_moment_increment * scaled_deviation * (value_count - 2.0)
+            - 3.0 * scaled_deviation * self.squared_deviation_sum;
+        self.squared_deviation_sum += second_moment_increment;
+        self.running_mean += scaled_deviation;
+
+        self.value_sum += numerical_value;
+        self.minimum_value = self.minimum_value.min(numerical_value);
+        self.maximum_value = self.maximum_value.max(numerical_value);
+    }
+
+    /// Variance with a delta degrees of freedom (divides by count - ddof)
+    ///
+    /// # Arguments
+    /// * `delta_degrees_of_freedom` - 1 for the sample variance, 0 for the population variance
+    ///
+    /// # Returns
+    /// * `f64` - The variance, NaN when there are no more values than `delta_degrees_of_freedom`
+    pub fn variance(&self, delta_degrees_of_freedom: u32) -> f64 {
+        let divisor = self.value_count as f64 - f64::from(delta_degrees_of_freedom);
+        if divisor <= 0.0 {
+            return f64::NAN;
+        }
+        self.squared_deviation_sum / divisor
+    }
+
+    /// Skewness of the values
+    ///
+    /// # Arguments
+    /// * `bias_corrected` - Adjusted Fisher-Pearson coefficient (pandas `skew()`) instead of the population one
+    ///
+    /// # Returns
+    /// * `f64` - The skewness; 0.0 for constant values, NaN for too few values (3 when bias corrected)
+    pub fn skewness(&self, bias_corrected: bool) -> f64 {
+        let value_count = self.value_count as f64;
+        if self.value_count == 0 || (bias_corrected && self.value_count < 3) {
+            return f64::NAN;
+        }
+        if self.squared_deviation_sum == 0.0 {
+            return 0.0;
+        }
+
+        let population_skewness = value_count.sqrt() * self.cubed_deviation_sum / self.squared_deviation_sum.powf(1.5);
+        if bias_corrected {
+            population_skewness * (value_count * (value_count - 1.0)).sqrt() / (value_count - 2.0)
+        } else {
+            population_skewness
+        }
+    }
+
+    /// Excess kurtosis of the values (0.0 for a normal distribution)
+    ///
+    /// # Arguments
+    /// * `bias_corrected` - Unbiased estimate (pandas `kurt()`) instead of the population one
+    ///
+    /// # Returns
+    /// * `f64` - The excess kurtosis; 0.0 for constant values, NaN for too few values (4 when bias corrected)
+    pub fn excess_kurtosis(&self, bias_corrected: bool) -> f64 {
+        let value_count = self.value_count as f64;
+        if self.value_count == 0 || (bias_corrected && self.value_count < 4) {
+            return f64::NAN;
+        }
+        if self.squared_deviation_sum == 0.0 {
+            return 0.0;
+        }
+
+        let population_excess_kurtosis = value_count * self.fourth_power_deviation_sum
+            / (self.squared_deviation_sum * self.squared_deviation_sum)
+            - 3.0;
+        if bias_corrected {
+            ((value_count + 1.0) * population_excess_kurtosis + 6.0) * (value_count - 1.0)
+                / ((value_count - 2.0) * (value_count - 3.0))
+        } else {
+            population_excess_kurtosis
+        }
+    }
+}
+
+/// Streaming estimate of one quantile with the P² algorithm
+///
+/// Keeps five markers: the minimum, the maximum, the target quantile and the
+/// two quantiles halfway to the extremes. Each new value moves the marker
+/// positions, and markers that drift from their desired position are adjusted
+/// with a piecewise-parabolic (or, failing that, linear) prediction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct P2QuantileEstimator {
+    /// Target quantile between 0.0 and 1.0
+    target_quantile: f64,
+
+    /// Number of values added
+    value_count: usize,
+
+    /// Marker heights (estimated values at the marker positions)
+    marker_heights: [f64; P2_MARKER_COUNT],
+
+    /// Actual marker positions (0-based ranks)
+    marker_positions: [f64; P2_MARKER_COUNT],
+
+    /// Desired marker positions
+    desired_marker_positions: [f64; P2_MARKER_COUNT],
+
+    /// Increment of each desired position per added value
+    desired_position_increments: [f64; P2_MARKER_COUNT],
+
+    /// Interpolation of the exact result while fewer than five values were added
+    small_sample_interpolation: PercentileInterpolation,
+}
+
+impl P2QuantileEstimator {
+    /// Creates an estimator for one quantile
+    ///
+    /// # Arguments
+    /// * `target_quantile` - The quantile to estimate, between 0.0 and 1.0 (e.g. 0.5 for the median)
+    /// * `small_sample_interpolation` - Interpolation while fewer than five values were added
+    ///
+    /// # Returns
+    /// * `P2QuantileEstimator` - An empty estimator
+    pub fn new(target_quantile: f64, small_sample_interpolation: PercentileInterpolation) -> Self {
+        let target_quantile = target_quantile.clamp(0.0, 1.0);
+        P2QuantileEstimator {
+            target_quantile,
+            value_count: 0,
+            marker_heights: [0.0; P2_MARKER_COUNT],
+            marker_positions: [0.0, 1.0, 2.0, 3.0, 4.0],
+            desired_marker_positions: [
+                0.0,
+                2.0 * target_quantile,
+                4.0 * target_quantile,
+                2.0 + 2.0 * target_quantile,
+                4.0,
+            ],
+            desired_position_increments: [0.0, target_quantile / 2.0, target_quantile, (1.0 + target_quantile) / 2.0, 1.0],
+            small_sample_interpolation,
+        }
+    }
+
+    /// Creates an estimator that continues from values already seen
+    ///
+    /// The markers start at the ranks of the minimum, the maximum, the target
+    /// quantile and the two quantiles halfway to the extremes, with the sorted
+    /// values at those ranks as heights, so the estimate starts out exact.
+    ///
+    /// # Arguments
+    /// * `target_quantile` - The quantile to estimate, between 0.0 and 1.0
+    /// * `sorted_values` - At least five values sorted in ascending order
+    ///
+    /// # Returns
+    /// * `P2QuantileEstimator` - An estimator that has seen `sorted_values`
+    pub fn from_sorted_values(target_quantile: f64, sorted_values: &[f64]) -> Self {
+        debug_assert!(sorted_values.len() >= P2_MARKER_COUNT);
+        let mut quantile_estimator = P2QuantileEstimator::new(target_quantile, PercentileInterpolation::Linear);
+        let target_quantile = quantile_estimator.target_quantile;
+        let last_rank = (sorted_values.len() - 1) as f64;
+
+        quantile_estimator.desired_marker_positions =
+            [0.0, target_quantile / 2.0, target_quantile, (1.0 + target_quantile) / 2.0, 1.0].map(|quantile| quantile * last_rank);
+
+        // Markers need distinct ranks, in order, between the first and the last
+        let mut marker_positions = quantile_estimator.desired_marker_positions.map(f64::round);
+        for marker_index in 1..(P2_MARKER_COUNT - 1) {
+            marker_positions[marker_index] = marker_positions[marker_index].max(marker_positions[marker_index - 1] + 1.0);
+        }
+        for marker_index in (1..(P2_MARKER_COUNT - 1)).rev() {
+            marker_positions[marker_index] = marker_positions[marker_index].min(marker_positions[marker_index + 1] - 1.0);
+        }
+
+        quantile_estimator.marker_heights = marker_positions.map(|marker_position| sorted_values[marker_position as usize]);
+        quantile_estimator.marker_positions = marker_positions;
+        quantile_estimator.value_count = sorted_values.len();
+        quantile_estimator
+    }
+
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value (must not be NaN)
+    pub fn add_value(&mut self, numerical_value: f64) {
+        // The first five values become the initial markers
+        if self.value_count < P2_MARKER_COUNT {
+            self.marker_heights[self.value_count] = numerical_value;
+            self.value_count += 1;
+            if self.value_count == P2_MARKER_COUNT {
+                self.marker_heights.sort_by(compare_numerical_values);
+            }
+            return;
+        }
+        self.value_count += 1;
+
+        // Find the cell the value falls into, widening the extremes if needed
+        let cell_index = if numerical_value < self.marker_heights[0] {
+            self.marker_heights[0] = numerical_value;
+            0
+        } else if numerical_value >= self.marker_heights[4] {
+            self.marker_heights[4] = numerical_value;
+            3
+        } else {
+            (1..P2_MARKER_COUNT)
+                .find(|marker_index| numerical_value < self.marker_heights[*marker_index])
+                .map_or(3, |marker_index| marker_index - 1)
+        };
+
+        for marker_index in (cell_index + 1)..P2_MARKER_COUNT {
+            self.marker_positions[marker_index] += 1.0;
+        }
+        for marker_index in 0..P2_MARKER_COUNT {
+            self.desired_marker_positions[marker_index] += self.desired_position_increments[marker_index];
+        }
+
+        // Move the three middle markers back towards their desired positions
+        for marker_index in 1..(P2_MARKER_COUNT - 1) {
+            let position_drift = self.desired_marker_positions[marker_index] - self.marker_positions[marker_index];
+            let room_above = self.marker_positions[marker_index + 1] - self.marker_positions[marker_index];
+            let room_below = self.marker_positions[marker_index - 1] - self.marker_positions[marker_index];
+            if (position_drift >= 1.0 && room_above > 1.0) || (position_drift <= -1.0 && room_below < -1.0) {
+                let step = position_drift.signum();
+                let parabolic_height = self.parabolic_prediction(marker_index, step);
+                self.marker_heights[marker_index] = if self.marker_heights[marker_index - 1] < parabolic_height
+                    && parabolic_height < self.marker_heights[marker_index + 1]
+                {
+                    parabolic_height
+                } else {
+                    self.linear_prediction(marker_index, step)
+                };
+                self.marker_positions[marker_index] += step;
+            }
+        }
+    }
+
+    /// Current estimate of the quantile
+    ///
+    /// With fewer than five values the estimate is interpolated from the sorted
+    /// values, exactly as `calculate_sorted_percentile` does.
+    ///
+    /// # Returns
+    /// * `Option<f64>` - The estimate, or None if no value was added
+    pub fn estimate(&self) -> Option<f64> {
+        if self.value_count == 0 {
+            return None;
+        }
+        if self.value_count < P2_MARKER_COUNT {
+            let mut sorted_values = self.marker_heights[..self.value_count].to_vec();
+            sorted_values.sort_by(compare_numerical_values);
+            return Some(calculate_sorted_percentile(
+                &sorted_values,
+                self.target_quantile * 100.0,
+                self.small_sample_interpolation,
+            ));
+        }
+        Some(self.marker_heights[2])
+    }
+
+    /// Piecewise-parabolic prediction of a marker height after moving it one step
+    ///
+    /// # Arguments
+    /// * `marker_index` - One of the three middle markers
+    /// * `step` - +1.0 or -1.0
+    ///
+    /// # Returns
+    /// * `f64` - The predicted height
+    fn parabolic_prediction(&self, marker_index: usize, step: f64) -> f64 {
+        let heights = &self.marker_heights;
+        let positions = &self.marker_positions;
+        heights[marker_index]
+            + step / (positions[marker_index + 1] - positions[marker_index - 1])
+                * ((positions[marker_index] - positions[marker_index - 1] + step)
+                    * (heights[marker_index + 1] - heights[marker_index])
+                    / (positions[marker_index + 1] - positions[marker_index])
+                    + (positions[marker_index + 1] - positions[marker_index] - step)
+                        * (heights[marker_index] - heights[marker_index - 1])
+                        / (positions[marker_index] - positions[marker_index - 1]))
+    }
+
+    /// Linear prediction of a marker height after moving it one step
+    ///
+    /// # Arguments
+    /// * `marker_index` - One of the three middle markers
+    /// * `step` - +1.0 or -1.0
+    ///
+    /// # Returns
+    /// * `f64` - The predicted height
+    fn linear_prediction(&self, marker_index: usize, step: f64) -> f64 {
+        let neighbor_index = if step > 0.0 { marker_index + 1 } else { marker_index - 1 };
+        self.marker_heights[marker_index]
+            + step * (self.marker_heights[neighbor_index] - self.marker_heights[marker_index])
+                / (self.marker_positions[neighbor_index] - self.marker_positions[marker_index])
+    }
+}
+
+/// Quantiles of a stream: exact while the values fit a buffer, P² estimates beyond
+///
+/// The first `EXACT_QUANTILE_BUFFER_CAPACITY` values are kept and the quantiles
+/// are interpolated from them exactly as the exact mode does. The value after
+/// that seeds one P² estimator per quantile from the sorted buffer, which is
+/// then dropped, so memory stays bounded however many values follow.
+#[derive(Debug, Clone)]
+pub struct StreamingQuantileEstimates {
+    /// Target quantiles between 0.0 and 1.0
+    target_quantiles: Vec<f64>,
+
+    /// How a quantile between two ranks is computed while the values are buffered
+    percentile_interpolation: PercentileInterpolation,
+
+    /// Every value so far, until the buffer overflows
+    buffered_values: Vec<f64>,
+
+    /// One P² estimator per target quantile, once the buffer has overflowed
+    quantile_estimators: Vec<P2QuantileEstimator>,
+}
+
+impl StreamingQuantileEstimates {
+    /// Creates empty estimates
+    ///
+    /// # Arguments
+    /// * `target_quantiles` - The quantiles to estimate, between 0.0 and 1.0
+    /// * `percentile_interpolation` - Interpolation while the values are buffered
+    ///
+    /// # Returns
+    /// * `StreamingQuantileEstimates` - Estimates with no values
+    pub fn new(target_quantiles: Vec<f64>, percentile_interpolation: PercentileInterpolation) -> Self {
+        StreamingQuantileEstimates {
+            target_quantiles,
+            percentile_interpolation,
+            buffered_values: Vec::new(),
+            quantile_estimators: Vec::new(),
+        }
+    }
+
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value (must not be NaN)
+    pub fn add_value(&mut self, numerical_value: f64) {
+        if self.quantile_estimators.is_empty() {
+            if self.buffered_values.len() < EXACT_QUANTILE_BUFFER_CAPACITY {
+                self.buffered_values.push(numerical_value);
+                return;
+            }
+
+            // The buffer is full: hand it over to the P² estimators
+            let mut sorted_values = std::mem::take(&mut self.buffered_values);
+            sorted_values.sort_by(compare_numerical_values);
+            self.quantile_estimators = self
+                .target_quantiles
+                .iter()
+                .map(|target_quantile| P2QuantileEstimator::from_sorted_values(*target_quantile, &sorted_values))
+                .collect();
+        }
+        for quantile_estimator in &mut self.quantile_estimators {
+            quantile_estimator.add_value(numerical_value);
+        }
+    }
+
+    /// Whether the quantiles are P² estimates rather than exact values
+    ///
+    /// # Returns
+    /// * `bool` - True once more values were added than the buffer holds
+    pub fn are_estimates(&self) -> bool {
+        !self.quantile_estimators.is_empty()
+    }
+
+    /// Produces the quantiles
+    ///
+    /// # Returns
+    /// * `Vec<f64>` - One value per target quantile, in order (0.0 when no value was added)
+    pub fn into_quantile_values(self) -> Vec<f64> {
+        if self.are_estimates() {
+            return self
+                .quantile_estimators
+                .iter()
+                .map(|quantile_estimator| quantile_estimator.estimate().unwrap_or_default())
+                .collect();
+        }
+        let mut sorted_values = self.buffered_values;
+        sorted_values.sort_by(compare_numerical_values);
+        self.target_quantiles
+            .iter()
+            .map(|target_quantile| {
+                calculate_sorted_percentile(&sorted_values, target_quantile * 100.0, self.percentile_interpolation)
+            })
+            .collect()
+    }
+}
+
+/// Accumulates the statistics of one integer, float or decimal column
+///
+/// Feed every value of the column with `add_value`, then call `finish`.
+#[derive(Debug, Clone)]
+pub struct NumericalStatisticsAccumulator {
+    /// Column type (decimals are parsed with currency formatting)
+    detected_data_type: CsvColumnDataType,
+
+    /// Count, sum, central moments and extremes of the parsed values
+    running_moments: RunningMoments,
+
+    /// Parsed values equal to zero
+    zero_value_count: usize,
+
+    /// Parsed values below zero
+    negative_value_count: usize,
+
+    /// Delta degrees of freedom of the variance and standard deviation
+    delta_degrees_of_freedom: u32,
+
+    /// How quartiles and percentiles between two ranks are computed
+    percentile_interpolation: PercentileInterpolation,
+
+    /// Percentiles reported besides the quartiles
+    requested_percentiles: Vec<f64>,
+
+    /// The 25th, 50th and 75th percentile, then the requested percentiles (streaming mode)
+    streaming_quantile_estimates: StreamingQuantileEstimates,
+
+    /// Every parsed value (exact mode only)
+    exact_mode_values: Option<Vec<f64>>,
+
+    /// Blank, null sentinel and unparseable values
+    missing_value_counts: MissingValueCounts,
+
+    /// Number of values fed, parsed or not
+    total_value_count: usize,
+}
+
+impl NumericalStatisticsAccumulator {
+    /// Creates an empty accumulator
+    ///
+    /// # Arguments
+    /// * `detected_data_type` - Integer, Float or Decimal
+    /// * `statistics_settings` - Streaming or exact quartiles, the delta degrees of freedom and the percentiles
+    ///
+    /// # Returns
+    /// * `NumericalStatisticsAccumulator` - An accumulator with no values
+    pub fn new(detected_data_type: CsvColumnDataType, statistics_settings: &StatisticsSettings) -> Self {
+        let percentile_interpolation = statistics_settings.percentile_interpolation;
+        NumericalStatisticsAccumulator {
+            detected_data_type,
+            running_moments: RunningMoments::default(),
+            zero_value_count: 0,
+            negative_value_count: 0,
+            delta_degrees_of_freedom: statistics_settings.delta_degrees_of_freedom,
+            percentile_interpolation,
+            requested_percentiles: statistics_settings.requested_percentiles.clone(),
+            streaming_quantile_estimates: StreamingQuantileEstimates::new(
+                [0.25, 0.50, 0.75]
+                    .into_iter()
+                    .chain(statistics_settings.requested_percentiles.iter().map(|percentile| percentile / 100.0))
+                    .collect(),
+                percentile_interpolation,
+            ),
+            exact_mode_values: (statistics_settings.statistics_mode == StatisticsMode::Exact).then(Vec::new),
+            missing_value_counts: MissingValueCounts::default(),
+            total_value_count: 0,
+        }
+    }
+
+    /// Adds one raw field value
+    ///
+    /// # Arguments
+    /// * `value_string` - The field value as read from the file
+    /// * `null_value_sentinels` - Values counted as missing instead of parsed
+    pub fn add_value(&mut self, value_string: &str, null_value_sentinels: &NullValueSentinels) {
+        self.total_value_count += 1;
+        let trimmed_value = value_string.trim();
+
+        if let Some(missing_value_kind) = null_value_sentinels.classify_missing_value(trimmed_value) {
+            self.missing_value_counts.record_missing_value(missing_value_kind);
+            return;
+        }
+
+        let numerical_value = match parse_numerical_field_value(trimmed_value, &self.detected_data_type) {
+            Some(numerical_value) => numerical_value,
+            None => {
+                self.missing_value_counts.type_invalid_count += 1;
+                return;
+            }
+        };
+
+        self.running_moments.add_value(numerical_value);
+        if numerical_value == 0.0 {
+            self.zero_value_count += 1;
+        } else if numerical_value < 0.0 {
+            self.negative_value_count += 1;
+        }
+        match &mut self.exact_mode_values {
+            Some(exact_mode_values) => exact_mode_values.push(numerical_value),
+            None => self.streaming_quantile_estimates.add_value(numerical_value),
+        }
+    }
+
+    /// Produces the column statistics
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<NumericalColumnStatistics>` - Complete numerical analysis
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If no value parsed as a number
+    pub fn finish(self) -> RowsAndColumnsResult<NumericalColumnStatistics> {
+        if self.running_moments.value_count == 0 {
+            return Err(create_csv_processing_error(
+                "No valid numerical values found for statistical analysis",
+                None,
+                None,
+            ));
+        }
+
+        let percentiles_are_estimates =
+            self.exact_mode_values.is_none() && self.streaming_quantile_estimates.are_estimates();
+        let ([q1_value, q2_median_value, q3_value], requested_percentile_values) = match self.exact_mode_values {
+            Some(mut exact_mode_values) => {
+                exact_mode_values.sort_by(compare_numerical_values);
+                let percentile_of_values =
+                    |percentile: f64| calculate_sorted_percentile(&exact_mode_values, percentile, self.percentile_interpolation);
+                (
+                    [25.0, 50.0, 75.0].map(percentile_of_values),
+                    self.requested_percentiles.iter().map(|percentile| percentile_of_values(*percentile)).collect(),
+                )
+            }
+            None => {
+                let mut quantile_values = self.streaming_quantile_estimates.into_quantile_values();
+                let requested_percentile_values = quantile_values.split_off(3);
+                ([quantile_values[0], quantile_values[1], quantile_values[2]], requested_percentile_values)
+            }
+        };
+        let percentile_values = self
+            .requested_percentiles
+            .iter()
+            .zip(requested_percentile_values)
+            .map(|(percentile, percentile_value)| PercentileValue { percentile: *percentile, percentile_value })
+            .collect();
+
+        let running_moments = self.running_moments;
+        let variance = running_moments.variance(self.delta_degrees_of_freedom);
+        let standard_deviation = variance.sqrt();
+        let bias_corrected = self.delta_degrees_of_freedom > 0;
+        let coefficient_of_variation =
+            (running_moments.running_mean != 0.0).then(|| standard_deviation / running_moments.running_mean);
+
+        Ok(NumericalColumnStatistics {
+            min_value: running_moments.minimum_value,
+            q1_value,
+            q2_median_value,
+            q3_value,
+            max_value: running_moments.maximum_value,
+            mean_value: running_moments.running_mean,
+            standard_deviation,
+            value_count: running_moments.value_count,
+            value_sum: running_moments.value_sum,
+            variance,
+            delta_degrees_of_freedom: self.delta_degrees_of_freedom,
+            skewness: running_moments.skewness(bias_corrected),
+            excess_kurtosis: running_moments.excess_kurtosis(bias_corrected),
+            interquartile_range: q3_value - q1_value,
+            value_range: running_moments.maximum_value - running_moments.minimum_value,
+            coefficient_of_variation,
+            zero_value_count: self.zero_value_count,
+            negative_value_count: self.negative_value_count,
+            percentile_values,
+            percentile_interpolation: self.percentile_interpolation,
+            percentiles_are_estimates,
+            missing_percentage: self.missing_value_counts.missing_percentage(self.total_value_count),
+            missing_value_counts: self.missing_value_counts,
+        })
+    }
+}
+
+/// Parses a trimmed, non-missing field value of an integer, float or decimal column
+///
+/// # Arguments
+/// * `trimmed_value` - The field value without surrounding whitespace
+/// * `detected_data_type` - Integer, Float or Decimal (decimals may carry currency formatting)
+///
+/// # Returns
+/// * `Option<f64>` - The number, or None if the value does not parse (NaN counts as unparseable)
+pub fn parse_numerical_field_value(trimmed_value: &str, detected_data_type: &CsvColumnDataType) -> Option<f64> {
+    // Parse as float (works for both integers and floats); decimals may carry currency formatting
+    let parsed_numerical_value = match detected_data_type {
+        CsvColumnDataType::Decimal => parse_decimal_value(trimmed_value)
+            .map(|parsed_decimal_value| parsed_decimal_value.decimal_value.to_f64()),
+        _ => trimmed_value.parse::<f64>().ok(),
+    };
+    parsed_numerical_value.filter(|numerical_value| !numerical_value.is_nan())
+}
+
+/// Tukey's fences around the interquartile range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TukeyFences {
+    /// q1 - 1.5 × IQR: below it a value is a (mild) outlier
+    pub lower_inner_fence: f64,
+
+    /// q3 + 1.5 × IQR
+    pub upper_inner_fence: f64,
+
+    /// q1 - 3 × IQR: below it a value is an extreme outlier
+    pub lower_outer_fence: f64,
+
+    /// q3 + 3 × IQR
+    pub upper_outer_fence: f64,
+}
+
+impl TukeyFences {
+    /// Places the fences around the quartiles
+    ///
+    /// # Arguments
+    /// * `q1_value` - First quartile
+    /// * `q3_value` - Third quartile
+    ///
+    /// # Returns
+    /// * `Option<TukeyFences>` - The inner (1.5 × IQR) and outer (3 × IQR) fences,
+    ///   or None when the IQR is 0 (every value off the median would be flagged)
+    pub fn from_quartiles(q1_value: f64, q3_value: f64) -> Option<Self> {
+        let interquartile_range = q3_value - q1_value;
+        if interquartile_range <= 0.0 || interquartile_range.is_nan() {
+            return None;
+        }
+        Some(TukeyFences {
+            lower_inner_fence: q1_value - TUKEY_INNER_FENCE_MULTIPLIER * interquartile_range,
+            upper_inner_fence: q3_value + TUKEY_INNER_FENCE_MULTIPLIER * interquartile_range,
+            lower_outer_fence: q1_value - TUKEY_OUTER_FENCE_MULTIPLIER * interquartile_range,
+            upper_outer_fence: q3_value + TUKEY_OUTER_FENCE_MULTIPLIER * interquartile_range,
+        })
+    }
+}
+
+/// How far outside Tukey's fences a value lies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TukeyOutlierSeverity {
+    /// Outside the inner fences, inside the outer fences
+    Mild,
+
+    /// Outside the outer fences
+    Extreme,
+}
+
+/// The outlier tests a value failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutlierFlags {
+    /// Outside Tukey's inner fences (and how far)
+    pub tukey_outlier_severity: Option<TukeyOutlierSeverity>,
+
+    /// |z-score| above `Z_SCORE_OUTLIER_THRESHOLD`
+    pub z_score_outlier: bool,
+
+    /// |modified z-score| above `MODIFIED_Z_SCORE_OUTLIER_THRESHOLD`
+    pub modified_z_score_outlier: bool,
+}
+
+/// The three outlier tests of one numerical column
+///
+/// Tukey's fences come from the quartiles, the z-score from the mean and the
+/// standard deviation, and the modified z-score (Iglewicz & Hoaglin, 1993)
+/// `0.6745 × (value - median) / MAD` from the median and the median absolute
+/// deviation. A test is skipped when its spread is 0 or undefined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierDetectionRules {
+    /// Inner and outer fences (None when the IQR is 0)
+    pub tukey_fences: Option<TukeyFences>,
+
+    /// Mean for the z-score
+    pub mean_value: f64,
+
+    /// Standard deviation for the z-score
+    pub standard_deviation: f64,
+
+    /// Median for the modified z-score
+    pub median_value: f64,
+
+    /// Median absolute deviation from the median, for the modified z-score
+    pub median_absolute_deviation: f64,
+}
+
+impl OutlierDetectionRules {
+    /// Runs the three tests on one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value
+    ///
+    /// # Returns
+    /// * `OutlierFlags` - The tests the value failed
+    pub fn classify_value(&self, numerical_value: f64) -> OutlierFlags {
+        let tukey_outlier_severity = self.tukey_fences.and_then(|fences| {
+            if numerical_value < fences.lower_outer_fence || numerical_value > fences.upper_outer_fence {
+                Some(TukeyOutlierSeverity::Extreme)
+            } else if numerical_value < fences.lower_inner_fence || numerical_value > fences.upper_inner_fence {
+                Some(TukeyOutlierSeverity::Mild)
+            } else {
+                None
+            }
+        });
+
+        let z_score_outlier = self.standard_deviation > 0.0
+            && ((numerical_value - self.mean_value) / self.standard_deviation).abs() > Z_SCORE_OUTLIER_THRESHOLD;
+        let modified_z_score_outlier = self.median_absolute_deviation > 0.0
+            && (MODIFIED_Z_SCORE_SCALE * (numerical_value - self.median_value) / self.median_absolute_deviation).abs()
+                > MODIFIED_Z_SCORE_OUTLIER_THRESHOLD;
+
+        OutlierFlags { tukey_outlier_severity, z_score_outlier, modified_z_score_outlier }
+    }
+}
+
+/// Median absolute deviation from a known median, in one pass
+///
+/// Exact mode keeps every deviation; streaming mode keeps them up to the exact
+/// buffer capacity and estimates their median with P² beyond it.
+#[derive(Debug, Clone)]
+pub struct MedianAbsoluteDeviationAccumulator {
+    /// The column's median
+    median_value: f64,
+
+    /// Every absolute deviation (exact mode only)
+    exact_mode_deviations: Option<Vec<f64>>,
+
+    /// Median of the deviations (streaming mode)
+    deviation_median_estimates: StreamingQuantileEstimates,
+}
+
+impl MedianAbsoluteDeviationAccumulator {
+    /// Creates an empty accumulator
+    ///
+    /// # Arguments
+    /// * `median_value` - The column's median (from the statistics pass)
+    /// * `statistics_mode` - Exact or streaming
+    ///
+    /// # Returns
+    /// * `MedianAbsoluteDeviationAccumulator` - An accumulator with no values
+    pub fn new(median_value: f64, statistics_mode: StatisticsMode) -> Self {
+        MedianAbsoluteDeviationAccumulator {
+            median_value,
+            exact_mode_deviations: (statistics_mode == StatisticsMode::Exact).then(Vec::new),
+            deviation_median_estimates: StreamingQuantileEstimates::new(vec![0.5], PercentileInterpolation::Linear),
+        }
+    }
+
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value (must not be NaN)
+    pub fn add_value(&mut self, numerical_value: f64) {
+        let absolute_deviation = (numerical_value - self.median_value).abs();
+        match &mut self.exact_mode_deviations {
+            Some(exact_mode_deviations) => exact_mode_deviations.push(absolute_deviation),
+            None => self.deviation_median_estimates.add_value(absolute_deviation),
+        }
+    }
+
+    /// Produces the median absolute deviation
+    ///
+    /// # Returns
+    /// * `f64` - The MAD (0.0 when no value was added)
+    pub fn finish(self) -> f64 {
+        match self.exact_mode_deviations {
+            Some(mut exact_mode_deviations) => {
+                exact_mode_deviations.sort_by(compare_numerical_values);
+                calculate_sorted_percentile(&exact_mode_deviations, 50.0, PercentileInterpolation::Linear)
+            }
+            None => self.deviation_median_estimates.into_quantile_values()[0],
+        }
+    }
+}
+
+/// Count of one value kept by the Space-Saving sketch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackedValueCount {
+    /// Counted occurrences (never below the true count)
+    pub estimated_count: usize,
+
+    /// Largest possible overcount: the true count is at least `estimated_count - overestimate_bound`
+    pub overestimate_bound: usize,
+}
+
+/// The most frequent values of a stream in fixed memory (Space-Saving)
+///
+/// Tracks at most `value_capacity` values. A new value arriving when the table
+/// is full replaces the value with the smallest count and inherits that count,
+/// which becomes its overestimate bound. Any value with a true count above
+/// `stream length / value_capacity` is guaranteed to be tracked. Until the
+/// first replacement every count is exact.
+#[derive(Debug, Clone)]
+pub struct SpaceSavingTopValues {
+    /// Maximum number of tracked values
+    value_capacity: usize,
+
+    /// Slot of each tracked value
+    slots_by_value: HashMap<String, usize>,
+
+    /// Tracked values with their counts, by slot
+    tracked_slots: Vec<(String, TrackedValueCount)>,
+
+    /// (estimated count, slot) pairs, smallest count first
+    slots_by_count: BTreeSet<(usize, usize)>,
+
+    /// Whether a value was ever replaced (counts are then estimates)
+    value_replaced: bool,
+}
+
+impl SpaceSavingTopValues {
+    /// Creates an empty sketch
+    ///
+    /// # Arguments
+    /// * `value_capacity` - Maximum number of tracked values (at least 1)
+    ///
+    /// # Returns
+    /// * `SpaceSavingTopValues` - A sketch with no values
+    pub fn new(value_capacity: usize) -> Self {
+        SpaceSavingTopValues {
+            value_capacity: value_capacity.max(1),
+            slots_by_value: HashMap::new(),
+            tracked_slots: Vec::new(),
+            slots_by_count: BTreeSet::new(),
+            value_replaced: false,
+        }
+    }
+
+    /// Counts one occurrence of a value
+    ///
+    /// # Arguments
+    /// * `value_text` - The value
+    pub fn add_value(&mut self, value_text: &str) {
+        if let Some(tracked_slot) = self.slots_by_value.get(value_text).copied() {
+            let tracked_count = &mut self.tracked_slots[tracked_slot].1;
+            self.slots_by_count.remove(&(tracked_count.estimated_count, tracked_slot));
+            tracked_count.estimated_count += 1;
+            self.slots_by_count.insert((tracked_count.estimated_count, tracked_slot));
+            return;
+        }
+
+        if self.tracked_slots.len() < self.value_capacity {
+            let new_slot = self.tracked_slots.len();
+            let first_count = TrackedValueCount { estimated_count: 1, overestimate_bound: 0 };
+            self.tracked_slots.push((value_text.to_string(), first_count));
+            self.slots_by_value.insert(value_text.to_string(), new_slot);
+            self.slots_by_count.insert((1, new_slot));
+            return;
+        }
+
+        // Replace the least counted value; the newcomer inherits its count
+        let Some((smallest_count, replaced_slot)) = self.slots_by_count.pop_first() else {
+            return;
+        };
+        let (replaced_value, _) = std::mem::replace(
+            &mut self.tracked_slots[replaced_slot],
+            (
+                value_text.to_string(),
+                TrackedValueCount { estimated_count: smallest_count + 1, overestimate_bound: smallest_count },
+            ),
+        );
+        self.slots_by_value.remove(&replaced_value);
+        self.slots_by_value.insert(value_text.to_string(), replaced_slot);
+        self.slots_by_count.insert((smallest_count + 1, replaced_slot));
+        self.value_replaced = true;
+    }
+
+    /// Whether every count is exact (no value was ever replaced)
+    ///
+    /// # Returns
+    /// * `bool` - True while the stream had no more distinct values than the capacity
+    pub fn is_exact(&self) -> bool {
+        !self.value_replaced
+    }
+
+    /// Number of tracked values
+    ///
+    /// # Returns
+    /// * `usize` - Tracked values (the distinct value count when exact)
+    pub fn tracked_value_count(&self) -> usize {
+        self.tracked_slots.len()
+    }
+
+    /// Consumes the sketch and returns the tracked values, most counted first
+    ///
+    /// # Returns
+    /// * `Vec<(String, TrackedValueCount)>` - Values with their counts
+    pub fn into_values_by_descending_count(self) -> Vec<(String, TrackedValueCount)> {
+        let mut tracked_values = self.tracked_slots;
+        tracked_values.sort_by(|first_value, second_value| {
+            second_value.1.estimated_count.cmp(&first_value.1.estimated_count)
+        });
+        tracked_values
+    }
+}
+
+/// Distinct value estimate in fixed memory (HyperLogLog)
+///
+/// Each value is hashed; the first bits of the hash choose a register and the
+/// register keeps the longest run of leading zeros seen in the remaining bits.
+#[derive(Debug, Clone)]
+pub struct HyperLogLogCounter {
+    /// Longest leading-zero run plus one, per register
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLogCounter {
+    fn default() -> Self {
+        HyperLogLogCounter { registers: vec![0; 1 << HYPERLOGLOG_PRECISION_BITS] }
+    }
+}
+
+impl HyperLogLogCounter {
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `value_text` - The value
+    pub fn add_value(&mut self, value_text: &str) {
+        // DefaultHasher::new() uses fixed keys, so estimates are reproducible
+        let mut value_hasher = DefaultHasher::new();
+        value_text.hash(&mut value_hasher);
+        let value_hash = value_hasher.finish();
+
+        let register_index = (value_hash >> (64 - HYPERLOGLOG_PRECISION_BITS)) as usize;
+        let remaining_bits = value_hash << HYPERLOGLOG_PRECISION_BITS;
+        let run_length = (remaining_bits.leading_zeros() + 1).min(64 - HYPERLOGLOG_PRECISION_BITS + 1) as u8;
+        self.registers[register_index] = self.registers[register_index].max(run_length);
+    }
+
+    /// Estimated number of distinct values added
+    ///
+    /// Uses linear counting while many registers are still empty.
+    ///
+    /// # Returns
+    /// * `f64` - The estimate
+    pub fn estimate(&self) -> f64 {
+        let register_count = self.registers.len() as f64;
+        let harmonic_sum: f64 = self.registers.iter().map(|register| 2f64.powi(-i32::from(*register))).sum();
+        let raw_estimate = 0.7213 / (1.0 + 1.079 / register_count) * register_count * register_count / harmonic_sum;
+
+        let empty_register_count = self.registers.iter().filter(|register| **register == 0).count();
+        if raw_estimate <= 2.5 * register_count && empty_register_count > 0 {
+            return register_count * (register_count / empty_register_count as f64).ln();
+        }
+        raw_estimate
+    }
+
+    /// Relative standard error of the estimate
+    ///
+    /// # Returns
+    /// * `f64` - About 0.008 (0.8%) for 2^14 registers
+    pub fn relative_standard_error(&self) -> f64 {
+        1.04 / (self.registers.len() as f64).sqrt()
+    }
+}
+
+/// Calculates a percentile of sorted values at rank `percentile / 100 * (count - 1)`
+///
+/// The interpolations match numpy's `percentile` and pandas' `quantile`.
+///
+/// # Arguments
+/// * `sorted_values` - Values sorted in ascending order
+/// * `percentile` - Percentile to calculate (0.0 to 100.0)
+/// * `percentile_interpolation` - How a rank between two values is resolved
+///
+/// # Returns
+/// * `f64` - The percentile value (0.0 for no values)
+pub fn calculate_sorted_percentile(
+    sorted_values: &[f64],
+    percentile: f64,
+    percentile_interpolation: PercentileInterpolation,
+) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+
+    let index = (percentile.clamp(0.0, 100.0) / 100.0) * (sorted_values.len() - 1) as f64;
+    let lower_index = index.floor() as usize;
+    let upper_index = index.ceil() as usize;
+    let weight = index - lower_index as f64;
+    let (lower_value, upper_value) = (sorted_values[lower_index], sorted_values[upper_index]);
+
+    match percentile_interpolation {
+        PercentileInterpolation::Linear if lower_index == upper_index => lower_value,
+        PercentileInterpolation::Linear => lower_value * (1.0 - weight) + upper_value * weight,
+        PercentileInterpolation::Lower => lower_value,
+        PercentileInterpolation::Higher => upper_value,
+        PercentileInterpolation::Nearest => {
+            // Round half to even, as numpy does
+            if weight < 0.5 || (weight == 0.5 && lower_index.is_multiple_of(2)) {
+                lower_value
+            } else {
+                upper_value
+            }
+        }
+        PercentileInterpolation::Midpoint => (lower_value + upper_value) / 2.0,
+    }
+}
+
+/// Orders two numbers for sorting (NaN never reaches the accumulators)
+///
+/// # Arguments
+/// * `first_value` - Left value
+/// * `second_value` - Right value
+///
+/// # Returns
+/// * `Ordering` - Their order
+fn compare_numerical_values(first_value: &f64, second_value: &f64) -> Ordering {
+    first_value.partial_cmp(second_value).unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic shuffle of 1..=count (linear congruential generator)
+    fn shuffled_test_values(value_count: usize) -> Vec<f64> {
+        let mut test_values: Vec<f64> = (1..=value_count).map(|value| value as f64).collect();
+        let mut generator_state: u64 = 0x2545_F491_4F6C_DD1D;
+        for value_position in (1..test_values.len()).rev() {
+            generator_state = generator_state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            test_values.swap(value_position, (generator_state >> 33) as usize % (value_position + 1));
+        }
+        test_values
+    }
+
+    #[test]
+    fn test_running_moments_match_two_pass_results() {
+        let test_values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let mut running_moments = RunningMoments::default();
+        for test_value in test_values {
+            running_moments.add_value(test_value);
+        }
+
+        assert_eq!((running_moments.value_count, running_moments.value_sum), (8, 40.0));
+        assert!((running_moments.running_mean - 5.0).abs() < 1e-12);
+        assert!((running_moments.variance(0) - 4.0).abs() < 1e-12);
+        assert!((running_moments.variance(1) - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!((running_moments.minimum_value, running_moments.maximum_value), (2.0, 9.0));
+
+        // Reference values from the two-pass formulas (population and pandas' bias-corrected)
+        assert!((running_moments.skewness(false) - 0.65625).abs() < 1e-12);
+        assert!((running_moments.excess_kurtosis(false) + 0.21875).abs() < 1e-12);
+        assert!((running_moments.skewness(true) - 0.818_487_553_356_799_7).abs() < 1e-12);
+        assert!((running_moments.excess_kurtosis(true) - 0.940625).abs() < 1e-12);
+
+        let mut single_value_moments = RunningMoments::default();
+        single_value_moments.add_value(3.0);
+        assert!(single_value_moments.variance(1).is_nan());
+        assert_eq!(single_value_moments.variance(0), 0.0);
+        assert!(single_value_moments.skewness(true).is_nan());
+    }
+
+    #[test]
+    fn test_p2_estimates_are_close_and_exact_for_few_values() {
+        let test_values = shuffled_test_values(10_000);
+        for (target_quantile, expected_value) in [(0.25, 2_500.75), (0.5, 5_000.5), (0.75, 7_500.25)] {
+            let mut quantile_estimator = P2QuantileEstimator::new(target_quantile, PercentileInterpolation::Linear);
+            for test_value in &test_values {
+                quantile_estimator.add_value(*test_value);
+            }
+            let estimate = quantile_estimator.estimate().expect("values were added");
+            assert!((estimate - expected_value).abs() < 100.0, "quantile {}: {}", target_quantile, estimate);
+        }
+
+        let mut median_estimator = P2QuantileEstimator::new(0.5, PercentileInterpolation::Linear);
+        assert_eq!(median_estimator.estimate(), None);
+        for test_value in [9.0, 1.0, 4.0, 3.0] {
+            median_estimator.add_value(test_value);
+        }
+        assert_eq!(median_estimator.estimate(), Some(3.5));
+    }
+
+    #[test]
+    fn test_percentile_interpolations_match_numpy() {
+        let sorted_values = [1.0, 2.0, 3.0, 4.0, 10.0];
+        // Rank 0.4 * 4 = 1.6 lies between 2.0 and 3.0; numpy gives 2.6, 2, 3, 3 and 2.5
+        let expected_values = [
+            (PercentileInterpolation::Linear, 2.6),
+            (PercentileInterpolation::Lower, 2.0),
+            (PercentileInterpolation::Higher, 3.0),
+            (PercentileInterpolation::Nearest, 3.0),
+            (PercentileInterpolation::Midpoint, 2.5),
+        ];
+        for (percentile_interpolation, expected_value) in expected_values {
+            let percentile_value = calculate_sorted_percentile(&sorted_values, 40.0, percentile_interpolation);
+            assert!((percentile_value - expected_value).abs() < 1e-12, "{:?}", percentile_interpolation);
+            assert_eq!(
+                PercentileInterpolation::from_toml_string(percentile_interpolation.to_toml_string()),
+                Some(percentile_interpolation)
+            );
+        }
+
+        // Exactly halfway, nearest picks the even rank: 12.5% of 4 is rank 0.5, 62.5% is rank 2.5
+        assert_eq!(calculate_sorted_percentile(&sorted_values, 12.5, PercentileInterpolation::Nearest), 1.0);
+        assert_eq!(calculate_sorted_percentile(&sorted_values, 62.5, PercentileInterpolation::Nearest), 3.0);
+        assert_eq!(calculate_sorted_percentile(&sorted_values, 100.0, PercentileInterpolation::Midpoint), 10.0);
+        assert_eq!(PercentileInterpolation::from_toml_string("cubic"), None);
+    }
+
+    #[test]
+    fn test_streaming_percentiles_follow_the_interpolation() {
+        let null_value_sentinels = NullValueSentinels::default();
+        // 1..=11 in shuffled order: p1 falls at rank 0.1, p45 at rank 4.5, p99 at rank 9.9
+        let expected_values = [
+            (PercentileInterpolation::Linear, [1.1, 5.5, 10.9]),
+            (PercentileInterpolation::Lower, [1.0, 5.0, 10.0]),
+            (PercentileInterpolation::Higher, [2.0, 6.0, 11.0]),
+            (PercentileInterpolation::Nearest, [1.0, 5.0, 11.0]),
+            (PercentileInterpolation::Midpoint, [1.5, 5.5, 10.5]),
+        ];
+        for (percentile_interpolation, expected_percentile_values) in expected_values {
+            let streaming_settings = StatisticsSettings {
+                requested_percentiles: vec![1.0, 45.0, 99.0],
+                percentile_interpolation,
+                ..StatisticsSettings::default()
+            };
+            let mut streaming_accumulator =
+                NumericalStatisticsAccumulator::new(CsvColumnDataType::Integer, &streaming_settings);
+            for test_value in shuffled_test_values(11) {
+                streaming_accumulator.add_value(&test_value.to_string(), &null_value_sentinels);
+            }
+
+            let streaming_statistics = streaming_accumulator.finish().expect("numbers were added");
+            assert!(!streaming_statistics.percentiles_are_estimates);
+            for (percentile_value, expected_value) in streaming_statistics.percentile_values.iter().zip(expected_percentile_values) {
+                assert!(
+                    (percentile_value.percentile_value - expected_value).abs() < 1e-9,
+                    "{:?} p{}: {}",
+                    percentile_interpolation,
+                    percentile_value.percentile,
+                    percentile_value.percentile_value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_outlier_rules_and_median_absolute_deviation() {
+        let test_values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 30.0];
+        let mut deviation_accumulator = MedianAbsoluteDeviationAccumulator::new(5.0, StatisticsMode::Exact);
+        for test_value in test_values {
+            deviation_accumulator.add_value(test_value);
+        }
+        let median_absolute_deviation = deviation_accumulator.finish();
+        assert_eq!(median_absolute_deviation, 2.0);
+
+        // q1 3, q3 7: inner fences -3 and 13, outer fences -9 and 19
+        let tukey_fences = TukeyFences::from_quartiles(3.0, 7.0).expect("IQR is 4");
+        assert_eq!(
+            (tukey_fences.lower_inner_fence, tukey_fences.upper_inner_fence, tukey_fences.lower_outer_fence, tukey_fences.upper_outer_fence),
+            (-3.0, 13.0, -9.0, 19.0)
+        );
+        let outlier_rules = OutlierDetectionRules {
+            tukey_fences: Some(tukey_fences),
+            mean_value: 7.333_333_333_333_333,
+            standard_deviation: 8.888_194_417_315_59,
+            median_value: 5.0,
+            median_absolute_deviation,
+        };
+
+        let far_flags = outlier_rules.classify_value(30.0);
+        assert_eq!(far_flags.tukey_outlier_severity, Some(TukeyOutlierSeverity::Extreme));
+        assert!(far_flags.modified_z_score_outlier && !far_flags.z_score_outlier);
+        assert_eq!(outlier_rules.classify_value(15.0).tukey_outlier_severity, Some(TukeyOutlierSeverity::Mild));
+        assert_eq!(outlier_rules.classify_value(8.0), OutlierFlags::default());
+        assert!(outlier_rules.classify_value(40.0).z_score_outlier);
+
+        // Zero spread skips the tests that divide by it
+        assert_eq!(TukeyFences::from_quartiles(3.0, 3.0), None);
+        let constant_rules = OutlierDetectionRules {
+            tukey_fences: None,
+            standard_deviation: 0.0,
+            median_absolute_deviation: 0.0,
+            ..outlier_rules
+        };
+        assert_eq!(constant_rules.classify_value(30.0), OutlierFlags::default());
+        assert_eq!(parse_numerical_field_value("NaN", &CsvColumnDataType::Float), None);
+    }
+
+    #[test]
+    fn test_space_saving_is_exact_below_capacity_and_bounds_heavy_hitters() {
+        let mut exact_top_values = SpaceSavingTopValues::new(3);
+        for value_text in ["b", "a", "b", "c", "b", "a"] {
+            exact_top_values.add_value(value_text);
+        }
+        assert!(exact_top_values.is_exact());
+        let exact_counts: Vec<(String, usize)> = exact_top_values
+            .into_values_by_descending_count()
+            .into_iter()
+            .map(|(value_text, tracked_count)| (value_text, tracked_count.estimated_count))
+            .collect();
+        assert_eq!(exact_counts, vec![("b".to_string(), 3), ("a".to_string(), 2), ("c".to_string(), 1)]);
+
+        // Every fifth value is "hot"; the rest are all different
+        let mut bounded_top_values = SpaceSavingTopValues::new(10);
+        for value_number in 0..5_000 {
+            if value_number % 5 == 0 {
+                bounded_top_values.add_value("hot");
+            } else {
+                bounded_top_values.add_value(&format!("cold_{}", value_number));
+            }
+        }
+        assert!(!bounded_top_values.is_exact());
+        assert_eq!(bounded_top_values.tracked_value_count(), 10);
+        let (top_value, top_count) = bounded_top_values.into_values_by_descending_count().remove(0);
+        assert_eq!(top_value, "hot");
+        assert!(top_count.estimated_count >= 1_000);
+        assert!(top_count.estimated_count - top_count.overestimate_bound <= 1_000);
+    }
+
+    #[test]
+    fn test_hyperloglog_estimates_within_error_bounds() {
+        let mut distinct_value_counter = HyperLogLogCounter::default();
+        assert_eq!(distinct_value_counter.estimate(), 0.0);
+        for value_number in 0..50_000 {
+            distinct_value_counter.add_value(&format!("user{}@example.com", value_number));
+            distinct_value_counter.add_value(&format!("user{}@example.com", value_number / 2));
+        }
+
+        let relative_error = (distinct_value_counter.estimate() - 50_000.0).abs() / 50_000.0;
+        assert!(relative_error < 4.0 * distinct_value_counter.relative_standard_error(), "{}", relative_error);
+
+        let mut small_counter = HyperLogLogCounter::default();
+        for value_text in ["a", "b", "c", "a"] {
+            small_counter.add_value(value_text);
+        }
+        assert_eq!(small_counter.estimate().round(), 3.0);
+    }
+
+    #[test]
+    fn test_accumulator_modes_and_missing_values() {
+        let null_value_sentinels = NullValueSentinels::default();
+        let field_values = ["3", "", "1", "NA", "x", "2", "5", "4"];
+        let exact_settings = StatisticsSettings {
+            statistics_mode: StatisticsMode::Exact,
+            delta_degrees_of_freedom: 0,
+            requested_percentiles: vec![10.0, 90.0],
+            percentile_interpolation: PercentileInterpolation::Lower,
+            ..StatisticsSettings::default()
+        };
+
+        let mut exact_accumulator = NumericalStatisticsAccumulator::new(CsvColumnDataType::Integer, &exact_settings);
+        let mut streaming_accumulator =
+            NumericalStatisticsAccumulator::new(CsvColumnDataType::Integer, &StatisticsSettings::default());
+        for field_value in field_values {
+            exact_accumulator.add_value(field_value, &null_value_sentinels);
+            streaming_accumulator.add_value(field_value, &null_value_sentinels);
+        }
+
+        let exact_statistics = exact_accumulator.finish().expect("numbers were added");
+        assert_eq!(
+            (exact_statistics.min_value, exact_statistics.q2_median_value, exact_statistics.max_value),
+            (1.0, 3.0, 5.0)
+        );
+        assert_eq!((exact_statistics.q1_value, exact_statistics.q3_value), (2.0, 4.0));
+        assert!((exact_statistics.standard_deviation - 2.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(exact_statistics.missing_value_counts.type_invalid_count, 1);
+        assert!((exact_statistics.missing_percentage - 37.5).abs() < 1e-12);
+
+        assert_eq!((exact_statistics.value_count, exact_statistics.value_sum), (5, 15.0));
+        assert_eq!((exact_statistics.interquartile_range, exact_statistics.value_range), (2.0, 4.0));
+        assert_eq!(exact_statistics.skewness, 0.0);
+        let exact_percentiles: Vec<(f64, f64)> = exact_statistics
+            .percentile_values
+            .iter()
+            .map(|percentile_value| (percentile_value.percentile, percentile_value.percentile_value))
+            .collect();
+        assert_eq!(exact_percentiles, vec![(10.0, 1.0), (90.0, 4.0)]);
+        assert!(!exact_statistics.percentiles_are_estimates);
+
+        let streaming_statistics = streaming_accumulator.finish().expect("numbers were added");
+        assert_eq!(streaming_statistics.mean_value, exact_statistics.mean_value);
+        assert_eq!(
+            (streaming_statistics.q1_value, streaming_statistics.q2_median_value, streaming_statistics.q3_value),
+            (2.0, 3.0, 4.0)
+        );
+        assert!(streaming_statistics.percentile_values.is_empty());
+        assert!(!streaming_statistics.percentiles_are_estimates);
+
+        // Small columns get the exact linear quartiles, not P² estimates
+        let mut twenty_value_accumulator =
+            NumericalStatisticsAccumulator::new(CsvColumnDataType::Integer, &StatisticsSettings::default());
+        for test_value in shuffled_test_values(20) {
+            twenty_value_accumulator.add_value(&test_value.to_string(), &null_value_sentinels);
+        }
+        let twenty_value_statistics = twenty_value_accumulator.finish().expect("numbers were added");
+        assert_eq!((twenty_value_statistics.q1_value, twenty_value_statistics.q3_value), (5.75, 15.25));
+        assert_eq!(twenty_value_statistics.interquartile_range, 9.5);
+
+        // Past the exact buffer the quartiles become P² estimates that stay close
+        let mut large_accumulator =
+            NumericalStatisticsAccumulator::new(CsvColumnDataType::Integer, &StatisticsSettings::default());
+        for test_value in shuffled_test_values(10_000) {
+            large_accumulator.add_value(&test_value.to_string(), &null_value_sentinels);
+        }
+        let large_statistics = large_accumulator.finish().expect("numbers were added");
+        assert!(large_statistics.percentiles_are_estimates);
+        assert!((large_statistics.q1_value - 2_500.75).abs() < 100.0, "{}", large_statistics.q1_value);
+        assert!((large_statistics.q3_value - 7_500.25).abs() < 100.0, "{}", large_statistics.q3_value);
+        assert!((streaming_statistics.variance - 2.5).abs() < 1e-12);
+        assert!((streaming_statistics.coefficient_of_variation.expect("mean is not zero") - 2.5_f64.sqrt() / 3.0).abs() < 1e-12);
+
+        let mut signed_accumulator = NumericalStatisticsAccumulator::new(CsvColumnDataType::Float, &exact_settings);
+        for field_value in ["-1.5", "0", "0.0", "1.5"] {
+            signed_accumulator.add_value(field_value, &null_value_sentinels);
+        }
+        let signed_statistics = signed_accumulator.finish().expect("numbers were added");
+        assert_eq!((signed_statistics.zero_value_count, signed_statistics.negative_value_count), (2, 1));
+        assert_eq!(signed_statistics.coefficient_of_variation, None);
+
+        let empty_accumulator = NumericalStatisticsAccumulator::new(CsvColumnDataType::Float, &StatisticsSettings::default());
+        assert!(empty_accumulator.finish().is_err());
+    }
+}
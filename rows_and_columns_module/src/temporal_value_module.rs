@@ -0,0 +1,684 @@
+// src/temporal_value_module.rs
+
+/// Date, time and datetime parsing for rows_and_columns
+///
+/// Values are parsed with hand-written standard library code. ISO-8601 is always
+/// recognised; additional formats can be listed in the `[type_inference]` section
+/// of the metadata file (`date_formats`, `time_formats`, `datetime_formats`).
+///
+/// # ISO-8601 Forms
+/// - Date: `2024-03-01`
+/// - Time: `14:05`, `14:05:09`, `14:05:09.250`
+/// - Datetime: a date and a time joined by `T` or a space, optionally followed
+///   by `Z` or a UTC offset such as `+02:00` (plain dates count as midnight)
+///
+/// # Format Directives
+/// `%Y` four-digit year, `%y` two-digit year (69-99 are 19xx), `%m` month,
+/// `%d` day, `%b`/`%B` month name, `%H` hour (24h), `%I` hour (12h) with `%p`
+/// AM/PM, `%M` minute, `%S` second, `%f` fraction of a second, `%z` `Z` or a
+/// UTC offset, `%F` = `%Y-%m-%d`, `%T` = `%H:%M:%S`, `%%` a literal `%`.
+/// Any other character must appear literally.
+///
+/// # Numeric Scales
+/// For statistics, dates are counted in days since 1970-01-01, times in seconds
+/// since midnight, and datetimes in seconds since 1970-01-01T00:00:00 (converted
+/// to UTC when the value carries an offset).
+use std::fmt;
+
+/// Seconds in one day
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Time formats tried before any configured format
+const ISO_TIME_FORMATS: [&str; 3] = ["%H:%M:%S.%f", "%H:%M:%S", "%H:%M"];
+
+/// Date format tried before any configured format
+const ISO_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// English month names, used by `%b` and `%B`
+const MONTH_NAMES: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+/// Directive letters understood after `%`
+const SUPPORTED_FORMAT_DIRECTIVES: &str = "YymdbBHIMSfpzFT%";
+
+/// Extra formats to try after ISO-8601, per temporal type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemporalFormatSet {
+    /// Formats for date-only values, e.g. `%m/%d/%Y`
+    pub date_formats: Vec<String>,
+
+    /// Formats for time-only values, e.g. `%I:%M %p`
+    pub time_formats: Vec<String>,
+
+    /// Formats for date-and-time values, e.g. `%d/%m/%Y %H:%M`
+    pub datetime_formats: Vec<String>,
+}
+
+/// A calendar date in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CalendarDate {
+    /// Year (e.g. 2024)
+    pub year: i64,
+
+    /// Month, 1 to 12
+    pub month: u32,
+
+    /// Day of month, 1 to 31
+    pub day: u32,
+}
+
+impl CalendarDate {
+    /// Counts days since 1970-01-01 (negative before it)
+    ///
+    /// # Returns
+    /// * `i64` - Days since the Unix epoch
+    pub fn days_since_unix_epoch(self) -> i64 {
+        let march_based_year = if self.month <= 2 { self.year - 1 } else { self.year };
+        let era = march_based_year.div_euclid(400);
+        let year_of_era = march_based_year - era * 400;
+        let march_based_month = (i64::from(self.month) + 9) % 12;
+        let day_of_year = (153 * march_based_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Builds the date that lies a number of days after 1970-01-01
+    ///
+    /// # Arguments
+    /// * `days_since_unix_epoch` - Days since the Unix epoch (negative before it)
+    ///
+    /// # Returns
+    /// * `CalendarDate` - The matching calendar date
+    pub fn from_days_since_unix_epoch(days_since_unix_epoch: i64) -> CalendarDate {
+        let shifted_days = days_since_unix_epoch + 719_468;
+        let era = shifted_days.div_euclid(146_097);
+        let day_of_era = shifted_days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let march_based_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * march_based_month + 2) / 5 + 1;
+        let month = if march_based_month < 10 { march_based_month + 3 } else { march_based_month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        CalendarDate { year, month: month as u32, day: day as u32 }
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day without a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeOfDay {
+    /// Hour, 0 to 23
+    pub hour: u32,
+
+    /// Minute, 0 to 59
+    pub minute: u32,
+
+    /// Second, 0 to 59
+    pub second: u32,
+
+    /// Fraction of the second in nanoseconds
+    pub nanosecond: u32,
+}
+
+impl TimeOfDay {
+    /// Counts seconds since midnight, including the fraction
+    ///
+    /// # Returns
+    /// * `f64` - Seconds since midnight
+    pub fn seconds_since_midnight(self) -> f64 {
+        f64::from(self.hour * 3600 + self.minute * 60 + self.second) + f64::from(self.nanosecond) / 1e9
+    }
+}
+
+/// A date and time, optionally with a UTC offset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalendarDateTime {
+    /// The calendar date
+    pub date: CalendarDate,
+
+    /// The time of day
+    pub time: TimeOfDay,
+
+    /// Offset from UTC in seconds, if the value carried one
+    pub utc_offset_seconds: Option<i32>,
+}
+
+impl CalendarDateTime {
+    /// Counts seconds since 1970-01-01T00:00:00, converted to UTC when an offset is known
+    ///
+    /// # Returns
+    /// * `f64` - Seconds since the Unix epoch
+    pub fn seconds_since_unix_epoch(self) -> f64 {
+        let local_seconds =
+            self.date.days_since_unix_epoch() as f64 * SECONDS_PER_DAY + self.time.seconds_since_midnight();
+        local_seconds - f64::from(self.utc_offset_seconds.unwrap_or(0))
+    }
+}
+
+/// Bucket size of a date histogram
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemporalHistogramGranularity {
+    /// One bucket per hour of the day (used for time columns)
+    HourOfDay,
+
+    /// One bucket per calendar day
+    Day,
+
+    /// One bucket per calendar month
+    Month,
+
+    /// One bucket per calendar year
+    Year,
+}
+
+impl TemporalHistogramGranularity {
+    /// Picks a granularity that keeps a date range readable
+    ///
+    /// # Arguments
+    /// * `spanned_day_count` - Days between the earliest and latest value
+    ///
+    /// # Returns
+    /// * `TemporalHistogramGranularity` - Day up to two months, month up to five years, else year
+    pub fn for_spanned_days(spanned_day_count: i64) -> TemporalHistogramGranularity {
+        if spanned_day_count <= 62 {
+            TemporalHistogramGranularity::Day
+        } else if spanned_day_count <= 5 * 366 {
+            TemporalHistogramGranularity::Month
+        } else {
+            TemporalHistogramGranularity::Year
+        }
+    }
+
+    /// Returns the display name of the granularity
+    ///
+    /// # Returns
+    /// * `&str` - "hour", "day", "month" or "year"
+    pub fn display_name(self) -> &'static str {
+        match self {
+            TemporalHistogramGranularity::HourOfDay => "hour",
+            TemporalHistogramGranularity::Day => "day",
+            TemporalHistogramGranularity::Month => "month",
+            TemporalHistogramGranularity::Year => "year",
+        }
+    }
+
+    /// Maps a day since the Unix epoch to an ordered bucket index
+    ///
+    /// # Arguments
+    /// * `days_since_unix_epoch` - The day to place (ignored for `HourOfDay`)
+    ///
+    /// # Returns
+    /// * `i64` - Bucket index; consecutive buckets have consecutive indexes
+    pub fn bucket_index_for_day(self, days_since_unix_epoch: i64) -> i64 {
+        let calendar_date = CalendarDate::from_days_since_unix_epoch(days_since_unix_epoch);
+        match self {
+            TemporalHistogramGranularity::HourOfDay | TemporalHistogramGranularity::Day => days_since_unix_epoch,
+            TemporalHistogramGranularity::Month => calendar_date.year * 12 + i64::from(calendar_date.month) - 1,
+            TemporalHistogramGranularity::Year => calendar_date.year,
+        }
+    }
+
+    /// Formats a bucket index as a label
+    ///
+    /// # Arguments
+    /// * `bucket_index` - Index from `bucket_index_for_day`, or the hour for `HourOfDay`
+    ///
+    /// # Returns
+    /// * `String` - e.g. "14:00", "2024-03-01", "2024-03" or "2024"
+    pub fn bucket_label(self, bucket_index: i64) -> String {
+        match self {
+            TemporalHistogramGranularity::HourOfDay => format!("{:02}:00", bucket_index),
+            TemporalHistogramGranularity::Day => CalendarDate::from_days_since_unix_epoch(bucket_index).to_string(),
+            TemporalHistogramGranularity::Month => {
+                format!("{:04}-{:02}", bucket_index.div_euclid(12), bucket_index.rem_euclid(12) + 1)
+            }
+            TemporalHistogramGranularity::Year => format!("{:04}", bucket_index),
+        }
+    }
+}
+
+/// Fields captured while matching a value against one format
+#[derive(Debug, Default)]
+struct ParsedTemporalFields {
+    year: Option<i64>,
+    month: Option<u32>,
+    day: Option<u32>,
+    hour: Option<u32>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    nanosecond: Option<u32>,
+    utc_offset_seconds: Option<i32>,
+    uses_twelve_hour_clock: bool,
+    is_afternoon: Option<bool>,
+}
+
+impl ParsedTemporalFields {
+    /// Builds a validated date from the captured fields
+    fn to_calendar_date(&self) -> Option<CalendarDate> {
+        let calendar_date = CalendarDate { year: self.year?, month: self.month?, day: self.day? };
+        let day_is_valid = (1..=12).contains(&calendar_date.month)
+            && calendar_date.day >= 1
+            && calendar_date.day <= days_in_month(calendar_date.year, calendar_date.month);
+
+        day_is_valid.then_some(calendar_date)
+    }
+
+    /// Builds a validated time of day from the captured fields
+    fn to_time_of_day(&self) -> Option<TimeOfDay> {
+        let mut hour = self.hour?;
+        if self.uses_twelve_hour_clock {
+            if !(1..=12).contains(&hour) {
+                return None;
+            }
+            hour = hour % 12 + if self.is_afternoon? { 12 } else { 0 };
+        }
+
+        let time_of_day = TimeOfDay {
+            hour,
+            minute: self.minute?,
+            second: self.second.unwrap_or(0),
+            nanosecond: self.nanosecond.unwrap_or(0),
+        };
+        let time_is_valid = time_of_day.hour < 24 && time_of_day.minute < 60 && time_of_day.second < 60;
+
+        time_is_valid.then_some(time_of_day)
+    }
+
+    /// Checks whether any date field was captured
+    fn has_date_fields(&self) -> bool {
+        self.year.is_some() || self.month.is_some() || self.day.is_some()
+    }
+}
+
+/// Parses a date-only value (ISO-8601 first, then the configured date formats)
+///
+/// # Arguments
+/// * `trimmed_value` - The value to parse
+/// * `temporal_formats` - Configured extra formats
+///
+/// # Returns
+/// * `Option<CalendarDate>` - The date, or None if no format matches
+pub fn parse_date_value(trimmed_value: &str, temporal_formats: &TemporalFormatSet) -> Option<CalendarDate> {
+    std::iter::once(ISO_DATE_FORMAT)
+        .chain(temporal_formats.date_formats.iter().map(String::as_str))
+        .filter_map(|date_format| parse_temporal_fields(trimmed_value, date_format))
+        .filter(|parsed_fields| parsed_fields.hour.is_none())
+        .find_map(|parsed_fields| parsed_fields.to_calendar_date())
+}
+
+/// Parses a time-only value (ISO-8601 first, then the configured time formats)
+///
+/// # Arguments
+/// * `trimmed_value` - The value to parse
+/// * `temporal_formats` - Configured extra formats
+///
+/// # Returns
+/// * `Option<TimeOfDay>` - The time, or None if no format matches
+pub fn parse_time_value(trimmed_value: &str, temporal_formats: &TemporalFormatSet) -> Option<TimeOfDay> {
+    ISO_TIME_FORMATS
+        .iter()
+        .copied()
+        .chain(temporal_formats.time_formats.iter().map(String::as_str))
+        .filter_map(|time_format| parse_temporal_fields(trimmed_value, time_format))
+        .filter(|parsed_fields| !parsed_fields.has_date_fields())
+        .find_map(|parsed_fields| parsed_fields.to_time_of_day())
+}
+
+/// Parses a date-and-time value (ISO-8601 first, then the configured datetime formats)
+///
+/// A date without a time is accepted as midnight, so a column mixing dates and
+/// datetimes is still a datetime column.
+///
+/// # Arguments
+/// * `trimmed_value` - The value to parse
+/// * `temporal_formats` - Configured extra formats
+///
+/// # Returns
+/// * `Option<CalendarDateTime>` - The datetime, or None if no format matches
+pub fn parse_datetime_value(trimmed_value: &str, temporal_formats: &TemporalFormatSet) -> Option<CalendarDateTime> {
+    if let Some(calendar_date_time) = parse_iso_datetime_value(trimmed_value) {
+        return Some(calendar_date_time);
+    }
+
+    let configured_match = temporal_formats
+        .datetime_formats
+        .iter()
+        .filter_map(|datetime_format| parse_temporal_fields(trimmed_value, datetime_format))
+        .find_map(|parsed_fields| {
+            Some(CalendarDateTime {
+                date: parsed_fields.to_calendar_date()?,
+                time: parsed_fields.to_time_of_day()?,
+                utc_offset_seconds: parsed_fields.utc_offset_seconds,
+            })
+        });
+
+    configured_match.or_else(|| {
+        parse_date_value(trimmed_value, temporal_formats).map(|calendar_date| CalendarDateTime {
+            date: calendar_date,
+            time: TimeOfDay { hour: 0, minute: 0, second: 0, nanosecond: 0 },
+            utc_offset_seconds: None,
+        })
+    })
+}
+
+/// Checks that a configured format only uses supported directives
+///
+/// # Arguments
+/// * `temporal_format` - The format string from the metadata file
+///
+/// # Returns
+/// * `Result<(), String>` - Ok, or a description of the first unsupported directive
+pub fn validate_temporal_format(temporal_format: &str) -> Result<(), String> {
+    let mut format_characters = temporal_format.chars();
+    while let Some(format_character) = format_characters.next() {
+        if format_character != '%' {
+            continue;
+        }
+        match format_characters.next() {
+            Some(directive) if SUPPORTED_FORMAT_DIRECTIVES.contains(directive) => {}
+            Some(directive) => return Err(format!("unsupported directive '%{}'", directive)),
+            None => return Err("format ends with a lone '%'".to_string()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats seconds since the Unix epoch as an ISO-8601 datetime
+///
+/// # Arguments
+/// * `seconds_since_unix_epoch` - Seconds since 1970-01-01T00:00:00
+///
+/// # Returns
+/// * `String` - e.g. "2024-03-01T14:05:09"
+pub fn format_seconds_since_unix_epoch(seconds_since_unix_epoch: f64) -> String {
+    let whole_days = (seconds_since_unix_epoch / SECONDS_PER_DAY).floor();
+    let calendar_date = CalendarDate::from_days_since_unix_epoch(whole_days as i64);
+
+    format!(
+        "{}T{}",
+        calendar_date,
+        format_seconds_since_midnight(seconds_since_unix_epoch - whole_days * SECONDS_PER_DAY)
+    )
+}
+
+/// Formats seconds since midnight as a time of day
+///
+/// # Arguments
+/// * `seconds_since_midnight` - Seconds since midnight
+///
+/// # Returns
+/// * `String` - e.g. "14:05:09"
+pub fn format_seconds_since_midnight(seconds_since_midnight: f64) -> String {
+    let whole_seconds = seconds_since_midnight.floor() as i64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        whole_seconds / 3600,
+        (whole_seconds / 60) % 60,
+        whole_seconds % 60
+    )
+}
+
+/// Formats a duration in seconds as days and a clock time
+///
+/// # Arguments
+/// * `duration_seconds` - Length of the duration
+///
+/// # Returns
+/// * `String` - e.g. "12 days 03:00:00" or "03:00:00"
+pub fn format_duration_seconds(duration_seconds: f64) -> String {
+    let whole_days = (duration_seconds / SECONDS_PER_DAY).floor();
+    let clock_text = format_seconds_since_midnight(duration_seconds - whole_days * SECONDS_PER_DAY);
+
+    match whole_days as i64 {
+        0 => clock_text,
+        1 => format!("1 day {}", clock_text),
+        day_count => format!("{} days {}", day_count, clock_text),
+    }
+}
+
+/// Parses an ISO-8601 datetime: date, `T` or space, time, optional `Z` or offset
+///
+/// # Arguments
+/// * `trimmed_value` - The value to parse
+///
+/// # Returns
+/// * `Option<CalendarDateTime>` - The datetime, or None if the value is not ISO-8601
+fn parse_iso_datetime_value(trimmed_value: &str) -> Option<CalendarDateTime> {
+    let separator_position = trimmed_value.find(['T', 't', ' '])?;
+    let calendar_date = parse_temporal_fields(&trimmed_value[..separator_position], ISO_DATE_FORMAT)?
+        .to_calendar_date()?;
+
+    let time_and_offset_text = &trimmed_value[separator_position + 1..];
+    let (time_text, utc_offset_seconds) = if let Some(time_text) = time_and_offset_text.strip_suffix(['Z', 'z']) {
+        (time_text, Some(0))
+    } else if let Some(sign_position) = time_and_offset_text.rfind(['+', '-']) {
+        let offset_fields = parse_temporal_fields(&time_and_offset_text[sign_position..], "%z")?;
+        (&time_and_offset_text[..sign_position], offset_fields.utc_offset_seconds)
+    } else {
+        (time_and_offset_text, None)
+    };
+
+    let time_of_day = ISO_TIME_FORMATS
+        .iter()
+        .filter_map(|time_format| parse_temporal_fields(time_text, time_format))
+        .find_map(|parsed_fields| parsed_fields.to_time_of_day())?;
+
+    Some(CalendarDateTime { date: calendar_date, time: time_of_day, utc_offset_seconds })
+}
+
+/// Matches a value against one format and captures the fields it names
+///
+/// # Arguments
+/// * `trimmed_value` - The value to match
+/// * `temporal_format` - The format to match against
+///
+/// # Returns
+/// * `Option<ParsedTemporalFields>` - Captured fields, or None if the value does not match the whole format
+fn parse_temporal_fields(trimmed_value: &str, temporal_format: &str) -> Option<ParsedTemporalFields> {
+    let expanded_format = temporal_format.replace("%F", "%Y-%m-%d").replace("%T", "%H:%M:%S");
+    let value_characters: Vec<char> = trimmed_value.chars().collect();
+    let mut value_position = 0;
+    let mut parsed_fields = ParsedTemporalFields::default();
+    let mut format_characters = expanded_format.chars();
+
+    while let Some(format_character) = format_characters.next() {
+        if format_character != '%' {
+            if value_characters.get(value_position) != Some(&format_character) {
+                return None;
+            }
+            value_position += 1;
+            continue;
+        }
+
+        match format_characters.next()? {
+            'Y' => parsed_fields.year = Some(take_digits(&value_characters, &mut value_position, 4, 4)?),
+            'y' => {
+                let two_digit_year = take_digits(&value_characters, &mut value_position, 2, 2)?;
+                parsed_fields.year = Some(if two_digit_year < 69 { 2000 + two_digit_year } else { 1900 + two_digit_year });
+            }
+            'm' => parsed_fields.month = Some(take_digits(&value_characters, &mut value_position, 1, 2)? as u32),
+            'd' => parsed_fields.day = Some(take_digits(&value_characters, &mut value_position, 1, 2)? as u32),
+            'H' => parsed_fields.hour = Some(take_digits(&value_characters, &mut value_position, 1, 2)? as u32),
+            'I' => {
+                parsed_fields.hour = Some(take_digits(&value_characters, &mut value_position, 1, 2)? as u32);
+                parsed_fields.uses_twelve_hour_clock = true;
+            }
+            'M' => parsed_fields.minute = Some(take_digits(&value_characters, &mut value_position, 2, 2)? as u32),
+            'S' => parsed_fields.second = Some(take_digits(&value_characters, &mut value_position, 2, 2)? as u32),
+            'f' => {
+                let fraction_start = value_position;
+                let fraction_value = take_digits(&value_characters, &mut value_position, 1, 9)?;
+                let fraction_digit_count = (value_position - fraction_start) as u32;
+                parsed_fields.nanosecond = Some((fraction_value * 10_i64.pow(9 - fraction_digit_count)) as u32);
+            }
+            'b' | 'B' => {
+                let month_word = take_letters(&value_characters, &mut value_position).to_lowercase();
+                let month_index = MONTH_NAMES.iter().position(|month_name| {
+                    month_word == *month_name || (month_word.len() == 3 && month_name.starts_with(&month_word))
+                })?;
+                parsed_fields.month = Some(month_index as u32 + 1);
+            }
+            'p' => {
+                parsed_fields.is_afternoon = match take_letters(&value_characters, &mut value_position).to_lowercase().as_str() {
+                    "am" => Some(false),
+                    "pm" => Some(true),
+                    _ => return None,
+                };
+            }
+            'z' => parsed_fields.utc_offset_seconds = Some(take_utc_offset(&value_characters, &mut value_position)?),
+            '%' => {
+                if value_characters.get(value_position) != Some(&'%') {
+                    return None;
+                }
+                value_position += 1;
+            }
+            _ => return None,
+        }
+    }
+
+    (value_position == value_characters.len()).then_some(parsed_fields)
+}
+
+/// Consumes between `minimum_digits` and `maximum_digits` ASCII digits
+fn take_digits(
+    value_characters: &[char],
+    value_position: &mut usize,
+    minimum_digits: usize,
+    maximum_digits: usize,
+) -> Option<i64> {
+    let digit_count = value_characters[*value_position..]
+        .iter()
+        .take(maximum_digits)
+        .take_while(|character| character.is_ascii_digit())
+        .count();
+    if digit_count < minimum_digits {
+        return None;
+    }
+
+    let digit_text: String = value_characters[*value_position..*value_position + digit_count].iter().collect();
+    *value_position += digit_count;
+    digit_text.parse::<i64>().ok()
+}
+
+/// Consumes a run of alphabetic characters
+fn take_letters(value_characters: &[char], value_position: &mut usize) -> String {
+    let letter_text: String = value_characters[*value_position..]
+        .iter()
+        .take_while(|character| character.is_alphabetic())
+        .collect();
+    *value_position += letter_text.chars().count();
+    letter_text
+}
+
+/// Consumes `Z` or a `+HH:MM` / `+HHMM` / `+HH` offset and returns it in seconds
+fn take_utc_offset(value_characters: &[char], value_position: &mut usize) -> Option<i32> {
+    let offset_sign = match value_characters.get(*value_position)? {
+        'Z' | 'z' => {
+            *value_position += 1;
+            return Some(0);
+        }
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    *value_position += 1;
+
+    let offset_hours = take_digits(value_characters, value_position, 2, 2)?;
+    if value_characters.get(*value_position) == Some(&':') {
+        *value_position += 1;
+    }
+    let offset_minutes = if *value_position < value_characters.len() {
+        take_digits(value_characters, value_position, 2, 2)?
+    } else {
+        0
+    };
+
+    if offset_hours > 23 || offset_minutes > 59 {
+        return None;
+    }
+    Some(offset_sign * (offset_hours * 3600 + offset_minutes * 60) as i32)
+}
+
+/// Returns the number of days in a month, accounting for leap years
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_day_round_trip() {
+        for days_since_unix_epoch in [-719_468, -1, 0, 59, 11_016, 19_783, 2_932_896] {
+            let calendar_date = CalendarDate::from_days_since_unix_epoch(days_since_unix_epoch);
+            assert_eq!(calendar_date.days_since_unix_epoch(), days_since_unix_epoch);
+        }
+        assert_eq!(
+            CalendarDate { year: 2024, month: 2, day: 29 }.days_since_unix_epoch(),
+            19_782
+        );
+    }
+
+    #[test]
+    fn test_iso_and_configured_dates() {
+        let us_dates = TemporalFormatSet { date_formats: vec!["%m/%d/%Y".to_string()], ..Default::default() };
+
+        assert_eq!(
+            parse_date_value("2024-02-29", &TemporalFormatSet::default()),
+            Some(CalendarDate { year: 2024, month: 2, day: 29 })
+        );
+        assert_eq!(parse_date_value("2023-02-29", &TemporalFormatSet::default()), None);
+        assert_eq!(parse_date_value("03/01/2024", &TemporalFormatSet::default()), None);
+        assert_eq!(
+            parse_date_value("3/1/2024", &us_dates),
+            Some(CalendarDate { year: 2024, month: 3, day: 1 })
+        );
+    }
+
+    #[test]
+    fn test_times_and_datetimes() {
+        let twelve_hour_times = TemporalFormatSet { time_formats: vec!["%I:%M %p".to_string()], ..Default::default() };
+
+        assert_eq!(parse_time_value("14:05:09.5", &TemporalFormatSet::default()).map(TimeOfDay::seconds_since_midnight), Some(50_709.5));
+        assert_eq!(parse_time_value("2:05 PM", &twelve_hour_times).map(|time_of_day| time_of_day.hour), Some(14));
+        assert_eq!(parse_time_value("25:00", &TemporalFormatSet::default()), None);
+
+        let utc_datetime = parse_datetime_value("2024-03-01T12:00:00Z", &TemporalFormatSet::default());
+        let offset_datetime = parse_datetime_value("2024-03-01 14:00+02:00", &TemporalFormatSet::default());
+        assert_eq!(
+            utc_datetime.map(CalendarDateTime::seconds_since_unix_epoch),
+            offset_datetime.map(CalendarDateTime::seconds_since_unix_epoch)
+        );
+        assert_eq!(
+            format_seconds_since_unix_epoch(utc_datetime.map(CalendarDateTime::seconds_since_unix_epoch).unwrap_or_default()),
+            "2024-03-01T12:00:00"
+        );
+    }
+
+    #[test]
+    fn test_histogram_buckets_and_format_validation() {
+        let march_first = CalendarDate { year: 2024, month: 3, day: 1 }.days_since_unix_epoch();
+        let month_bucket = TemporalHistogramGranularity::Month.bucket_index_for_day(march_first);
+
+        assert_eq!(TemporalHistogramGranularity::Month.bucket_label(month_bucket), "2024-03");
+        assert_eq!(TemporalHistogramGranularity::for_spanned_days(400), TemporalHistogramGranularity::Month);
+        assert!(validate_temporal_format("%d.%m.%Y %H:%M").is_ok());
+        assert!(validate_temporal_format("%Q").is_err());
+    }
+}
@@ -0,0 +1,602 @@
+// src/text_encoding_module.rs
+
+/// Text encoding detection and transcoding for rows_and_columns
+///
+/// Spreadsheet programs export CSV files in many encodings, but the CSV record
+/// reader only understands UTF-8. This module detects the encoding of a file
+/// from a sample of its first bytes, and provides `TextDecodingReader`, a
+/// `BufRead` adapter that transcodes the file to UTF-8 as it is streamed.
+///
+/// # Supported Encodings
+/// - UTF-8, with or without a byte order mark (the BOM is stripped)
+/// - UTF-16 LE and UTF-16 BE, recognized by their byte order marks
+/// - Windows-1252 and Latin-1 (ISO-8859-1) as the fallback for files that are
+///   not valid UTF-8; Windows-1252 is chosen when bytes 0x80-0x9F appear, since
+///   those are printable characters (curly quotes, euro sign) in Windows-1252
+///   but unused control codes in Latin-1
+///
+/// # Byte Offsets
+/// Undecodable data is reported with its byte offsets in the *source* file.
+/// Record positions reported by the CSV reader on top of this adapter are
+/// offsets in the decoded UTF-8 stream, which equal source offsets only for
+/// UTF-8 files without a BOM.
+///
+/// # Design Philosophy
+/// - Vanilla Rust: all decoders are written with std only
+/// - No-Load: the file is decoded in fixed-size chunks
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+};
+
+/// Configuration constants for encoding detection and decoding
+const TEXT_ENCODING_DETECTION_SAMPLE_BYTES: u64 = 64 * 1024;
+const TEXT_DECODING_CHUNK_BYTES: usize = 8 * 1024;
+const UTF8_BYTE_ORDER_MARK: [u8; 3] = [0xEF, 0xBB, 0xBF];
+const UTF16_LE_BYTE_ORDER_MARK: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BYTE_ORDER_MARK: [u8; 2] = [0xFE, 0xFF];
+
+/// Windows-1252 characters for bytes 0x80-0x9F
+///
+/// The five bytes Windows-1252 leaves undefined (0x81, 0x8D, 0x8F, 0x90, 0x9D)
+/// decode to the matching C1 control characters, as web browsers do.
+const WINDOWS_1252_HIGH_CONTROL_RANGE_CHARACTERS: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// The text encoding of a CSV file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvTextEncoding {
+    /// UTF-8 without a byte order mark
+    Utf8,
+
+    /// UTF-8 starting with the EF BB BF byte order mark
+    Utf8WithByteOrderMark,
+
+    /// UTF-16 little endian (starts with FF FE)
+    Utf16LittleEndian,
+
+    /// UTF-16 big endian (starts with FE FF)
+    Utf16BigEndian,
+
+    /// Windows code page 1252 (Western European spreadsheet exports)
+    Windows1252,
+
+    /// ISO-8859-1, every byte is the Unicode code point of the same value
+    Latin1,
+}
+
+impl CsvTextEncoding {
+    /// Converts the encoding to its string representation for TOML storage
+    ///
+    /// # Returns
+    /// * `&str` - String representation of the encoding
+    pub fn to_toml_string(self) -> &'static str {
+        match self {
+            CsvTextEncoding::Utf8 => "utf-8",
+            CsvTextEncoding::Utf8WithByteOrderMark => "utf-8-bom",
+            CsvTextEncoding::Utf16LittleEndian => "utf-16le",
+            CsvTextEncoding::Utf16BigEndian => "utf-16be",
+            CsvTextEncoding::Windows1252 => "windows-1252",
+            CsvTextEncoding::Latin1 => "latin-1",
+        }
+    }
+
+    /// Creates an encoding from its TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from the TOML file
+    ///
+    /// # Returns
+    /// * `Option<CsvTextEncoding>` - The encoding or None if unsupported
+    pub fn from_toml_string(toml_string: &str) -> Option<CsvTextEncoding> {
+        match toml_string.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(CsvTextEncoding::Utf8),
+            "utf-8-bom" | "utf8-bom" | "utf-8-sig" => Some(CsvTextEncoding::Utf8WithByteOrderMark),
+            "utf-16le" | "utf16le" => Some(CsvTextEncoding::Utf16LittleEndian),
+            "utf-16be" | "utf16be" => Some(CsvTextEncoding::Utf16BigEndian),
+            "windows-1252" | "cp1252" => Some(CsvTextEncoding::Windows1252),
+            "latin-1" | "latin1" | "iso-8859-1" => Some(CsvTextEncoding::Latin1),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte order mark that starts files in this encoding
+    ///
+    /// # Returns
+    /// * `&[u8]` - The BOM bytes (empty for encodings without one)
+    fn byte_order_mark(self) -> &'static [u8] {
+        match self {
+            CsvTextEncoding::Utf8WithByteOrderMark => &UTF8_BYTE_ORDER_MARK,
+            CsvTextEncoding::Utf16LittleEndian => &UTF16_LE_BYTE_ORDER_MARK,
+            CsvTextEncoding::Utf16BigEndian => &UTF16_BE_BYTE_ORDER_MARK,
+            _ => &[],
+        }
+    }
+}
+
+/// Details of bytes that could not be decoded
+///
+/// Carried inside an `io::Error` of kind `InvalidData` so it can travel through
+/// `BufRead`; callers convert it into `RowsAndColumnsError::TextEncodingError`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndecodableTextError {
+    /// Name of the encoding being decoded
+    pub text_encoding_name: &'static str,
+
+    /// Byte offset in the source where the undecodable data starts
+    pub undecodable_byte_offset_start: u64,
+
+    /// Byte offset in the source just past the undecodable data
+    pub undecodable_byte_offset_end: u64,
+}
+
+impl fmt::Display for UndecodableTextError {
+    /// Formats the undecodable byte range for display
+    ///
+    /// # Arguments
+    /// * `formatter` - The formatter to write the message to
+    ///
+    /// # Returns
+    /// * `fmt::Result` - Success or failure of the formatting operation
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "bytes {}..{} are not valid {}",
+            self.undecodable_byte_offset_start, self.undecodable_byte_offset_end, self.text_encoding_name
+        )
+    }
+}
+
+impl std::error::Error for UndecodableTextError {}
+
+/// Detects the encoding of a file from a sample of its first bytes
+///
+/// # Arguments
+/// * `file_path` - Path to the file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvTextEncoding>` - The detected encoding
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be opened or read
+pub fn detect_text_encoding_of_file(file_path: &Path) -> RowsAndColumnsResult<CsvTextEncoding> {
+    let opened_file = File::open(file_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to open file for encoding detection: {}", file_path.display()),
+            io_error,
+        )
+    })?;
+
+    // Read one byte more than the sample size to learn whether the sample is the whole file
+    let mut sample_bytes = Vec::new();
+    opened_file
+        .take(TEXT_ENCODING_DETECTION_SAMPLE_BYTES + 1)
+        .read_to_end(&mut sample_bytes)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to read sample for encoding detection: {}", file_path.display()),
+                io_error,
+            )
+        })?;
+
+    let sample_was_truncated = sample_bytes.len() as u64 > TEXT_ENCODING_DETECTION_SAMPLE_BYTES;
+    sample_bytes.truncate(TEXT_ENCODING_DETECTION_SAMPLE_BYTES as usize);
+
+    Ok(detect_text_encoding_from_sample(&sample_bytes, sample_was_truncated))
+}
+
+/// Detects the encoding from the first bytes of a file
+///
+/// # Arguments
+/// * `sample_bytes` - Bytes from the start of the file
+/// * `sample_was_truncated` - Whether the file continues past the sample
+///   (a multi-byte UTF-8 character cut off at the end is then accepted)
+///
+/// # Returns
+/// * `CsvTextEncoding` - The detected encoding
+pub fn detect_text_encoding_from_sample(sample_bytes: &[u8], sample_was_truncated: bool) -> CsvTextEncoding {
+    if sample_bytes.starts_with(&UTF8_BYTE_ORDER_MARK) {
+        return CsvTextEncoding::Utf8WithByteOrderMark;
+    }
+    if sample_bytes.starts_with(&UTF16_LE_BYTE_ORDER_MARK) {
+        return CsvTextEncoding::Utf16LittleEndian;
+    }
+    if sample_bytes.starts_with(&UTF16_BE_BYTE_ORDER_MARK) {
+        return CsvTextEncoding::Utf16BigEndian;
+    }
+
+    match std::str::from_utf8(sample_bytes) {
+        Ok(_) => return CsvTextEncoding::Utf8,
+        Err(utf8_error) if utf8_error.error_len().is_none() && sample_was_truncated => {
+            return CsvTextEncoding::Utf8;
+        }
+        Err(_) => {}
+    }
+
+    if sample_bytes.iter().any(|byte| (0x80..=0x9F).contains(byte)) {
+        CsvTextEncoding::Windows1252
+    } else {
+        CsvTextEncoding::Latin1
+    }
+}
+
+/// `BufRead` adapter that transcodes a byte source into UTF-8
+///
+/// # Examples
+/// ```rust
+/// let csv_file = File::open("export.csv")?;
+/// let decoding_reader = TextDecodingReader::new(csv_file, CsvTextEncoding::Windows1252);
+/// let mut record_reader = CsvRecordReader::new_with_dialect(decoding_reader, CsvDialect::default());
+/// ```
+pub struct TextDecodingReader<R: Read> {
+    /// The underlying (undecoded) byte source
+    source_reader: R,
+
+    /// Encoding of the source bytes
+    text_encoding: CsvTextEncoding,
+
+    /// Scratch buffer for one raw chunk read from the source
+    raw_chunk_buffer: Vec<u8>,
+
+    /// Source bytes read but not yet decoded (e.g. half of a multi-byte character)
+    pending_source_bytes: Vec<u8>,
+
+    /// Source byte offset of the first pending byte
+    pending_source_byte_offset: u64,
+
+    /// Decoded UTF-8 bytes waiting to be handed out
+    decoded_bytes: Vec<u8>,
+
+    /// Position of the next decoded byte to hand out
+    decoded_read_position: usize,
+
+    /// Whether the source has reported end of input
+    source_is_exhausted: bool,
+
+    /// Whether the byte order mark has been looked for and skipped
+    byte_order_mark_handled: bool,
+}
+
+impl<R: Read> TextDecodingReader<R> {
+    /// Creates a decoding reader positioned at the start of the source
+    ///
+    /// # Arguments
+    /// * `source_reader` - Any byte source (file, in-memory cursor)
+    /// * `text_encoding` - Encoding of the source bytes
+    ///
+    /// # Returns
+    /// * `TextDecodingReader<R>` - A reader producing UTF-8 bytes
+    pub fn new(source_reader: R, text_encoding: CsvTextEncoding) -> Self {
+        TextDecodingReader {
+            source_reader,
+            text_encoding,
+            raw_chunk_buffer: vec![0u8; TEXT_DECODING_CHUNK_BYTES],
+            pending_source_bytes: Vec::new(),
+            pending_source_byte_offset: 0,
+            decoded_bytes: Vec::new(),
+            decoded_read_position: 0,
+            source_is_exhausted: false,
+            byte_order_mark_handled: false,
+        }
+    }
+
+    /// Reads the next raw chunk and decodes as much of the pending bytes as possible
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - Success, or an `InvalidData` error carrying `UndecodableTextError`
+    fn decode_next_chunk(&mut self) -> io::Result<()> {
+        self.decoded_bytes.clear();
+        self.decoded_read_position = 0;
+
+        if !self.source_is_exhausted {
+            let bytes_read = self.source_reader.read(&mut self.raw_chunk_buffer)?;
+            if bytes_read == 0 {
+                self.source_is_exhausted = true;
+            } else {
+                self.pending_source_bytes.extend_from_slice(&self.raw_chunk_buffer[..bytes_read]);
+            }
+        }
+
+        // Skip the byte order mark once enough bytes are available to recognize it
+        if !self.byte_order_mark_handled {
+            let byte_order_mark = self.text_encoding.byte_order_mark();
+            if self.pending_source_bytes.len() < byte_order_mark.len() && !self.source_is_exhausted {
+                return Ok(());
+            }
+            if !byte_order_mark.is_empty() && self.pending_source_bytes.starts_with(byte_order_mark) {
+                self.pending_source_bytes.drain(..byte_order_mark.len());
+                self.pending_source_byte_offset += byte_order_mark.len() as u64;
+            }
+            self.byte_order_mark_handled = true;
+        }
+
+        let consumed_byte_count = match self.text_encoding {
+            CsvTextEncoding::Utf8 | CsvTextEncoding::Utf8WithByteOrderMark => self.decode_pending_utf8()?,
+            CsvTextEncoding::Utf16LittleEndian => self.decode_pending_utf16(u16::from_le_bytes)?,
+            CsvTextEncoding::Utf16BigEndian => self.decode_pending_utf16(u16::from_be_bytes)?,
+            CsvTextEncoding::Windows1252 | CsvTextEncoding::Latin1 => self.decode_pending_single_byte(),
+        };
+
+        self.pending_source_bytes.drain(..consumed_byte_count);
+        self.pending_source_byte_offset += consumed_byte_count as u64;
+        Ok(())
+    }
+
+    /// Validates pending UTF-8 bytes and moves the valid prefix to the decoded buffer
+    ///
+    /// # Returns
+    /// * `io::Result<usize>` - Number of pending bytes consumed, or an undecodable data error
+    fn decode_pending_utf8(&mut self) -> io::Result<usize> {
+        match std::str::from_utf8(&self.pending_source_bytes) {
+            Ok(valid_text) => {
+                self.decoded_bytes.extend_from_slice(valid_text.as_bytes());
+                Ok(self.pending_source_bytes.len())
+            }
+            Err(utf8_error) => {
+                let valid_byte_count = utf8_error.valid_up_to();
+                self.decoded_bytes.extend_from_slice(&self.pending_source_bytes[..valid_byte_count]);
+
+                let invalid_byte_count = match utf8_error.error_len() {
+                    // A character cut off at the chunk boundary completes with the next chunk
+                    None if !self.source_is_exhausted => return Ok(valid_byte_count),
+                    None => self.pending_source_bytes.len() - valid_byte_count,
+                    Some(invalid_byte_count) => invalid_byte_count,
+                };
+
+                // Hand out the valid text first; the error is raised on the next call
+                if valid_byte_count > 0 {
+                    return Ok(valid_byte_count);
+                }
+                Err(self.create_undecodable_error(0, invalid_byte_count))
+            }
+        }
+    }
+
+    /// Decodes pending UTF-16 code units (including surrogate pairs) into UTF-8
+    ///
+    /// # Arguments
+    /// * `code_unit_from_bytes` - `u16::from_le_bytes` or `u16::from_be_bytes`
+    ///
+    /// # Returns
+    /// * `io::Result<usize>` - Number of pending bytes consumed, or an undecodable data error
+    fn decode_pending_utf16(&mut self, code_unit_from_bytes: fn([u8; 2]) -> u16) -> io::Result<usize> {
+        let pending_length = self.pending_source_bytes.len();
+        let mut byte_index = 0;
+        let mut utf8_encoding_buffer = [0u8; 4];
+
+        while byte_index + 1 < pending_length {
+            let code_unit = code_unit_from_bytes([
+                self.pending_source_bytes[byte_index],
+                self.pending_source_bytes[byte_index + 1],
+            ]);
+
+            let (decoded_character, code_unit_byte_count) = match code_unit {
+                0xD800..=0xDBFF => {
+                    // A high surrogate must be followed by a low surrogate
+                    if byte_index + 3 >= pending_length {
+                        break;
+                    }
+                    let following_code_unit = code_unit_from_bytes([
+                        self.pending_source_bytes[byte_index + 2],
+                        self.pending_source_bytes[byte_index + 3],
+                    ]);
+                    if !(0xDC00..=0xDFFF).contains(&following_code_unit) {
+                        return self.finish_with_undecodable_error(byte_index, 2);
+                    }
+                    let code_point = 0x10000
+                        + ((u32::from(code_unit) - 0xD800) << 10)
+                        + (u32::from(following_code_unit) - 0xDC00);
+                    match char::from_u32(code_point) {
+                        Some(surrogate_pair_character) => (surrogate_pair_character, 4),
+                        None => return self.finish_with_undecodable_error(byte_index, 4),
+                    }
+                }
+                0xDC00..=0xDFFF => return self.finish_with_undecodable_error(byte_index, 2),
+                _ => match char::from_u32(u32::from(code_unit)) {
+                    Some(basic_plane_character) => (basic_plane_character, 2),
+                    None => return self.finish_with_undecodable_error(byte_index, 2),
+                },
+            };
+
+            self.decoded_bytes
+                .extend_from_slice(decoded_character.encode_utf8(&mut utf8_encoding_buffer).as_bytes());
+            byte_index += code_unit_byte_count;
+        }
+
+        // An odd trailing byte or a lone high surrogate at the very end cannot be completed
+        if self.source_is_exhausted && byte_index < pending_length {
+            return self.finish_with_undecodable_error(byte_index, pending_length - byte_index);
+        }
+
+        Ok(byte_index)
+    }
+
+    /// Decodes every pending byte as Windows-1252 or Latin-1
+    ///
+    /// # Returns
+    /// * `usize` - Number of pending bytes consumed (always all of them)
+    fn decode_pending_single_byte(&mut self) -> usize {
+        let mut utf8_encoding_buffer = [0u8; 4];
+
+        for source_byte in &self.pending_source_bytes {
+            let decoded_character = match (self.text_encoding, *source_byte) {
+                (CsvTextEncoding::Windows1252, 0x80..=0x9F) => {
+                    WINDOWS_1252_HIGH_CONTROL_RANGE_CHARACTERS[usize::from(*source_byte - 0x80)]
+                }
+                (_, other_byte) => char::from(other_byte),
+            };
+            self.decoded_bytes
+                .extend_from_slice(decoded_character.encode_utf8(&mut utf8_encoding_buffer).as_bytes());
+        }
+
+        self.pending_source_bytes.len()
+    }
+
+    /// Hands out the text decoded so far, or raises the error if nothing was decoded
+    ///
+    /// # Arguments
+    /// * `bad_byte_index` - Index into the pending bytes where the bad data starts
+    /// * `bad_byte_count` - Number of bad bytes
+    ///
+    /// # Returns
+    /// * `io::Result<usize>` - Bytes consumed before the bad data, or the undecodable data error
+    fn finish_with_undecodable_error(&self, bad_byte_index: usize, bad_byte_count: usize) -> io::Result<usize> {
+        if bad_byte_index > 0 {
+            return Ok(bad_byte_index);
+        }
+        Err(self.create_undecodable_error(bad_byte_index, bad_byte_count))
+    }
+
+    /// Creates the `InvalidData` error describing undecodable pending bytes
+    ///
+    /// # Arguments
+    /// * `bad_byte_index` - Index into the pending bytes where the bad data starts
+    /// * `bad_byte_count` - Number of bad bytes
+    ///
+    /// # Returns
+    /// * `io::Error` - An `InvalidData` error wrapping `UndecodableTextError`
+    fn create_undecodable_error(&self, bad_byte_index: usize, bad_byte_count: usize) -> io::Error {
+        let undecodable_byte_offset_start = self.pending_source_byte_offset + bad_byte_index as u64;
+
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            UndecodableTextError {
+                text_encoding_name: self.text_encoding.to_toml_string(),
+                undecodable_byte_offset_start,
+                undecodable_byte_offset_end: undecodable_byte_offset_start + bad_byte_count as u64,
+            },
+        )
+    }
+}
+
+impl<R: Read> Read for TextDecodingReader<R> {
+    /// Copies decoded UTF-8 bytes into the caller's buffer
+    ///
+    /// # Arguments
+    /// * `output_buffer` - Buffer to fill
+    ///
+    /// # Returns
+    /// * `io::Result<usize>` - Number of bytes copied (0 at end of input)
+    fn read(&mut self, output_buffer: &mut [u8]) -> io::Result<usize> {
+        let available_bytes = self.fill_buf()?;
+        let copied_byte_count = available_bytes.len().min(output_buffer.len());
+        output_buffer[..copied_byte_count].copy_from_slice(&available_bytes[..copied_byte_count]);
+        self.consume(copied_byte_count);
+        Ok(copied_byte_count)
+    }
+}
+
+impl<R: Read> BufRead for TextDecodingReader<R> {
+    /// Returns the decoded bytes not yet consumed, decoding more if needed
+    ///
+    /// # Returns
+    /// * `io::Result<&[u8]>` - Decoded UTF-8 bytes (empty at end of input)
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.decoded_read_position >= self.decoded_bytes.len() {
+            if self.source_is_exhausted && self.pending_source_bytes.is_empty() {
+                self.decoded_bytes.clear();
+                self.decoded_read_position = 0;
+                break;
+            }
+            self.decode_next_chunk()?;
+        }
+
+        Ok(&self.decoded_bytes[self.decoded_read_position..])
+    }
+
+    /// Marks decoded bytes as consumed
+    ///
+    /// # Arguments
+    /// * `consumed_byte_count` - Number of bytes the caller used
+    fn consume(&mut self, consumed_byte_count: usize) {
+        self.decoded_read_position = (self.decoded_read_position + consumed_byte_count).min(self.decoded_bytes.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Source that hands out one byte per read, to exercise chunk boundaries
+    struct OneByteAtATimeReader {
+        remaining_bytes: Vec<u8>,
+    }
+
+    impl Read for OneByteAtATimeReader {
+        fn read(&mut self, output_buffer: &mut [u8]) -> io::Result<usize> {
+            if self.remaining_bytes.is_empty() || output_buffer.is_empty() {
+                return Ok(0);
+            }
+            output_buffer[0] = self.remaining_bytes.remove(0);
+            Ok(1)
+        }
+    }
+
+    /// Helper to decode a complete byte slice to a String
+    fn decode_test_bytes(source_bytes: &[u8], text_encoding: CsvTextEncoding) -> io::Result<String> {
+        let mut decoded_text = String::new();
+        TextDecodingReader::new(Cursor::new(source_bytes.to_vec()), text_encoding).read_to_string(&mut decoded_text)?;
+        Ok(decoded_text)
+    }
+
+    #[test]
+    fn test_detects_byte_order_marks_and_fallbacks() {
+        assert_eq!(detect_text_encoding_from_sample(b"\xEF\xBB\xBFa,b\n", false), CsvTextEncoding::Utf8WithByteOrderMark);
+        assert_eq!(detect_text_encoding_from_sample(b"\xFF\xFEa\x00", false), CsvTextEncoding::Utf16LittleEndian);
+        assert_eq!(detect_text_encoding_from_sample(b"\xFE\xFF\x00a", false), CsvTextEncoding::Utf16BigEndian);
+        assert_eq!(detect_text_encoding_from_sample("caf\u{e9}\n".as_bytes(), false), CsvTextEncoding::Utf8);
+        assert_eq!(detect_text_encoding_from_sample(b"caf\xC3", true), CsvTextEncoding::Utf8);
+        assert_eq!(detect_text_encoding_from_sample(b"caf\xE9\n", false), CsvTextEncoding::Latin1);
+        assert_eq!(detect_text_encoding_from_sample(b"\x93hi\x94 caf\xE9\n", false), CsvTextEncoding::Windows1252);
+    }
+
+    #[test]
+    fn test_decodes_windows_1252_and_strips_utf8_bom() {
+        assert_eq!(
+            decode_test_bytes(b"\x93caf\xE9\x94,\x80 5\n", CsvTextEncoding::Windows1252).expect("decodable"),
+            "\u{201C}caf\u{e9}\u{201D},\u{20AC} 5\n"
+        );
+        assert_eq!(
+            decode_test_bytes(b"\xEF\xBB\xBFid,name\n", CsvTextEncoding::Utf8WithByteOrderMark).expect("decodable"),
+            "id,name\n"
+        );
+    }
+
+    #[test]
+    fn test_decodes_utf16_across_one_byte_reads() {
+        let mut source_bytes = vec![0xFF, 0xFE];
+        for code_unit in "a,\u{e9}\n".encode_utf16().chain("\u{1F600}".encode_utf16()) {
+            source_bytes.extend_from_slice(&code_unit.to_le_bytes());
+        }
+
+        let mut decoded_text = String::new();
+        TextDecodingReader::new(OneByteAtATimeReader { remaining_bytes: source_bytes }, CsvTextEncoding::Utf16LittleEndian)
+            .read_to_string(&mut decoded_text)
+            .expect("decodable");
+        assert_eq!(decoded_text, "a,\u{e9}\n\u{1F600}");
+    }
+
+    #[test]
+    fn test_invalid_utf8_reports_source_byte_offsets() {
+        let decode_result = decode_test_bytes(b"id\n1\n\xFFx\n", CsvTextEncoding::Utf8);
+
+        let io_error = decode_result.expect_err("invalid UTF-8 must fail");
+        let undecodable_error = io_error
+            .get_ref()
+            .and_then(|inner_error| inner_error.downcast_ref::<UndecodableTextError>())
+            .expect("undecodable text details");
+        assert_eq!(undecodable_error.undecodable_byte_offset_start, 5);
+        assert_eq!(undecodable_error.undecodable_byte_offset_end, 6);
+    }
+}
@@ -12,6 +12,8 @@
 /// - Create and manage CSV metadata TOML files
 /// - Validate CSV format and accessibility
 /// - Generate column analysis reports
+/// - Compute pairwise covariance and Pearson correlation of the continuous columns
+///   while reading, and Spearman correlation on request
 ///
 /// # Design Philosophy
 /// - Sample-based analysis: analyze first N rows for type detection
@@ -20,10 +22,16 @@
 /// - Fallback handling: graceful handling of missing headers or mixed types
 
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufReader};
 use std::path::{PathBuf};
 use std::collections::HashMap;
 
+// Import the streaming RFC 4180 record reader
+use super::csv_record_reader_module::CsvRecordReader;
+
+// Import the one-pass, bounded-memory numerical statistics
+use super::streaming_statistics_module::NumericalStatisticsAccumulator;
+
 // Import our error handling system
 use super::error_types_module::{
     RowsAndColumnsError,
@@ -37,6 +45,103 @@ use super::error_types_module::{
 const CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION: usize = 10;
 const METADATA_FILE_EXTENSION: &str = "csv_metadata.toml";
 
+/// Default delta degrees of freedom for variance and standard deviation (sample statistics, like pandas)
+pub const DEFAULT_DELTA_DEGREES_OF_FREEDOM: u32 = 1;
+
+/// Longest column label shown in the terminal correlation matrices
+const CORRELATION_LABEL_MAX_WIDTH: usize = 12;
+
+/// Narrowest cell of the terminal correlation matrices ("-1.000")
+const CORRELATION_CELL_MIN_WIDTH: usize = 6;
+
+/// Most continuous values kept for Spearman ranks (rows × continuous columns, about 16 bytes each)
+pub const SPEARMAN_EXACT_VALUE_LIMIT: usize = 5_000_000;
+
+/// How a percentile falling between two ranks is computed (numpy/pandas `interpolation`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PercentileInterpolation {
+    /// Weighted between the lower and the higher value
+    #[default]
+    Linear,
+
+    /// The lower value
+    Lower,
+
+    /// The higher value
+    Higher,
+
+    /// The value at the nearest rank (the even rank when exactly halfway)
+    Nearest,
+
+    /// The mean of the lower and the higher value
+    Midpoint,
+}
+
+impl PercentileInterpolation {
+    /// Returns the interpolation name used on the command line and in the report
+    ///
+    /// # Returns
+    /// * `&'static str` - "linear", "lower", "higher", "nearest" or "midpoint"
+    pub fn as_name(self) -> &'static str {
+        match self {
+            PercentileInterpolation::Linear => "linear",
+            PercentileInterpolation::Lower => "lower",
+            PercentileInterpolation::Higher => "higher",
+            PercentileInterpolation::Nearest => "nearest",
+            PercentileInterpolation::Midpoint => "midpoint",
+        }
+    }
+
+    /// Parses an interpolation name
+    ///
+    /// # Arguments
+    /// * `interpolation_name` - The name, case-insensitive
+    ///
+    /// # Returns
+    /// * `Option<PercentileInterpolation>` - The interpolation or None if unknown
+    pub fn from_name(interpolation_name: &str) -> Option<PercentileInterpolation> {
+        match interpolation_name.trim().to_lowercase().as_str() {
+            "linear" => Some(PercentileInterpolation::Linear),
+            "lower" => Some(PercentileInterpolation::Lower),
+            "higher" => Some(PercentileInterpolation::Higher),
+            "nearest" => Some(PercentileInterpolation::Nearest),
+            "midpoint" => Some(PercentileInterpolation::Midpoint),
+            _ => None,
+        }
+    }
+}
+
+/// Options of the numerical statistics (set on the command line)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericalStatisticsOptions {
+    /// ddof of variance and standard deviation (1: sample, 0: population)
+    pub delta_degrees_of_freedom: u32,
+
+    /// Percentiles (0.0 to 100.0) reported besides the quartiles, in the order given
+    pub requested_percentiles: Vec<f64>,
+
+    /// How quartiles and percentiles between two ranks are computed
+    pub percentile_interpolation: PercentileInterpolation,
+
+    /// Whether to compute Spearman correlations (keeps the continuous values in memory, up to a limit)
+    pub spearman_correlation: bool,
+
+    /// Whether to keep every number for exact percentiles instead of P² estimates past the exact buffer
+    pub exact_percentiles: bool,
+}
+
+impl Default for NumericalStatisticsOptions {
+    fn default() -> Self {
+        NumericalStatisticsOptions {
+            delta_degrees_of_freedom: DEFAULT_DELTA_DEGREES_OF_FREEDOM,
+            requested_percentiles: Vec::new(),
+            percentile_interpolation: PercentileInterpolation::default(),
+            spearman_correlation: false,
+            exact_percentiles: false,
+        }
+    }
+}
+
 /// Represents the detected data type for a CSV column
 ///
 /// This enum covers the MVP data types that the system can detect
@@ -204,8 +309,8 @@ pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndC
 
 /// Analyzes basic CSV file structure (row count, column count, header detection)
 ///
-/// This function reads through the CSV file to determine fundamental structure
-/// without performing detailed type analysis.
+/// This function reads through the CSV file record by record to determine
+/// fundamental structure without performing detailed type analysis.
 ///
 /// # Arguments
 /// * `csv_file_path` - Path to the CSV file to analyze
@@ -213,109 +318,131 @@ pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndC
 /// # Returns
 /// * `RowsAndColumnsResult<(bool, usize, usize)>` - (has_header, column_count, data_rows)
 fn analyze_csv_basic_structure(csv_file_path: &PathBuf) -> RowsAndColumnsResult<(bool, usize, usize)> {
-    let csv_file = File::open(csv_file_path)
-        .map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to open CSV file for analysis: {}", csv_file_path.display()),
-                io_error
-            )
-        })?;
+    let mut csv_record_reader = open_csv_record_reader(csv_file_path, "analysis")?;
 
-    let csv_reader = BufReader::new(csv_file);
-    let mut csv_lines = csv_reader.lines();
-
-    // Read the first line to determine column count
-    let first_line = csv_lines.next()
+    // Read the first record to determine column count
+    let first_record = csv_record_reader.read_next_record()
+        .map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read first record of CSV file", io_error)
+        })?
         .ok_or_else(|| {
             create_csv_processing_error(
                 "CSV file appears to be empty",
                 Some(1),
                 None
             )
-        })?
-        .map_err(|io_error| {
-            create_file_system_error(
-                "Failed to read first line of CSV file",
-                io_error
-            )
         })?;
 
-    let column_count = count_csv_columns_in_line(&first_line);
+    let column_count = first_record.field_values.len();
 
-    // Check if first line looks like a header by analyzing the second line
-    let has_header_row = detect_csv_header_row(&mut csv_lines, column_count, &first_line)?;
+    // Check if first record looks like a header by analyzing the second record
+    let (has_header_row, second_record_was_read) = detect_csv_header_row(
+        &mut csv_record_reader,
+        column_count,
+        &first_record.field_values,
+    )?;
 
     // Count total data rows (excluding header if present)
-    let total_rows = count_remaining_csv_lines(csv_lines)?;
-    let data_row_count = if has_header_row { total_rows } else { total_rows + 1 };
+    let remaining_records = count_remaining_csv_records(csv_record_reader)?;
+    let total_records = 1 + usize::from(second_record_was_read) + remaining_records;
+    let data_row_count = if has_header_row { total_records - 1 } else { total_records };
 
     Ok((has_header_row, column_count, data_row_count))
 }
 
-/// Counts the number of CSV columns in a line by splitting on commas
+/// Opens a CSV file and wraps it in a streaming record reader
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file to open
+/// * `purpose_description` - Short description of why the file is opened (for error messages)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvRecordReader<BufReader<File>>>` - Reader positioned at the first record
+fn open_csv_record_reader(
+    csv_file_path: &PathBuf,
+    purpose_description: &str,
+) -> RowsAndColumnsResult<CsvRecordReader<BufReader<File>>> {
+    let csv_file = File::open(csv_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to open CSV file for {}: {}", purpose_description, csv_file_path.display()),
+                io_error
+            )
+        })?;
+
+    Ok(CsvRecordReader::new(BufReader::new(csv_file)))
+}
+
+/// Converts a record reader error into the matching RowsAndColumnsError
 ///
-/// This handles basic CSV comma separation. For MVP, we assume simple comma
-/// separation without escaped commas in quoted fields.
+/// Malformed CSV content (e.g. an unterminated quote) is reported as a CSV
+/// processing error; anything else is a genuine file system failure.
 ///
 /// # Arguments
-/// * `csv_line` - The CSV line to analyze
+/// * `operation_description` - Description of the read operation that failed
+/// * `io_error` - The error returned by the record reader
 ///
 /// # Returns
-/// * `usize` - Number of columns detected
-fn count_csv_columns_in_line(csv_line: &str) -> usize {
-    // Simple comma split for MVP - could be enhanced for quoted fields later
-    csv_line.split(',').count()
+/// * `RowsAndColumnsError` - A properly contextualized error
+fn convert_csv_record_read_error(operation_description: &str, io_error: io::Error) -> RowsAndColumnsError {
+    if io_error.kind() == io::ErrorKind::InvalidData {
+        create_csv_processing_error(
+            &format!("{}: {}", operation_description, io_error),
+            None,
+            None
+        )
+    } else {
+        create_file_system_error(operation_description, io_error)
+    }
 }
+
 /// Detects whether the CSV file has a header row
 ///
-/// This function uses heuristics to determine if the first row contains
+/// This function uses heuristics to determine if the first record contains
 /// column headers rather than data.
 ///
 /// # Arguments
-/// * `remaining_lines` - Iterator of remaining lines after the first
+/// * `csv_record_reader` - Reader positioned just after the first record
 /// * `expected_column_count` - Expected number of columns
-/// * `first_line` - The first line content for analysis
+/// * `first_record_fields` - The fields of the first record for analysis
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<bool>` - True if header row detected
+/// * `RowsAndColumnsResult<(bool, bool)>` - (header row detected, whether a second record was consumed)
 fn detect_csv_header_row(
-    remaining_lines: &mut std::io::Lines<BufReader<File>>,
+    csv_record_reader: &mut CsvRecordReader<BufReader<File>>,
     expected_column_count: usize,
-    first_line: &str,
-) -> RowsAndColumnsResult<bool> {
-    // Read the second line for comparison
-    let second_line = match remaining_lines.next() {
-        Some(line_result) => {
-            line_result.map_err(|io_error| {
-                create_file_system_error("Failed to read second line of CSV", io_error)
-            })?
+    first_record_fields: &[String],
+) -> RowsAndColumnsResult<(bool, bool)> {
+    // Read the second record for comparison
+    let second_record = match csv_record_reader.read_next_record() {
+        Ok(Some(csv_record)) => csv_record,
+        Ok(None) => {
+            // Only one record in file - assume it's data, not header
+            return Ok((false, false));
         }
-        None => {
-            // Only one line in file - assume it's data, not header
-            return Ok(false);
+        Err(io_error) => {
+            return Err(convert_csv_record_read_error("Failed to read second record of CSV", io_error));
         }
     };
 
-    // Split both lines into fields
-    let first_fields: Vec<&str> = first_line.split(',').collect();
-    let second_fields: Vec<&str> = second_line.split(',').collect();
+    let second_record_fields = &second_record.field_values;
 
     // Check if field count matches expected column count
-    if first_fields.len() != expected_column_count ||
-       second_fields.len() != expected_column_count {
+    if first_record_fields.len() != expected_column_count ||
+       second_record_fields.len() != expected_column_count {
         // Inconsistent column counts - this is suspicious but proceed
         println!("  Warning: Inconsistent column counts detected");
     }
 
-    // Heuristic: if first line contains non-numeric values and second line
-    // contains more numeric values, first line is likely a header
-    let first_line_numeric_fields = count_numeric_fields(&first_fields);
-    let second_line_numeric_fields = count_numeric_fields(&second_fields);
+    // Heuristic: if first record contains non-numeric values and second record
+    // contains more numeric values, first record is likely a header
+    let first_record_numeric_fields = count_numeric_fields(first_record_fields);
+    let second_record_numeric_fields = count_numeric_fields(second_record_fields);
 
-    // If first line has fewer numeric fields than second line, it's likely a header
-    let likely_header = first_line_numeric_fields < second_line_numeric_fields;
+    // If first record has fewer numeric fields than second record, it's likely a header
+    let likely_header = first_record_numeric_fields < second_record_numeric_fields;
 
-    Ok(likely_header)
+    Ok((likely_header, true))
 }
 
 /// Counts how many fields in a list appear to be numeric (int or float)
@@ -325,7 +452,7 @@ fn detect_csv_header_row(
 ///
 /// # Returns
 /// * `usize` - Number of fields that appear numeric
-fn count_numeric_fields(fields: &[&str]) -> usize {
+fn count_numeric_fields(fields: &[String]) -> usize {
     fields.iter()
         .filter(|field| {
             let trimmed_field = field.trim();
@@ -336,26 +463,29 @@ fn count_numeric_fields(fields: &[&str]) -> usize {
         .count()
 }
 
-/// Counts remaining lines in the CSV file
+/// Counts remaining records in the CSV file
+///
+/// A record may span several physical lines when a quoted field contains
+/// line breaks, so this counts logical records rather than lines.
 ///
 /// # Arguments
-/// * `lines_iterator` - Iterator over remaining lines
+/// * `csv_record_reader` - Reader positioned at the first record to count
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<usize>` - Number of remaining lines or error
-fn count_remaining_csv_lines(
-    lines_iterator: std::io::Lines<BufReader<File>>
+/// * `RowsAndColumnsResult<usize>` - Number of remaining records or error
+fn count_remaining_csv_records(
+    csv_record_reader: CsvRecordReader<BufReader<File>>
 ) -> RowsAndColumnsResult<usize> {
-    let mut line_count = 0;
+    let mut record_count = 0;
 
-    for line_result in lines_iterator {
-        line_result.map_err(|io_error| {
-            create_file_system_error("Failed to read CSV line during counting", io_error)
+    for record_result in csv_record_reader {
+        record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during counting", io_error)
         })?;
-        line_count += 1;
+        record_count += 1;
     }
 
-    Ok(line_count)
+    Ok(record_count)
 }
 
 /// Analyzes column data types and content by sampling CSV data
@@ -375,16 +505,7 @@ fn analyze_csv_column_types_and_content(
     has_header_row: bool,
     column_count: usize,
 ) -> RowsAndColumnsResult<Vec<CsvColumnInformation>> {
-    let csv_file = File::open(csv_file_path)
-        .map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to open CSV file for type analysis: {}", csv_file_path.display()),
-                io_error
-            )
-        })?;
-
-    let csv_reader = BufReader::new(csv_file);
-    let mut csv_lines = csv_reader.lines();
+    let mut csv_record_reader = open_csv_record_reader(csv_file_path, "type analysis")?;
 
     // Initialize column information structures
     let mut column_info_list = Vec::new();
@@ -394,14 +515,12 @@ fn analyze_csv_column_types_and_content(
 
     // Read header row if it exists to get column names
     let column_names = if has_header_row {
-        match csv_lines.next() {
-            Some(header_line_result) => {
-                let header_line = header_line_result.map_err(|io_error| {
-                    create_file_system_error("Failed to read header line", io_error)
-                })?;
-                parse_csv_line_into_fields(&header_line)
+        match csv_record_reader.read_next_record() {
+            Ok(Some(header_record)) => header_record.field_values,
+            Err(io_error) => {
+                return Err(convert_csv_record_read_error("Failed to read header record", io_error));
             }
-            None => {
+            Ok(None) => {
                 return Err(create_csv_processing_error(
                     "CSV file appears empty when trying to read header",
                     Some(1),
@@ -418,22 +537,17 @@ fn analyze_csv_column_types_and_content(
 
     // Sample data rows for type detection
     let mut rows_processed = 0;
-    for (line_number, line_result) in csv_lines.enumerate() {
+    for record_result in csv_record_reader {
         if rows_processed >= CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION {
             break;
         }
 
-        let csv_line = line_result.map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to read CSV line {}", line_number + if has_header_row { 2 } else { 1 }),
-                io_error
-            )
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during type analysis", io_error)
         })?;
 
-        let field_values = parse_csv_line_into_fields(&csv_line);
-
         // Process each field in this row
-        for (column_index, field_value) in field_values.iter().enumerate() {
+        for (column_index, field_value) in csv_record.field_values.iter().enumerate() {
             if column_index >= column_count {
                 // More fields than expected - skip extras
                 continue;
@@ -479,22 +593,6 @@ fn analyze_csv_column_types_and_content(
     Ok(column_info_list)
 }
 
-/// Parses a CSV line into individual field values
-///
-/// For MVP, this uses simple comma splitting. Could be enhanced later
-/// for proper CSV parsing with quoted fields and escaped commas.
-///
-/// # Arguments
-/// * `csv_line` - The CSV line to parse
-///
-/// # Returns
-/// * `Vec<String>` - List of field values
-fn parse_csv_line_into_fields(csv_line: &str) -> Vec<String> {
-    csv_line.split(',')
-        .map(|field| field.to_string())
-        .collect()
-}
-
 /// Detects the most likely data type for a column based on sample values
 ///
 /// This function analyzes sample values and determines the most appropriate
@@ -626,7 +724,7 @@ fn create_or_update_metadata_file(
     for column_info in column_information_list {
         let column_section = format!("column_{}", column_info.column_index + 1);
         toml_content.push_str(&format!("[{}]\n", column_section));
-        toml_content.push_str(&format!("name = \"{}\"\n", column_info.column_name));
+        toml_content.push_str(&format!("name = \"{}\"\n", escape_toml_basic_string(&column_info.column_name)));
         toml_content.push_str(&format!("data_type = \"{}\"\n", column_info.detected_data_type.to_toml_string()));
         toml_content.push_str(&format!("column_index = {}\n", column_info.column_index));
         toml_content.push_str(&format!("non_empty_values = {}\n", column_info.non_empty_value_count));
@@ -670,13 +768,65 @@ pub struct NumericalColumnStatistics {
     /// Mean (average) value
     pub mean_value: f64,
 
-    /// Standard deviation
+    /// Standard deviation (square root of `variance`)
     pub standard_deviation: f64,
 
+    /// Number of values that parsed as numbers (pandas `count`)
+    pub value_count: usize,
+
+    /// Sum of the values
+    pub value_sum: f64,
+
+    /// Variance dividing by count - ddof (NaN when there are too few values)
+    pub variance: f64,
+
+    /// Delta degrees of freedom of `variance` (1: sample, as in pandas; 0: population)
+    pub delta_degrees_of_freedom: u32,
+
+    /// Skewness (pandas' adjusted Fisher-Pearson coefficient when ddof is 1 or more)
+    pub skewness: f64,
+
+    /// Excess kurtosis, 0 for a normal distribution (pandas' unbiased estimate when ddof is 1 or more)
+    pub excess_kurtosis: f64,
+
+    /// Interquartile range (q3 - q1)
+    pub interquartile_range: f64,
+
+    /// Range (max - min)
+    pub value_range: f64,
+
+    /// Coefficient of variation (stdev / mean; None when the mean is zero)
+    pub coefficient_of_variation: Option<f64>,
+
+    /// Values equal to zero
+    pub zero_value_count: usize,
+
+    /// Values below zero
+    pub negative_value_count: usize,
+
+    /// The requested percentiles with their values, in the order requested
+    pub percentile_values: Vec<PercentileValue>,
+
+    /// How the quartiles and percentiles between two ranks were computed
+    pub percentile_interpolation: PercentileInterpolation,
+
+    /// Whether the quartiles and percentiles are P² estimates (past the exact buffer, without `--exact-percentiles`)
+    pub percentiles_are_estimates: bool,
+
     /// Percentage of missing/empty values
     pub missing_percentage: f64,
 }
 
+/// One requested percentile of a numerical column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercentileValue {
+    /// The percentile (0.0 to 100.0)
+    pub percentile: f64,
+
+    /// The value at that percentile
+    pub percentile_value: f64,
+}
+
 /// Value frequency information for categorical columns
 ///
 /// This represents how often each unique value appears in a categorical column.
@@ -750,44 +900,53 @@ pub struct EnhancedCsvColumnInformation {
 ///
 /// This function provides comprehensive pandas-style statistical analysis
 /// including appropriate measures for categorical and continuous data types.
+/// Every value is read once and fed to a per-column accumulator, so memory
+/// stays bounded however many rows the file has (see `streaming_statistics_module`).
+/// The continuous columns are correlated in the same pass over the file.
 ///
 /// # Arguments
 /// * `csv_file_path` - Path to the CSV file to analyze
 /// * `basic_analysis_results` - Results from basic CSV structure analysis
+/// * `numerical_statistics_options` - ddof, requested percentiles, percentile interpolation,
+///   exact percentiles and Spearman
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>>` - Enhanced column analysis
+/// * `RowsAndColumnsResult<(Vec<EnhancedCsvColumnInformation>, CorrelationMatrix)>` - Enhanced column analysis
+///   and the correlation matrices of the continuous columns
 pub fn perform_enhanced_statistical_analysis(
     csv_file_path: &PathBuf,
     basic_analysis_results: &CsvAnalysisResults,
-) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
+    numerical_statistics_options: &NumericalStatisticsOptions,
+) -> RowsAndColumnsResult<(Vec<EnhancedCsvColumnInformation>, CorrelationMatrix)> {
     // println!("📊 Performing enhanced statistical analysis...");
 
-    let mut enhanced_column_info_list = Vec::new();
-
-    // Collect all data values for each column for comprehensive analysis
-    let all_column_values = collect_all_column_values(
+    // One accumulator per column, chosen by field type (categorical vs continuous)
+    let mut column_accumulators: Vec<FieldStatisticsAccumulator> = basic_analysis_results.column_information_list
+        .iter()
+        .map(|basic_column_info| match determine_field_type(&basic_column_info.detected_data_type) {
+            CsvFieldType::Continuous => FieldStatisticsAccumulator::Continuous(Box::new(
+                NumericalStatisticsAccumulator::new(numerical_statistics_options),
+            )),
+            CsvFieldType::Categorical => FieldStatisticsAccumulator::Categorical(CategoricalStatisticsAccumulator::default()),
+        })
+        .collect();
+    let mut correlation_accumulator = CorrelationAccumulator::new(basic_analysis_results, numerical_statistics_options);
+    accumulate_all_column_values(
         csv_file_path,
-        basic_analysis_results.has_header_row,
-        basic_analysis_results.total_column_count,
+        basic_analysis_results,
+        &mut column_accumulators,
+        &mut correlation_accumulator,
     )?;
 
     // Analyze each column with enhanced statistics
-    for basic_column_info in &basic_analysis_results.column_information_list {
-        let column_values = &all_column_values[basic_column_info.column_index];
-
-        // Determine field type (categorical vs continuous)
-        let field_type = determine_field_type(&basic_column_info.detected_data_type);
-
-        // Generate appropriate statistics based on field type
-        let (numerical_statistics, categorical_statistics) = match field_type {
-            CsvFieldType::Continuous => {
-                let numerical_stats = calculate_numerical_statistics(column_values)?;
-                (Some(numerical_stats), None)
+    let mut enhanced_column_info_list = Vec::new();
+    for (basic_column_info, column_accumulator) in basic_analysis_results.column_information_list.iter().zip(column_accumulators) {
+        let (field_type, numerical_statistics, categorical_statistics) = match column_accumulator {
+            FieldStatisticsAccumulator::Continuous(numerical_accumulator) => {
+                (CsvFieldType::Continuous, Some(numerical_accumulator.finish()?), None)
             }
-            CsvFieldType::Categorical => {
-                let categorical_stats = calculate_categorical_statistics(column_values)?;
-                (None, Some(categorical_stats))
+            FieldStatisticsAccumulator::Categorical(categorical_accumulator) => {
+                (CsvFieldType::Categorical, None, Some(categorical_accumulator.finish()))
             }
         };
 
@@ -803,64 +962,65 @@ pub fn perform_enhanced_statistical_analysis(
 
     println!("  Data analysis complete: OK");
 
-    Ok(enhanced_column_info_list)
+    Ok((enhanced_column_info_list, correlation_accumulator.finish()))
+}
+
+/// Statistics being accumulated for one column
+#[derive(Debug, Clone)]
+enum FieldStatisticsAccumulator {
+    /// Moments and percentiles of a continuous column
+    Continuous(Box<NumericalStatisticsAccumulator>),
+
+    /// Value counts of a categorical column
+    Categorical(CategoricalStatisticsAccumulator),
 }
 
-/// Collects all values from CSV columns for comprehensive statistical analysis
+/// Feeds every value of the CSV file to its column's accumulator in one pass
 ///
-/// This function reads through the entire CSV file to collect all values
-/// for detailed statistical calculations.
+/// No values are kept: each record is dropped once its fields are added.
 ///
 /// # Arguments
 /// * `csv_file_path` - Path to the CSV file
-/// * `has_header_row` - Whether file has header row to skip
-/// * `column_count` - Number of columns expected
+/// * `basic_analysis_results` - Whether the file has a header row, and the columns in the order of `column_accumulators`
+/// * `column_accumulators` - One accumulator per analyzed column
+/// * `correlation_accumulator` - Also fed every data record, so correlating needs no pass of its own
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<Vec<Vec<String>>>` - All values for each column
-fn collect_all_column_values(
+/// * `RowsAndColumnsResult<()>` - Success or a read error
+fn accumulate_all_column_values(
     csv_file_path: &PathBuf,
-    has_header_row: bool,
-    column_count: usize,
-) -> RowsAndColumnsResult<Vec<Vec<String>>> {
-    let csv_file = File::open(csv_file_path)
-        .map_err(|io_error| {
-            create_file_system_error(
-                &format!("Failed to open CSV file for enhanced analysis: {}", csv_file_path.display()),
-                io_error
-            )
-        })?;
-
-    let csv_reader = BufReader::new(csv_file);
-    let mut csv_lines = csv_reader.lines();
+    basic_analysis_results: &CsvAnalysisResults,
+    column_accumulators: &mut [FieldStatisticsAccumulator],
+    correlation_accumulator: &mut CorrelationAccumulator,
+) -> RowsAndColumnsResult<()> {
+    let mut csv_record_reader = open_csv_record_reader(csv_file_path, "enhanced analysis")?;
 
     // Skip header row if present
-    if has_header_row {
-        csv_lines.next();
+    if basic_analysis_results.has_header_row {
+        csv_record_reader.read_next_record().map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read header record during enhanced analysis", io_error)
+        })?;
     }
 
-    // Initialize storage for all column values
-    let mut all_column_values: Vec<Vec<String>> = vec![Vec::new(); column_count];
-
-    // Read all data rows
-    for line_result in csv_lines {
-        let csv_line = line_result.map_err(|io_error| {
-            create_file_system_error("Failed to read CSV line during enhanced analysis", io_error)
+    // Read all data rows (short records are missing their last values)
+    for record_result in csv_record_reader {
+        let csv_record = record_result.map_err(|io_error| {
+            convert_csv_record_read_error("Failed to read CSV record during enhanced analysis", io_error)
         })?;
+        correlation_accumulator.add_record(&csv_record.field_values);
 
-        let field_values = parse_csv_line_into_fields(&csv_line);
-
-        // Store values for each column
-        for (column_index, field_value) in field_values.iter().enumerate() {
-            if column_index >= column_count {
-                continue; // Skip extra fields
+        for (basic_column_info, column_accumulator) in basic_analysis_results.column_information_list.iter().zip(column_accumulators.iter_mut()) {
+            let Some(field_value) = csv_record.field_values.get(basic_column_info.column_index) else {
+                continue;
+            };
+            match column_accumulator {
+                FieldStatisticsAccumulator::Continuous(numerical_accumulator) => numerical_accumulator.add_value(field_value),
+                FieldStatisticsAccumulator::Categorical(categorical_accumulator) => categorical_accumulator.add_value(field_value),
             }
-
-            all_column_values[column_index].push(field_value.trim().to_string());
         }
     }
 
-    Ok(all_column_values)
+    Ok(())
 }
 
 /// Determines field type based on detected data type
@@ -877,172 +1037,496 @@ fn determine_field_type(detected_data_type: &CsvColumnDataType) -> CsvFieldType
     }
 }
 
-/// Calculates comprehensive numerical statistics for continuous data
-///
-/// # Arguments
-/// * `column_values` - All values from the column
+/// Accumulates the value counts of one categorical column
 ///
-/// # Returns
-/// * `RowsAndColumnsResult<NumericalColumnStatistics>` - Complete numerical analysis
-fn calculate_numerical_statistics(column_values: &[String]) -> RowsAndColumnsResult<NumericalColumnStatistics> {
-    // Parse all numerical values, filtering out empty/invalid ones
-    let mut numerical_values = Vec::new();
-    let mut empty_count = 0;
+/// Memory grows with the number of distinct values, not with the number of rows.
+#[derive(Debug, Clone, Default)]
+struct CategoricalStatisticsAccumulator {
+    /// Occurrences of each non-empty value
+    value_counts: HashMap<String, usize>,
+
+    /// Empty values
+    empty_value_count: usize,
+
+    /// Number of values fed, empty or not
+    total_value_count: usize,
+}
 
-    for value_string in column_values {
+impl CategoricalStatisticsAccumulator {
+    /// Adds one raw field value
+    ///
+    /// # Arguments
+    /// * `value_string` - The field value as read from the file
+    fn add_value(&mut self, value_string: &str) {
+        self.total_value_count += 1;
         let trimmed_value = value_string.trim();
 
         if trimmed_value.is_empty() {
-            empty_count += 1;
+            self.empty_value_count += 1;
+        } else if let Some(value_count) = self.value_counts.get_mut(trimmed_value) {
+            *value_count += 1;
         } else {
-            // Try to parse as float (works for both integers and floats)
-            match trimmed_value.parse::<f64>() {
-                Ok(numerical_value) => numerical_values.push(numerical_value),
-                Err(_) => empty_count += 1, // Count unparseable as empty
-            }
+            self.value_counts.insert(trimmed_value.to_string(), 1);
         }
     }
 
-    if numerical_values.is_empty() {
-        return Err(create_csv_processing_error(
-            "No valid numerical values found for statistical analysis",
-            None,
-            None
-        ));
-    }
+    /// Calculates comprehensive categorical statistics
+    ///
+    /// # Returns
+    /// * `CategoricalColumnStatistics` - Complete categorical analysis
+    fn finish(self) -> CategoricalColumnStatistics {
+        let total_non_empty_values: usize = self.value_counts.values().sum();
+        let unique_value_count = self.value_counts.len();
+
+        // Create frequency list sorted by count (descending)
+        let mut value_frequencies: Vec<CategoricalValueFrequency> = self.value_counts
+            .into_iter()
+            .map(|(value, count)| {
+                let percentage = if total_non_empty_values > 0 {
+                    (count as f64 / total_non_empty_values as f64) * 100.0
+                } else {
+                    0.0
+                };
+
+                CategoricalValueFrequency {
+                    value,
+                    count,
+                    percentage,
+                }
+            })
+            .collect();
 
-    // Sort values for percentile calculations
-    numerical_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Calculate basic statistics
-    let min_value = numerical_values[0];
-    let max_value = numerical_values[numerical_values.len() - 1];
-    let mean_value = numerical_values.iter().sum::<f64>() / numerical_values.len() as f64;
-
-    // Calculate percentiles
-    let q1_value = calculate_percentile(&numerical_values, 25.0);
-    let q2_median_value = calculate_percentile(&numerical_values, 50.0);
-    let q3_value = calculate_percentile(&numerical_values, 75.0);
-
-    // Calculate standard deviation
-    let variance = numerical_values.iter()
-        .map(|value| (value - mean_value).powi(2))
-        .sum::<f64>() / numerical_values.len() as f64;
-    let standard_deviation = variance.sqrt();
-
-    // Calculate missing percentage
-    let total_values = column_values.len();
-    let missing_percentage = if total_values > 0 {
-        (empty_count as f64 / total_values as f64) * 100.0
-    } else {
-        0.0
-    };
+        // Sort by count (descending)
+        value_frequencies.sort_by(|a, b| b.count.cmp(&a.count));
 
-    Ok(NumericalColumnStatistics {
-        min_value,
-        q1_value,
-        q2_median_value,
-        q3_value,
-        max_value,
-        mean_value,
-        standard_deviation,
-        missing_percentage,
-    })
+        // Find mode (most common value)
+        let (mode_value, mode_percentage) = if let Some(most_frequent) = value_frequencies.first() {
+            (Some(most_frequent.value.clone()), most_frequent.percentage)
+        } else {
+            (None, 0.0)
+        };
+
+        // Calculate missing percentage
+        let missing_percentage = if self.total_value_count > 0 {
+            (self.empty_value_count as f64 / self.total_value_count as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        CategoricalColumnStatistics {
+            unique_value_count,
+            value_frequencies,
+            missing_percentage,
+            mode_value,
+            mode_percentage,
+        }
+    }
 }
 
-/// Calculates percentile value from sorted numerical data
+/// Covariance and correlations of one pair of continuous columns
 ///
-/// # Arguments
-/// * `sorted_values` - Sorted numerical values
-/// * `percentile` - Percentile to calculate (0.0 to 100.0)
+/// Computed over the rows where both columns have a number (pairwise deletion,
+/// as pandas `DataFrame.cov()` and `DataFrame.corr()` do).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairwiseColumnStatistics {
+    /// Rows where both columns have a number
+    pub pair_count: usize,
+
+    /// Covariance dividing by pair_count - ddof (NaN when there are too few pairs)
+    pub covariance: f64,
+
+    /// Pearson correlation (NaN with fewer than 2 pairs or when a column is constant)
+    pub pearson_correlation: f64,
+
+    /// Spearman rank correlation, tied values sharing their average rank (NaN like Pearson,
+    /// and when Spearman was not computed)
+    pub spearman_correlation: f64,
+}
+
+/// Pairwise covariance, Pearson and Spearman matrices of the continuous columns
+#[derive(Debug, Clone)]
+pub struct CorrelationMatrix {
+    /// Names of the continuous columns in file order (the row and column labels)
+    pub column_names: Vec<String>,
+
+    /// Delta degrees of freedom of the covariances
+    pub delta_degrees_of_freedom: u32,
+
+    /// Square, symmetric matrix of pair statistics (the diagonal holds each column's variance)
+    pub pairwise_statistics: Vec<Vec<PairwiseColumnStatistics>>,
+
+    /// Whether the Spearman correlations were computed
+    pub spearman_status: SpearmanCorrelationStatus,
+}
+
+/// Running means and co-moments of one column pair (Welford's update)
+#[derive(Debug, Clone, Copy, Default)]
+struct PairwiseMomentAccumulator {
+    pair_count: usize,
+    first_mean: f64,
+    second_mean: f64,
+    first_sum_of_squared_deviations: f64,
+    second_sum_of_squared_deviations: f64,
+    sum_of_deviation_products: f64,
+}
+
+impl PairwiseMomentAccumulator {
+    /// Adds one row where both columns have a number
+    ///
+    /// # Arguments
+    /// * `first_value` - The value of the first column
+    /// * `second_value` - The value of the second column
+    fn add_pair(&mut self, first_value: f64, second_value: f64) {
+        self.pair_count += 1;
+        let first_deviation = first_value - self.first_mean;
+        let second_deviation = second_value - self.second_mean;
+        self.first_mean += first_deviation / self.pair_count as f64;
+        self.second_mean += second_deviation / self.pair_count as f64;
+        self.first_sum_of_squared_deviations += first_deviation * (first_value - self.first_mean);
+        self.second_sum_of_squared_deviations += second_deviation * (second_value - self.second_mean);
+        self.sum_of_deviation_products += first_deviation * (second_value - self.second_mean);
+    }
+
+    /// Covariance dividing by pair count - ddof
+    ///
+    /// # Arguments
+    /// * `delta_degrees_of_freedom` - 1: sample covariance, 0: population covariance
+    ///
+    /// # Returns
+    /// * `f64` - The covariance, NaN when pair count - ddof is not positive
+    fn covariance(&self, delta_degrees_of_freedom: u32) -> f64 {
+        let delta_degrees_of_freedom = delta_degrees_of_freedom as usize;
+        if self.pair_count > delta_degrees_of_freedom {
+            self.sum_of_deviation_products / (self.pair_count - delta_degrees_of_freedom) as f64
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Pearson correlation of the pairs
+    ///
+    /// # Returns
+    /// * `f64` - The correlation, NaN with fewer than 2 pairs or when a column is constant
+    fn pearson_correlation(&self) -> f64 {
+        let deviation_scale = (self.first_sum_of_squared_deviations * self.second_sum_of_squared_deviations).sqrt();
+        if self.pair_count < 2 || deviation_scale <= 0.0 {
+            f64::NAN
+        } else {
+            (self.sum_of_deviation_products / deviation_scale).clamp(-1.0, 1.0)
+        }
+    }
+}
+
+/// Whether and how the Spearman matrix was computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpearmanCorrelationStatus {
+    /// Computed from every row (`--spearman` was given)
+    Computed,
+
+    /// Not requested; Spearman ranks need every value in memory
+    NotRequested,
+
+    /// Requested, but the continuous values went past `SPEARMAN_EXACT_VALUE_LIMIT`
+    ValueLimitExceeded,
+}
+
+impl SpearmanCorrelationStatus {
+    /// Converts the status to its name in the TOML report
+    ///
+    /// # Returns
+    /// * `&str` - computed, not_requested or value_limit_exceeded
+    pub fn as_name(self) -> &'static str {
+        match self {
+            SpearmanCorrelationStatus::Computed => "computed",
+            SpearmanCorrelationStatus::NotRequested => "not_requested",
+            SpearmanCorrelationStatus::ValueLimitExceeded => "value_limit_exceeded",
+        }
+    }
+}
+
+/// Accumulates the pairwise covariance, Pearson and (optionally) Spearman correlation of the continuous columns
 ///
-/// # Returns
-/// * `f64` - The percentile value
-fn calculate_percentile(sorted_values: &[f64], percentile: f64) -> f64 {
-    if sorted_values.is_empty() {
-        return 0.0;
+/// Fed one record at a time by the enhanced analysis, so the correlations
+/// need no pass over the file of their own. Covariance and Pearson use running
+/// co-moments: fixed memory per column pair, however many rows there are.
+/// Spearman ranks need every value, so it is an explicit exact mode
+/// (`--spearman`) that keeps the continuous numbers of each row, and gives up
+/// once more than `SPEARMAN_EXACT_VALUE_LIMIT` are kept. Missing values are
+/// handled pairwise: each pair uses the rows where both columns have a number
+/// (empty, unparseable and NaN fields count as missing).
+#[derive(Debug, Clone)]
+pub struct CorrelationAccumulator {
+    /// Index and name of each continuous column, in file order
+    continuous_columns: Vec<(usize, String)>,
+
+    /// Delta degrees of freedom of the covariances
+    delta_degrees_of_freedom: u32,
+
+    /// Running co-moments; only the upper triangle (second position >= first position) is filled
+    moment_accumulators: Vec<Vec<PairwiseMomentAccumulator>>,
+
+    /// The numbers of every row for Spearman, per column (exact mode, until the value limit)
+    spearman_column_numbers: Option<Vec<Vec<Option<f64>>>>,
+
+    /// Whether Spearman is computed
+    spearman_status: SpearmanCorrelationStatus,
+}
+
+impl CorrelationAccumulator {
+    /// Creates an accumulator over the continuous columns of an analysis
+    ///
+    /// Columns are classified with `determine_field_type`, like the enhanced analysis.
+    ///
+    /// # Arguments
+    /// * `basic_analysis_results` - Results from basic CSV structure analysis
+    /// * `numerical_statistics_options` - ddof of the covariances and whether to compute Spearman
+    ///
+    /// # Returns
+    /// * `CorrelationAccumulator` - An accumulator with no rows
+    pub fn new(basic_analysis_results: &CsvAnalysisResults, numerical_statistics_options: &NumericalStatisticsOptions) -> Self {
+        let continuous_columns: Vec<(usize, String)> = basic_analysis_results.column_information_list
+            .iter()
+            .filter(|column_info| determine_field_type(&column_info.detected_data_type) == CsvFieldType::Continuous)
+            .map(|column_info| (column_info.column_index, column_info.column_name.clone()))
+            .collect();
+        let continuous_column_count = continuous_columns.len();
+        let (spearman_column_numbers, spearman_status) = if numerical_statistics_options.spearman_correlation {
+            (Some(vec![Vec::new(); continuous_column_count]), SpearmanCorrelationStatus::Computed)
+        } else {
+            (None, SpearmanCorrelationStatus::NotRequested)
+        };
+
+        CorrelationAccumulator {
+            continuous_columns,
+            delta_degrees_of_freedom: numerical_statistics_options.delta_degrees_of_freedom,
+            moment_accumulators: vec![vec![PairwiseMomentAccumulator::default(); continuous_column_count]; continuous_column_count],
+            spearman_column_numbers,
+            spearman_status,
+        }
     }
 
-    let index = (percentile / 100.0) * (sorted_values.len() - 1) as f64;
-    let lower_index = index.floor() as usize;
-    let upper_index = index.ceil() as usize;
+    /// Adds one data record
+    ///
+    /// # Arguments
+    /// * `field_values` - The record's fields (short records are missing their last values)
+    pub fn add_record(&mut self, field_values: &[String]) {
+        let row_numbers: Vec<Option<f64>> = self.continuous_columns
+            .iter()
+            .map(|(column_index, _)| {
+                field_values
+                    .get(*column_index)
+                    .and_then(|field_value| parse_continuous_field_value(field_value))
+            })
+            .collect();
+
+        for (first_position, first_number) in row_numbers.iter().enumerate() {
+            let Some(first_value) = *first_number else {
+                continue;
+            };
+            let pair_accumulators = self.moment_accumulators[first_position].iter_mut().zip(&row_numbers).skip(first_position);
+            for (moment_accumulator, second_number) in pair_accumulators {
+                if let Some(second_value) = *second_number {
+                    moment_accumulator.add_pair(first_value, second_value);
+                }
+            }
+        }
 
-    if lower_index == upper_index {
-        sorted_values[lower_index]
-    } else {
-        let weight = index - lower_index as f64;
-        sorted_values[lower_index] * (1.0 - weight) + sorted_values[upper_index] * weight
+        if let Some(spearman_column_numbers) = &mut self.spearman_column_numbers {
+            let kept_value_count = spearman_column_numbers.first().map_or(0, |column_numbers| column_numbers.len()) * row_numbers.len();
+            if kept_value_count + row_numbers.len() > SPEARMAN_EXACT_VALUE_LIMIT {
+                // Give up on Spearman rather than let memory grow with the file
+                self.spearman_column_numbers = None;
+                self.spearman_status = SpearmanCorrelationStatus::ValueLimitExceeded;
+                return;
+            }
+            for (column_numbers, row_number) in spearman_column_numbers.iter_mut().zip(row_numbers) {
+                column_numbers.push(row_number);
+            }
+        }
+    }
+
+    /// Produces the matrices
+    ///
+    /// # Returns
+    /// * `CorrelationMatrix` - The matrices (empty without continuous columns)
+    pub fn finish(self) -> CorrelationMatrix {
+        let continuous_column_count = self.continuous_columns.len();
+
+        // Fill both triangles from the accumulated upper triangle
+        let undefined_pair_statistics = PairwiseColumnStatistics {
+            pair_count: 0,
+            covariance: f64::NAN,
+            pearson_correlation: f64::NAN,
+            spearman_correlation: f64::NAN,
+        };
+        let mut pairwise_statistics = vec![vec![undefined_pair_statistics; continuous_column_count]; continuous_column_count];
+        for first_position in 0..continuous_column_count {
+            for second_position in first_position..continuous_column_count {
+                let moment_accumulator = &self.moment_accumulators[first_position][second_position];
+                let spearman_correlation = match &self.spearman_column_numbers {
+                    Some(spearman_column_numbers) => calculate_spearman_correlation(
+                        &spearman_column_numbers[first_position],
+                        &spearman_column_numbers[second_position],
+                    ),
+                    None => f64::NAN,
+                };
+                let pair_statistics = PairwiseColumnStatistics {
+                    pair_count: moment_accumulator.pair_count,
+                    covariance: moment_accumulator.covariance(self.delta_degrees_of_freedom),
+                    pearson_correlation: moment_accumulator.pearson_correlation(),
+                    spearman_correlation,
+                };
+                pairwise_statistics[first_position][second_position] = pair_statistics;
+                pairwise_statistics[second_position][first_position] = pair_statistics;
+            }
+        }
+
+        CorrelationMatrix {
+            column_names: self.continuous_columns.into_iter().map(|(_, column_name)| column_name).collect(),
+            delta_degrees_of_freedom: self.delta_degrees_of_freedom,
+            pairwise_statistics,
+            spearman_status: self.spearman_status,
+        }
     }
 }
 
-/// Calculates comprehensive categorical statistics
+/// Parses a field of a continuous column for the correlation analysis
 ///
 /// # Arguments
-/// * `column_values` - All values from the column
+/// * `field_value` - The raw field
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<CategoricalColumnStatistics>` - Complete categorical analysis
-fn calculate_categorical_statistics(column_values: &[String]) -> RowsAndColumnsResult<CategoricalColumnStatistics> {
-    let mut value_counts = HashMap::new();
-    let mut empty_count = 0;
+/// * `Option<f64>` - The number, or None when the field is empty, unparseable or NaN (missing)
+fn parse_continuous_field_value(field_value: &str) -> Option<f64> {
+    field_value.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|numerical_value| !numerical_value.is_nan())
+}
 
-    // Count occurrences of each value
-    for value_string in column_values {
-        let trimmed_value = value_string.trim();
+/// Calculates Spearman's rank correlation over the rows where both columns have a number
+///
+/// # Arguments
+/// * `first_column_numbers` - The first column, one entry per row (None when missing)
+/// * `second_column_numbers` - The second column, one entry per row (None when missing)
+///
+/// # Returns
+/// * `f64` - Pearson correlation of the average ranks (NaN with fewer than 2 pairs or a constant column)
+fn calculate_spearman_correlation(first_column_numbers: &[Option<f64>], second_column_numbers: &[Option<f64>]) -> f64 {
+    let (first_values, second_values): (Vec<f64>, Vec<f64>) = first_column_numbers
+        .iter()
+        .zip(second_column_numbers)
+        .filter_map(|(first_number, second_number)| Some(((*first_number)?, (*second_number)?)))
+        .unzip();
+
+    let mut rank_moment_accumulator = PairwiseMomentAccumulator::default();
+    for (first_rank, second_rank) in calculate_average_ranks(&first_values).into_iter().zip(calculate_average_ranks(&second_values)) {
+        rank_moment_accumulator.add_pair(first_rank, second_rank);
+    }
+    rank_moment_accumulator.pearson_correlation()
+}
 
-        if trimmed_value.is_empty() {
-            empty_count += 1;
-        } else {
-            *value_counts.entry(trimmed_value.to_string()).or_insert(0) += 1;
+/// Ranks values from 1, giving tied values the average of their ranks
+///
+/// # Arguments
+/// * `values` - The values to rank
+///
+/// # Returns
+/// * `Vec<f64>` - The rank of each value, in the order of `values`
+fn calculate_average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut sorted_positions: Vec<usize> = (0..values.len()).collect();
+    sorted_positions.sort_by(|first_position, second_position| values[*first_position].total_cmp(&values[*second_position]));
+
+    let mut average_ranks = vec![0.0; values.len()];
+    let mut tie_start = 0;
+    while tie_start < sorted_positions.len() {
+        let mut tie_end = tie_start + 1;
+        while tie_end < sorted_positions.len() && values[sorted_positions[tie_end]] == values[sorted_positions[tie_start]] {
+            tie_end += 1;
         }
+
+        // Sorted positions tie_start..tie_end hold ranks tie_start + 1 to tie_end
+        let average_rank = (tie_start + 1 + tie_end) as f64 / 2.0;
+        for sorted_position in &sorted_positions[tie_start..tie_end] {
+            average_ranks[*sorted_position] = average_rank;
+        }
+        tie_start = tie_end;
     }
 
-    let total_non_empty_values: usize = value_counts.values().sum();
-    let unique_value_count = value_counts.len();
+    average_ranks
+}
 
-    // Create frequency list sorted by count (descending)
-    let mut value_frequencies: Vec<CategoricalValueFrequency> = value_counts
-        .into_iter()
-        .map(|(value, count)| {
-            let percentage = if total_non_empty_values > 0 {
-                (count as f64 / total_non_empty_values as f64) * 100.0
-            } else {
-                0.0
-            };
+/// Displays the Pearson and Spearman correlation matrices with column labels
+///
+/// Nothing is shown with fewer than two continuous columns. Without Spearman
+/// correlations a line says why instead.
+///
+/// # Arguments
+/// * `correlation_matrix` - The matrices to display
+pub fn display_correlation_matrix(correlation_matrix: &CorrelationMatrix) {
+    if correlation_matrix.column_names.len() < 2 {
+        return;
+    }
+
+    display_labelled_correlation_matrix(correlation_matrix, "Pearson correlation", |pair_statistics| pair_statistics.pearson_correlation);
+    match correlation_matrix.spearman_status {
+        SpearmanCorrelationStatus::Computed => {
+            display_labelled_correlation_matrix(correlation_matrix, "Spearman correlation", |pair_statistics| pair_statistics.spearman_correlation);
+        }
+        SpearmanCorrelationStatus::NotRequested => {
+            println!("    Spearman correlation: not computed (add --spearman; it keeps the numeric values in memory)");
+        }
+        SpearmanCorrelationStatus::ValueLimitExceeded => {
+            println!("    Spearman correlation: not computed (more than {} numeric values)", SPEARMAN_EXACT_VALUE_LIMIT);
+        }
+    }
+}
 
-            CategoricalValueFrequency {
-                value,
-                count,
-                percentage,
+/// Displays one matrix of the correlation analysis with a header row and a label per row
+///
+/// # Arguments
+/// * `correlation_matrix` - The column names and pair statistics
+/// * `matrix_title` - The title above the matrix
+/// * `select_value` - Picks the value to show from each pair's statistics
+fn display_labelled_correlation_matrix(
+    correlation_matrix: &CorrelationMatrix,
+    matrix_title: &str,
+    select_value: impl Fn(&PairwiseColumnStatistics) -> f64,
+) {
+    let column_labels: Vec<String> = correlation_matrix.column_names
+        .iter()
+        .map(|column_name| {
+            if column_name.chars().count() > CORRELATION_LABEL_MAX_WIDTH {
+                let shortened_label: String = column_name.chars().take(CORRELATION_LABEL_MAX_WIDTH - 1).collect();
+                format!("{}…", shortened_label)
+            } else {
+                column_name.clone()
             }
         })
         .collect();
+    let label_width = column_labels.iter().map(|column_label| column_label.chars().count()).max().unwrap_or(0);
+    let cell_width = label_width.max(CORRELATION_CELL_MIN_WIDTH);
 
-    // Sort by count (descending)
-    value_frequencies.sort_by(|a, b| b.count.cmp(&a.count));
-
-    // Find mode (most common value)
-    let (mode_value, mode_percentage) = if let Some(most_frequent) = value_frequencies.first() {
-        (Some(most_frequent.value.clone()), most_frequent.percentage)
-    } else {
-        (None, 0.0)
-    };
-
-    // Calculate missing percentage
-    let total_values = column_values.len();
-    let missing_percentage = if total_values > 0 {
-        (empty_count as f64 / total_values as f64) * 100.0
-    } else {
-        0.0
-    };
-
-    Ok(CategoricalColumnStatistics {
-        unique_value_count,
-        value_frequencies,
-        missing_percentage,
-        mode_value,
-        mode_percentage,
-    })
+    println!("    {} (pairwise complete rows):", matrix_title);
+    let mut header_line = format!("    {:label_width$}", "");
+    for column_label in &column_labels {
+        header_line.push_str(&format!("  {:>cell_width$}", column_label));
+    }
+    println!("{}", header_line);
+
+    for (row_label, row_statistics) in column_labels.iter().zip(&correlation_matrix.pairwise_statistics) {
+        let mut matrix_line = format!("    {:<label_width$}", row_label);
+        for pair_statistics in row_statistics {
+            let correlation_value = select_value(pair_statistics);
+            let cell_text = if correlation_value.is_nan() {
+                "nan".to_string()
+            } else {
+                format!("{:.3}", correlation_value)
+            };
+            matrix_line.push_str(&format!("  {:>cell_width$}", cell_text));
+        }
+        println!("{}", matrix_line);
+    }
 }
 
 /// Saves CSV analysis summary information to a TOML-formatted report file
@@ -1113,7 +1597,7 @@ pub fn save_analysis_summary_to_file(
     // Metadata section
     toml_content.push_str("[metadata]\n");
     toml_content.push_str(&format!("timestamp = \"{}\"\n", timestamp));
-    toml_content.push_str(&format!("original_csv_file = \"{}\"\n", csv_file_path.display()));
+    toml_content.push_str(&format!("original_csv_file = \"{}\"\n", escape_toml_basic_string(&csv_file_path.display().to_string())));
     toml_content.push_str("report_generated_by = \"rows_and_columns\"\n");
     toml_content.push_str("\n");
 
@@ -1129,8 +1613,11 @@ pub fn save_analysis_summary_to_file(
 
     // File paths section
     toml_content.push_str("[file_paths]\n");
-    toml_content.push_str(&format!("csv_file = \"{}\"\n", csv_file_path.display()));
-    toml_content.push_str(&format!("metadata_file = \"{}\"\n", analysis_results.metadata_file_path.display()));
+    toml_content.push_str(&format!("csv_file = \"{}\"\n", escape_toml_basic_string(&csv_file_path.display().to_string())));
+    toml_content.push_str(&format!(
+        "metadata_file = \"{}\"\n",
+        escape_toml_basic_string(&analysis_results.metadata_file_path.display().to_string())
+    ));
     // toml_content.push_str(&format!("storage_directory = \"{}\"\n", directory_paths.csv_imports_directory.display()));
     toml_content.push_str("\n");
 
@@ -1147,8 +1634,10 @@ pub fn save_analysis_summary_to_file(
 /// Appends detailed column-by-column analysis to the TOML report file
 ///
 /// This function adds detailed statistical analysis for each column including
-/// numerical statistics (min, max, quartiles, mean, stdev) for continuous columns
-/// and categorical statistics (unique values, mode, value distributions) for categorical columns.
+/// numerical statistics (the pandas `describe()` set plus sum, variance, skewness,
+/// excess kurtosis, IQR, range, coefficient of variation and zero/negative counts)
+/// for continuous columns and categorical statistics (unique values, mode, value
+/// distributions) for categorical columns. Undefined values are written as TOML `nan`.
 ///
 /// # Arguments
 /// * `report_file_path` - Path to the existing report file to append to
@@ -1181,12 +1670,16 @@ pub fn save_analysis_details_to_file(
         let column_name = &column_info.basic_info.column_name;
         let safe_column_name = column_name.replace(" ", "_").replace(".", "_");
 
-        // Column header
-        toml_content.push_str(&format!("[columns.{}]\n", safe_column_name));
+        // Column header (quoted when the name is not a bare TOML key)
+        if safe_column_name.chars().all(|name_character| name_character.is_ascii_alphanumeric() || name_character == '_' || name_character == '-') {
+            toml_content.push_str(&format!("[columns.{}]\n", safe_column_name));
+        } else {
+            toml_content.push_str(&format!("[columns.\"{}\"]\n", escape_toml_basic_string(&safe_column_name)));
+        }
 
         // Basic information
         toml_content.push_str(&format!("column_index = {}\n", column_info.basic_info.column_index));
-        toml_content.push_str(&format!("column_name = \"{}\"\n", column_name));
+        toml_content.push_str(&format!("column_name = \"{}\"\n", escape_toml_basic_string(column_name)));
         toml_content.push_str(&format!("data_type = \"{:?}\"\n", column_info.basic_info.detected_data_type));
         toml_content.push_str(&format!("field_type = \"{:?}\"\n", column_info.field_type));
         toml_content.push_str(&format!("non_empty_values = {}\n", column_info.basic_info.non_empty_value_count));
@@ -1200,7 +1693,36 @@ pub fn save_analysis_details_to_file(
             toml_content.push_str(&format!("q3_value = {:.3}\n", num_stats.q3_value));
             toml_content.push_str(&format!("max_value = {:.3}\n", num_stats.max_value));
             toml_content.push_str(&format!("mean_value = {:.3}\n", num_stats.mean_value));
-            toml_content.push_str(&format!("standard_deviation = {:.3}\n", num_stats.standard_deviation));
+            toml_content.push_str(&format!("standard_deviation = {}\n", format_toml_float(num_stats.standard_deviation)));
+            toml_content.push_str(&format!("value_count = {}\n", num_stats.value_count));
+            toml_content.push_str(&format!("value_sum = {:.3}\n", num_stats.value_sum));
+            toml_content.push_str(&format!("variance = {}\n", format_toml_float(num_stats.variance)));
+            toml_content.push_str(&format!("delta_degrees_of_freedom = {}\n", num_stats.delta_degrees_of_freedom));
+            toml_content.push_str(&format!("skewness = {}\n", format_toml_float(num_stats.skewness)));
+            toml_content.push_str(&format!("excess_kurtosis = {}\n", format_toml_float(num_stats.excess_kurtosis)));
+            toml_content.push_str(&format!("interquartile_range = {:.3}\n", num_stats.interquartile_range));
+            toml_content.push_str(&format!("value_range = {:.3}\n", num_stats.value_range));
+            match num_stats.coefficient_of_variation {
+                Some(coefficient_of_variation) => toml_content.push_str(&format!(
+                    "coefficient_of_variation = {}\n",
+                    format_toml_float(coefficient_of_variation)
+                )),
+                None => toml_content.push_str("# coefficient_of_variation: undefined (mean is 0)\n"),
+            }
+            toml_content.push_str(&format!("zero_value_count = {}\n", num_stats.zero_value_count));
+            toml_content.push_str(&format!("negative_value_count = {}\n", num_stats.negative_value_count));
+            toml_content.push_str(&format!("percentile_interpolation = \"{}\"\n", num_stats.percentile_interpolation.as_name()));
+            toml_content.push_str(&format!("percentiles_are_estimates = {}\n", num_stats.percentiles_are_estimates));
+            if !num_stats.percentile_values.is_empty() {
+                let percentile_texts: Vec<String> = num_stats.percentile_values.iter()
+                    .map(|percentile_value| percentile_value.percentile.to_string())
+                    .collect();
+                let percentile_value_texts: Vec<String> = num_stats.percentile_values.iter()
+                    .map(|percentile_value| format_toml_float(percentile_value.percentile_value))
+                    .collect();
+                toml_content.push_str(&format!("percentiles = [{}]\n", percentile_texts.join(", ")));
+                toml_content.push_str(&format!("percentile_values = [{}]\n", percentile_value_texts.join(", ")));
+            }
             toml_content.push_str(&format!("missing_percentage = {:.1}\n", num_stats.missing_percentage));
         }
 
@@ -1211,7 +1733,7 @@ pub fn save_analysis_details_to_file(
 
             // Mode information
             if let Some(ref mode) = cat_stats.mode_value {
-                toml_content.push_str(&format!("mode_value = \"{}\"\n", mode));
+                toml_content.push_str(&format!("mode_value = \"{}\"\n", escape_toml_basic_string(mode)));
                 toml_content.push_str(&format!("mode_percentage = {:.1}\n", cat_stats.mode_percentage));
             }
 
@@ -1226,7 +1748,7 @@ pub fn save_analysis_details_to_file(
                 }
                 toml_content.push_str(&format!(
                     "  {{ value = \"{}\", count = {}, percentage = {:.1} }},\n",
-                    freq.value.replace("\"", "\\\""), // Escape quotes in values
+                    escape_toml_basic_string(&freq.value),
                     freq.count,
                     freq.percentage
                 ));
@@ -1240,7 +1762,7 @@ pub fn save_analysis_details_to_file(
             if index > 0 {
                 toml_content.push_str(", ");
             }
-            toml_content.push_str(&format!("\"{}\"", sample.replace("\"", "\\\"")));
+            toml_content.push_str(&format!("\"{}\"", escape_toml_basic_string(sample)));
         }
         toml_content.push_str("]\n");
 
@@ -1256,3 +1778,363 @@ pub fn save_analysis_details_to_file(
 
     Ok(())
 }
+
+/// Appends the correlation matrices to the TOML report file
+///
+/// Writes a `[correlations]` table with the column labels, the pair counts and
+/// the covariance, Pearson and Spearman matrices as arrays of rows in column
+/// order (undefined entries as TOML `nan`). `spearman_status` tells whether the
+/// Spearman matrix was computed; it is left out otherwise. Nothing is written
+/// with fewer than two continuous columns.
+///
+/// # Arguments
+/// * `report_file_path` - Path to the existing report file to append to
+/// * `correlation_matrix` - The correlation analysis of the continuous columns
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If file reading or writing fails
+pub fn save_correlation_matrix_to_file(
+    report_file_path: &PathBuf,
+    correlation_matrix: &CorrelationMatrix,
+) -> RowsAndColumnsResult<()> {
+    if correlation_matrix.column_names.len() < 2 {
+        return Ok(());
+    }
+
+    // Read existing content
+    let mut toml_content = std::fs::read_to_string(report_file_path)
+        .map_err(|io_error| create_file_system_error(
+            &format!("Failed to read existing report file {}", report_file_path.display()),
+            io_error
+        ))?;
+
+    let column_name_texts: Vec<String> = correlation_matrix.column_names.iter()
+        .map(|column_name| format!("\"{}\"", escape_toml_basic_string(column_name)))
+        .collect();
+    toml_content.push_str("[correlations]\n");
+    toml_content.push_str("missing_values = \"pairwise\"\n");
+    toml_content.push_str(&format!("columns = [{}]\n", column_name_texts.join(", ")));
+    toml_content.push_str(&format!("delta_degrees_of_freedom = {}\n", correlation_matrix.delta_degrees_of_freedom));
+    push_toml_correlation_matrix(&mut toml_content, "pair_counts", correlation_matrix, |pair_statistics| {
+        pair_statistics.pair_count.to_string()
+    });
+    push_toml_correlation_matrix(&mut toml_content, "covariance", correlation_matrix, |pair_statistics| {
+        format_toml_float(pair_statistics.covariance)
+    });
+    push_toml_correlation_matrix(&mut toml_content, "pearson", correlation_matrix, |pair_statistics| {
+        format_toml_float(pair_statistics.pearson_correlation)
+    });
+    toml_content.push_str(&format!("spearman_status = \"{}\"\n", correlation_matrix.spearman_status.as_name()));
+    if correlation_matrix.spearman_status == SpearmanCorrelationStatus::Computed {
+        push_toml_correlation_matrix(&mut toml_content, "spearman", correlation_matrix, |pair_statistics| {
+            format_toml_float(pair_statistics.spearman_correlation)
+        });
+    }
+    toml_content.push('\n');
+
+    // Write updated content back to file
+    std::fs::write(report_file_path, toml_content)
+        .map_err(|io_error| create_file_system_error(
+            &format!("Failed to write correlation matrices to {}", report_file_path.display()),
+            io_error
+        ))?;
+
+    Ok(())
+}
+
+/// Appends one matrix of the correlation analysis as a TOML array of rows
+///
+/// # Arguments
+/// * `toml_content` - The report content to append to
+/// * `matrix_key` - The TOML key of the matrix
+/// * `correlation_matrix` - The pair statistics
+/// * `format_entry` - Formats the entry to write from each pair's statistics
+fn push_toml_correlation_matrix(
+    toml_content: &mut String,
+    matrix_key: &str,
+    correlation_matrix: &CorrelationMatrix,
+    format_entry: impl Fn(&PairwiseColumnStatistics) -> String,
+) {
+    toml_content.push_str(&format!("{} = [\n", matrix_key));
+    for row_statistics in &correlation_matrix.pairwise_statistics {
+        let entry_texts: Vec<String> = row_statistics.iter().map(&format_entry).collect();
+        toml_content.push_str(&format!("  [{}],\n", entry_texts.join(", ")));
+    }
+    toml_content.push_str("]\n");
+}
+
+/// Formats a float for the TOML report with three decimals
+///
+/// # Arguments
+/// * `float_value` - The value
+///
+/// # Returns
+/// * `String` - The value, or TOML's `nan`/`inf`/`-inf` when it is not finite
+fn format_toml_float(float_value: f64) -> String {
+    if float_value.is_nan() {
+        "nan".to_string()
+    } else if float_value.is_infinite() {
+        if float_value > 0.0 { "inf".to_string() } else { "-inf".to_string() }
+    } else {
+        format!("{:.3}", float_value)
+    }
+}
+
+/// Escapes text for a TOML basic (double-quoted) string
+///
+/// Quoted CSV fields may hold newlines and tabs, which TOML basic strings
+/// do not allow raw; they are written as escape sequences instead.
+///
+/// # Arguments
+/// * `text` - The text to escape
+///
+/// # Returns
+/// * `String` - The text with `"`, `\` and control characters escaped
+fn escape_toml_basic_string(text: &str) -> String {
+    let mut escaped_text = String::with_capacity(text.len());
+
+    for current_character in text.chars() {
+        match current_character {
+            '"' => escaped_text.push_str("\\\""),
+            '\\' => escaped_text.push_str("\\\\"),
+            '\t' => escaped_text.push_str("\\t"),
+            '\n' => escaped_text.push_str("\\n"),
+            '\r' => escaped_text.push_str("\\r"),
+            control_character if control_character.is_control() => {
+                escaped_text.push_str(&format!("\\u{:04X}", control_character as u32));
+            }
+            other_character => escaped_text.push(other_character),
+        }
+    }
+
+    escaped_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_column_values(value_texts: &[&str]) -> Vec<String> {
+        value_texts.iter().map(|value_text| value_text.to_string()).collect()
+    }
+
+    /// Feeds column values through the streaming accumulator of the enhanced analysis
+    fn calculate_numerical_statistics(
+        column_values: &[String],
+        numerical_statistics_options: &NumericalStatisticsOptions,
+    ) -> RowsAndColumnsResult<NumericalColumnStatistics> {
+        let mut numerical_accumulator = NumericalStatisticsAccumulator::new(numerical_statistics_options);
+        for column_value in column_values {
+            numerical_accumulator.add_value(column_value);
+        }
+        numerical_accumulator.finish()
+    }
+
+    #[test]
+    fn test_describe_statistics_with_sample_and_population_ddof() {
+        let column_values = to_column_values(&["2", "4", "4", "4", "5", "5", "7", "9", ""]);
+
+        let sample_statistics = calculate_numerical_statistics(&column_values, &NumericalStatisticsOptions::default())
+            .expect("numbers should parse");
+        assert_eq!((sample_statistics.value_count, sample_statistics.value_sum), (8, 40.0));
+        assert!((sample_statistics.variance - 32.0 / 7.0).abs() < 1e-12);
+        assert!((sample_statistics.skewness - 0.818_487_553_356_799_7).abs() < 1e-12);
+        assert!((sample_statistics.excess_kurtosis - 0.940625).abs() < 1e-12);
+        assert_eq!((sample_statistics.interquartile_range, sample_statistics.value_range), (1.5, 7.0));
+
+        let population_options = NumericalStatisticsOptions {
+            delta_degrees_of_freedom: 0,
+            ..NumericalStatisticsOptions::default()
+        };
+        let population_statistics = calculate_numerical_statistics(&column_values, &population_options)
+            .expect("numbers should parse");
+        assert!((population_statistics.standard_deviation - 2.0).abs() < 1e-12);
+        assert!((population_statistics.skewness - 0.65625).abs() < 1e-12);
+        assert!((population_statistics.excess_kurtosis + 0.21875).abs() < 1e-12);
+        assert!((population_statistics.coefficient_of_variation.expect("mean is not zero") - 0.4).abs() < 1e-12);
+
+        let single_value_statistics = calculate_numerical_statistics(&to_column_values(&["0"]), &NumericalStatisticsOptions::default())
+            .expect("number should parse");
+        assert!(single_value_statistics.standard_deviation.is_nan());
+        assert_eq!(single_value_statistics.coefficient_of_variation, None);
+        assert_eq!(single_value_statistics.zero_value_count, 1);
+        assert_eq!(format_toml_float(single_value_statistics.variance), "nan");
+    }
+
+    #[test]
+    fn test_requested_percentiles_with_each_interpolation() {
+        let column_values = to_column_values(&["10", "4", "1", "3", "2"]);
+        // Rank 0.4 * 4 = 1.6 lies between 2 and 3; numpy gives 2.6, 2, 3, 3 and 2.5
+        let expected_values = [
+            (PercentileInterpolation::Linear, 2.6),
+            (PercentileInterpolation::Lower, 2.0),
+            (PercentileInterpolation::Higher, 3.0),
+            (PercentileInterpolation::Nearest, 3.0),
+            (PercentileInterpolation::Midpoint, 2.5),
+        ];
+        for (percentile_interpolation, expected_value) in expected_values {
+            let percentile_options = NumericalStatisticsOptions {
+                requested_percentiles: vec![40.0, 100.0],
+                percentile_interpolation,
+                ..NumericalStatisticsOptions::default()
+            };
+            let statistics = calculate_numerical_statistics(&column_values, &percentile_options)
+                .expect("numbers should parse");
+            assert!((statistics.percentile_values[0].percentile_value - expected_value).abs() < 1e-12);
+            assert_eq!(statistics.percentile_values[1].percentile_value, 10.0);
+            assert_eq!(PercentileInterpolation::from_name(percentile_interpolation.as_name()), Some(percentile_interpolation));
+        }
+    }
+
+    #[test]
+    fn test_pairwise_covariance_pearson_and_spearman() {
+        // The third row misses its second value, so the pair uses the other four rows
+        let first_column_numbers = [Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0)];
+        let second_column_numbers = [Some(2.0), Some(4.0), None, Some(5.0), Some(100.0)];
+        let mut moment_accumulator = PairwiseMomentAccumulator::default();
+        for (first_number, second_number) in first_column_numbers.iter().zip(&second_column_numbers) {
+            if let (Some(first_value), Some(second_value)) = (first_number, second_number) {
+                moment_accumulator.add_pair(*first_value, *second_value);
+            }
+        }
+        assert_eq!(moment_accumulator.pair_count, 4);
+        assert!((moment_accumulator.covariance(1) - 65.666_666_666_666_67).abs() < 1e-9);
+        assert!((moment_accumulator.pearson_correlation() - 0.746_471_893_562_138_2).abs() < 1e-12);
+        assert_eq!(calculate_spearman_correlation(&first_column_numbers, &second_column_numbers), 1.0);
+
+        // Ties share their average rank; a constant column has no correlation
+        assert_eq!(calculate_average_ranks(&[3.0, 1.0, 3.0, 2.0]), vec![3.5, 1.0, 3.5, 2.0]);
+        let tied_spearman = calculate_spearman_correlation(
+            &[Some(1.0), Some(2.0), Some(3.0), Some(4.0)],
+            &[Some(1.0), Some(1.0), Some(2.0), Some(3.0)],
+        );
+        assert!((tied_spearman - 0.948_683_298_050_513_8).abs() < 1e-12);
+        assert!(calculate_spearman_correlation(&[Some(1.0), Some(2.0)], &[Some(7.0), Some(7.0)]).is_nan());
+        assert_eq!(parse_continuous_field_value(" NaN "), None);
+    }
+
+    #[test]
+    fn test_correlation_accumulator_streams_pearson_and_keeps_spearman_on_request() {
+        let test_column = |column_index: usize, column_name: &str, detected_data_type: CsvColumnDataType| CsvColumnInformation {
+            column_index,
+            column_name: column_name.to_string(),
+            detected_data_type,
+            non_empty_value_count: 0,
+            empty_value_count: 0,
+            sample_values: Vec::new(),
+        };
+        let basic_analysis_results = CsvAnalysisResults {
+            has_header_row: true,
+            total_column_count: 3,
+            total_data_row_count: 5,
+            column_information_list: vec![
+                test_column(0, "x", CsvColumnDataType::Integer),
+                test_column(1, "name", CsvColumnDataType::String),
+                test_column(2, "y", CsvColumnDataType::Float),
+            ],
+            metadata_file_path: PathBuf::new(),
+        };
+        let records = [["1", "a", "2"], ["2", "b", "4"], ["3", "c", ""], ["4", "d", "5"], ["5", "e", "100"]];
+
+        let spearman_options = NumericalStatisticsOptions { spearman_correlation: true, ..NumericalStatisticsOptions::default() };
+        for numerical_statistics_options in [NumericalStatisticsOptions::default(), spearman_options] {
+            let mut correlation_accumulator = CorrelationAccumulator::new(&basic_analysis_results, &numerical_statistics_options);
+            for record in records {
+                correlation_accumulator.add_record(&to_column_values(&record));
+            }
+            let correlation_matrix = correlation_accumulator.finish();
+
+            // Only the continuous columns are correlated, pairwise over the rows where both have a number
+            assert_eq!(correlation_matrix.column_names, vec!["x".to_string(), "y".to_string()]);
+            let pair_statistics = correlation_matrix.pairwise_statistics[0][1];
+            assert_eq!((pair_statistics.pair_count, correlation_matrix.pairwise_statistics[0][0].pair_count), (4, 5));
+            assert!((pair_statistics.covariance - 65.666_666_666_666_67).abs() < 1e-9);
+            assert!((pair_statistics.pearson_correlation - 0.746_471_893_562_138_2).abs() < 1e-12);
+
+            if numerical_statistics_options.spearman_correlation {
+                assert_eq!(correlation_matrix.spearman_status, SpearmanCorrelationStatus::Computed);
+                assert_eq!(pair_statistics.spearman_correlation, 1.0);
+            } else {
+                assert_eq!(correlation_matrix.spearman_status, SpearmanCorrelationStatus::NotRequested);
+                assert!(pair_statistics.spearman_correlation.is_nan());
+            }
+        }
+    }
+
+    /// Reads back a TOML basic string body (the escapes the report writer uses)
+    fn decode_toml_basic_string(escaped_text: &str) -> String {
+        let mut decoded_text = String::new();
+        let mut escaped_characters = escaped_text.chars();
+        while let Some(current_character) = escaped_characters.next() {
+            if current_character != '\\' {
+                decoded_text.push(current_character);
+                continue;
+            }
+            match escaped_characters.next() {
+                Some('n') => decoded_text.push('\n'),
+                Some('r') => decoded_text.push('\r'),
+                Some('t') => decoded_text.push('\t'),
+                Some('u') => {
+                    let code_text: String = escaped_characters.by_ref().take(4).collect();
+                    let code_point = u32::from_str_radix(&code_text, 16).expect("\\u needs four hex digits");
+                    decoded_text.push(char::from_u32(code_point).expect("\\u should name a character"));
+                }
+                Some(escaped_character) => decoded_text.push(escaped_character),
+                None => panic!("dangling backslash in {:?}", escaped_text),
+            }
+        }
+        decoded_text
+    }
+
+    #[test]
+    fn test_toml_strings_escape_backslashes_before_quotes() {
+        assert_eq!(escape_toml_basic_string(r"C:\temp"), r"C:\\temp");
+        assert_eq!(escape_toml_basic_string(r#"say "hi""#), r#"say \"hi\""#);
+        // A backslash before a quote stays a literal backslash followed by a literal quote
+        assert_eq!(escape_toml_basic_string(r#"a\"b"#), r#"a\\\"b"#);
+    }
+
+    #[test]
+    fn test_report_strings_round_trip_newlines_and_control_characters() {
+        let raw_value = "two\nlines\r\tand a \u{7} bell";
+        let escaped_value = escape_toml_basic_string(raw_value);
+        assert_eq!(escaped_value, r"two\nlines\r\tand a \u0007 bell");
+        assert!(!escaped_value.chars().any(char::is_control));
+        assert_eq!(decode_toml_basic_string(&escaped_value), raw_value);
+
+        // A quoted multi-line CSV field lands on one report line
+        let test_directory = std::env::temp_dir().join(format!("{}_report_escaping_test", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("test directory should be creatable");
+        let csv_file_path = test_directory.join("notes.csv");
+        std::fs::write(&csv_file_path, "note,count\n\"two\nlines\",1\n\"two\nlines\",2\nplain,3\n")
+            .expect("test CSV should be writable");
+        let basic_analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should work");
+        let (enhanced_analysis_results, _) = perform_enhanced_statistical_analysis(
+            &csv_file_path,
+            &basic_analysis_results,
+            &NumericalStatisticsOptions::default(),
+        )
+        .expect("statistics should work");
+        let report_file_path = save_analysis_summary_to_file(&csv_file_path, &basic_analysis_results)
+            .expect("summary should be writable");
+        save_analysis_details_to_file(&report_file_path, &enhanced_analysis_results).expect("details should be writable");
+        let report_text = std::fs::read_to_string(&report_file_path).expect("report should be readable");
+
+        let mode_value_line = report_text
+            .lines()
+            .find(|report_line| report_line.starts_with("mode_value = "))
+            .expect("note should have a mode");
+        let escaped_mode_value = mode_value_line
+            .strip_prefix("mode_value = \"")
+            .and_then(|quoted_text| quoted_text.strip_suffix('"'))
+            .expect("mode should be a basic string");
+        assert_eq!(decode_toml_basic_string(escaped_mode_value), "two\nlines");
+        assert!(report_text.contains(r#"sample_values = ["two\nlines", "two\nlines", "plain"]"#), "{}", report_text);
+        assert!(!report_text.lines().any(|report_line| report_line.starts_with("lines")));
+
+        std::fs::remove_dir_all(&test_directory).expect("test directory should be removable");
+    }
+}
@@ -0,0 +1,356 @@
+// src/csv_record_reader_module.rs
+
+/// RFC 4180 streaming CSV record reader for rows_and_columns
+///
+/// This module reads CSV data one logical record at a time from any `BufRead`
+/// source. A logical record may span several physical lines when a quoted field
+/// contains a line break, so splitting a file on newlines and then on commas is
+/// not enough to recover the real fields. This reader is a small state machine
+/// that follows RFC 4180 and reports where each record starts in the source.
+///
+/// # Core Responsibilities
+/// - Yield one record at a time without reading ahead more than one line
+/// - Handle quoted fields containing commas, escaped `""` quotes, and line breaks
+/// - Accept both LF and CRLF line endings
+/// - Report the 1-based physical line number and byte offset of every record
+///
+/// # Design Philosophy
+/// - No-Load: only the record currently being assembled is held in memory
+/// - Vanilla Rust: std-only and returns `io::Error`, so every crate can use it
+/// - Lenient where RFC 4180 is silent: a stray quote inside an unquoted field,
+///   or text following a closing quote, is kept as literal field content
+/// - Blank lines between records are skipped but still counted for line numbers
+use std::io::{self, BufRead};
+
+/// Field delimiter defined by RFC 4180
+const CSV_FIELD_DELIMITER_CHARACTER: char = ',';
+
+/// Quote character defined by RFC 4180
+const CSV_QUOTE_CHARACTER: char = '"';
+
+/// One logical CSV record together with its position in the source
+///
+/// The position information lets callers produce precise diagnostics
+/// (e.g. "line 42") and lets other tools seek straight back to a record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRecord {
+    /// Field values in column order, with surrounding quotes and escapes removed
+    pub field_values: Vec<String>,
+
+    /// Physical line number (1-based) on which this record begins
+    pub starting_line_number: usize,
+
+    /// Byte offset from the start of the source at which this record begins
+    pub starting_byte_offset: u64,
+
+    /// Number of physical lines this record occupies
+    /// (greater than 1 when a quoted field contains line breaks)
+    pub physical_line_count: usize,
+}
+
+/// Parser state while scanning the characters of one record
+///
+/// Each variant describes what the previous character left us expecting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CsvFieldParseState {
+    /// At the beginning of a field (start of record or just after a delimiter)
+    AtFieldStart,
+
+    /// Inside a field that did not begin with a quote
+    InUnquotedContent,
+
+    /// Inside a quoted field, where delimiters and line breaks are literal
+    InQuotedContent,
+
+    /// Just saw a quote inside a quoted field: either an escaped `""` or the closing quote
+    QuoteSeenInQuotedContent,
+}
+
+/// Streaming reader that yields one `CsvRecord` at a time
+///
+/// # Examples
+/// ```rust
+/// let csv_file = File::open("data.csv")?;
+/// let mut record_reader = CsvRecordReader::new(BufReader::new(csv_file));
+/// while let Some(csv_record) = record_reader.read_next_record()? {
+///     println!("line {}: {:?}", csv_record.starting_line_number, csv_record.field_values);
+/// }
+/// ```
+pub struct CsvRecordReader<R: BufRead> {
+    /// The underlying buffered byte source
+    source_reader: R,
+
+    /// Physical line number (1-based) of the next line to be read
+    next_physical_line_number: usize,
+
+    /// Byte offset of the next line to be read
+    next_byte_offset: u64,
+
+    /// Reusable buffer for the raw bytes of one physical line
+    raw_line_buffer: Vec<u8>,
+}
+
+impl<R: BufRead> CsvRecordReader<R> {
+    /// Creates a reader positioned at the very start of the source
+    ///
+    /// # Arguments
+    /// * `source_reader` - Any buffered reader (file, stdin, in-memory cursor)
+    ///
+    /// # Returns
+    /// * `CsvRecordReader<R>` - A reader whose first record starts at line 1, byte 0
+    pub fn new(source_reader: R) -> Self {
+        CsvRecordReader {
+            source_reader,
+            next_physical_line_number: 1,
+            next_byte_offset: 0,
+            raw_line_buffer: Vec::new(),
+        }
+    }
+
+    /// Reads the next logical record from the source
+    ///
+    /// Blank physical lines between records are skipped. A quoted field that
+    /// contains line breaks causes additional physical lines to be consumed
+    /// until the closing quote is found.
+    ///
+    /// # Returns
+    /// * `io::Result<Option<CsvRecord>>` - The next record, `None` at end of input, or an error
+    ///
+    /// # Errors
+    /// * `io::ErrorKind::InvalidData` - If a line is not valid UTF-8, or the input
+    ///   ends inside a quoted field
+    /// * Any other `io::Error` from the underlying source
+    pub fn read_next_record(&mut self) -> io::Result<Option<CsvRecord>> {
+        let mut completed_field_values: Vec<String> = Vec::new();
+        let mut current_field_value = String::new();
+        let mut parse_state = CsvFieldParseState::AtFieldStart;
+
+        let mut record_starting_line_number = self.next_physical_line_number;
+        let mut record_starting_byte_offset = self.next_byte_offset;
+        let mut record_has_started = false;
+        let mut physical_lines_in_record = 0;
+
+        loop {
+            // Remember where this physical line begins before consuming it
+            let line_starting_line_number = self.next_physical_line_number;
+            let line_starting_byte_offset = self.next_byte_offset;
+
+            let (line_content, line_terminator) = match self.read_next_physical_line()? {
+                Some(line_parts) => line_parts,
+                None => {
+                    // End of input between records is the normal way to finish
+                    if !record_has_started {
+                        return Ok(None);
+                    }
+
+                    // A started record only continues past a line break inside
+                    // quotes, so reaching the end here means the quote never closed
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "Unterminated quoted field in record starting at line {} (byte offset {})",
+                            record_starting_line_number, record_starting_byte_offset
+                        ),
+                    ));
+                }
+            };
+
+            // Skip blank lines that sit between records
+            if !record_has_started && line_content.is_empty() {
+                continue;
+            }
+
+            if !record_has_started {
+                record_has_started = true;
+                record_starting_line_number = line_starting_line_number;
+                record_starting_byte_offset = line_starting_byte_offset;
+            }
+            physical_lines_in_record += 1;
+
+            // Feed every character of this physical line through the state machine
+            for current_character in line_content.chars() {
+                parse_state = match parse_state {
+                    CsvFieldParseState::AtFieldStart => {
+                        if current_character == CSV_QUOTE_CHARACTER {
+                            CsvFieldParseState::InQuotedContent
+                        } else if current_character == CSV_FIELD_DELIMITER_CHARACTER {
+                            completed_field_values.push(std::mem::take(&mut current_field_value));
+                            CsvFieldParseState::AtFieldStart
+                        } else {
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InUnquotedContent
+                        }
+                    }
+                    CsvFieldParseState::InUnquotedContent => {
+                        if current_character == CSV_FIELD_DELIMITER_CHARACTER {
+                            completed_field_values.push(std::mem::take(&mut current_field_value));
+                            CsvFieldParseState::AtFieldStart
+                        } else {
+                            // Stray quotes in unquoted fields are kept literally
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InUnquotedContent
+                        }
+                    }
+                    CsvFieldParseState::InQuotedContent => {
+                        if current_character == CSV_QUOTE_CHARACTER {
+                            CsvFieldParseState::QuoteSeenInQuotedContent
+                        } else {
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InQuotedContent
+                        }
+                    }
+                    CsvFieldParseState::QuoteSeenInQuotedContent => {
+                        if current_character == CSV_QUOTE_CHARACTER {
+                            // Escaped quote: "" inside a quoted field means one literal "
+                            current_field_value.push(CSV_QUOTE_CHARACTER);
+                            CsvFieldParseState::InQuotedContent
+                        } else if current_character == CSV_FIELD_DELIMITER_CHARACTER {
+                            completed_field_values.push(std::mem::take(&mut current_field_value));
+                            CsvFieldParseState::AtFieldStart
+                        } else {
+                            // Text after a closing quote is kept as literal content
+                            current_field_value.push(current_character);
+                            CsvFieldParseState::InUnquotedContent
+                        }
+                    }
+                };
+            }
+
+            // A line break inside a quoted field belongs to the field value
+            if parse_state == CsvFieldParseState::InQuotedContent {
+                current_field_value.push_str(&line_terminator);
+                continue;
+            }
+
+            // Otherwise the line break ends the record
+            completed_field_values.push(current_field_value);
+            return Ok(Some(CsvRecord {
+                field_values: completed_field_values,
+                starting_line_number: record_starting_line_number,
+                starting_byte_offset: record_starting_byte_offset,
+                physical_line_count: physical_lines_in_record,
+            }));
+        }
+    }
+
+    /// Reads one physical line and splits off its line terminator
+    ///
+    /// # Returns
+    /// * `io::Result<Option<(String, String)>>` - (line content, line terminator), or `None` at end of input
+    fn read_next_physical_line(&mut self) -> io::Result<Option<(String, String)>> {
+        self.raw_line_buffer.clear();
+        let bytes_read = self.source_reader.read_until(b'\n', &mut self.raw_line_buffer)?;
+
+        if bytes_read == 0 {
+            return Ok(None);
+        }
+
+        let line_number_being_read = self.next_physical_line_number;
+        let byte_offset_being_read = self.next_byte_offset;
+        self.next_physical_line_number += 1;
+        self.next_byte_offset += bytes_read as u64;
+
+        // Split the terminator (LF or CRLF) away from the content
+        let mut content_length = self.raw_line_buffer.len();
+        if content_length > 0 && self.raw_line_buffer[content_length - 1] == b'\n' {
+            content_length -= 1;
+            if content_length > 0 && self.raw_line_buffer[content_length - 1] == b'\r' {
+                content_length -= 1;
+            }
+        }
+
+        let line_content = std::str::from_utf8(&self.raw_line_buffer[..content_length])
+            .map_err(|utf8_error| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Invalid UTF-8 data at line {} (byte offset {}): {}",
+                        line_number_being_read,
+                        byte_offset_being_read + utf8_error.valid_up_to() as u64,
+                        utf8_error
+                    ),
+                )
+            })?
+            .to_string();
+
+        let line_terminator = String::from_utf8_lossy(&self.raw_line_buffer[content_length..]).to_string();
+
+        Ok(Some((line_content, line_terminator)))
+    }
+}
+
+impl<R: BufRead> Iterator for CsvRecordReader<R> {
+    type Item = io::Result<CsvRecord>;
+
+    /// Yields the next record, so the reader can be used in `for` loops
+    ///
+    /// # Returns
+    /// * `Option<io::Result<CsvRecord>>` - Next record or error, `None` at end of input
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Helper to read every record from an in-memory string
+    fn read_all_test_records(csv_text: &str) -> io::Result<Vec<CsvRecord>> {
+        CsvRecordReader::new(Cursor::new(csv_text.as_bytes().to_vec())).collect()
+    }
+
+    #[test]
+    fn test_simple_records_and_positions() {
+        let records = read_all_test_records("a,b,c\n1,2,3\n").expect("valid CSV");
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].field_values, vec!["a", "b", "c"]);
+        assert_eq!(records[1].field_values, vec!["1", "2", "3"]);
+        assert_eq!(records[1].starting_line_number, 2);
+        assert_eq!(records[1].starting_byte_offset, 6);
+    }
+
+    #[test]
+    fn test_quoted_field_with_comma_and_escaped_quote() {
+        let records = read_all_test_records("name,note\n\"Smith, J\",\"said \"\"hi\"\"\"\n")
+            .expect("valid CSV");
+
+        assert_eq!(records[1].field_values, vec!["Smith, J", "said \"hi\""]);
+    }
+
+    #[test]
+    fn test_quoted_field_spanning_lines() {
+        let records = read_all_test_records("id,text\n1,\"first\nsecond\"\n2,plain\n")
+            .expect("valid CSV");
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].field_values, vec!["1", "first\nsecond"]);
+        assert_eq!(records[1].physical_line_count, 2);
+        assert_eq!(records[2].starting_line_number, 4);
+        assert_eq!(records[2].starting_byte_offset, 25);
+    }
+
+    #[test]
+    fn test_crlf_line_endings_and_blank_lines() {
+        let records = read_all_test_records("a,b\r\n\r\n1,\r\n").expect("valid CSV");
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].field_values, vec!["1", ""]);
+        assert_eq!(records[1].starting_line_number, 3);
+    }
+
+    #[test]
+    fn test_unterminated_quote_is_an_error() {
+        let read_result = read_all_test_records("a,b\n1,\"never closed\n");
+
+        match read_result {
+            Err(io_error) => {
+                assert_eq!(io_error.kind(), io::ErrorKind::InvalidData);
+                assert!(io_error.to_string().contains("line 2"));
+            }
+            Ok(_) => panic!("Expected an unterminated quote error"),
+        }
+    }
+}
@@ -24,6 +24,8 @@ mod error_types_module;
 mod manage_absolute_executable_directory_relative_paths;
 mod rows_and_columns_module;
 mod csv_processor_module;
+mod csv_record_reader_module;
+mod streaming_statistics_module;
 use rows_and_columns_module::run_rows_and_columns_application;
 
 /// Application entry point - delegates to primary module
@@ -34,10 +34,18 @@ use super::csv_processor_module::{
     // CsvAnalysisResults,
     perform_enhanced_statistical_analysis,
     // display_enhanced_csv_analysis_results,
+    NumericalStatisticsOptions,
+    PercentileInterpolation,
     save_analysis_details_to_file,
     save_analysis_summary_to_file,
+    display_correlation_matrix,
+    save_correlation_matrix_to_file,
+    SPEARMAN_EXACT_VALUE_LIMIT,
 };
 
+// Import the size of the exact percentile buffer for the help text
+use super::streaming_statistics_module::EXACT_QUANTILE_BUFFER_CAPACITY;
+
 // Import our custom error types for comprehensive error handling
 use super::error_types_module::{
     // RowsAndColumnsError,
@@ -108,16 +116,94 @@ pub fn run_rows_and_columns_application() -> RowsAndColumnsResult<PathBuf> {
                 return Ok(PathBuf::new());
             }
             _ => {
-                // Treat the first argument as a CSV file path
+                // Treat the first argument as a CSV file path, followed by options
                 let csv_file_path = &command_line_arguments[1];
-                return rc_analyze_datafile_save_results_to_resultsfile(csv_file_path);
+                let numerical_statistics_options = parse_numerical_statistics_options(&command_line_arguments[2..])?;
+                return rc_analyze_datafile_save_results_to_resultsfile(csv_file_path, &numerical_statistics_options);
             }
         }
     } else {
         // No command line arguments - start interactive file input
         let csv_file_path_from_qa = interactive_csv_file_path_input()?;
-        return rc_analyze_datafile_save_results_to_resultsfile(&csv_file_path_from_qa);
+        return rc_analyze_datafile_save_results_to_resultsfile(
+            &csv_file_path_from_qa,
+            &NumericalStatisticsOptions::default(),
+        );
+    }
+}
+
+/// Parses the options after the CSV file path
+///
+/// Supported: `--ddof N`, `--percentiles 1,5,95,99`, `--interpolation NAME`
+/// (linear, lower, higher, nearest or midpoint), `--exact-percentiles` and `--spearman`.
+///
+/// # Arguments
+/// * `option_arguments` - The command line arguments after the CSV file path
+///
+/// # Returns
+/// * `RowsAndColumnsResult<NumericalStatisticsOptions>` - The options, with defaults for those not given
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If an option is unknown, lacks its value or the value is invalid
+fn parse_numerical_statistics_options(option_arguments: &[String]) -> RowsAndColumnsResult<NumericalStatisticsOptions> {
+    let mut numerical_statistics_options = NumericalStatisticsOptions::default();
+    let mut option_iterator = option_arguments.iter();
+
+    while let Some(option_argument) = option_iterator.next() {
+        match option_argument.as_str() {
+            "--ddof" => {
+                let ddof_text = option_iterator.next().ok_or_else(|| {
+                    create_configuration_error("--ddof needs a value (0 for population, 1 for sample statistics)")
+                })?;
+                numerical_statistics_options.delta_degrees_of_freedom = ddof_text.trim().parse::<u32>().map_err(|_| {
+                    create_configuration_error(&format!(
+                        "Invalid --ddof value '{}' (expected a whole number such as 0 or 1)",
+                        ddof_text
+                    ))
+                })?;
+            }
+            "--percentiles" => {
+                let percentiles_text = option_iterator.next().ok_or_else(|| {
+                    create_configuration_error("--percentiles needs a comma-separated list such as 1,5,95,99")
+                })?;
+                numerical_statistics_options.requested_percentiles = percentiles_text
+                    .split(',')
+                    .map(|percentile_text| {
+                        percentile_text.trim().parse::<f64>().ok()
+                            .filter(|percentile| (0.0..=100.0).contains(percentile))
+                            .ok_or_else(|| create_configuration_error(&format!(
+                                "Invalid percentile '{}' in --percentiles (expected numbers from 0 to 100)",
+                                percentile_text.trim()
+                            )))
+                    })
+                    .collect::<RowsAndColumnsResult<Vec<f64>>>()?;
+            }
+            "--interpolation" => {
+                let interpolation_text = option_iterator.next().ok_or_else(|| {
+                    create_configuration_error("--interpolation needs a value (linear, lower, higher, nearest or midpoint)")
+                })?;
+                numerical_statistics_options.percentile_interpolation = PercentileInterpolation::from_name(interpolation_text)
+                    .ok_or_else(|| create_configuration_error(&format!(
+                        "Invalid --interpolation value '{}' (expected linear, lower, higher, nearest or midpoint)",
+                        interpolation_text
+                    )))?;
+            }
+            "--exact-percentiles" => {
+                numerical_statistics_options.exact_percentiles = true;
+            }
+            "--spearman" => {
+                numerical_statistics_options.spearman_correlation = true;
+            }
+            unknown_option => {
+                return Err(create_configuration_error(&format!(
+                    "Unknown option '{}' (see --help)",
+                    unknown_option
+                )));
+            }
+        }
     }
+
+    Ok(numerical_statistics_options)
 }
 
 /// Displays usage help information for command line interface
@@ -127,16 +213,33 @@ pub fn run_rows_and_columns_application() -> RowsAndColumnsResult<PathBuf> {
 fn display_usage_help_information() {
     println!("USAGE:");
     println!("  rows_and_columns <csv_file_path>     Process a specific CSV file");
+    println!("  rows_and_columns <csv_file_path> --ddof <n>");
+    println!("                                       Variance/stdev/covariance divide by count - n");
+    println!("                                       (default 1: sample, as in pandas; 0: population)");
+    println!("  rows_and_columns <csv_file_path> --percentiles <p,p,...> [--interpolation <method>]");
+    println!("                                       Report extra percentiles (0 to 100); method is");
+    println!("                                       linear (default), lower, higher, nearest or midpoint");
+    println!("  rows_and_columns <csv_file_path> --exact-percentiles");
+    println!("                                       Keep every number for exact quartiles/percentiles (past");
+    println!("                                       {} values per column they are P² estimates otherwise)", EXACT_QUANTILE_BUFFER_CAPACITY);
+    println!("  rows_and_columns <csv_file_path> --spearman");
+    println!("                                       Also compute Spearman correlations (keeps the numeric");
+    println!("                                       values in memory, up to {} of them)", SPEARMAN_EXACT_VALUE_LIMIT);
     println!("  rows_and_columns --help              Show this help information");
     println!();
     println!("EXAMPLES:");
     println!("  rows_and_columns data/customers.csv");
+    println!("  rows_and_columns data/customers.csv --ddof 0");
+    println!("  rows_and_columns data/customers.csv --percentiles 1,5,95,99 --interpolation nearest");
+    println!("  rows_and_columns data/customers.csv --spearman");
+    println!("  rows_and_columns data/customers.csv --exact-percentiles --percentiles 99");
     println!("  rows_and_columns /home/user/sales_data.csv");
     println!("  rows_and_columns ../reports/quarterly.csv");
     println!();
     println!("FEATURES:");
     println!("  • Directory-based CSV data storage for scalability");
-    println!("  • Pandas-style statistical analysis");
+    println!("  • Pandas-style statistical analysis in one bounded-memory pass");
+    println!("  • Pearson correlation and covariance matrices of numeric columns (Spearman on request)");
     println!("  • ASCII/Unicode TUI charts and visualizations");
     println!("  • Binary-relative path management for portability");
     println!();
@@ -351,7 +454,7 @@ fn validate_user_provided_csv_file_path(user_file_path: &str) -> Result<String,
 ///
 /// # Arguments
 /// * `csv_file_path_argument` - The CSV file path provided as command line argument
-/// * `directory_paths` - The application directory structure for data storage
+/// * `numerical_statistics_options` - ddof (also of the covariances), extra percentiles, percentile interpolation and Spearman for the report
 ///
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or detailed error information
@@ -362,6 +465,7 @@ fn validate_user_provided_csv_file_path(user_file_path: &str) -> Result<String,
 /// * `RowsAndColumnsError::MetadataError` - If metadata operations fail
 pub fn rc_analyze_datafile_save_results_to_resultsfile(
     csv_file_path_argument: &str,
+    numerical_statistics_options: &NumericalStatisticsOptions,
 ) -> RowsAndColumnsResult<PathBuf> {
     // Step 1: Validate the provided file path
     let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
@@ -369,29 +473,36 @@ pub fn rc_analyze_datafile_save_results_to_resultsfile(
     // Step 2: Analyze CSV structure and column types (basic analysis)
     let csv_analysis_results = analyze_csv_file_structure_and_types(&csv_file_absolute_path)?;
 
-    // Step 3: Perform enhanced statistical analysis
-    let enhanced_analysis_results = perform_enhanced_statistical_analysis(
+    // Step 3: Perform enhanced statistical analysis, correlating the continuous columns in the same pass
+    let (enhanced_analysis_results, correlation_matrix) = perform_enhanced_statistical_analysis(
         &csv_file_absolute_path,
-        &csv_analysis_results
+        &csv_analysis_results,
+        numerical_statistics_options,
     )?;
 
-    // Step 4: Save summary to report file (creates new file)
+    // Step 4: Show the correlation matrices
+    display_correlation_matrix(&correlation_matrix);
+
+    // Step 5: Save summary to report file (creates new file)
     let report_file_path = save_analysis_summary_to_file(
         &csv_file_absolute_path,
         &csv_analysis_results,
         // directory_paths
     )?;
 
-    // Step 5: Append detailed analysis to report file
+    // Step 6: Append detailed analysis to report file
     save_analysis_details_to_file(
         &report_file_path,
         &enhanced_analysis_results
     )?;
 
-    // Step 6: Notify user of report creation (minimal output to terminal)
+    // Step 7: Append the correlation matrices to report file
+    save_correlation_matrix_to_file(&report_file_path, &correlation_matrix)?;
+
+    // Step 8: Notify user of report creation (minimal output to terminal)
     println!("Analysis report here: {}", report_file_path.display());
 
-    // Step 7: Return the report file path
+    // Step 9: Return the report file path
     Ok(report_file_path)
 }
 
@@ -679,6 +790,30 @@ fn validate_directory_structure_initialization(
 mod tests {
     use super::*;
 
+    /// Test the option parsing after the CSV file path
+    #[test]
+    fn test_parse_numerical_statistics_options() {
+        let to_arguments = |argument_texts: &[&str]| -> Vec<String> {
+            argument_texts.iter().map(|argument_text| argument_text.to_string()).collect()
+        };
+
+        assert_eq!(parse_numerical_statistics_options(&[]).unwrap(), NumericalStatisticsOptions::default());
+        let parsed_options = parse_numerical_statistics_options(&to_arguments(&[
+            "--ddof", "0", "--percentiles", "1, 2.5,99", "--interpolation", "Nearest",
+        ])).unwrap();
+        assert_eq!(parsed_options.delta_degrees_of_freedom, 0);
+        assert_eq!(parsed_options.requested_percentiles, vec![1.0, 2.5, 99.0]);
+        assert_eq!(parsed_options.percentile_interpolation, PercentileInterpolation::Nearest);
+
+        assert!(parse_numerical_statistics_options(&to_arguments(&["--ddof", "-1"])).is_err());
+        assert!(parse_numerical_statistics_options(&to_arguments(&["--ddof"])).is_err());
+        assert!(parse_numerical_statistics_options(&to_arguments(&["--percentiles", "5,101"])).is_err());
+        assert!(parse_numerical_statistics_options(&to_arguments(&["--interpolation", "cubic"])).is_err());
+        assert!(parse_numerical_statistics_options(&to_arguments(&["--sample"])).is_err());
+        assert!(parse_numerical_statistics_options(&to_arguments(&["--spearman"])).unwrap().spearman_correlation);
+        assert!(parse_numerical_statistics_options(&to_arguments(&["--exact-percentiles"])).unwrap().exact_percentiles);
+    }
+
     /// Test that the directory structure constants are reasonable
     #[test]
     fn test_directory_constants() {
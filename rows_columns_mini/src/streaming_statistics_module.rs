@@ -0,0 +1,828 @@
+// src/streaming_statistics_module.rs
+
+/// One-pass, bounded-memory numerical statistics for rows_and_columns
+///
+/// The enhanced analysis reads each value once and feeds it to an accumulator
+/// instead of collecting whole columns, so memory does not grow with the
+/// number of rows. Count, sum, minimum and maximum are exact; mean, variance,
+/// skewness and kurtosis use one-pass central moment updates (Welford, with
+/// Terriberry's extension to the third and fourth moment), which are exact up
+/// to floating point rounding. Quartiles and percentiles are exact while a
+/// column has at most `EXACT_QUANTILE_BUFFER_CAPACITY` values; beyond that they
+/// come from P² quantile estimators (Jain & Chlamtac, 1985), which keep five
+/// markers per quantile, and the report flags them as estimates.
+///
+/// `--exact-percentiles` keeps every number of each numeric column instead,
+/// for exact quartiles and percentiles at the cost of memory proportional to
+/// the row count. The interpolation (`--interpolation`) applies to exact
+/// percentiles only, since the P² estimates are not taken at ranks.
+///
+/// # Design Philosophy
+/// - No-Load: fixed memory per column by default
+/// - Vanilla Rust: no statistics crates
+/// - Exact when small: up to 4096 values per column the percentiles interpolate
+///   the sorted values, exactly like `--exact-percentiles`
+use std::cmp::Ordering;
+
+// Import the options and the statistics structures the accumulator fills
+use super::csv_processor_module::{
+    NumericalColumnStatistics,
+    NumericalStatisticsOptions,
+    PercentileInterpolation,
+    PercentileValue,
+};
+
+// Import our error handling system
+use super::error_types_module::{RowsAndColumnsResult, create_csv_processing_error};
+
+/// Number of markers a P² estimator keeps
+const P2_MARKER_COUNT: usize = 5;
+
+/// Values per column kept for exact percentiles before switching to P² estimates
+pub const EXACT_QUANTILE_BUFFER_CAPACITY: usize = 4096;
+
+/// Running count, sum, central moments, minimum and maximum (Welford's method)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningMoments {
+    /// Number of values added
+    pub value_count: usize,
+
+    /// Sum of the values added
+    pub value_sum: f64,
+
+    /// Mean of the values added so far
+    pub running_mean: f64,
+
+    /// Sum of squared deviations from the running mean
+    pub squared_deviation_sum: f64,
+
+    /// Sum of cubed deviations from the running mean
+    pub cubed_deviation_sum: f64,
+
+    /// Sum of fourth powers of the deviations from the running mean
+    pub fourth_power_deviation_sum: f64,
+
+    /// Smallest value added
+    pub minimum_value: f64,
+
+    /// Largest value added
+    pub maximum_value: f64,
+}
+
+impl Default for RunningMoments {
+    fn default() -> Self {
+        RunningMoments {
+            value_count: 0,
+            value_sum: 0.0,
+            running_mean: 0.0,
+            squared_deviation_sum: 0.0,
+            cubed_deviation_sum: 0.0,
+            fourth_power_deviation_sum: 0.0,
+            minimum_value: f64::INFINITY,
+            maximum_value: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl RunningMoments {
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value (must not be NaN)
+    pub fn add_value(&mut self, numerical_value: f64) {
+        let previous_count = self.value_count as f64;
+        self.value_count += 1;
+        let value_count = self.value_count as f64;
+
+        let deviation_before_update = numerical_value - self.running_mean;
+        let scaled_deviation = deviation_before_update / value_count;
+        let scaled_deviation_squared = scaled_deviation * scaled_deviation;
+        let second_moment_increment = deviation_before_update * scaled_deviation * previous_count;
+
+        // The higher moments are updated first: they use the previous lower moments
+        self.fourth_power_deviation_sum += second_moment_increment
+            * scaled_deviation_squared
+            * (value_count * value_count - 3.0 * value_count + 3.0)
+            + 6.0 * scaled_deviation_squared * self.squared_deviation_sum
+            - 4.0 * scaled_deviation * self.cubed_deviation_sum;
+        self.cubed_deviation_sum += second_moment_increment * scaled_deviation * (value_count - 2.0)
+            - 3.0 * scaled_deviation * self.squared_deviation_sum;
+        self.squared_deviation_sum += second_moment_increment;
+        self.running_mean += scaled_deviation;
+
+        self.value_sum += numerical_value;
+        self.minimum_value = self.minimum_value.min(numerical_value);
+        self.maximum_value = self.maximum_value.max(numerical_value);
+    }
+
+    /// Variance with a delta degrees of freedom (divides by count - ddof)
+    ///
+    /// # Arguments
+    /// * `delta_degrees_of_freedom` - 1 for the sample variance, 0 for the population variance
+    ///
+    /// # Returns
+    /// * `f64` - The variance, NaN when there are no more values than `delta_degrees_of_freedom`
+    pub fn variance(&self, delta_degrees_of_freedom: u32) -> f64 {
+        let divisor = self.value_count as f64 - f64::from(delta_degrees_of_freedom);
+        if divisor <= 0.0 {
+            return f64::NAN;
+        }
+        self.squared_deviation_sum / divisor
+    }
+
+    /// Skewness of the values
+    ///
+    /// # Arguments
+    /// * `bias_corrected` - Adjusted Fisher-Pearson coefficient (pandas `skew()`) instead of the population one
+    ///
+    /// # Returns
+    /// * `f64` - The skewness; 0.0 for constant values, NaN for too few values (3 when bias corrected)
+    pub fn skewness(&self, bias_corrected: bool) -> f64 {
+        let value_count = self.value_count as f64;
+        if self.value_count == 0 || (bias_corrected && self.value_count < 3) {
+            return f64::NAN;
+        }
+        if self.squared_deviation_sum == 0.0 {
+            return 0.0;
+        }
+
+        let population_skewness = value_count.sqrt() * self.cubed_deviation_sum / self.squared_deviation_sum.powf(1.5);
+        if bias_corrected {
+            population_skewness * (value_count * (value_count - 1.0)).sqrt() / (value_count - 2.0)
+        } else {
+            population_skewness
+        }
+    }
+
+    /// Excess kurtosis of the values (0.0 for a normal distribution)
+    ///
+    /// # Arguments
+    /// * `bias_corrected` - Unbiased estimate (pandas `kurt()`) instead of the population one
+    ///
+    /// # Returns
+    /// * `f64` - The excess kurtosis; 0.0 for constant values, NaN for too few values (4 when bias corrected)
+    pub fn excess_kurtosis(&self, bias_corrected: bool) -> f64 {
+        let value_count = self.value_count as f64;
+        if self.value_count == 0 || (bias_corrected && self.value_count < 4) {
+            return f64::NAN;
+        }
+        if self.squared_deviation_sum == 0.0 {
+            return 0.0;
+        }
+
+        let population_excess_kurtosis = value_count * self.fourth_power_deviation_sum
+            / (self.squared_deviation_sum * self.squared_deviation_sum)
+            - 3.0;
+        if bias_corrected {
+            ((value_count + 1.0) * population_excess_kurtosis + 6.0) * (value_count - 1.0)
+                / ((value_count - 2.0) * (value_count - 3.0))
+        } else {
+            population_excess_kurtosis
+        }
+    }
+}
+
+/// Streaming estimate of one quantile with the P² algorithm
+///
+/// Keeps five markers: the minimum, the maximum, the target quantile and the
+/// two quantiles halfway to the extremes. Each new value moves the marker
+/// positions, and markers that drift from their desired position are adjusted
+/// with a piecewise-parabolic (or, failing that, linear) prediction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct P2QuantileEstimator {
+    /// Target quantile between 0.0 and 1.0
+    target_quantile: f64,
+
+    /// Number of values added
+    value_count: usize,
+
+    /// Marker heights (estimated values at the marker positions)
+    marker_heights: [f64; P2_MARKER_COUNT],
+
+    /// Actual marker positions (0-based ranks)
+    marker_positions: [f64; P2_MARKER_COUNT],
+
+    /// Desired marker positions
+    desired_marker_positions: [f64; P2_MARKER_COUNT],
+
+    /// Increment of each desired position per added value
+    desired_position_increments: [f64; P2_MARKER_COUNT],
+
+    /// Interpolation of the exact result while fewer than five values were added
+    small_sample_interpolation: PercentileInterpolation,
+}
+
+impl P2QuantileEstimator {
+    /// Creates an estimator for one quantile
+    ///
+    /// # Arguments
+    /// * `target_quantile` - The quantile to estimate, between 0.0 and 1.0 (e.g. 0.5 for the median)
+    /// * `small_sample_interpolation` - Interpolation while fewer than five values were added
+    ///
+    /// # Returns
+    /// * `P2QuantileEstimator` - An empty estimator
+    pub fn new(target_quantile: f64, small_sample_interpolation: PercentileInterpolation) -> Self {
+        let target_quantile = target_quantile.clamp(0.0, 1.0);
+        P2QuantileEstimator {
+            target_quantile,
+            value_count: 0,
+            marker_heights: [0.0; P2_MARKER_COUNT],
+            marker_positions: [0.0, 1.0, 2.0, 3.0, 4.0],
+            desired_marker_positions: [
+                0.0,
+                2.0 * target_quantile,
+                4.0 * target_quantile,
+                2.0 + 2.0 * target_quantile,
+                4.0,
+            ],
+            desired_position_increments: [0.0, target_quantile / 2.0, target_quantile, (1.0 + target_quantile) / 2.0, 1.0],
+            small_sample_interpolation,
+        }
+    }
+
+    /// Creates an estimator that continues from values already seen
+    ///
+    /// The markers start at the ranks of the minimum, the maximum, the target
+    /// quantile and the two quantiles halfway to the extremes, with the sorted
+    /// values at those ranks as heights, so the estimate starts out exact.
+    ///
+    /// # Arguments
+    /// * `target_quantile` - The quantile to estimate, between 0.0 and 1.0
+    /// * `sorted_values` - At least five values sorted in ascending order
+    ///
+    /// # Returns
+    /// * `P2QuantileEstimator` - An estimator that has seen `sorted_values`
+    pub fn from_sorted_values(target_quantile: f64, sorted_values: &[f64]) -> Self {
+        debug_assert!(sorted_values.len() >= P2_MARKER_COUNT);
+        let mut quantile_estimator = P2QuantileEstimator::new(target_quantile, PercentileInterpolation::Linear);
+        let target_quantile = quantile_estimator.target_quantile;
+        let last_rank = (sorted_values.len() - 1) as f64;
+
+        quantile_estimator.desired_marker_positions =
+            [0.0, target_quantile / 2.0, target_quantile, (1.0 + target_quantile) / 2.0, 1.0].map(|quantile| quantile * last_rank);
+
+        // Markers need distinct ranks, in order, between the first and the last
+        let mut marker_positions = quantile_estimator.desired_marker_positions.map(f64::round);
+        for marker_index in 1..(P2_MARKER_COUNT - 1) {
+            marker_positions[marker_index] = marker_positions[marker_index].max(marker_positions[marker_index - 1] + 1.0);
+        }
+        for marker_index in (1..(P2_MARKER_COUNT - 1)).rev() {
+            marker_positions[marker_index] = marker_positions[marker_index].min(marker_positions[marker_index + 1] - 1.0);
+        }
+
+        quantile_estimator.marker_heights = marker_positions.map(|marker_position| sorted_values[marker_position as usize]);
+        quantile_estimator.marker_positions = marker_positions;
+        quantile_estimator.value_count = sorted_values.len();
+        quantile_estimator
+    }
+
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value (must not be NaN)
+    pub fn add_value(&mut self, numerical_value: f64) {
+        // The first five values become the initial markers
+        if self.value_count < P2_MARKER_COUNT {
+            self.marker_heights[self.value_count] = numerical_value;
+            self.value_count += 1;
+            if self.value_count == P2_MARKER_COUNT {
+                self.marker_heights.sort_by(compare_numerical_values);
+            }
+            return;
+        }
+        self.value_count += 1;
+
+        // Find the cell the value falls into, widening the extremes if needed
+        let cell_index = if numerical_value < self.marker_heights[0] {
+            self.marker_heights[0] = numerical_value;
+            0
+        } else if numerical_value >= self.marker_heights[4] {
+            self.marker_heights[4] = numerical_value;
+            3
+        } else {
+            (1..P2_MARKER_COUNT)
+                .find(|marker_index| numerical_value < self.marker_heights[*marker_index])
+                .map_or(3, |marker_index| marker_index - 1)
+        };
+
+        for marker_index in (cell_index + 1)..P2_MARKER_COUNT {
+            self.marker_positions[marker_index] += 1.0;
+        }
+        for marker_index in 0..P2_MARKER_COUNT {
+            self.desired_marker_positions[marker_index] += self.desired_position_increments[marker_index];
+        }
+
+        // Move the three middle markers back towards their desired positions
+        for marker_index in 1..(P2_MARKER_COUNT - 1) {
+            let position_drift = self.desired_marker_positions[marker_index] - self.marker_positions[marker_index];
+            let room_above = self.marker_positions[marker_index + 1] - self.marker_positions[marker_index];
+            let room_below = self.marker_positions[marker_index - 1] - self.marker_positions[marker_index];
+            if (position_drift >= 1.0 && room_above > 1.0) || (position_drift <= -1.0 && room_below < -1.0) {
+                let step = position_drift.signum();
+                let parabolic_height = self.parabolic_prediction(marker_index, step);
+                self.marker_heights[marker_index] = if self.marker_heights[marker_index - 1] < parabolic_height
+                    && parabolic_height < self.marker_heights[marker_index + 1]
+                {
+                    parabolic_height
+                } else {
+                    self.linear_prediction(marker_index, step)
+                };
+                self.marker_positions[marker_index] += step;
+            }
+        }
+    }
+
+    /// Current estimate of the quantile
+    ///
+    /// With fewer than five values the estimate is interpolated from the sorted
+    /// values, exactly as `calculate_percentile` does.
+    ///
+    /// # Returns
+    /// * `Option<f64>` - The estimate, or None if no value was added
+    pub fn estimate(&self) -> Option<f64> {
+        if self.value_count == 0 {
+            return None;
+        }
+        if self.value_count < P2_MARKER_COUNT {
+            let mut sorted_values = self.marker_heights[..self.value_count].to_vec();
+            sorted_values.sort_by(compare_numerical_values);
+            return Some(calculate_percentile(
+                &sorted_values,
+                self.target_quantile * 100.0,
+                self.small_sample_interpolation,
+            ));
+        }
+        Some(self.marker_heights[2])
+    }
+
+    /// Piecewise-parabolic prediction of a marker height after moving it one step
+    ///
+    /// # Arguments
+    /// * `marker_index` - One of the three middle markers
+    /// * `step` - +1.0 or -1.0
+    ///
+    /// # Returns
+    /// * `f64` - The predicted height
+    fn parabolic_prediction(&self, marker_index: usize, step: f64) -> f64 {
+        let heights = &self.marker_heights;
+        let positions = &self.marker_positions;
+        heights[marker_index]
+            + step / (positions[marker_index + 1] - positions[marker_index - 1])
+                * ((positions[marker_index] - positions[marker_index - 1] + step)
+                    * (heights[marker_index + 1] - heights[marker_index])
+                    / (positions[marker_index + 1] - positions[marker_index])
+                    + (positions[marker_index + 1] - positions[marker_index] - step)
+                        * (heights[marker_index] - heights[marker_index - 1])
+                        / (positions[marker_index] - positions[marker_index - 1]))
+    }
+
+    /// Linear prediction of a marker height after moving it one step
+    ///
+    /// # Arguments
+    /// * `marker_index` - One of the three middle markers
+    /// * `step` - +1.0 or -1.0
+    ///
+    /// # Returns
+    /// * `f64` - The predicted height
+    fn linear_prediction(&self, marker_index: usize, step: f64) -> f64 {
+        let neighbor_index = if step > 0.0 { marker_index + 1 } else { marker_index - 1 };
+        self.marker_heights[marker_index]
+            + step * (self.marker_heights[neighbor_index] - self.marker_heights[marker_index])
+                / (self.marker_positions[neighbor_index] - self.marker_positions[marker_index])
+    }
+}
+
+/// Quantiles of a stream: exact while the values fit a buffer, P² estimates beyond
+///
+/// The first `EXACT_QUANTILE_BUFFER_CAPACITY` values are kept and the quantiles
+/// are interpolated from them exactly as the exact mode does. The value after
+/// that seeds one P² estimator per quantile from the sorted buffer, which is
+/// then dropped, so memory stays bounded however many values follow.
+#[derive(Debug, Clone)]
+pub struct StreamingQuantileEstimates {
+    /// Target quantiles between 0.0 and 1.0
+    target_quantiles: Vec<f64>,
+
+    /// How a quantile between two ranks is computed while the values are buffered
+    percentile_interpolation: PercentileInterpolation,
+
+    /// Every value so far, until the buffer overflows
+    buffered_values: Vec<f64>,
+
+    /// One P² estimator per target quantile, once the buffer has overflowed
+    quantile_estimators: Vec<P2QuantileEstimator>,
+}
+
+impl StreamingQuantileEstimates {
+    /// Creates empty estimates
+    ///
+    /// # Arguments
+    /// * `target_quantiles` - The quantiles to estimate, between 0.0 and 1.0
+    /// * `percentile_interpolation` - Interpolation while the values are buffered
+    ///
+    /// # Returns
+    /// * `StreamingQuantileEstimates` - Estimates with no values
+    pub fn new(target_quantiles: Vec<f64>, percentile_interpolation: PercentileInterpolation) -> Self {
+        StreamingQuantileEstimates {
+            target_quantiles,
+            percentile_interpolation,
+            buffered_values: Vec::new(),
+            quantile_estimators: Vec::new(),
+        }
+    }
+
+    /// Adds one value
+    ///
+    /// # Arguments
+    /// * `numerical_value` - The value (must not be NaN)
+    pub fn add_value(&mut self, numerical_value: f64) {
+        if self.quantile_estimators.is_empty() {
+            if self.buffered_values.len() < EXACT_QUANTILE_BUFFER_CAPACITY {
+                self.buffered_values.push(numerical_value);
+                return;
+            }
+
+            // The buffer is full: hand it over to the P² estimators
+            let mut sorted_values = std::mem::take(&mut self.buffered_values);
+            sorted_values.sort_by(compare_numerical_values);
+            self.quantile_estimators = self
+                .target_quantiles
+                .iter()
+                .map(|target_quantile| P2QuantileEstimator::from_sorted_values(*target_quantile, &sorted_values))
+                .collect();
+        }
+        for quantile_estimator in &mut self.quantile_estimators {
+            quantile_estimator.add_value(numerical_value);
+        }
+    }
+
+    /// Whether the quantiles are P² estimates rather than exact values
+    ///
+    /// # Returns
+    /// * `bool` - True once more values were added than the buffer holds
+    pub fn are_estimates(&self) -> bool {
+        !self.quantile_estimators.is_empty()
+    }
+
+    /// Produces the quantiles
+    ///
+    /// # Returns
+    /// * `Vec<f64>` - One value per target quantile, in order (0.0 when no value was added)
+    pub fn into_quantile_values(self) -> Vec<f64> {
+        if self.are_estimates() {
+            return self
+                .quantile_estimators
+                .iter()
+                .map(|quantile_estimator| quantile_estimator.estimate().unwrap_or_default())
+                .collect();
+        }
+        let mut sorted_values = self.buffered_values;
+        sorted_values.sort_by(compare_numerical_values);
+        self.target_quantiles
+            .iter()
+            .map(|target_quantile| {
+                calculate_percentile(&sorted_values, target_quantile * 100.0, self.percentile_interpolation)
+            })
+            .collect()
+    }
+}
+
+/// Accumulates the statistics of one integer or float column
+///
+/// Feed every value of the column with `add_value`, then call `finish`.
+#[derive(Debug, Clone)]
+pub struct NumericalStatisticsAccumulator {
+    /// Count, sum, central moments and extremes of the parsed values
+    running_moments: RunningMoments,
+
+    /// Parsed values equal to zero
+    zero_value_count: usize,
+
+    /// Parsed values below zero
+    negative_value_count: usize,
+
+    /// Delta degrees of freedom of the variance and standard deviation
+    delta_degrees_of_freedom: u32,
+
+    /// How quartiles and percentiles between two ranks are computed
+    percentile_interpolation: PercentileInterpolation,
+
+    /// Percentiles reported besides the quartiles
+    requested_percentiles: Vec<f64>,
+
+    /// The 25th, 50th and 75th percentile, then the requested percentiles (default mode)
+    streaming_quantile_estimates: StreamingQuantileEstimates,
+
+    /// Every parsed value (`--exact-percentiles` only)
+    exact_mode_values: Option<Vec<f64>>,
+
+    /// Empty and unparseable values
+    missing_value_count: usize,
+
+    /// Number of values fed, parsed or not
+    total_value_count: usize,
+}
+
+impl NumericalStatisticsAccumulator {
+    /// Creates an empty accumulator
+    ///
+    /// # Arguments
+    /// * `numerical_statistics_options` - ddof, requested percentiles, interpolation and whether
+    ///   percentiles are exact
+    ///
+    /// # Returns
+    /// * `NumericalStatisticsAccumulator` - An accumulator with no values
+    pub fn new(numerical_statistics_options: &NumericalStatisticsOptions) -> Self {
+        let percentile_interpolation = numerical_statistics_options.percentile_interpolation;
+        NumericalStatisticsAccumulator {
+            running_moments: RunningMoments::default(),
+            zero_value_count: 0,
+            negative_value_count: 0,
+            delta_degrees_of_freedom: numerical_statistics_options.delta_degrees_of_freedom,
+            percentile_interpolation,
+            requested_percentiles: numerical_statistics_options.requested_percentiles.clone(),
+            streaming_quantile_estimates: StreamingQuantileEstimates::new(
+                [0.25, 0.50, 0.75]
+                    .into_iter()
+                    .chain(numerical_statistics_options.requested_percentiles.iter().map(|percentile| percentile / 100.0))
+                    .collect(),
+                percentile_interpolation,
+            ),
+            exact_mode_values: numerical_statistics_options.exact_percentiles.then(Vec::new),
+            missing_value_count: 0,
+            total_value_count: 0,
+        }
+    }
+
+    /// Adds one raw field value
+    ///
+    /// # Arguments
+    /// * `value_string` - The field value as read from the file (empty, unparseable and NaN count as missing)
+    pub fn add_value(&mut self, value_string: &str) {
+        self.total_value_count += 1;
+
+        let numerical_value = match value_string.trim().parse::<f64>() {
+            Ok(numerical_value) if !numerical_value.is_nan() => numerical_value,
+            _ => {
+                self.missing_value_count += 1;
+                return;
+            }
+        };
+
+        self.running_moments.add_value(numerical_value);
+        if numerical_value == 0.0 {
+            self.zero_value_count += 1;
+        } else if numerical_value < 0.0 {
+            self.negative_value_count += 1;
+        }
+        match &mut self.exact_mode_values {
+            Some(exact_mode_values) => exact_mode_values.push(numerical_value),
+            None => self.streaming_quantile_estimates.add_value(numerical_value),
+        }
+    }
+
+    /// Produces the column statistics
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<NumericalColumnStatistics>` - Complete numerical analysis
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If no value parsed as a number
+    pub fn finish(self) -> RowsAndColumnsResult<NumericalColumnStatistics> {
+        if self.running_moments.value_count == 0 {
+            return Err(create_csv_processing_error(
+                "No valid numerical values found for statistical analysis",
+                None,
+                None,
+            ));
+        }
+
+        let percentiles_are_estimates =
+            self.exact_mode_values.is_none() && self.streaming_quantile_estimates.are_estimates();
+        let ([q1_value, q2_median_value, q3_value], requested_percentile_values) = match self.exact_mode_values {
+            Some(mut exact_mode_values) => {
+                exact_mode_values.sort_by(compare_numerical_values);
+                let percentile_of_values =
+                    |percentile: f64| calculate_percentile(&exact_mode_values, percentile, self.percentile_interpolation);
+                (
+                    [25.0, 50.0, 75.0].map(percentile_of_values),
+                    self.requested_percentiles.iter().map(|percentile| percentile_of_values(*percentile)).collect(),
+                )
+            }
+            None => {
+                let mut quantile_values = self.streaming_quantile_estimates.into_quantile_values();
+                let requested_percentile_values = quantile_values.split_off(3);
+                ([quantile_values[0], quantile_values[1], quantile_values[2]], requested_percentile_values)
+            }
+        };
+        let percentile_values = self
+            .requested_percentiles
+            .iter()
+            .zip(requested_percentile_values)
+            .map(|(percentile, percentile_value)| PercentileValue { percentile: *percentile, percentile_value })
+            .collect();
+
+        let running_moments = self.running_moments;
+        let variance = running_moments.variance(self.delta_degrees_of_freedom);
+        let standard_deviation = variance.sqrt();
+        let bias_corrected = self.delta_degrees_of_freedom > 0;
+        let coefficient_of_variation =
+            (running_moments.running_mean != 0.0).then(|| standard_deviation / running_moments.running_mean);
+        let missing_percentage = if self.total_value_count > 0 {
+            (self.missing_value_count as f64 / self.total_value_count as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(NumericalColumnStatistics {
+            min_value: running_moments.minimum_value,
+            q1_value,
+            q2_median_value,
+            q3_value,
+            max_value: running_moments.maximum_value,
+            mean_value: running_moments.running_mean,
+            standard_deviation,
+            value_count: running_moments.value_count,
+            value_sum: running_moments.value_sum,
+            variance,
+            delta_degrees_of_freedom: self.delta_degrees_of_freedom,
+            skewness: running_moments.skewness(bias_corrected),
+            excess_kurtosis: running_moments.excess_kurtosis(bias_corrected),
+            interquartile_range: q3_value - q1_value,
+            value_range: running_moments.maximum_value - running_moments.minimum_value,
+            coefficient_of_variation,
+            zero_value_count: self.zero_value_count,
+            negative_value_count: self.negative_value_count,
+            percentile_values,
+            percentile_interpolation: self.percentile_interpolation,
+            percentiles_are_estimates,
+            missing_percentage,
+        })
+    }
+}
+
+/// Calculates percentile value from sorted numerical data
+///
+/// The percentile sits at rank `percentile / 100 * (count - 1)`; the interpolations
+/// match numpy's `percentile` and pandas' `quantile`.
+///
+/// # Arguments
+/// * `sorted_values` - Sorted numerical values
+/// * `percentile` - Percentile to calculate (0.0 to 100.0)
+/// * `percentile_interpolation` - How a rank between two values is resolved
+///
+/// # Returns
+/// * `f64` - The percentile value
+fn calculate_percentile(sorted_values: &[f64], percentile: f64, percentile_interpolation: PercentileInterpolation) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+
+    let index = (percentile.clamp(0.0, 100.0) / 100.0) * (sorted_values.len() - 1) as f64;
+    let lower_index = index.floor() as usize;
+    let upper_index = index.ceil() as usize;
+    let weight = index - lower_index as f64;
+    let lower_value = sorted_values[lower_index];
+    let upper_value = sorted_values[upper_index];
+
+    match percentile_interpolation {
+        PercentileInterpolation::Linear if lower_index == upper_index => lower_value,
+        PercentileInterpolation::Linear => lower_value * (1.0 - weight) + upper_value * weight,
+        PercentileInterpolation::Lower => lower_value,
+        PercentileInterpolation::Higher => upper_value,
+        PercentileInterpolation::Nearest => {
+            // Round half to even, as numpy does
+            if weight < 0.5 || (weight == 0.5 && lower_index.is_multiple_of(2)) {
+                lower_value
+            } else {
+                upper_value
+            }
+        }
+        PercentileInterpolation::Midpoint => (lower_value + upper_value) / 2.0,
+    }
+}
+
+/// Orders two numbers for sorting (NaN never reaches the accumulators)
+///
+/// # Arguments
+/// * `first_value` - Left value
+/// * `second_value` - Right value
+///
+/// # Returns
+/// * `Ordering` - Their order
+fn compare_numerical_values(first_value: &f64, second_value: &f64) -> Ordering {
+    first_value.partial_cmp(second_value).unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic shuffle of 1..=count (linear congruential generator)
+    fn shuffled_test_values(value_count: usize) -> Vec<f64> {
+        let mut test_values: Vec<f64> = (1..=value_count).map(|value| value as f64).collect();
+        let mut generator_state: u64 = 0x2545_F491_4F6C_DD1D;
+        for value_position in (1..test_values.len()).rev() {
+            generator_state = generator_state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            test_values.swap(value_position, (generator_state >> 33) as usize % (value_position + 1));
+        }
+        test_values
+    }
+
+    #[test]
+    fn test_running_moments_match_two_pass_results() {
+        let test_values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let mut running_moments = RunningMoments::default();
+        for test_value in test_values {
+            running_moments.add_value(test_value);
+        }
+
+        assert_eq!((running_moments.value_count, running_moments.value_sum), (8, 40.0));
+        assert!((running_moments.running_mean - 5.0).abs() < 1e-12);
+        assert!((running_moments.variance(0) - 4.0).abs() < 1e-12);
+        assert!((running_moments.variance(1) - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!((running_moments.minimum_value, running_moments.maximum_value), (2.0, 9.0));
+
+        // Reference values from the two-pass formulas (population and pandas' bias-corrected)
+        assert!((running_moments.skewness(false) - 0.65625).abs() < 1e-12);
+        assert!((running_moments.excess_kurtosis(false) + 0.21875).abs() < 1e-12);
+        assert!((running_moments.skewness(true) - 0.818_487_553_356_799_7).abs() < 1e-12);
+        assert!((running_moments.excess_kurtosis(true) - 0.940625).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_p2_estimates_are_close_and_exact_for_few_values() {
+        let test_values = shuffled_test_values(10_000);
+        for (target_quantile, expected_value) in [(0.25, 2_500.75), (0.5, 5_000.5), (0.75, 7_500.25)] {
+            let mut quantile_estimator = P2QuantileEstimator::new(target_quantile, PercentileInterpolation::Linear);
+            for test_value in &test_values {
+                quantile_estimator.add_value(*test_value);
+            }
+            let estimate = quantile_estimator.estimate().expect("values were added");
+            assert!((estimate - expected_value).abs() < 100.0, "quantile {}: {}", target_quantile, estimate);
+        }
+
+        let mut median_estimator = P2QuantileEstimator::new(0.5, PercentileInterpolation::Linear);
+        assert_eq!(median_estimator.estimate(), None);
+        for test_value in [9.0, 1.0, 4.0, 3.0] {
+            median_estimator.add_value(test_value);
+        }
+        assert_eq!(median_estimator.estimate(), Some(3.5));
+    }
+
+    #[test]
+    fn test_nearest_percentile_rounds_half_to_even() {
+        let sorted_values = [1.0, 2.0, 3.0, 4.0, 10.0];
+        // 12.5% of 4 is rank 0.5, 62.5% is rank 2.5
+        assert_eq!(calculate_percentile(&sorted_values, 12.5, PercentileInterpolation::Nearest), 1.0);
+        assert_eq!(calculate_percentile(&sorted_values, 62.5, PercentileInterpolation::Nearest), 3.0);
+        assert_eq!(calculate_percentile(&sorted_values, 100.0, PercentileInterpolation::Midpoint), 10.0);
+        assert_eq!(calculate_percentile(&[], 50.0, PercentileInterpolation::Linear), 0.0);
+    }
+
+    #[test]
+    fn test_percentiles_are_estimates_only_past_the_exact_buffer() {
+        let numerical_statistics_options = NumericalStatisticsOptions {
+            requested_percentiles: vec![90.0],
+            ..NumericalStatisticsOptions::default()
+        };
+        let exact_options = NumericalStatisticsOptions {
+            exact_percentiles: true,
+            ..numerical_statistics_options.clone()
+        };
+
+        let mut small_accumulator = NumericalStatisticsAccumulator::new(&numerical_statistics_options);
+        for value_string in ["3", "", "1", "x", "NaN", "2", "4"] {
+            small_accumulator.add_value(value_string);
+        }
+        let small_statistics = small_accumulator.finish().expect("numbers were added");
+        assert!(!small_statistics.percentiles_are_estimates);
+        assert_eq!(small_statistics.value_count, 4);
+        assert_eq!(small_statistics.q2_median_value, 2.5);
+        assert!((small_statistics.missing_percentage - 300.0 / 7.0).abs() < 1e-9);
+
+        let test_values = shuffled_test_values(10_000);
+        let mut streaming_accumulator = NumericalStatisticsAccumulator::new(&numerical_statistics_options);
+        let mut exact_accumulator = NumericalStatisticsAccumulator::new(&exact_options);
+        for test_value in &test_values {
+            streaming_accumulator.add_value(&test_value.to_string());
+            exact_accumulator.add_value(&test_value.to_string());
+        }
+
+        let streaming_statistics = streaming_accumulator.finish().expect("numbers were added");
+        assert!(streaming_statistics.percentiles_are_estimates);
+        assert!((streaming_statistics.q2_median_value - 5_000.5).abs() < 100.0);
+        assert!((streaming_statistics.percentile_values[0].percentile_value - 9_000.1).abs() < 100.0);
+        assert_eq!(streaming_statistics.value_sum, 50_005_000.0);
+
+        let exact_statistics = exact_accumulator.finish().expect("numbers were added");
+        assert!(!exact_statistics.percentiles_are_estimates);
+        assert_eq!(exact_statistics.q2_median_value, 5_000.5);
+        assert!((exact_statistics.percentile_values[0].percentile_value - 9_000.1).abs() < 1e-9);
+
+        let missing_accumulator = NumericalStatisticsAccumulator::new(&exact_options);
+        assert!(missing_accumulator.finish().is_err());
+    }
+}